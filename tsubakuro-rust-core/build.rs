@@ -26,6 +26,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     tonic_prost_build::configure().compile_protos(
         &[
             "protos/data_relay_grpc/proto/blob_relay/blob_reference.proto",
+            "protos/data_relay_grpc/proto/blob_relay/blob_relay_local.proto",
             "protos/data_relay_grpc/proto/blob_relay/blob_relay_streaming.proto",
         ],
         &["protos/"],
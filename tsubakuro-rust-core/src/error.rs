@@ -1,3 +1,10 @@
+use crate::{
+    jogasaki::proto::sql::error::Code as SqlServiceCode,
+    tateyama::proto::{
+        diagnostics::Code as CoreServiceCode, system::diagnostic::ErrorCode as SystemServiceCode,
+    },
+};
+
 /// Error for tsubakuro-rust-core.
 pub enum TgError {
     /// Client error.
@@ -112,17 +119,139 @@ impl TgError {
             TgError::ServerError(_, _, code, _) => Some(code),
         }
     }
+
+    /// Get server message of ServerError.
+    ///
+    /// since 0.11.0
+    pub fn server_message(&self) -> Option<&String> {
+        match self {
+            TgError::ServerError(_, _, _, server_message) => Some(server_message),
+            _ => None,
+        }
+    }
+
+    /// Get supplemental text of ServerError.
+    ///
+    /// Returns `None` if the server does not provide it. (See [`DiagnosticCode::supplemental_text`])
+    ///
+    /// since 0.11.0
+    pub fn supplemental_text(&self) -> Option<&String> {
+        self.diagnostic_code()
+            .and_then(|code| code.supplemental_text())
+    }
+
+    /// Get SQL service error code of ServerError.
+    ///
+    /// since 0.11.0
+    pub fn sql_service_code(&self) -> Option<SqlServiceCode> {
+        self.diagnostic_code()
+            .and_then(|code| code.sql_service_code())
+    }
+
+    /// Get core service error code of ServerError.
+    ///
+    /// since 0.11.0
+    pub fn core_service_code(&self) -> Option<CoreServiceCode> {
+        self.diagnostic_code()
+            .and_then(|code| code.core_service_code())
+    }
+
+    /// Whether the error is `UNIQUE_CONSTRAINT_VIOLATION_EXCEPTION`.
+    ///
+    /// since 0.11.0
+    pub fn is_unique_violation(&self) -> bool {
+        self.sql_service_code() == Some(SqlServiceCode::UniqueConstraintViolationException)
+    }
+
+    /// Whether the error is a constraint violation.
+    ///
+    /// `CONSTRAINT_VIOLATION_EXCEPTION` and its subclasses.
+    ///
+    /// since 0.11.0
+    pub fn is_constraint_violation(&self) -> bool {
+        matches!(
+            self.sql_service_code(),
+            Some(
+                SqlServiceCode::ConstraintViolationException
+                    | SqlServiceCode::UniqueConstraintViolationException
+                    | SqlServiceCode::NotNullConstraintViolationException
+                    | SqlServiceCode::ReferentialIntegrityConstraintViolationException
+                    | SqlServiceCode::CheckConstraintViolationException
+            )
+        )
+    }
+
+    /// Whether the error means that the target was not found.
+    ///
+    /// - `TARGET_NOT_FOUND_EXCEPTION` (e.g. table not found)
+    /// - `TRANSACTION_NOT_FOUND_EXCEPTION`
+    /// - `STATEMENT_NOT_FOUND_EXCEPTION`
+    /// - `SERVICE_NOT_FOUND`
+    /// - `NOT_FOUND` (system service)
+    ///
+    /// since 0.11.0
+    pub fn is_not_found(&self) -> bool {
+        if matches!(
+            self.sql_service_code(),
+            Some(
+                SqlServiceCode::TargetNotFoundException
+                    | SqlServiceCode::TransactionNotFoundException
+                    | SqlServiceCode::StatementNotFoundException
+            )
+        ) {
+            return true;
+        }
+        if self.core_service_code() == Some(CoreServiceCode::ServiceNotFound) {
+            return true;
+        }
+        self.diagnostic_code()
+            .and_then(|code| code.system_service_code())
+            == Some(SystemServiceCode::NotFound)
+    }
+
+    /// Whether the error is `INACTIVE_TRANSACTION_EXCEPTION`.
+    ///
+    /// since 0.11.0
+    pub fn is_transaction_inactive(&self) -> bool {
+        self.sql_service_code() == Some(SqlServiceCode::InactiveTransactionException)
+    }
+
+    /// Whether the error is a timeout.
+    ///
+    /// [`TgError::TimeoutError`] (client side timeout) or `SQL_REQUEST_TIMEOUT_EXCEPTION`.
+    ///
+    /// since 0.11.0
+    pub fn is_timeout(&self) -> bool {
+        match self {
+            TgError::TimeoutError(_) => true,
+            _ => self.sql_service_code() == Some(SqlServiceCode::SqlRequestTimeoutException),
+        }
+    }
 }
 
 /// Diagnostic code of ServerError.
-#[derive(Debug, Clone, PartialEq, Eq)]
+///
+/// Equality compares the code only. (The supplemental text is not compared)
+#[derive(Debug, Clone)]
 pub struct DiagnosticCode {
     category_number: i32,
     category_str: String,
     code_number: i32,
     name: String,
+    supplemental_text: String,
 }
 
+impl PartialEq for DiagnosticCode {
+    fn eq(&self, other: &Self) -> bool {
+        self.category_number == other.category_number
+            && self.category_str == other.category_str
+            && self.code_number == other.code_number
+            && self.name == other.name
+    }
+}
+
+impl Eq for DiagnosticCode {}
+
 impl DiagnosticCode {
     pub(crate) fn new(
         category_number: i32,
@@ -135,9 +264,15 @@ impl DiagnosticCode {
             category_str: category_str.to_string(),
             code_number,
             name: name.to_string(),
+            supplemental_text: String::new(),
         }
     }
 
+    pub(crate) fn with_supplemental_text(mut self, supplemental_text: &str) -> DiagnosticCode {
+        self.supplemental_text = supplemental_text.to_string();
+        self
+    }
+
     /// Get error category.
     pub fn category_number(&self) -> i32 {
         self.category_number
@@ -162,6 +297,55 @@ impl DiagnosticCode {
     pub fn name(&self) -> &String {
         &self.name
     }
+
+    /// Get supplemental text of the error.
+    ///
+    /// Returns `None` if the server does not provide it.
+    ///
+    /// since 0.11.0
+    pub fn supplemental_text(&self) -> Option<&String> {
+        if self.supplemental_text.is_empty() {
+            None
+        } else {
+            Some(&self.supplemental_text)
+        }
+    }
+
+    /// Get SQL service error code.
+    ///
+    /// Returns `None` if this is not a SQL service error.
+    ///
+    /// since 0.11.0
+    pub fn sql_service_code(&self) -> Option<SqlServiceCode> {
+        if self.category_str != "SQL" {
+            return None;
+        }
+        SqlServiceCode::from_str_name(&self.name)
+    }
+
+    /// Get core service error code.
+    ///
+    /// Returns `None` if this is not a core service error.
+    ///
+    /// since 0.11.0
+    pub fn core_service_code(&self) -> Option<CoreServiceCode> {
+        if self.category_str != "SCD" {
+            return None;
+        }
+        CoreServiceCode::from_str_name(&self.name)
+    }
+
+    /// Get system service error code.
+    ///
+    /// Returns `None` if this is not a system service error.
+    ///
+    /// since 0.11.0
+    pub fn system_service_code(&self) -> Option<SystemServiceCode> {
+        if self.category_str != "SYS" {
+            return None;
+        }
+        SystemServiceCode::from_str_name(&self.name)
+    }
 }
 
 impl std::fmt::Display for DiagnosticCode {
@@ -240,5 +424,81 @@ mod test {
         assert_eq!("TST-00456", code.structured_code());
         assert_eq!("TEST_EXCEPTION", code.name());
         assert_eq!("TST-00456 (TEST_EXCEPTION)", code.to_string());
+        assert_eq!(None, code.sql_service_code());
+        assert_eq!(None, code.core_service_code());
+    }
+
+    fn server_error(code: DiagnosticCode, supplemental_text: &str) -> TgError {
+        TgError::ServerError(
+            "test".to_string(),
+            "test error".to_string(),
+            code.with_supplemental_text(supplemental_text),
+            "server message".to_string(),
+        )
+    }
+
+    #[test]
+    fn sql_service_code() {
+        let code = DiagnosticCode::new(3, "SQL", 2002, "UNIQUE_CONSTRAINT_VIOLATION_EXCEPTION");
+        assert_eq!(
+            Some(SqlServiceCode::UniqueConstraintViolationException),
+            code.sql_service_code()
+        );
+        assert_eq!(None, code.core_service_code());
+
+        let error = server_error(code, "");
+        assert!(error.is_unique_violation());
+        assert!(error.is_constraint_violation());
+        assert!(!error.is_not_found());
+        assert!(!error.is_transaction_inactive());
+        assert!(!error.is_timeout());
+        assert_eq!(Some(&"server message".to_string()), error.server_message());
+        assert_eq!(None, error.supplemental_text());
+
+        let code = DiagnosticCode::new(3, "SQL", 2014, "TARGET_NOT_FOUND_EXCEPTION");
+        let error = server_error(code, "table=tb");
+        assert!(!error.is_unique_violation());
+        assert!(error.is_not_found());
+        assert_eq!(Some(&"table=tb".to_string()), error.supplemental_text());
+        assert_eq!(
+            &DiagnosticCode::new(3, "SQL", 2014, "TARGET_NOT_FOUND_EXCEPTION"),
+            error.diagnostic_code().unwrap()
+        );
+
+        let code = DiagnosticCode::new(3, "SQL", 2025, "INACTIVE_TRANSACTION_EXCEPTION");
+        assert!(server_error(code, "").is_transaction_inactive());
+
+        let code = DiagnosticCode::new(3, "SQL", 2039, "SQL_REQUEST_TIMEOUT_EXCEPTION");
+        assert!(server_error(code, "").is_timeout());
+
+        let code = DiagnosticCode::new(3, "SQL", 1000, "UnknownSqlError999");
+        assert_eq!(None, code.sql_service_code());
+    }
+
+    #[test]
+    fn core_service_code() {
+        let code = DiagnosticCode::new(0, "SCD", 401, "SERVICE_NOT_FOUND");
+        assert_eq!(
+            Some(CoreServiceCode::ServiceNotFound),
+            code.core_service_code()
+        );
+        assert_eq!(None, code.sql_service_code());
+        assert!(server_error(code, "").is_not_found());
+
+        let code = DiagnosticCode::new(1234, "SYS", 101, "NOT_FOUND");
+        assert_eq!(
+            Some(SystemServiceCode::NotFound),
+            code.system_service_code()
+        );
+        assert!(server_error(code, "").is_not_found());
+    }
+
+    #[test]
+    fn client_error() {
+        let error = TgError::TimeoutError("test timeout".to_string());
+        assert!(error.is_timeout());
+        assert!(!error.is_not_found());
+        assert_eq!(None, error.server_message());
+        assert_eq!(None, error.sql_service_code());
     }
 }
@@ -1,6 +1,6 @@
 use std::{future::Future, pin::Pin, sync::Mutex, time::Duration};
 
-use tokio::task::JoinHandle;
+use tokio::task::{AbortHandle, JoinHandle};
 use tonic::async_trait;
 
 use crate::{
@@ -77,6 +77,9 @@ impl<T: Send> InnerJob<T> for SpawnInnerJob<T> {
             }
         };
 
+        // abort the task if this future is dropped (or timed out) before the task is finished
+        let _abort_guard = AbortGuard(handle.abort_handle());
+
         let join_result = if timeout.is_zero() {
             handle.await
         } else {
@@ -134,3 +137,11 @@ impl<T: Send> SpawnInnerJob<T> {
         }
     }
 }
+
+struct AbortGuard(AbortHandle);
+
+impl Drop for AbortGuard {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
@@ -125,7 +125,7 @@ impl<T: Send> Drop for WireSlotInnerJob<T> {
 
 impl<T: Send> WireSlotInnerJob<T> {
     fn dispose(&self) {
-        if self.slot_handle.exists_wire_response() {
+        if self.slot_handle.exists_wire_response() || self.slot_handle.is_cancelled() {
             return;
         }
 
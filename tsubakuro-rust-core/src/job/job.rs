@@ -1,4 +1,10 @@
-use std::{sync::Arc, time::Duration};
+use std::{
+    future::{Future, IntoFuture},
+    pin::Pin,
+    sync::Arc,
+    task::Poll,
+    time::Duration,
+};
 
 use super::cancel_job::CancelJob;
 
@@ -7,12 +13,14 @@ use crate::{
     error::TgError,
     job::inner::{
         convert_job::ConvertInnerJob,
+        inspect_job::{InspectInnerJob, JobInspector},
         spawn_job::{BoxFuture, SpawnInnerJob},
         value_job::ValueInnerJob,
         wire_slot_job::WireSlotInnerJob,
         InnerJob,
     },
     session::wire::{response::WireResponse, response_box::SlotEntryHandle, Wire},
+    timeout_error,
 };
 
 /// Job.
@@ -22,6 +30,10 @@ use crate::{
 ///
 /// **thread unsafe**
 ///
+/// `Job` can be `.await`ed directly. (equivalent to [`Self::take`])
+///
+/// If a job is dropped before the response is received, the job is canceled.
+///
 /// # Examples
 /// ```
 /// use tsubakuro_rust_core::prelude::*;
@@ -128,6 +140,188 @@ impl<T: Send + 'static> Job<T> {
         }
     }
 
+    /// Calls the inspector with the result when the result value is taken.
+    pub(crate) fn inspect(self, inspector: JobInspector<T>) -> Job<T> {
+        let inner = Box::new(InspectInnerJob::new(self.inner, inspector));
+
+        Job {
+            name: self.name,
+            inner,
+            default_timeout: self.default_timeout,
+            taked: self.taked,
+            closed: self.closed,
+        }
+    }
+
+    /// Converts the result value of this job.
+    ///
+    /// The converter is called when the result value is taken.
+    ///
+    /// # Examples
+    /// ```
+    /// use tsubakuro_rust_core::prelude::*;
+    ///
+    /// async fn example(job: Job<SqlExecuteResult>) -> Result<(), TgError> {
+    ///     let job = job.map(|execute_result| execute_result.rows());
+    ///     let rows = job.await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// since 0.11.0
+    pub fn map<R, F>(self, f: F) -> Job<R>
+    where
+        R: Send + 'static,
+        F: Fn(T) -> R + Send + 'static,
+    {
+        let name = self.name.clone();
+        self.convert(&name, Box::new(move |value| Ok(f(value))))
+    }
+
+    /// Retrieves the result values of all jobs.
+    ///
+    /// The values are returned in the order of the jobs.
+    /// If one of the jobs fails, the error is returned and the remaining jobs are canceled.
+    ///
+    /// # Examples
+    /// ```
+    /// use tsubakuro_rust_core::prelude::*;
+    ///
+    /// async fn example(client: &SqlClient, transaction: &Transaction) -> Result<(), TgError> {
+    ///     let job1 = client.execute_async(transaction, "insert into tb values(1, 'abc')").await?;
+    ///     let job2 = client.execute_async(transaction, "insert into tb values(2, 'def')").await?;
+    ///
+    ///     let execute_results = Job::join_all(vec![job1, job2]).await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// since 0.11.0
+    pub async fn join_all(jobs: Vec<Job<T>>) -> Result<Vec<T>, TgError> {
+        let mut values = Vec::with_capacity(jobs.len());
+        for mut job in jobs {
+            let value = job.take().await?;
+            values.push(value);
+        }
+        Ok(values)
+    }
+
+    /// Retrieves the result value of the job which is completed first.
+    ///
+    /// # Returns
+    /// - `Ok((index, value, remaining_jobs))` - index of the completed job, its result value, and the other jobs.
+    /// - `Err((error, remaining_jobs))` - error, and the jobs which are not completed.
+    ///
+    /// If the completed job fails, the error and the other jobs are returned.
+    /// The remaining jobs are not canceled until they are dropped.
+    ///
+    /// # Examples
+    /// ```
+    /// use tsubakuro_rust_core::prelude::*;
+    ///
+    /// async fn example(jobs: Vec<Job<SqlExecuteResult>>) -> Result<(), TgError> {
+    ///     let (index, execute_result, remaining_jobs) = match Job::first_completed(jobs).await {
+    ///         Ok(completed) => completed,
+    ///         Err((e, remaining_jobs)) => {
+    ///             for job in remaining_jobs {
+    ///                 job.close().await?;
+    ///             }
+    ///             return Err(e);
+    ///         }
+    ///     };
+    ///
+    ///     for job in remaining_jobs {
+    ///         job.close().await?;
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// since 0.11.0
+    pub async fn first_completed(
+        jobs: Vec<Job<T>>,
+    ) -> Result<(usize, T, Vec<Job<T>>), (TgError, Vec<Job<T>>)> {
+        Self::first_completed_for(jobs, Duration::ZERO).await
+    }
+
+    /// Retrieves the result value of the job which is completed first.
+    ///
+    /// If `timeout` is zero, waits without limit.
+    /// If no job is completed in time, the timeout error and all jobs are returned.
+    ///
+    /// See [`Self::first_completed`].
+    ///
+    /// since 0.11.0
+    pub async fn first_completed_for(
+        mut jobs: Vec<Job<T>>,
+        timeout: Duration,
+    ) -> Result<(usize, T, Vec<Job<T>>), (TgError, Vec<Job<T>>)> {
+        const FUNCTION_NAME: &str = "Job::first_completed()";
+
+        if jobs.is_empty() {
+            return Err((
+                client_error!(format!("{FUNCTION_NAME}: jobs is empty")),
+                jobs,
+            ));
+        }
+
+        let completed = Self::wait_first(&mut jobs, timeout).await;
+        match completed {
+            Some((index, Ok(_))) => {
+                let mut job = jobs.remove(index);
+                match job.take().await {
+                    Ok(value) => Ok((index, value, jobs)),
+                    Err(e) => Err((e, jobs)),
+                }
+            }
+            Some((index, Err(e))) => {
+                jobs.remove(index);
+                Err((e, jobs))
+            }
+            None => Err((timeout_error!(FUNCTION_NAME), jobs)),
+        }
+    }
+
+    /// Waits for the jobs at the same time, and returns the index of the job which is completed (or failed) first.
+    ///
+    /// Returns None if no job is completed in time.
+    async fn wait_first(
+        jobs: &mut [Job<T>],
+        timeout: Duration,
+    ) -> Option<(usize, Result<(), TgError>)> {
+        type WaitFuture<'a> = Pin<Box<dyn Future<Output = Result<bool, TgError>> + 'a>>;
+
+        let mut waits: Vec<Option<WaitFuture>> = jobs
+            .iter_mut()
+            .map(|job| Some(Box::pin(job.wait(timeout)) as WaitFuture))
+            .collect();
+        std::future::poll_fn(|cx| {
+            let mut pending = false;
+            for (index, slot) in waits.iter_mut().enumerate() {
+                let wait = match slot {
+                    Some(wait) => wait,
+                    None => continue,
+                };
+                match wait.as_mut().poll(cx) {
+                    Poll::Ready(Ok(true)) => return Poll::Ready(Some((index, Ok(())))),
+                    Poll::Ready(Err(e)) => return Poll::Ready(Some((index, Err(e)))),
+                    // timeout
+                    Poll::Ready(Ok(false)) => *slot = None,
+                    Poll::Pending => pending = true,
+                }
+            }
+            if pending {
+                Poll::Pending
+            } else {
+                Poll::Ready(None)
+            }
+        })
+        .await
+    }
+
     /// Get job name.
     pub fn name(&self) -> &String {
         &self.name
@@ -371,3 +565,197 @@ impl<T: Send + 'static> Job<T> {
         self.inner.set_fail_on_drop_error(value);
     }
 }
+
+/// Retrieves the result value with the default timeout. (equivalent to [`Job::take`])
+///
+/// If the future is dropped before the response is received, the job is canceled.
+///
+/// # Examples
+/// ```
+/// use tsubakuro_rust_core::prelude::*;
+///
+/// async fn example(client: &SqlClient, transaction: &Transaction) -> Result<(), TgError> {
+///     let sql = "insert into tb values(1, 'abc')";
+///     let execute_result = client.execute_async(transaction, sql).await?.await?;
+///
+///     Ok(())
+/// }
+/// ```
+///
+/// since 0.11.0
+impl<T: Send + 'static> IntoFuture for Job<T> {
+    type Output = Result<T, TgError>;
+    type IntoFuture = Pin<Box<dyn std::future::Future<Output = Result<T, TgError>>>>;
+
+    fn into_future(mut self) -> Self::IntoFuture {
+        Box::pin(async move { self.take().await })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    use super::*;
+
+    fn sleep_job(name: &str, millis: u64, value: i32) -> Job<i32> {
+        Job::supplier(
+            name,
+            Box::new(move |_| {
+                Box::pin(async move {
+                    tokio::time::sleep(Duration::from_millis(millis)).await;
+                    Ok(value)
+                })
+            }),
+            Duration::ZERO,
+        )
+    }
+
+    #[tokio::test]
+    async fn into_future() {
+        let job = Job::returns("test", 123);
+        assert_eq!(123, job.await.unwrap());
+
+        let job = sleep_job("test", 10, 456);
+        assert_eq!(456, job.await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn map() {
+        let job = Job::returns("test", 123).map(|value| value.to_string());
+        assert_eq!("test", job.name());
+        assert_eq!("123", job.await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn join_all() {
+        let jobs = vec![
+            sleep_job("job1", 50, 1),
+            sleep_job("job2", 10, 2),
+            Job::returns("job3", 3),
+        ];
+        assert_eq!(vec![1, 2, 3], Job::join_all(jobs).await.unwrap());
+
+        assert!(Job::<i32>::join_all(vec![]).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn first_completed() {
+        let jobs = vec![
+            sleep_job("job1", 1000, 1),
+            sleep_job("job2", 10, 2),
+            sleep_job("job3", 1000, 3),
+        ];
+        let (index, value, remaining_jobs) = Job::first_completed(jobs).await.unwrap();
+        assert_eq!(1, index);
+        assert_eq!(2, value);
+        assert_eq!(2, remaining_jobs.len());
+        assert_eq!("job1", remaining_jobs[0].name());
+        assert_eq!("job3", remaining_jobs[1].name());
+
+        assert!(Job::<i32>::first_completed(vec![]).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn first_completed_for() {
+        let jobs = vec![sleep_job("job1", 1000, 1), sleep_job("job2", 1000, 2)];
+        let (error, remaining_jobs) = Job::first_completed_for(jobs, Duration::from_millis(10))
+            .await
+            .unwrap_err();
+        assert!(matches!(error, TgError::TimeoutError(_)));
+        assert_eq!(2, remaining_jobs.len());
+
+        let (index, value, remaining_jobs) =
+            Job::first_completed_for(remaining_jobs, Duration::from_secs(10))
+                .await
+                .unwrap();
+        assert_eq!(0, index);
+        assert_eq!(1, value);
+        assert_eq!("job2", remaining_jobs[0].name());
+    }
+
+    #[tokio::test]
+    async fn first_completed_wire() {
+        use crate::{
+            jogasaki::proto::sql::common::Transaction as ProtoTransaction,
+            prelude::{ConnectionOption, SqlClient, Transaction},
+            session::tcp::test_server::TestServer,
+        };
+
+        let server = TestServer::start().await;
+        let (session, mut connection) = server.connect(&ConnectionOption::new()).await;
+        let client: SqlClient = session.make_client();
+        let transaction = Transaction::new(
+            session.clone(),
+            ProtoTransaction::default(),
+            "tx1".to_string(),
+            Duration::ZERO,
+        );
+
+        let mut jobs = Vec::new();
+        let mut slots = Vec::new();
+        for _ in 0..3 {
+            let job = client.rollback_async(&transaction).await.unwrap();
+            jobs.push(job);
+            let (slot, _) = connection.read_sql_request().await;
+            slots.push(slot);
+        }
+
+        let server_task = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            connection.write_sql_success(slots[1]).await;
+            connection
+        });
+        let (index, _, remaining_jobs) = Job::first_completed_for(jobs, Duration::from_secs(10))
+            .await
+            .unwrap();
+        assert_eq!(1, index);
+        assert_eq!(2, remaining_jobs.len());
+
+        // keep the connection open until the jobs are dropped
+        let _connection = server_task.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn first_completed_error() {
+        let failure: Job<i32> = Job::supplier(
+            "failure",
+            Box::new(|_| Box::pin(async { Err(client_error!("failure")) })),
+            Duration::ZERO,
+        );
+        let jobs = vec![
+            sleep_job("job1", 1000, 1),
+            failure,
+            sleep_job("job3", 1000, 3),
+        ];
+        let (error, remaining_jobs) = Job::first_completed(jobs).await.unwrap_err();
+        assert_eq!("failure", error.message());
+        assert_eq!(2, remaining_jobs.len());
+        assert_eq!("job1", remaining_jobs[0].name());
+        assert_eq!("job3", remaining_jobs[1].name());
+    }
+
+    #[tokio::test]
+    async fn drop_cancel() {
+        let finished = Arc::new(AtomicBool::new(false));
+        let flag = finished.clone();
+        let job: Job<()> = Job::supplier(
+            "test",
+            Box::new(move |_| {
+                let flag = flag.clone();
+                Box::pin(async move {
+                    tokio::time::sleep(Duration::from_millis(50)).await;
+                    flag.store(true, Ordering::SeqCst);
+                    Ok(())
+                })
+            }),
+            Duration::ZERO,
+        );
+
+        let result = tokio::time::timeout(Duration::from_millis(10), job.into_future()).await;
+        assert!(result.is_err());
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(!finished.load(Ordering::SeqCst));
+    }
+}
@@ -162,6 +162,9 @@ pub(crate) mod data_relay_grpc {
             pub(crate) mod blob_reference {
                 tonic::include_proto!("data_relay_grpc.proto.blob_relay.blob_reference");
             }
+            pub(crate) mod blob_relay_local {
+                tonic::include_proto!("data_relay_grpc.proto.blob_relay.blob_relay_local");
+            }
             pub(crate) mod blob_relay_streaming {
                 tonic::include_proto!("data_relay_grpc.proto.blob_relay.blob_relay_streaming");
             }
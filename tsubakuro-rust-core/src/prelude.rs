@@ -3,6 +3,7 @@ pub use crate::job::cancel_job::CancelJob;
 pub use crate::job::Job;
 pub use crate::jogasaki::proto::sql::common::AtomType;
 pub use crate::jogasaki::proto::sql::common::Column as SqlColumn;
+pub use crate::jogasaki::proto::sql::error::Code as SqlServiceCode;
 pub use crate::jogasaki::proto::sql::request::CommitOption;
 pub use crate::jogasaki::proto::sql::request::CommitStatus as CommitType;
 pub use crate::jogasaki::proto::sql::request::Parameter as SqlParameter;
@@ -15,11 +16,18 @@ pub use crate::jogasaki::proto::sql::response::TransactionStatus;
 pub use crate::service::core::core_service::CoreClient;
 pub use crate::service::endpoint::endpoint_broker::EndpointBrokerClient;
 pub use crate::service::lob::downloader::*;
+pub use crate::service::lob::reader::*;
+#[cfg(feature = "blob_relay_stub")]
+pub use crate::service::lob::relay::stub_server::BlobRelayStubServer;
+pub use crate::service::lob::upload_option::*;
 pub use crate::service::lob::uploader::*;
+pub use crate::service::lob::writer::*;
 pub use crate::service::ServiceMessageVersion;
 // pub use crate::service::sql::column::*;
 pub use crate::service::sql::execute_result::*;
 pub use crate::service::sql::explain::SqlExplainResult;
+pub use crate::service::sql::interceptor::{SqlInterceptContext, SqlInterceptor, SqlOperation};
+pub use crate::service::sql::migration::*;
 pub use crate::service::sql::prepare::parameter::*;
 pub use crate::service::sql::prepare::placeholder::*;
 pub use crate::service::sql::prepare::*;
@@ -33,22 +41,37 @@ pub use crate::service::sql::r#type::time_of_day::*;
 pub use crate::service::sql::r#type::time_of_day_with_time_zone::*;
 pub use crate::service::sql::r#type::time_point::*;
 pub use crate::service::sql::r#type::time_point_with_time_zone::*;
+pub use crate::service::sql::script::*;
+pub use crate::service::sql::search_path::*;
 pub use crate::service::sql::sql_client::LobOperation;
+pub use crate::service::sql::sql_text::{
+    positional_placeholder_name, replace_positional_placeholders, split_statements, SqlCodeChars,
+};
+pub use crate::service::sql::table_diff::*;
 pub use crate::service::sql::table_list::*;
 pub use crate::service::sql::table_metadata::*;
 pub use crate::service::sql::*;
 pub use crate::service::system::*;
 pub use crate::service::*;
 pub use crate::session::credential::*;
+pub use crate::session::drain::*;
 pub use crate::session::endpoint::*;
 pub use crate::session::lob_transfer_type::*;
 pub use crate::session::option::*;
+pub use crate::session::reconnect_option::*;
+pub use crate::session::resilient_session::*;
+#[cfg(feature = "fault_injection")]
+pub use crate::session::tcp::fault_injection::*;
 pub use crate::session::wire::wire::WireClient;
 pub use crate::session::Session;
 pub use crate::tateyama::proto::core::request::ShutdownType;
+pub use crate::tateyama::proto::diagnostics::Code as CoreServiceCode;
+pub use crate::tateyama::proto::system::diagnostic::ErrorCode as SystemServiceCode;
 pub use crate::tateyama::proto::system::response::SystemInfo;
+pub use crate::transaction::commit_handle::*;
 pub use crate::transaction::error_info::*;
 pub use crate::transaction::option::*;
 pub use crate::transaction::status::*;
+pub use crate::transaction::status_watch::*;
 pub use crate::transaction::Transaction;
 pub use crate::transaction::*;
@@ -32,5 +32,6 @@ impl From<crate::tateyama::proto::endpoint::response::Error> for DiagnosticCode
             code_number,
             name,
         )
+        .with_supplemental_text(&value.supplemental_text)
     }
 }
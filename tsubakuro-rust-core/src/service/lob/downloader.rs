@@ -5,8 +5,10 @@ use tonic::async_trait;
 
 use crate::error::TgError;
 
+use super::reader::{BlobReader, ClobReader};
+
 #[async_trait]
-pub(crate) trait LobDownloader {
+pub(crate) trait LobDownloader: Send {
     /// ## Returns
     /// - `Ok(0)` if the end of the stream is reached.
     async fn download_chunk(
@@ -119,7 +121,11 @@ impl BlobDownloader {
         Ok(n)
     }
 
-    async fn fill_buffer(&mut self, length: usize, timeout: Duration) -> Result<(), TgError> {
+    pub(crate) async fn fill_buffer(
+        &mut self,
+        length: usize,
+        timeout: Duration,
+    ) -> Result<(), TgError> {
         if self.eof {
             return Ok(());
         }
@@ -154,6 +160,28 @@ impl BlobDownloader {
 
         Ok(self.eof && self.buffer.is_empty())
     }
+
+    /// Converts into [`BlobReader`] which implements [`tokio::io::AsyncRead`].
+    ///
+    /// # Parameters
+    /// - `timeout` - timeout for each chunk download.
+    ///
+    /// since 0.11.0
+    pub fn into_reader(self, timeout: Duration) -> BlobReader {
+        BlobReader::new(self, timeout)
+    }
+
+    pub(crate) fn buffered(&self) -> &[u8] {
+        &self.buffer
+    }
+
+    pub(crate) fn consume(&mut self, amt: usize) {
+        self.buffer.advance(amt.min(self.buffer.len()));
+    }
+
+    pub(crate) fn is_inner_eof(&self) -> bool {
+        self.eof
+    }
 }
 
 /// CLOB downloader.
@@ -231,4 +259,18 @@ impl ClobDownloader {
     pub async fn is_eof(&mut self, timeout: Duration) -> Result<bool, TgError> {
         self.inner.is_eof(timeout).await
     }
+
+    /// Converts into [`ClobReader`] which implements [`tokio::io::AsyncBufRead`].
+    ///
+    /// # Parameters
+    /// - `timeout` - timeout for each chunk download.
+    ///
+    /// since 0.11.0
+    pub fn into_reader(self, timeout: Duration) -> ClobReader {
+        ClobReader::new(self, timeout)
+    }
+
+    pub(crate) fn into_blob_downloader(self) -> BlobDownloader {
+        self.inner
+    }
 }
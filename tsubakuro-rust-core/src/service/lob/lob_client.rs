@@ -12,8 +12,11 @@ use crate::{
     job::Job,
     service::{
         lob::{
-            downloader::LobDownloader, lob_transfer_info::LobTransferInfo,
-            privileged::client::PrivilegedLobClient, relay::client::RelayLobClient,
+            downloader::LobDownloader,
+            lob_transfer_info::LobTransferInfo,
+            privileged::client::PrivilegedLobClient,
+            relay::client::RelayLobClient,
+            upload_option::{retry_upload, LobUploadOption, LobUploadProgress},
             uploader::LobUploader,
         },
         sql::r#type::large_object::TgLargeObjectReference,
@@ -53,6 +56,37 @@ pub(crate) trait LobClient: Send + Sync {
 
     async fn upload_lob_file_async(&self, path: &Path) -> Result<Job<RemoteLob>, TgError>;
 
+    /// Uploads files one by one, retrying each file according to `option`.
+    ///
+    /// The data is not transferred by the client (e.g. privileged mode),
+    /// so the progress is reported once for each file when it is handed over.
+    ///
+    /// ## Returns
+    /// the results in the same order as `paths`.
+    async fn upload_lob_files(
+        &self,
+        paths: &[PathBuf],
+        option: &LobUploadOption,
+        timeout: Duration,
+    ) -> Vec<Result<RemoteLob, TgError>> {
+        let mut results = Vec::with_capacity(paths.len());
+        for (index, path) in paths.iter().enumerate() {
+            // the file may be readable only on the server
+            let size = tokio::fs::metadata(path).await.map(|m| m.len()).ok();
+            let result = retry_upload(option, path, |attempt| async move {
+                let lob = self.upload_lob_file(path, timeout).await?;
+                if let Some(size) = size {
+                    let progress = LobUploadProgress::new(index, path.clone(), size, size, attempt);
+                    option.notify_progress(progress);
+                }
+                Ok(lob)
+            })
+            .await;
+            results.push(result);
+        }
+        results
+    }
+
     async fn upload_lob(&self, value: &[u8], timeout: Duration) -> Result<RemoteLob, TgError>;
 
     async fn upload_lob_async(&self, value: &[u8]) -> Result<Job<RemoteLob>, TgError>;
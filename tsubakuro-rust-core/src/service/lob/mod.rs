@@ -4,8 +4,11 @@ pub(crate) mod downloader;
 pub(crate) mod lob_client;
 pub(crate) mod lob_transfer_info;
 pub(crate) mod privileged;
+pub(crate) mod reader;
 pub(crate) mod relay;
+pub(crate) mod upload_option;
 pub(crate) mod uploader;
+pub(crate) mod writer;
 
 fn storage_id(lob: &dyn TgLargeObjectReference) -> Result<u64, TgError> {
     use crate::jogasaki::proto::sql::common::LargeObjectProvider::*;
@@ -17,3 +20,10 @@ fn storage_id(lob: &dyn TgLargeObjectReference) -> Result<u64, TgError> {
         ))),
     }
 }
+
+fn to_io_error(e: TgError) -> std::io::Error {
+    match e {
+        TgError::TimeoutError(_) => std::io::Error::new(std::io::ErrorKind::TimedOut, e),
+        _ => std::io::Error::other(e),
+    }
+}
@@ -38,5 +38,6 @@ impl From<crate::tateyama::proto::blob_relay_privilege::response::Error> for Dia
             code_number,
             name,
         )
+        .with_supplemental_text(&value.supplemental_text)
     }
 }
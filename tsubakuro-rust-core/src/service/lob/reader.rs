@@ -0,0 +1,406 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{ready, Context, Poll},
+    time::Duration,
+};
+
+use tokio::io::{AsyncBufRead, AsyncRead, ReadBuf};
+
+use crate::error::TgError;
+
+use super::{
+    downloader::{BlobDownloader, ClobDownloader},
+    to_io_error,
+};
+
+/// The default size of a chunk to download.
+const DEFAULT_CHUNK_SIZE: usize = 64 * 1024;
+
+type FillFuture = Pin<Box<dyn Future<Output = (BlobDownloader, Result<(), TgError>)> + Send>>;
+
+enum ReaderState {
+    Idle(BlobDownloader),
+    Filling(FillFuture),
+    Closed,
+}
+
+/// BLOB reader.
+///
+/// Implements [`AsyncRead`] and [`AsyncBufRead`] over [`BlobDownloader`].
+///
+/// # Examples
+/// ```
+/// use tsubakuro_rust_core::prelude::*;
+///
+/// async fn example(client: &SqlClient, transaction: &Transaction, blob: &TgBlobReference) -> Result<u64, TgError> {
+///     let timeout = std::time::Duration::from_secs(10);
+///     let downloader = client.create_blob_downloader(transaction, blob, timeout).await?;
+///     let mut reader = downloader.into_reader(timeout);
+///
+///     let mut file = tokio::fs::File::create("/path/to/file").await.unwrap();
+///     let size = tokio::io::copy(&mut reader, &mut file).await.unwrap();
+///
+///     Ok(size)
+/// }
+/// ```
+///
+/// since 0.11.0
+pub struct BlobReader {
+    state: ReaderState,
+    chunk_size: usize,
+    timeout: Duration,
+}
+
+impl std::fmt::Debug for BlobReader {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BlobReader")
+            .field("chunk_size", &self.chunk_size)
+            .field("timeout", &self.timeout)
+            .finish()
+    }
+}
+
+impl BlobReader {
+    pub(crate) fn new(downloader: BlobDownloader, timeout: Duration) -> BlobReader {
+        BlobReader {
+            state: ReaderState::Idle(downloader),
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            timeout,
+        }
+    }
+
+    /// Set the size of a chunk to download at once.
+    pub fn set_chunk_size(&mut self, chunk_size: usize) {
+        self.chunk_size = chunk_size.max(1);
+    }
+
+    /// Get the size of a chunk to download at once.
+    pub fn chunk_size(&self) -> usize {
+        self.chunk_size
+    }
+
+    /// Returns the underlying downloader.
+    ///
+    /// Returns `None` if a download is in progress or an error occurred.
+    pub fn into_inner(self) -> Option<BlobDownloader> {
+        match self.state {
+            ReaderState::Idle(downloader) => Some(downloader),
+            _ => None,
+        }
+    }
+
+    /// Fills the buffer until it contains more than `min_len` bytes or the end of the stream is reached.
+    fn poll_fill(&mut self, cx: &mut Context<'_>, min_len: usize) -> Poll<std::io::Result<()>> {
+        loop {
+            match &mut self.state {
+                ReaderState::Idle(downloader) => {
+                    if downloader.buffered().len() > min_len || downloader.is_inner_eof() {
+                        return Poll::Ready(Ok(()));
+                    }
+
+                    let ReaderState::Idle(mut downloader) =
+                        std::mem::replace(&mut self.state, ReaderState::Closed)
+                    else {
+                        unreachable!()
+                    };
+                    let length = self.chunk_size.max(min_len + 1);
+                    let timeout = self.timeout;
+                    self.state = ReaderState::Filling(Box::pin(async move {
+                        let result = downloader.fill_buffer(length, timeout).await;
+                        (downloader, result)
+                    }));
+                }
+                ReaderState::Filling(future) => {
+                    let (downloader, result) = ready!(future.as_mut().poll(cx));
+                    match result {
+                        Ok(_) => self.state = ReaderState::Idle(downloader),
+                        Err(e) => {
+                            self.state = ReaderState::Closed;
+                            return Poll::Ready(Err(to_io_error(e)));
+                        }
+                    }
+                }
+                ReaderState::Closed => {
+                    return Poll::Ready(Err(std::io::Error::other("BlobReader already failed")))
+                }
+            }
+        }
+    }
+
+    fn buffered(&self) -> &[u8] {
+        match &self.state {
+            ReaderState::Idle(downloader) => downloader.buffered(),
+            _ => &[],
+        }
+    }
+}
+
+impl AsyncBufRead for BlobReader {
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<&[u8]>> {
+        let this = self.get_mut();
+        ready!(this.poll_fill(cx, 0))?;
+        Poll::Ready(Ok(this.buffered()))
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        if let ReaderState::Idle(downloader) = &mut self.get_mut().state {
+            downloader.consume(amt);
+        }
+    }
+}
+
+impl AsyncRead for BlobReader {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        let src = ready!(self.as_mut().poll_fill_buf(cx))?;
+        let n = src.len().min(buf.remaining());
+        buf.put_slice(&src[..n]);
+        self.consume(n);
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// CLOB reader.
+///
+/// Implements [`AsyncRead`] and [`AsyncBufRead`] over [`ClobDownloader`].
+///
+/// The data is returned at the boundaries of UTF-8 characters,
+/// that is, a multibyte character is never split between two reads.
+///
+/// # Examples
+/// ```
+/// use tokio::io::AsyncBufReadExt;
+/// use tsubakuro_rust_core::prelude::*;
+///
+/// async fn example(client: &SqlClient, transaction: &Transaction, clob: &TgClobReference) -> Result<(), TgError> {
+///     let timeout = std::time::Duration::from_secs(10);
+///     let downloader = client.create_clob_downloader(transaction, clob, timeout).await?;
+///     let reader = downloader.into_reader(timeout);
+///
+///     let mut lines = reader.lines();
+///     while let Some(line) = lines.next_line().await.unwrap() {
+///         println!("{line}");
+///     }
+///
+///     Ok(())
+/// }
+/// ```
+///
+/// since 0.11.0
+#[derive(Debug)]
+pub struct ClobReader {
+    inner: BlobReader,
+    /// length of the buffered bytes already validated as UTF-8
+    valid_len: usize,
+}
+
+impl ClobReader {
+    pub(crate) fn new(downloader: ClobDownloader, timeout: Duration) -> ClobReader {
+        ClobReader {
+            inner: BlobReader::new(downloader.into_blob_downloader(), timeout),
+            valid_len: 0,
+        }
+    }
+
+    /// Set the size of a chunk to download at once.
+    pub fn set_chunk_size(&mut self, chunk_size: usize) {
+        self.inner.set_chunk_size(chunk_size);
+    }
+
+    /// Get the size of a chunk to download at once.
+    pub fn chunk_size(&self) -> usize {
+        self.inner.chunk_size()
+    }
+}
+
+/// Returns the length of the leading bytes which consist of complete UTF-8 characters.
+///
+/// Returns `Err` if the bytes start with an invalid sequence.
+fn utf8_complete_len(bytes: &[u8]) -> Result<usize, std::str::Utf8Error> {
+    match std::str::from_utf8(bytes) {
+        Ok(_) => Ok(bytes.len()),
+        Err(e) if e.valid_up_to() > 0 || e.error_len().is_none() => Ok(e.valid_up_to()),
+        Err(e) => Err(e),
+    }
+}
+
+impl AsyncBufRead for ClobReader {
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<&[u8]>> {
+        let this = self.get_mut();
+        if this.valid_len > 0 {
+            return Poll::Ready(Ok(&this.inner.buffered()[..this.valid_len]));
+        }
+
+        let inner = &mut this.inner;
+        let mut min_len = 0;
+        let len = loop {
+            ready!(inner.poll_fill(cx, min_len))?;
+            let buffered = inner.buffered();
+            let len = utf8_complete_len(buffered)
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+            if len > 0 || buffered.is_empty() {
+                break len;
+            }
+            if buffered.len() <= min_len {
+                // end of stream within a character
+                return Poll::Ready(Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    "incomplete UTF-8 sequence at the end of CLOB",
+                )));
+            }
+            min_len = buffered.len();
+        };
+        this.valid_len = len;
+        Poll::Ready(Ok(&this.inner.buffered()[..len]))
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        let this = self.get_mut();
+        this.valid_len -= amt.min(this.valid_len);
+        Pin::new(&mut this.inner).consume(amt);
+    }
+}
+
+impl AsyncRead for ClobReader {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        let src = ready!(self.as_mut().poll_fill_buf(cx))?;
+        let mut n = src.len().min(buf.remaining());
+        if n < src.len() {
+            // do not split a character
+            while n > 0 && (src[n] & 0xc0) == 0x80 {
+                n -= 1;
+            }
+            if n == 0 && buf.remaining() > 0 {
+                return Poll::Ready(Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    "buffer too small for a UTF-8 character",
+                )));
+            }
+        }
+        buf.put_slice(&src[..n]);
+        self.consume(n);
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Write;
+
+    use tempfile::NamedTempFile;
+    use tokio::io::{AsyncBufReadExt, AsyncReadExt};
+
+    use super::*;
+    use crate::service::lob::privileged::downloader::PrivilegedLobDownloader;
+
+    async fn blob_reader(file: &NamedTempFile) -> BlobReader {
+        let target = PrivilegedLobDownloader::new(file.path()).await.unwrap();
+        BlobDownloader::new(Box::new(target)).into_reader(Duration::ZERO)
+    }
+
+    async fn clob_reader(file: &NamedTempFile) -> ClobReader {
+        let target = PrivilegedLobDownloader::new(file.path()).await.unwrap();
+        ClobDownloader::new(Box::new(target)).into_reader(Duration::ZERO)
+    }
+
+    #[tokio::test]
+    async fn blob_read_to_end() {
+        let mut file = NamedTempFile::new().unwrap();
+        let value: Vec<u8> = (0..=255).cycle().take(100_000).collect();
+        file.write_all(&value).unwrap();
+
+        let mut reader = blob_reader(&file).await;
+        reader.set_chunk_size(1000);
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf).await.unwrap();
+        assert_eq!(value, buf);
+    }
+
+    #[tokio::test]
+    async fn blob_copy() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(b"0123456789").unwrap();
+
+        let mut reader = blob_reader(&file).await;
+        let mut buf = Vec::new();
+        let size = tokio::io::copy(&mut reader, &mut buf).await.unwrap();
+        assert_eq!(10, size);
+        assert_eq!(b"0123456789".to_vec(), buf);
+    }
+
+    #[tokio::test]
+    async fn blob_empty() {
+        let file = NamedTempFile::new().unwrap();
+
+        let mut reader = blob_reader(&file).await;
+        let mut buf = Vec::new();
+        let size = reader.read_to_end(&mut buf).await.unwrap();
+        assert_eq!(0, size);
+    }
+
+    #[tokio::test]
+    async fn clob_utf8_boundary() {
+        let mut file = NamedTempFile::new().unwrap();
+        let value = "aあいうえおb".repeat(10);
+        file.write_all(value.as_bytes()).unwrap();
+
+        // chunk size which splits multibyte characters
+        let mut reader = clob_reader(&file).await;
+        reader.set_chunk_size(2);
+        loop {
+            let buf = reader.fill_buf().await.unwrap();
+            if buf.is_empty() {
+                break;
+            }
+            assert!(std::str::from_utf8(buf).is_ok());
+            let n = buf.len();
+            Pin::new(&mut reader).consume(n);
+        }
+
+        let mut reader = clob_reader(&file).await;
+        reader.set_chunk_size(4);
+        let mut s = String::new();
+        reader.read_to_string(&mut s).await.unwrap();
+        assert_eq!(value, s);
+    }
+
+    #[tokio::test]
+    async fn clob_lines() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all("あいう\nえお\n".as_bytes()).unwrap();
+
+        let mut reader = clob_reader(&file).await;
+        reader.set_chunk_size(1);
+        let mut lines = reader.lines();
+        assert_eq!(Some("あいう".to_string()), lines.next_line().await.unwrap());
+        assert_eq!(Some("えお".to_string()), lines.next_line().await.unwrap());
+        assert_eq!(None, lines.next_line().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn clob_invalid_utf8() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(&[b'a', 0xe3, 0x81]).unwrap();
+
+        let mut reader = clob_reader(&file).await;
+        let mut buf = Vec::new();
+        let e = reader.read_to_end(&mut buf).await.unwrap_err();
+        assert_eq!(std::io::ErrorKind::InvalidData, e.kind());
+
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(&[b'a', 0xff, b'b']).unwrap();
+
+        let mut reader = clob_reader(&file).await;
+        let mut buf = Vec::new();
+        let e = reader.read_to_end(&mut buf).await.unwrap_err();
+        assert_eq!(std::io::ErrorKind::InvalidData, e.kind());
+    }
+}
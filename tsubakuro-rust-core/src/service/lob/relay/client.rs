@@ -5,9 +5,13 @@ use std::{
 };
 
 use log::{debug, trace, warn};
+use sha1::{Digest, Sha1};
+use tokio::{io::AsyncReadExt, sync::Semaphore, task::JoinSet};
+use tokio_stream::wrappers::ReceiverStream;
 use tonic::{async_trait, transport::ClientTlsConfig, Streaming};
 
 use crate::{
+    client_error,
     data_relay_grpc::proto::blob_relay::{
         blob_reference::BlobReference as RelayLobReference,
         blob_relay_streaming::{
@@ -24,6 +28,7 @@ use crate::{
             lob_client::{LobClient, LobClientMethod, RemoteLob},
             relay::{downloader::RelayLobDownloader, uploader::RelayLobUploader},
             storage_id,
+            upload_option::{retry_upload, LobUploadOption, LobUploadProgress},
             uploader::LobUploader,
         },
         sql::r#type::large_object::TgLargeObjectReference,
@@ -203,6 +208,63 @@ impl LobClient for RelayLobClient {
         Ok(job)
     }
 
+    async fn upload_lob_files(
+        &self,
+        paths: &[PathBuf],
+        option: &LobUploadOption,
+        timeout: Duration,
+    ) -> Vec<Result<RemoteLob, TgError>> {
+        const FUNCTION_NAME: &str = "RelayLobClient::upload_lob_files()";
+        trace!("{} start. size={}", FUNCTION_NAME, paths.len());
+
+        let semaphore = Arc::new(Semaphore::new(option.parallelism()));
+        let mut tasks = JoinSet::new();
+        for (index, path) in paths.iter().enumerate() {
+            let semaphore = semaphore.clone();
+            let grpc_client = self.grpc_client.clone();
+            let blob_session_id = self.info.blob_session_id;
+            let chunk_size = self.stream_chunk_size;
+            let option = option.clone();
+            let path = path.clone();
+            tasks.spawn(async move {
+                let _permit = semaphore.acquire_owned().await;
+                let result = retry_upload(&option, &path, |attempt| {
+                    Self::upload_file_streaming(
+                        grpc_client.clone(),
+                        blob_session_id,
+                        UploadTarget {
+                            index,
+                            path: path.clone(),
+                            attempt,
+                        },
+                        chunk_size,
+                        &option,
+                        timeout,
+                    )
+                })
+                .await;
+                let result = result
+                    .map(|lob| RemoteLob::LobReference(lob.storage_id, lob.object_id, lob.tag));
+                (index, result)
+            });
+        }
+
+        let mut results: Vec<Option<Result<RemoteLob, TgError>>> =
+            (0..paths.len()).map(|_| None).collect();
+        while let Some(result) = tasks.join_next().await {
+            match result {
+                Ok((index, result)) => results[index] = Some(result),
+                Err(e) => warn!("{} upload task error. {}", FUNCTION_NAME, e),
+            }
+        }
+
+        trace!("{} end", FUNCTION_NAME);
+        results
+            .into_iter()
+            .map(|result| result.unwrap_or_else(|| Err(io_error!("upload task aborted"))))
+            .collect()
+    }
+
     async fn upload_lob(&self, value: &[u8], timeout: Duration) -> Result<RemoteLob, TgError> {
         const FUNCTION_NAME: &str = "RelayLobClient::upload_lob()";
         trace!("{} start", FUNCTION_NAME);
@@ -415,6 +477,154 @@ impl RelayLobClient {
         Ok(remote_lob)
     }
 
+    /// Uploads a file without reading the whole file into memory.
+    ///
+    /// The SHA-1 digest of the file is computed while uploading,
+    /// and compared with the digest of the uploaded data if `option.verify_checksum()`.
+    async fn upload_file_streaming(
+        mut grpc_client: BlobRelayStreamingClient<tonic::transport::Channel>,
+        blob_session_id: u64,
+        target: UploadTarget,
+        chunk_size: usize,
+        option: &LobUploadOption,
+        timeout: Duration,
+    ) -> Result<RelayLobReference, TgError> {
+        // errors on opening the file are not retryable (client_error)
+        let mut file = tokio::fs::File::open(&target.path)
+            .await
+            .map_err(|e| client_error!("Failed to open lob file", e))?;
+        let total = file
+            .metadata()
+            .await
+            .map_err(|e| client_error!("Failed to read lob file metadata", e))?
+            .len();
+
+        let (tx, rx) = tokio::sync::mpsc::channel(4);
+        let reader_option = option.clone();
+        let path = target.path.clone();
+        let mut reader = tokio::spawn(async move {
+            let first_request = Self::create_upload_metadata_request(blob_session_id, Some(total));
+            if tx.send(first_request).await.is_err() {
+                return Err(io_error!("Upload stream closed"));
+            }
+
+            let mut hasher = Sha1::new();
+            let mut transferred = 0u64;
+            loop {
+                let chunk = read_chunk(&mut file, chunk_size).await?;
+                if chunk.is_empty() {
+                    break;
+                }
+                hasher.update(&chunk);
+                transferred += chunk.len() as u64;
+                if tx
+                    .send(Self::create_upload_chunk_request(chunk))
+                    .await
+                    .is_err()
+                {
+                    return Err(io_error!("Upload stream closed"));
+                }
+                reader_option.notify_progress(LobUploadProgress::new(
+                    target.index,
+                    target.path.clone(),
+                    transferred,
+                    total,
+                    target.attempt,
+                ));
+            }
+            if transferred != total {
+                return Err(client_error!(format!(
+                    "lob file size changed during upload. expected={total}, actual={transferred}"
+                )));
+            }
+            Ok(hasher.finalize().to_vec())
+        });
+
+        let put = grpc_client.put(tonic::Request::new(ReceiverStream::new(rx)));
+        let result = if timeout.is_zero() {
+            Ok(put.await)
+        } else {
+            tokio::time::timeout(timeout, put).await
+        };
+        let response = match result {
+            Ok(Ok(response)) => response,
+            Ok(Err(status)) => {
+                reader.abort();
+                return Err(io_error!("Failed to upload to blob relay service", status));
+            }
+            Err(_) => {
+                reader.abort();
+                return Err(timeout_error!("RelayLobClient::upload_file_streaming()"));
+            }
+        };
+        let digest = (&mut reader)
+            .await
+            .map_err(|e| io_error!("Failed to read lob file", e))??;
+
+        let lob_ref = response.into_inner().blob.ok_or_else(|| {
+            io_error!("Failed to upload to blob relay service: missing blob reference in response")
+        })?;
+
+        if option.verify_checksum() {
+            let uploaded_digest =
+                Self::uploaded_digest(grpc_client, blob_session_id, &lob_ref, timeout).await?;
+            if digest != uploaded_digest {
+                return Err(io_error!(format!(
+                    "checksum mismatch in blob relay service. path={:?}",
+                    path
+                )));
+            }
+        }
+
+        Ok(lob_ref)
+    }
+
+    /// Reads the uploaded data back and computes its SHA-1 digest.
+    async fn uploaded_digest(
+        mut grpc_client: BlobRelayStreamingClient<tonic::transport::Channel>,
+        blob_session_id: u64,
+        lob_ref: &RelayLobReference,
+        timeout: Duration,
+    ) -> Result<Vec<u8>, TgError> {
+        use crate::data_relay_grpc::proto::blob_relay::blob_relay_streaming::{
+            get_streaming_request::ContextId, get_streaming_response::Payload, GetStreamingRequest,
+        };
+
+        let request = GetStreamingRequest {
+            api_version: API_VERSION,
+            blob: Some(*lob_ref),
+            context_id: Some(ContextId::SessionId(blob_session_id)),
+        };
+
+        let future = async {
+            let mut stream = grpc_client
+                .get(request)
+                .await
+                .map_err(|e| io_error!("Failed to verify upload in blob relay service", e))?
+                .into_inner();
+
+            let mut hasher = Sha1::new();
+            while let Some(response) = stream
+                .message()
+                .await
+                .map_err(|e| io_error!("Failed to verify upload in blob relay service", e))?
+            {
+                if let Some(Payload::Chunk(chunk)) = response.payload {
+                    hasher.update(&chunk);
+                }
+            }
+            Ok(hasher.finalize().to_vec())
+        };
+
+        if timeout.is_zero() {
+            future.await
+        } else {
+            tokio::time::timeout(timeout, future)
+                .await
+                .map_err(|_| timeout_error!("RelayLobClient::uploaded_digest()"))?
+        }
+    }
+
     pub(crate) fn create_upload_metadata_request(
         blob_session_id: u64,
         lob_size: Option<u64>,
@@ -543,3 +753,192 @@ impl RelayLobClient {
         Ok(stream)
     }
 }
+
+struct UploadTarget {
+    index: usize,
+    path: PathBuf,
+    attempt: u32,
+}
+
+/// Reads up to `chunk_size` bytes.
+///
+/// ## Returns
+/// empty if the end of the file is reached.
+async fn read_chunk(file: &mut tokio::fs::File, chunk_size: usize) -> Result<Vec<u8>, TgError> {
+    let mut chunk = Vec::with_capacity(chunk_size);
+    while chunk.len() < chunk_size {
+        let n = (&mut *file)
+            .take((chunk_size - chunk.len()) as u64)
+            .read_to_end(&mut chunk)
+            .await
+            .map_err(|e| io_error!("Failed to read lob file", e))?;
+        if n == 0 {
+            break;
+        }
+    }
+    Ok(chunk)
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::{prelude::ConnectionOption, service::lob::relay::stub_server::BlobRelayStubServer};
+
+    fn info(endpoint: String) -> BlobRelayServiceInfo {
+        let mut parameters = HashMap::new();
+        parameters.insert("stream_chunk_size".to_string(), "7".to_string());
+        BlobRelayServiceInfo {
+            blob_session_id: 1,
+            endpoint,
+            secure: false,
+            medium: "stream".to_string(),
+            parameters,
+        }
+    }
+
+    async fn client(server: &BlobRelayStubServer) -> RelayLobClient {
+        let mut connection_option = ConnectionOption::new();
+        server.configure(&mut connection_option);
+        let option = RelayLobClientOption::new(
+            connection_option.blob_relay_service_endpoint().cloned(),
+            connection_option.blob_relay_service_tls_config().cloned(),
+        );
+        RelayLobClient::new(info("dns:///localhost:0".to_string()), &option)
+            .await
+            .unwrap()
+    }
+
+    async fn download(client: &RelayLobClient, lob: RemoteLob) -> Result<Vec<u8>, TgError> {
+        let RemoteLob::LobReference(storage_id, object_id, tag) = lob else {
+            panic!("unexpected {:?}", lob);
+        };
+        RelayLobClient::download(
+            client.grpc_client.clone(),
+            1,
+            storage_id,
+            object_id,
+            tag,
+            Duration::ZERO,
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn upload_download() {
+        let dir = tempfile::tempdir().unwrap();
+        let server = BlobRelayStubServer::start(dir.path()).await.unwrap();
+        server.set_chunk_size(5);
+        let client = client(&server).await;
+        assert_eq!(7, client.stream_chunk_size);
+
+        let value: Vec<u8> = (0..=255).collect();
+        let lob = client.upload_lob(&value, Duration::ZERO).await.unwrap();
+        assert_eq!(value, download(&client, lob).await.unwrap());
+
+        let lob = client.upload_lob(&[], Duration::ZERO).await.unwrap();
+        assert!(download(&client, lob).await.unwrap().is_empty());
+
+        let lob = RemoteLob::LobReference(1, 12345, 0);
+        let error = download(&client, lob).await.unwrap_err();
+        assert!(matches!(error, TgError::IoError(..)), "{:?}", error);
+
+        server.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn uploader() {
+        let dir = tempfile::tempdir().unwrap();
+        let server = BlobRelayStubServer::start(dir.path()).await.unwrap();
+        let client = client(&server).await;
+
+        let uploader = client.create_lob_uploader().await.unwrap();
+        uploader.upload_chunk(b"abc", Duration::ZERO).await.unwrap();
+        uploader.upload_chunk(b"def", Duration::ZERO).await.unwrap();
+        let lob = uploader.finish(Duration::ZERO).await.unwrap();
+        assert_eq!(b"abcdef".to_vec(), download(&client, lob).await.unwrap());
+
+        server.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn upload_files_retry() {
+        let dir = tempfile::tempdir().unwrap();
+        let server = BlobRelayStubServer::start(dir.path().join("store"))
+            .await
+            .unwrap();
+        let client = client(&server).await;
+
+        let file1 = dir.path().join("file1.bin");
+        std::fs::write(&file1, b"0123456789abcdef").unwrap();
+        let file2 = dir.path().join("file2.bin");
+        std::fs::write(&file2, b"").unwrap();
+        let missing = dir.path().join("missing.bin");
+
+        let mut option = LobUploadOption::new();
+        option.set_initial_backoff(Duration::from_millis(1));
+        option.set_verify_checksum(true);
+        option.set_parallelism(1);
+        server.inject_failures(2);
+
+        let paths = vec![file1, file2, missing];
+        let results = client
+            .upload_lob_files(&paths, &option, Duration::ZERO)
+            .await;
+        assert_eq!(3, results.len());
+        let mut results = results.into_iter();
+
+        let lob = results.next().unwrap().unwrap();
+        assert_eq!(
+            b"0123456789abcdef".to_vec(),
+            download(&client, lob).await.unwrap()
+        );
+        let lob = results.next().unwrap().unwrap();
+        assert!(download(&client, lob).await.unwrap().is_empty());
+        let error = results.next().unwrap().unwrap_err();
+        assert!(matches!(error, TgError::ClientError(..)), "{:?}", error);
+
+        server.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn tls() {
+        let testdata = Path::new(env!("CARGO_MANIFEST_DIR")).join("testdata/blob_relay");
+        let cert_pem = std::fs::read(testdata.join("server_cert.pem")).unwrap();
+        let key_pem = std::fs::read(testdata.join("server_key.pem")).unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let server = BlobRelayStubServer::start_tls(dir.path(), &cert_pem, &key_pem)
+            .await
+            .unwrap();
+        assert!(server.endpoint().starts_with("https://localhost:"));
+
+        let mut connection_option = ConnectionOption::new();
+        server.configure(&mut connection_option);
+        connection_option
+            .set_blob_relay_service_ca_cert_pem_file(testdata.join("ca_cert.pem"))
+            .unwrap();
+        let option = RelayLobClientOption::new(
+            connection_option.blob_relay_service_endpoint().cloned(),
+            connection_option.blob_relay_service_tls_config().cloned(),
+        );
+        let client = RelayLobClient::new(info(server.endpoint()), &option)
+            .await
+            .unwrap();
+
+        let lob = client.upload_lob(b"secure", Duration::ZERO).await.unwrap();
+        assert_eq!(b"secure".to_vec(), download(&client, lob).await.unwrap());
+
+        // without the CA certificate
+        let option = RelayLobClientOption::new(Some(server.endpoint()), None);
+        let client = RelayLobClient::new(info(server.endpoint()), &option).await;
+        let result = match client {
+            Ok(client) => client.upload_lob(b"secure", Duration::ZERO).await,
+            Err(e) => Err(e),
+        };
+        assert!(result.is_err());
+
+        server.shutdown().await;
+    }
+}
@@ -1,3 +1,5 @@
 pub(crate) mod client;
 pub(crate) mod downloader;
+#[cfg(any(test, feature = "blob_relay_stub"))]
+pub(crate) mod stub_server;
 pub(crate) mod uploader;
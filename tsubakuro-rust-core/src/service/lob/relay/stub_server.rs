@@ -0,0 +1,551 @@
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU32, AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use log::{debug, trace};
+use tokio::{io::AsyncReadExt, sync::oneshot, task::JoinHandle};
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::{
+    async_trait,
+    transport::{server::TcpIncoming, Identity, Server, ServerTlsConfig},
+    Request, Response, Status, Streaming,
+};
+
+use crate::{
+    data_relay_grpc::proto::blob_relay::{
+        blob_reference::BlobReference,
+        blob_relay_local::{
+            blob_relay_local_server::{BlobRelayLocal, BlobRelayLocalServer},
+            BlobFile, GetLocalRequest, GetLocalResponse, PutLocalRequest, PutLocalResponse,
+        },
+        blob_relay_streaming::{
+            blob_relay_streaming_server::{BlobRelayStreaming, BlobRelayStreamingServer},
+            get_streaming_response, put_streaming_request, GetStreamingRequest,
+            GetStreamingResponse, PutStreamingRequest, PutStreamingResponse,
+        },
+    },
+    error::TgError,
+    io_error,
+    prelude::ConnectionOption,
+};
+
+const API_VERSION: u64 = 1;
+
+/// LIMESTONE_BLOB_STORE
+const STORAGE_ID: u64 = 1;
+
+const DEFAULT_CHUNK_SIZE: usize = 64 * 1024;
+
+/// In-process stand-in for the blob relay service.
+///
+/// Implements `BlobRelayStreaming` and `BlobRelayLocal` of `data_relay_grpc`,
+/// storing the BLOBs as files in the specified directory.
+/// The context id (session id and transaction id) is not validated.
+///
+/// Intended for tests and development only.
+/// Requires the `blob_relay_stub` feature.
+///
+/// # Examples
+/// ```ignore
+/// use tsubakuro_rust_core::prelude::*;
+///
+/// async fn example(endpoint: Endpoint) -> Result<(), TgError> {
+///     let dir = std::env::temp_dir().join("blob_relay_stub");
+///     let server = BlobRelayStubServer::start(&dir).await?;
+///
+///     let mut connection_option = ConnectionOption::new();
+///     connection_option.set_endpoint(endpoint);
+///     server.configure(&mut connection_option);
+///
+///     // connect and use BLOB
+///
+///     server.shutdown().await;
+///     Ok(())
+/// }
+/// ```
+///
+/// since 0.11.0
+pub struct BlobRelayStubServer {
+    local_addr: SocketAddr,
+    secure: bool,
+    state: Arc<StubState>,
+    shutdown: Option<oneshot::Sender<()>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl std::fmt::Debug for BlobRelayStubServer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BlobRelayStubServer")
+            .field("local_addr", &self.local_addr)
+            .field("secure", &self.secure)
+            .field("directory", &self.state.directory)
+            .finish()
+    }
+}
+
+impl BlobRelayStubServer {
+    /// Starts the server on a random port of the loopback address.
+    ///
+    /// `directory` is created if it does not exist.
+    pub async fn start<T: AsRef<Path>>(directory: T) -> Result<BlobRelayStubServer, TgError> {
+        Self::start_server(directory.as_ref(), None).await
+    }
+
+    /// Starts the server with TLS on a random port of the loopback address.
+    ///
+    /// The server certificate should be valid for `localhost`.
+    pub async fn start_tls<T: AsRef<Path>>(
+        directory: T,
+        cert_pem: &[u8],
+        key_pem: &[u8],
+    ) -> Result<BlobRelayStubServer, TgError> {
+        let identity = Identity::from_pem(cert_pem, key_pem);
+        let tls_config = ServerTlsConfig::new().identity(identity);
+        Self::start_server(directory.as_ref(), Some(tls_config)).await
+    }
+
+    async fn start_server(
+        directory: &Path,
+        tls_config: Option<ServerTlsConfig>,
+    ) -> Result<BlobRelayStubServer, TgError> {
+        const FUNCTION_NAME: &str = "BlobRelayStubServer::start()";
+        trace!("{} start", FUNCTION_NAME);
+
+        std::fs::create_dir_all(directory)
+            .map_err(|e| io_error!("Failed to create blob relay stub directory", e))?;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .map_err(|e| io_error!("Failed to bind blob relay stub server", e))?;
+        let local_addr = listener
+            .local_addr()
+            .map_err(|e| io_error!("Failed to bind blob relay stub server", e))?;
+
+        let secure = tls_config.is_some();
+        let mut builder = Server::builder();
+        if let Some(tls_config) = tls_config {
+            builder = builder
+                .tls_config(tls_config)
+                .map_err(|e| io_error!("Failed to configure TLS", e))?;
+        }
+
+        let state = Arc::new(StubState::new(directory.to_path_buf()));
+        let router = builder
+            .add_service(BlobRelayStreamingServer::new(StubService(state.clone())))
+            .add_service(BlobRelayLocalServer::new(StubService(state.clone())));
+
+        let (shutdown, shutdown_receiver) = oneshot::channel();
+        let handle = tokio::spawn(async move {
+            let signal = async {
+                let _ = shutdown_receiver.await;
+            };
+            if let Err(e) = router
+                .serve_with_incoming_shutdown(TcpIncoming::from(listener), signal)
+                .await
+            {
+                debug!("blob relay stub server error. {}", e);
+            }
+        });
+
+        debug!("blob relay stub server started. address={}", local_addr);
+        trace!("{} end", FUNCTION_NAME);
+        Ok(BlobRelayStubServer {
+            local_addr,
+            secure,
+            state,
+            shutdown: Some(shutdown),
+            handle: Some(handle),
+        })
+    }
+
+    /// Get the address the server is listening on.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Get the endpoint URL for [ConnectionOption::set_blob_relay_service_endpoint].
+    ///
+    /// `https://localhost:port` if TLS is enabled, otherwise `http://127.0.0.1:port`.
+    pub fn endpoint(&self) -> String {
+        if self.secure {
+            format!("https://localhost:{}", self.local_addr.port())
+        } else {
+            format!("http://{}", self.local_addr)
+        }
+    }
+
+    /// Set the endpoint of this server to the connection option.
+    ///
+    /// The CA certificate must be set separately if TLS is enabled.
+    pub fn configure(&self, connection_option: &mut ConnectionOption) {
+        connection_option.set_blob_relay_service_endpoint(&self.endpoint());
+    }
+
+    /// Get the directory in which the BLOBs are stored.
+    pub fn directory(&self) -> &Path {
+        &self.state.directory
+    }
+
+    /// Set the size of a chunk sent by `Get`.
+    pub fn set_chunk_size(&self, chunk_size: usize) {
+        self.state
+            .chunk_size
+            .store(chunk_size.max(1) as u64, Ordering::SeqCst);
+    }
+
+    /// Makes the next `count` requests fail with `UNAVAILABLE`.
+    pub fn inject_failures(&self, count: u32) {
+        self.state.failures.store(count, Ordering::SeqCst);
+    }
+
+    /// Stores a BLOB directly.
+    ///
+    /// ## Returns
+    /// `(storage_id, object_id, tag)` of the stored BLOB.
+    pub fn put_blob(&self, value: &[u8]) -> Result<(u64, u64, u64), TgError> {
+        let blob = self
+            .state
+            .store(|path| std::fs::write(path, value))
+            .map_err(|e| io_error!("Failed to store blob", e))?;
+        Ok((blob.storage_id, blob.object_id, blob.tag))
+    }
+
+    /// Get the content of a stored BLOB.
+    pub fn get_blob(&self, object_id: u64) -> Option<Vec<u8>> {
+        let (_, path) = self.state.lookup(object_id)?;
+        std::fs::read(path).ok()
+    }
+
+    /// Get the number of stored BLOBs.
+    pub fn blob_count(&self) -> usize {
+        self.state.blobs.lock().unwrap().len()
+    }
+
+    /// Stops the server.
+    pub async fn shutdown(mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.await;
+        }
+    }
+}
+
+impl Drop for BlobRelayStubServer {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+    }
+}
+
+#[derive(Debug)]
+struct StubState {
+    directory: PathBuf,
+    chunk_size: AtomicU64,
+    failures: AtomicU32,
+    next_object_id: AtomicU64,
+    blobs: Mutex<HashMap<u64, u64>>, // object_id -> tag
+}
+
+impl StubState {
+    fn new(directory: PathBuf) -> StubState {
+        StubState {
+            directory,
+            chunk_size: AtomicU64::new(DEFAULT_CHUNK_SIZE as u64),
+            failures: AtomicU32::new(0),
+            next_object_id: AtomicU64::new(1),
+            blobs: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn blob_path(&self, object_id: u64) -> PathBuf {
+        self.directory.join(format!("{object_id}.blob"))
+    }
+
+    fn check_failure(&self) -> Result<(), Status> {
+        let injected = self
+            .failures
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+            .is_ok();
+        if injected {
+            Err(Status::unavailable("injected failure"))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn store<F>(&self, write: F) -> std::io::Result<BlobReference>
+    where
+        F: FnOnce(&Path) -> std::io::Result<()>,
+    {
+        let object_id = self.next_object_id.fetch_add(1, Ordering::SeqCst);
+        write(&self.blob_path(object_id))?;
+        Ok(self.register(object_id))
+    }
+
+    fn register(&self, object_id: u64) -> BlobReference {
+        let tag = rand::random::<u64>();
+        self.blobs.lock().unwrap().insert(object_id, tag);
+        BlobReference {
+            storage_id: STORAGE_ID,
+            object_id,
+            tag,
+        }
+    }
+
+    fn lookup(&self, object_id: u64) -> Option<(u64, PathBuf)> {
+        let tag = *self.blobs.lock().unwrap().get(&object_id)?;
+        Some((tag, self.blob_path(object_id)))
+    }
+
+    fn resolve(&self, api_version: u64, blob: Option<BlobReference>) -> Result<PathBuf, Status> {
+        check_api_version(api_version)?;
+        let blob = blob.ok_or_else(|| Status::invalid_argument("blob is not specified"))?;
+        if blob.storage_id != STORAGE_ID {
+            return Err(Status::not_found(format!(
+                "unknown storage_id {}",
+                blob.storage_id
+            )));
+        }
+        match self.lookup(blob.object_id) {
+            Some((tag, path)) if tag == blob.tag => Ok(path),
+            Some(_) => Err(Status::permission_denied("tag mismatch")),
+            None => Err(Status::not_found(format!(
+                "unknown object_id {}",
+                blob.object_id
+            ))),
+        }
+    }
+}
+
+fn check_api_version(api_version: u64) -> Result<(), Status> {
+    if api_version != API_VERSION {
+        return Err(Status::invalid_argument(format!(
+            "unsupported api_version {api_version}"
+        )));
+    }
+    Ok(())
+}
+
+#[derive(Debug)]
+struct StubService(Arc<StubState>);
+
+#[async_trait]
+impl BlobRelayStreaming for StubService {
+    type GetStream = ReceiverStream<Result<GetStreamingResponse, Status>>;
+
+    async fn get(
+        &self,
+        request: Request<GetStreamingRequest>,
+    ) -> Result<Response<Self::GetStream>, Status> {
+        use get_streaming_response::{metadata::BlobSizeOpt, Metadata, Payload};
+
+        let state = &self.0;
+        state.check_failure()?;
+        let request = request.into_inner();
+        let path = state.resolve(request.api_version, request.blob)?;
+
+        let mut file = tokio::fs::File::open(&path)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?;
+        let size = file
+            .metadata()
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?
+            .len();
+        let chunk_size = state.chunk_size.load(Ordering::SeqCst) as usize;
+
+        let (tx, rx) = tokio::sync::mpsc::channel(4);
+        tokio::spawn(async move {
+            let metadata = Metadata {
+                blob_size_opt: Some(BlobSizeOpt::BlobSize(size)),
+            };
+            let response = GetStreamingResponse {
+                payload: Some(Payload::Metadata(metadata)),
+            };
+            if tx.send(Ok(response)).await.is_err() {
+                return;
+            }
+
+            loop {
+                let mut chunk = vec![0; chunk_size];
+                let response = match file.read(&mut chunk).await {
+                    Ok(0) => break,
+                    Ok(n) => {
+                        chunk.truncate(n);
+                        Ok(GetStreamingResponse {
+                            payload: Some(Payload::Chunk(chunk)),
+                        })
+                    }
+                    Err(e) => Err(Status::internal(e.to_string())),
+                };
+                let error = response.is_err();
+                if tx.send(response).await.is_err() || error {
+                    break;
+                }
+            }
+        });
+
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+
+    async fn put(
+        &self,
+        request: Request<Streaming<PutStreamingRequest>>,
+    ) -> Result<Response<PutStreamingResponse>, Status> {
+        use put_streaming_request::{metadata::BlobSizeOpt, Payload};
+
+        let state = &self.0;
+        state.check_failure()?;
+        let mut stream = request.into_inner();
+
+        let metadata = match stream.message().await? {
+            Some(PutStreamingRequest {
+                payload: Some(Payload::Metadata(metadata)),
+            }) => metadata,
+            _ => return Err(Status::invalid_argument("first message must be metadata")),
+        };
+        check_api_version(metadata.api_version)?;
+
+        let mut value = Vec::new();
+        while let Some(request) = stream.message().await? {
+            match request.payload {
+                Some(Payload::Chunk(chunk)) => value.extend_from_slice(&chunk),
+                _ => return Err(Status::invalid_argument("subsequent message must be chunk")),
+            }
+        }
+        if let Some(BlobSizeOpt::BlobSize(size)) = metadata.blob_size_opt {
+            if size != value.len() as u64 {
+                return Err(Status::invalid_argument(format!(
+                    "blob size mismatch. expected={}, actual={}",
+                    size,
+                    value.len()
+                )));
+            }
+        }
+
+        let blob = state
+            .store(|path| std::fs::write(path, &value))
+            .map_err(|e| Status::internal(e.to_string()))?;
+        Ok(Response::new(PutStreamingResponse { blob: Some(blob) }))
+    }
+}
+
+#[async_trait]
+impl BlobRelayLocal for StubService {
+    async fn get(
+        &self,
+        request: Request<GetLocalRequest>,
+    ) -> Result<Response<GetLocalResponse>, Status> {
+        let state = &self.0;
+        state.check_failure()?;
+        let request = request.into_inner();
+        let path = state.resolve(request.api_version, request.blob)?;
+
+        let data = BlobFile {
+            path: path.to_string_lossy().into_owned(),
+        };
+        Ok(Response::new(GetLocalResponse { data: Some(data) }))
+    }
+
+    async fn put(
+        &self,
+        request: Request<PutLocalRequest>,
+    ) -> Result<Response<PutLocalResponse>, Status> {
+        let state = &self.0;
+        state.check_failure()?;
+        let request = request.into_inner();
+        check_api_version(request.api_version)?;
+        let data = request
+            .data
+            .ok_or_else(|| Status::invalid_argument("data is not specified"))?;
+
+        let blob = state
+            .store(|path| std::fs::copy(&data.path, path).map(|_| ()))
+            .map_err(|e| Status::not_found(format!("{}: {}", data.path, e)))?;
+        Ok(Response::new(PutLocalResponse { blob: Some(blob) }))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::data_relay_grpc::proto::blob_relay::blob_relay_local::blob_relay_local_client::BlobRelayLocalClient;
+
+    #[tokio::test]
+    async fn local() {
+        let dir = tempfile::tempdir().unwrap();
+        let server = BlobRelayStubServer::start(dir.path().join("store"))
+            .await
+            .unwrap();
+        let mut client = BlobRelayLocalClient::connect(server.endpoint())
+            .await
+            .unwrap();
+
+        let file = dir.path().join("source.bin");
+        std::fs::write(&file, b"abc").unwrap();
+        let request = PutLocalRequest {
+            api_version: API_VERSION,
+            session_id: 1,
+            data: Some(BlobFile {
+                path: file.to_string_lossy().into_owned(),
+            }),
+        };
+        let blob = client
+            .put(request)
+            .await
+            .unwrap()
+            .into_inner()
+            .blob
+            .unwrap();
+        assert_eq!(Some(b"abc".to_vec()), server.get_blob(blob.object_id));
+
+        let request = GetLocalRequest {
+            api_version: API_VERSION,
+            context_id: None,
+            blob: Some(blob),
+        };
+        let data = client
+            .get(request)
+            .await
+            .unwrap()
+            .into_inner()
+            .data
+            .unwrap();
+        assert_eq!(b"abc".to_vec(), std::fs::read(data.path).unwrap());
+
+        let request = GetLocalRequest {
+            api_version: API_VERSION,
+            context_id: None,
+            blob: Some(BlobReference { tag: 0, ..blob }),
+        };
+        let status = client.get(request).await.unwrap_err();
+        assert_eq!(tonic::Code::PermissionDenied, status.code());
+
+        server.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn put_blob() {
+        let dir = tempfile::tempdir().unwrap();
+        let server = BlobRelayStubServer::start(dir.path()).await.unwrap();
+        assert_eq!(dir.path(), server.directory());
+        assert!(server.local_addr().ip().is_loopback());
+
+        let (storage_id, object_id, _tag) = server.put_blob(b"xyz").unwrap();
+        assert_eq!(STORAGE_ID, storage_id);
+        assert_eq!(1, server.blob_count());
+        assert_eq!(Some(b"xyz".to_vec()), server.get_blob(object_id));
+        assert_eq!(None, server.get_blob(object_id + 1));
+
+        server.shutdown().await;
+    }
+}
@@ -0,0 +1,342 @@
+use std::{
+    future::Future,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use log::{debug, warn};
+
+use crate::error::TgError;
+
+/// Progress of large object file upload.
+///
+/// See [LobUploadOption::set_progress_callback()].
+///
+/// since 0.11.0
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LobUploadProgress {
+    index: usize,
+    path: PathBuf,
+    transferred: u64,
+    total: u64,
+    attempt: u32,
+}
+
+impl LobUploadProgress {
+    pub(crate) fn new(
+        index: usize,
+        path: PathBuf,
+        transferred: u64,
+        total: u64,
+        attempt: u32,
+    ) -> LobUploadProgress {
+        LobUploadProgress {
+            index,
+            path,
+            transferred,
+            total,
+            attempt,
+        }
+    }
+
+    /// Get the index of the file in the upload request.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Get the path of the file.
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+
+    /// Get the number of bytes transferred in the current attempt.
+    pub fn transferred(&self) -> u64 {
+        self.transferred
+    }
+
+    /// Get the file size.
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// Get the attempt number (0-origin).
+    ///
+    /// Greater than 0 if the upload is retried.
+    pub fn attempt(&self) -> u32 {
+        self.attempt
+    }
+
+    /// Checks if the upload of the file is completed.
+    pub fn is_completed(&self) -> bool {
+        self.transferred >= self.total
+    }
+}
+
+/// Callback to receive [LobUploadProgress].
+///
+/// since 0.11.0
+pub type LobUploadProgressCallback = Arc<dyn Fn(&LobUploadProgress) + Send + Sync>;
+
+/// Option for large object file upload.
+///
+/// See [SqlClient::upload_blob_files()](crate::prelude::SqlClient::upload_blob_files).
+///
+/// # Examples
+/// ```
+/// use std::sync::Arc;
+/// use tsubakuro_rust_core::prelude::*;
+///
+/// fn example() -> LobUploadOption {
+///     let mut option = LobUploadOption::new();
+///     option.set_max_retries(5);
+///     option.set_parallelism(8);
+///     option.set_progress_callback(Arc::new(|progress: &LobUploadProgress| {
+///         println!("{:?} {}/{}", progress.path(), progress.transferred(), progress.total());
+///     }));
+///     option
+/// }
+/// ```
+///
+/// since 0.11.0
+#[derive(Clone)]
+pub struct LobUploadOption {
+    max_retries: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    verify_checksum: bool,
+    parallelism: usize,
+    progress_callback: Option<LobUploadProgressCallback>,
+}
+
+impl std::fmt::Debug for LobUploadOption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LobUploadOption")
+            .field("max_retries", &self.max_retries)
+            .field("initial_backoff", &self.initial_backoff)
+            .field("max_backoff", &self.max_backoff)
+            .field("verify_checksum", &self.verify_checksum)
+            .field("parallelism", &self.parallelism)
+            .field("progress_callback", &self.progress_callback.is_some())
+            .finish()
+    }
+}
+
+impl Default for LobUploadOption {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LobUploadOption {
+    /// Creates a new instance.
+    pub fn new() -> LobUploadOption {
+        LobUploadOption {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(10),
+            verify_checksum: false,
+            parallelism: 4,
+            progress_callback: None,
+        }
+    }
+
+    /// Set the maximum number of retries for each file.
+    ///
+    /// The retry restarts the upload of the file from the beginning.
+    pub fn set_max_retries(&mut self, max_retries: u32) {
+        self.max_retries = max_retries;
+    }
+
+    /// Get the maximum number of retries for each file.
+    pub fn max_retries(&self) -> u32 {
+        self.max_retries
+    }
+
+    /// Set the wait time before the first retry.
+    ///
+    /// The wait time doubles for each retry up to [`Self::max_backoff`].
+    pub fn set_initial_backoff(&mut self, backoff: Duration) {
+        self.initial_backoff = backoff;
+    }
+
+    /// Get the wait time before the first retry.
+    pub fn initial_backoff(&self) -> Duration {
+        self.initial_backoff
+    }
+
+    /// Set the maximum wait time between retries.
+    pub fn set_max_backoff(&mut self, backoff: Duration) {
+        self.max_backoff = backoff;
+    }
+
+    /// Get the maximum wait time between retries.
+    pub fn max_backoff(&self) -> Duration {
+        self.max_backoff
+    }
+
+    /// Set whether to verify the uploaded data.
+    ///
+    /// If enabled, the uploaded data is read back from the blob relay service
+    /// and its digest is compared with the digest computed while uploading.
+    /// The upload is retried if they do not match.
+    ///
+    /// Disabled by default, because reading back doubles the amount of transfer.
+    /// This is effective only with the blob relay service.
+    pub fn set_verify_checksum(&mut self, verify: bool) {
+        self.verify_checksum = verify;
+    }
+
+    /// Get whether to verify the uploaded data.
+    pub fn verify_checksum(&self) -> bool {
+        self.verify_checksum
+    }
+
+    /// Set the maximum number of files uploaded concurrently.
+    pub fn set_parallelism(&mut self, parallelism: usize) {
+        self.parallelism = parallelism.max(1);
+    }
+
+    /// Get the maximum number of files uploaded concurrently.
+    pub fn parallelism(&self) -> usize {
+        self.parallelism
+    }
+
+    /// Set the callback to receive the progress of upload.
+    ///
+    /// With the blob relay service, the progress is reported for each chunk sent.
+    /// In privileged mode, the server reads the file directly,
+    /// so the progress is reported once for each file when it is handed over to the server.
+    ///
+    /// The callback may be called from multiple threads concurrently.
+    pub fn set_progress_callback(&mut self, callback: LobUploadProgressCallback) {
+        self.progress_callback = Some(callback);
+    }
+
+    /// Get the callback to receive the progress of upload.
+    pub fn progress_callback(&self) -> Option<&LobUploadProgressCallback> {
+        self.progress_callback.as_ref()
+    }
+
+    pub(crate) fn notify_progress(&self, progress: LobUploadProgress) {
+        if let Some(callback) = &self.progress_callback {
+            callback(&progress);
+        }
+    }
+
+    /// Returns the wait time before the retry.
+    ///
+    /// `attempt` is the number of the failed attempt (0-origin).
+    pub(crate) fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 1u32.checked_shl(attempt).unwrap_or(u32::MAX);
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+}
+
+/// Returns true if the error may be resolved by retry.
+fn is_retryable(error: &TgError) -> bool {
+    matches!(error, TgError::IoError(..) | TgError::TimeoutError(..))
+}
+
+/// Executes `f` and retries it with exponential backoff if it fails.
+///
+/// `f` receives the attempt number (0-origin).
+pub(crate) async fn retry_upload<T, F, Fut>(
+    option: &LobUploadOption,
+    path: &Path,
+    mut f: F,
+) -> Result<T, TgError>
+where
+    F: FnMut(u32) -> Fut,
+    Fut: Future<Output = Result<T, TgError>>,
+{
+    let mut attempt = 0;
+    loop {
+        match f(attempt).await {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt < option.max_retries() && is_retryable(&e) => {
+                let backoff = option.backoff(attempt);
+                warn!(
+                    "upload failed. retry after {:?}. path={:?}, attempt={}, error={}",
+                    backoff, path, attempt, e
+                );
+                tokio::time::sleep(backoff).await;
+                attempt += 1;
+            }
+            Err(e) => {
+                debug!("upload failed. path={:?}, attempt={}", path, attempt);
+                return Err(e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use super::*;
+    use crate::{client_error, io_error};
+
+    #[test]
+    fn backoff() {
+        let mut option = LobUploadOption::new();
+        option.set_initial_backoff(Duration::from_millis(100));
+        option.set_max_backoff(Duration::from_millis(500));
+
+        assert_eq!(Duration::from_millis(100), option.backoff(0));
+        assert_eq!(Duration::from_millis(200), option.backoff(1));
+        assert_eq!(Duration::from_millis(400), option.backoff(2));
+        assert_eq!(Duration::from_millis(500), option.backoff(3));
+        assert_eq!(Duration::from_millis(500), option.backoff(100));
+    }
+
+    #[tokio::test]
+    async fn retry() {
+        let mut option = LobUploadOption::new();
+        option.set_initial_backoff(Duration::from_millis(1));
+        option.set_max_retries(3);
+        let path = Path::new("test");
+
+        let count = AtomicU32::new(0);
+        let result = retry_upload(&option, path, |attempt| {
+            count.fetch_add(1, Ordering::SeqCst);
+            async move {
+                if attempt < 2 {
+                    Err(io_error!("connection reset"))
+                } else {
+                    Ok(attempt)
+                }
+            }
+        })
+        .await;
+        assert_eq!(2, result.unwrap());
+        assert_eq!(3, count.load(Ordering::SeqCst));
+
+        let count = AtomicU32::new(0);
+        let result: Result<(), TgError> = retry_upload(&option, path, |_| {
+            count.fetch_add(1, Ordering::SeqCst);
+            async { Err(io_error!("connection reset")) }
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(4, count.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn retry_not_retryable() {
+        let option = LobUploadOption::new();
+        let path = Path::new("test");
+
+        let count = AtomicU32::new(0);
+        let result: Result<(), TgError> = retry_upload(&option, path, |_| {
+            count.fetch_add(1, Ordering::SeqCst);
+            async { Err(client_error!("illegal state")) }
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(1, count.load(Ordering::SeqCst));
+    }
+}
@@ -6,7 +6,10 @@ use tonic::async_trait;
 use crate::{
     error::TgError,
     service::{
-        lob::lob_client::RemoteLob,
+        lob::{
+            lob_client::RemoteLob,
+            writer::{BlobWriter, ClobWriter},
+        },
         sql::r#type::{blob::TgBlob, clob::TgClob},
     },
 };
@@ -47,6 +50,10 @@ impl BlobUploader {
     pub(crate) fn new(inner: Arc<dyn LobUploader + Send + Sync>) -> BlobUploader {
         BlobUploader { inner, done: false }
     }
+
+    pub(crate) fn inner(&self) -> Arc<dyn LobUploader + Send + Sync> {
+        self.inner.clone()
+    }
 }
 
 impl BlobUploader {
@@ -69,6 +76,16 @@ impl BlobUploader {
 
         self.inner.cancel()
     }
+
+    /// Converts into [`BlobWriter`] which implements [`tokio::io::AsyncWrite`].
+    ///
+    /// # Parameters
+    /// - `timeout` - timeout for each chunk upload and finish.
+    ///
+    /// since 0.11.0
+    pub fn into_writer(self, timeout: Duration) -> BlobWriter {
+        BlobWriter::new(self, timeout)
+    }
 }
 
 impl Drop for BlobUploader {
@@ -114,6 +131,10 @@ impl ClobUploader {
     pub(crate) fn new(inner: Arc<dyn LobUploader + Send + Sync>) -> ClobUploader {
         ClobUploader { inner, done: false }
     }
+
+    pub(crate) fn inner(&self) -> Arc<dyn LobUploader + Send + Sync> {
+        self.inner.clone()
+    }
 }
 
 impl ClobUploader {
@@ -140,6 +161,16 @@ impl ClobUploader {
 
         self.inner.cancel()
     }
+
+    /// Converts into [`ClobWriter`] which implements [`tokio::io::AsyncWrite`].
+    ///
+    /// # Parameters
+    /// - `timeout` - timeout for each chunk upload and finish.
+    ///
+    /// since 0.11.0
+    pub fn into_writer(self, timeout: Duration) -> ClobWriter {
+        ClobWriter::new(self, timeout)
+    }
 }
 
 impl Drop for ClobUploader {
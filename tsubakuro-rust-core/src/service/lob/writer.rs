@@ -0,0 +1,350 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{ready, Context, Poll},
+    time::Duration,
+};
+
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+use crate::{
+    error::TgError,
+    io_error,
+    service::sql::r#type::{blob::TgBlob, clob::TgClob},
+};
+
+use super::{
+    to_io_error,
+    uploader::{BlobUploader, ClobUploader, LobUploader},
+};
+
+/// The default size of a chunk to upload.
+const DEFAULT_CHUNK_SIZE: usize = 64 * 1024;
+
+type UploadFuture = Pin<Box<dyn Future<Output = Result<(), TgError>> + Send>>;
+
+/// Buffers written data and uploads it chunk by chunk.
+struct ChunkWriter {
+    inner: Arc<dyn LobUploader + Send + Sync>,
+    buffer: Vec<u8>,
+    chunk_size: usize,
+    timeout: Duration,
+    upload: Option<UploadFuture>,
+}
+
+impl ChunkWriter {
+    fn new(inner: Arc<dyn LobUploader + Send + Sync>, timeout: Duration) -> ChunkWriter {
+        ChunkWriter {
+            inner,
+            buffer: Vec::new(),
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            timeout,
+            upload: None,
+        }
+    }
+
+    fn poll_upload(&mut self, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        if let Some(upload) = &mut self.upload {
+            let result = ready!(upload.as_mut().poll(cx));
+            self.upload = None;
+            result.map_err(to_io_error)?;
+        }
+        Poll::Ready(Ok(()))
+    }
+
+    fn start_upload(&mut self) {
+        let chunk = std::mem::take(&mut self.buffer);
+        let inner = self.inner.clone();
+        let timeout = self.timeout;
+        self.upload = Some(Box::pin(async move {
+            inner.upload_chunk(&chunk, timeout).await
+        }));
+    }
+
+    fn poll_write(&mut self, cx: &mut Context<'_>, buf: &[u8]) -> Poll<std::io::Result<usize>> {
+        loop {
+            // drive the upload in progress
+            if let Poll::Ready(result) = self.poll_upload(cx) {
+                result?;
+            }
+
+            if self.buffer.len() < self.chunk_size {
+                let n = buf.len().min(self.chunk_size - self.buffer.len());
+                self.buffer.extend_from_slice(&buf[..n]);
+                if self.buffer.len() >= self.chunk_size && self.upload.is_none() {
+                    self.start_upload();
+                    let _ = self.poll_upload(cx)?;
+                }
+                return Poll::Ready(Ok(n));
+            }
+
+            // buffer is full
+            ready!(self.poll_upload(cx))?;
+            self.start_upload();
+        }
+    }
+
+    fn poll_flush(&mut self, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        loop {
+            ready!(self.poll_upload(cx))?;
+            if self.buffer.is_empty() {
+                return Poll::Ready(Ok(()));
+            }
+            self.start_upload();
+        }
+    }
+}
+
+impl AsyncWrite for ChunkWriter {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        self.get_mut().poll_write(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        self.get_mut().poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        self.get_mut().poll_flush(cx)
+    }
+}
+
+/// BLOB writer.
+///
+/// Implements [`AsyncWrite`] over [`BlobUploader`].
+///
+/// Call [`Self::finish`] after writing all data.
+///
+/// # Examples
+/// ```
+/// use tsubakuro_rust_core::prelude::*;
+///
+/// async fn example(client: &SqlClient) -> Result<TgBlob, TgError> {
+///     let timeout = std::time::Duration::from_secs(10);
+///     let uploader = client.create_blob_uploader().await?;
+///     let mut writer = uploader.into_writer(timeout);
+///
+///     let mut file = tokio::fs::File::open("/path/to/file").await.unwrap();
+///     tokio::io::copy(&mut file, &mut writer).await.unwrap();
+///     let blob = writer.finish().await?;
+///
+///     Ok(blob)
+/// }
+/// ```
+///
+/// since 0.11.0
+pub struct BlobWriter {
+    uploader: BlobUploader,
+    writer: ChunkWriter,
+}
+
+impl std::fmt::Debug for BlobWriter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BlobWriter")
+            .field("chunk_size", &self.writer.chunk_size)
+            .field("timeout", &self.writer.timeout)
+            .finish()
+    }
+}
+
+impl BlobWriter {
+    pub(crate) fn new(uploader: BlobUploader, timeout: Duration) -> BlobWriter {
+        let writer = ChunkWriter::new(uploader.inner(), timeout);
+        BlobWriter { uploader, writer }
+    }
+
+    /// Set the size of a chunk to upload at once.
+    pub fn set_chunk_size(&mut self, chunk_size: usize) {
+        self.writer.chunk_size = chunk_size.max(1);
+    }
+
+    /// Get the size of a chunk to upload at once.
+    pub fn chunk_size(&self) -> usize {
+        self.writer.chunk_size
+    }
+
+    /// Uploads the buffered data, finishes the upload and returns the resulting `TgBlob`.
+    pub async fn finish(mut self) -> Result<TgBlob, TgError> {
+        self.writer
+            .flush()
+            .await
+            .map_err(|e| io_error!("BlobWriter flush error", e))?;
+
+        let timeout = self.writer.timeout;
+        self.uploader.finish(timeout).await
+    }
+
+    /// Cancels the upload.
+    pub fn cancel(self) -> Result<(), TgError> {
+        self.uploader.cancel()
+    }
+}
+
+impl AsyncWrite for BlobWriter {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        self.get_mut().writer.poll_write(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        self.get_mut().writer.poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        self.get_mut().writer.poll_flush(cx)
+    }
+}
+
+/// CLOB writer.
+///
+/// Implements [`AsyncWrite`] over [`ClobUploader`].
+/// The written data must be UTF-8.
+///
+/// Call [`Self::finish`] after writing all data.
+///
+/// since 0.11.0
+pub struct ClobWriter {
+    uploader: ClobUploader,
+    writer: ChunkWriter,
+}
+
+impl std::fmt::Debug for ClobWriter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ClobWriter")
+            .field("chunk_size", &self.writer.chunk_size)
+            .field("timeout", &self.writer.timeout)
+            .finish()
+    }
+}
+
+impl ClobWriter {
+    pub(crate) fn new(uploader: ClobUploader, timeout: Duration) -> ClobWriter {
+        let writer = ChunkWriter::new(uploader.inner(), timeout);
+        ClobWriter { uploader, writer }
+    }
+
+    /// Set the size of a chunk to upload at once.
+    pub fn set_chunk_size(&mut self, chunk_size: usize) {
+        self.writer.chunk_size = chunk_size.max(1);
+    }
+
+    /// Get the size of a chunk to upload at once.
+    pub fn chunk_size(&self) -> usize {
+        self.writer.chunk_size
+    }
+
+    /// Uploads the buffered data, finishes the upload and returns the resulting `TgClob`.
+    pub async fn finish(mut self) -> Result<TgClob, TgError> {
+        self.writer
+            .flush()
+            .await
+            .map_err(|e| io_error!("ClobWriter flush error", e))?;
+
+        let timeout = self.writer.timeout;
+        self.uploader.finish(timeout).await
+    }
+
+    /// Cancels the upload.
+    pub fn cancel(self) -> Result<(), TgError> {
+        self.uploader.cancel()
+    }
+}
+
+impl AsyncWrite for ClobWriter {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        self.get_mut().writer.poll_write(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        self.get_mut().writer.poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        self.get_mut().writer.poll_flush(cx)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Mutex;
+
+    use tonic::async_trait;
+
+    use super::*;
+    use crate::service::lob::lob_client::RemoteLob;
+
+    #[derive(Default)]
+    struct TestUploader {
+        chunks: Mutex<Vec<Vec<u8>>>,
+    }
+
+    #[async_trait]
+    impl LobUploader for TestUploader {
+        async fn upload_chunk(&self, value: &[u8], _timeout: Duration) -> Result<(), TgError> {
+            tokio::task::yield_now().await;
+            self.chunks.lock().unwrap().push(value.to_vec());
+            Ok(())
+        }
+
+        async fn finish(&self, _timeout: Duration) -> Result<RemoteLob, TgError> {
+            Ok(RemoteLob::LobReference(1, 2, 3))
+        }
+
+        fn cancel(&self) -> Result<(), TgError> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn blob_copy() {
+        let target = Arc::new(TestUploader::default());
+        let mut writer = BlobUploader::new(target.clone()).into_writer(Duration::ZERO);
+        writer.set_chunk_size(1000);
+
+        let value: Vec<u8> = (0..=255).cycle().take(10_500).collect();
+        let size = tokio::io::copy(&mut value.as_slice(), &mut writer)
+            .await
+            .unwrap();
+        assert_eq!(10_500, size);
+        writer.finish().await.unwrap();
+
+        let chunks = target.chunks.lock().unwrap();
+        assert!(chunks.iter().all(|chunk| chunk.len() <= 1000));
+        assert_eq!(value, chunks.concat());
+    }
+
+    #[tokio::test]
+    async fn clob_write() {
+        let target = Arc::new(TestUploader::default());
+        let mut writer = ClobUploader::new(target.clone()).into_writer(Duration::ZERO);
+        writer.set_chunk_size(4);
+
+        writer.write_all("abcあいう".as_bytes()).await.unwrap();
+        writer.write_all(b"x").await.unwrap();
+        writer.finish().await.unwrap();
+
+        let chunks = target.chunks.lock().unwrap();
+        assert_eq!("abcあいうx".as_bytes(), chunks.concat());
+    }
+
+    #[tokio::test]
+    async fn empty() {
+        let target = Arc::new(TestUploader::default());
+        let writer = BlobUploader::new(target.clone()).into_writer(Duration::ZERO);
+        writer.finish().await.unwrap();
+
+        assert!(target.chunks.lock().unwrap().is_empty());
+    }
+}
@@ -31,13 +31,14 @@ impl From<crate::jogasaki::proto::sql::response::Error> for DiagnosticCode {
             value_code = code as i32;
         }
 
-        if code as i32 == value_code {
+        let diagnostic_code = if code as i32 == value_code {
             let name = code.as_str_name();
             DiagnosticCode::new(SERVICE_ID_SQL, "SQL", code_number, name)
         } else {
             let name = format!("UnknownSqlError{}", value_code);
             DiagnosticCode::new(SERVICE_ID_SQL, "SQL", code_number, &name)
-        }
+        };
+        diagnostic_code.with_supplemental_text(&value.supplemental_text)
     }
 }
 
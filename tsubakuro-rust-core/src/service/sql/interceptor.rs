@@ -0,0 +1,451 @@
+use std::{
+    collections::HashMap,
+    future::Future,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    error::TgError,
+    job::Job,
+    prelude::{SqlParameter, TransactionOption},
+    transaction::Transaction,
+};
+
+/// Operation of [SqlClient](crate::prelude::SqlClient) intercepted by [SqlInterceptor].
+///
+/// since 0.11.0
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SqlOperation {
+    /// [SqlClient::prepare()](crate::prelude::SqlClient::prepare)
+    Prepare,
+    /// [SqlClient::start_transaction()](crate::prelude::SqlClient::start_transaction)
+    StartTransaction,
+    /// [SqlClient::execute()](crate::prelude::SqlClient::execute)
+    Execute,
+    /// [SqlClient::prepared_execute()](crate::prelude::SqlClient::prepared_execute)
+    PreparedExecute,
+    /// [SqlClient::query()](crate::prelude::SqlClient::query)
+    Query,
+    /// [SqlClient::prepared_query()](crate::prelude::SqlClient::prepared_query)
+    PreparedQuery,
+    /// [SqlClient::commit()](crate::prelude::SqlClient::commit)
+    Commit,
+    /// [SqlClient::rollback()](crate::prelude::SqlClient::rollback)
+    Rollback,
+    /// Upload of BLOB/CLOB. (e.g. [SqlClient::upload_blob_file()](crate::prelude::SqlClient::upload_blob_file))
+    UploadLob,
+    /// Open BLOB/CLOB. (e.g. [SqlClient::open_blob()](crate::prelude::SqlClient::open_blob))
+    OpenLob,
+    /// Read BLOB/CLOB. (e.g. [SqlClient::read_blob()](crate::prelude::SqlClient::read_blob))
+    ReadLob,
+    /// Copy BLOB/CLOB to a file. (e.g. [SqlClient::copy_blob_to()](crate::prelude::SqlClient::copy_blob_to))
+    CopyLob,
+    /// Get BLOB/CLOB cache. (e.g. [SqlClient::get_blob_cache()](crate::prelude::SqlClient::get_blob_cache))
+    GetLobCache,
+}
+
+/// Request information passed to [SqlInterceptor].
+///
+/// since 0.11.0
+#[derive(Debug, Clone)]
+pub struct SqlInterceptContext {
+    operation: SqlOperation,
+    sql: Option<String>,
+    parameters: Option<Vec<SqlParameter>>,
+    transaction_id: Option<String>,
+    transaction_option: Option<TransactionOption>,
+    path: Option<PathBuf>,
+    attributes: HashMap<String, String>,
+    start_time: Instant,
+}
+
+impl SqlInterceptContext {
+    pub(crate) fn new(operation: SqlOperation) -> SqlInterceptContext {
+        SqlInterceptContext {
+            operation,
+            sql: None,
+            parameters: None,
+            transaction_id: None,
+            transaction_option: None,
+            path: None,
+            attributes: HashMap::new(),
+            start_time: Instant::now(),
+        }
+    }
+
+    /// Get the operation.
+    pub fn operation(&self) -> SqlOperation {
+        self.operation
+    }
+
+    pub(crate) fn set_sql(&mut self, sql: &str) {
+        self.sql = Some(sql.to_string());
+    }
+
+    /// Get the SQL statement.
+    ///
+    /// Available for [SqlOperation::Prepare], [SqlOperation::Execute], [SqlOperation::Query],
+    /// [SqlOperation::PreparedExecute] and [SqlOperation::PreparedQuery].
+    pub fn sql(&self) -> Option<&String> {
+        self.sql.as_ref()
+    }
+
+    pub(crate) fn set_parameters(&mut self, parameters: &[SqlParameter]) {
+        self.parameters = Some(parameters.to_vec());
+    }
+
+    /// Get the parameters.
+    ///
+    /// Available for [SqlOperation::PreparedExecute] and [SqlOperation::PreparedQuery].
+    pub fn parameters(&self) -> Option<&Vec<SqlParameter>> {
+        self.parameters.as_ref()
+    }
+
+    pub(crate) fn set_transaction(&mut self, transaction: &Transaction) {
+        self.transaction_id = Some(transaction.transaction_id().clone());
+    }
+
+    /// Get the transaction id.
+    ///
+    /// Available for the operations in a transaction,
+    /// and in [SqlInterceptor::after] of [SqlOperation::StartTransaction] if the transaction started.
+    pub fn transaction_id(&self) -> Option<&String> {
+        self.transaction_id.as_ref()
+    }
+
+    pub(crate) fn set_transaction_option(&mut self, transaction_option: &TransactionOption) {
+        self.transaction_option = Some(transaction_option.clone());
+    }
+
+    /// Get the transaction option.
+    ///
+    /// Available for [SqlOperation::StartTransaction].
+    pub fn transaction_option(&self) -> Option<&TransactionOption> {
+        self.transaction_option.as_ref()
+    }
+
+    /// Get the transaction option to modify.
+    ///
+    /// The modified option is used to start the transaction. (e.g. to add a tag to the transaction label)
+    pub fn transaction_option_mut(&mut self) -> Option<&mut TransactionOption> {
+        self.transaction_option.as_mut()
+    }
+
+    pub(crate) fn set_path(&mut self, path: &Path) {
+        self.path = Some(path.to_path_buf());
+    }
+
+    /// Get the file path.
+    ///
+    /// Available for [SqlOperation::UploadLob] from a file and [SqlOperation::CopyLob].
+    pub fn path(&self) -> Option<&PathBuf> {
+        self.path.as_ref()
+    }
+
+    /// Set an attribute.
+    ///
+    /// Attributes are used to pass information from [SqlInterceptor::before] to [SqlInterceptor::after].
+    pub fn set_attribute(&mut self, key: &str, value: &str) {
+        self.attributes.insert(key.to_string(), value.to_string());
+    }
+
+    /// Get an attribute.
+    pub fn attribute(&self, key: &str) -> Option<&String> {
+        self.attributes.get(key)
+    }
+
+    /// Get all attributes.
+    pub fn attributes(&self) -> &HashMap<String, String> {
+        &self.attributes
+    }
+
+    /// Get the elapsed time since the operation started.
+    pub fn elapsed(&self) -> Duration {
+        self.start_time.elapsed()
+    }
+}
+
+/// Interceptor of [SqlClient](crate::prelude::SqlClient) operations.
+///
+/// Register by [Session::add_sql_interceptor()](crate::prelude::Session::add_sql_interceptor)
+/// (for all SqlClients created after that) or [SqlClient::add_interceptor()](crate::prelude::SqlClient::add_interceptor).
+///
+/// For `_async` methods, [Self::after] is called when the result of the [Job] is taken.
+///
+/// # Examples
+/// ```
+/// use std::{sync::Arc, time::Duration};
+/// use tsubakuro_rust_core::prelude::*;
+///
+/// struct ExampleInterceptor;
+///
+/// impl SqlInterceptor for ExampleInterceptor {
+///     fn before(&self, context: &mut SqlInterceptContext) -> Result<(), TgError> {
+///         if let Some(sql) = context.sql() {
+///             if sql.trim_start().to_lowercase().starts_with("drop") {
+///                 return Err(TgError::ClientError("DDL is not allowed".to_string(), None));
+///             }
+///         }
+///         if let Some(option) = context.transaction_option_mut() {
+///             option.set_transaction_label("tenant-1");
+///         }
+///         Ok(())
+///     }
+///
+///     fn after(&self, context: &SqlInterceptContext, result: Result<(), &TgError>) {
+///         let elapsed = context.elapsed();
+///         if elapsed > Duration::from_secs(1) {
+///             println!("slow {:?}: {:?} {:?} {:?}", context.operation(), context.sql(), elapsed, result);
+///         }
+///     }
+/// }
+///
+/// fn example(session: &Session) {
+///     session.add_sql_interceptor(Arc::new(ExampleInterceptor));
+/// }
+/// ```
+///
+/// since 0.11.0
+pub trait SqlInterceptor: Send + Sync {
+    /// Called before the request is sent.
+    ///
+    /// If this returns an error, the request is not sent and the operation fails with the error.
+    /// (The following interceptors are not called.
+    /// [Self::after] is not called either, also for the preceding interceptors whose `before` has already been called.)
+    fn before(&self, context: &mut SqlInterceptContext) -> Result<(), TgError> {
+        let _ = context;
+        Ok(())
+    }
+
+    /// Called after the operation is completed.
+    ///
+    /// Not called if the request was vetoed by [Self::before] of any interceptor.
+    fn after(&self, context: &SqlInterceptContext, result: Result<(), &TgError>) {
+        let _ = (context, result);
+    }
+}
+
+/// List of [SqlInterceptor].
+#[derive(Clone, Default)]
+pub(crate) struct SqlInterceptors {
+    interceptors: Vec<Arc<dyn SqlInterceptor>>,
+}
+
+impl std::fmt::Debug for SqlInterceptors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SqlInterceptors")
+            .field("len", &self.interceptors.len())
+            .finish()
+    }
+}
+
+impl SqlInterceptors {
+    pub(crate) fn add(&mut self, interceptor: Arc<dyn SqlInterceptor>) {
+        self.interceptors.push(interceptor);
+    }
+
+    pub(crate) fn list(&self) -> &Vec<Arc<dyn SqlInterceptor>> {
+        &self.interceptors
+    }
+
+    /// Creates a context and calls [SqlInterceptor::before].
+    ///
+    /// Returns None if no interceptors are registered.
+    pub(crate) fn before<F>(
+        &self,
+        operation: SqlOperation,
+        init: F,
+    ) -> Result<Option<SqlInterceptContext>, TgError>
+    where
+        F: FnOnce(&mut SqlInterceptContext),
+    {
+        if self.interceptors.is_empty() {
+            return Ok(None);
+        }
+
+        let mut context = SqlInterceptContext::new(operation);
+        init(&mut context);
+        for interceptor in &self.interceptors {
+            interceptor.before(&mut context)?;
+        }
+        Ok(Some(context))
+    }
+
+    pub(crate) fn after<T>(
+        &self,
+        context: Option<&SqlInterceptContext>,
+        result: &Result<T, TgError>,
+    ) {
+        if let Some(context) = context {
+            let result = result.as_ref().map(|_| ());
+            for interceptor in &self.interceptors {
+                interceptor.after(context, result);
+            }
+        }
+    }
+
+    /// Awaits the future and calls [SqlInterceptor::after].
+    pub(crate) async fn intercept<T, F>(
+        &self,
+        context: Option<SqlInterceptContext>,
+        future: F,
+    ) -> Result<T, TgError>
+    where
+        F: Future<Output = Result<T, TgError>>,
+    {
+        let result = future.await;
+        self.after(context.as_ref(), &result);
+        result
+    }
+
+    /// Awaits the future which returns a job, and calls [SqlInterceptor::after] when the result of the job is taken.
+    pub(crate) async fn intercept_job<T, F>(
+        &self,
+        context: Option<SqlInterceptContext>,
+        future: F,
+    ) -> Result<Job<T>, TgError>
+    where
+        T: Send + 'static,
+        F: Future<Output = Result<Job<T>, TgError>>,
+    {
+        let result = future.await;
+        let context = match context {
+            Some(context) => context,
+            None => return result,
+        };
+        match result {
+            Ok(job) => {
+                let interceptors = self.clone();
+                Ok(job.inspect(Box::new(move |result| {
+                    interceptors.after(Some(&context), result)
+                })))
+            }
+            Err(e) => {
+                let result = Err(e);
+                self.after(Some(&context), &result);
+                result
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::{client_error, prelude::TransactionOptionSetter};
+
+    #[derive(Default)]
+    struct Recorder {
+        events: Mutex<Vec<String>>,
+        veto: bool,
+    }
+
+    impl SqlInterceptor for Recorder {
+        fn before(&self, context: &mut SqlInterceptContext) -> Result<(), TgError> {
+            self.events
+                .lock()
+                .unwrap()
+                .push(format!("before {:?}", context.operation()));
+            if self.veto {
+                return Err(client_error!("veto"));
+            }
+            context.set_attribute("tag", "t1");
+            if let Some(option) = context.transaction_option_mut() {
+                option.set_transaction_label("tenant");
+            }
+            Ok(())
+        }
+
+        fn after(&self, context: &SqlInterceptContext, result: Result<(), &TgError>) {
+            self.events.lock().unwrap().push(format!(
+                "after {:?} {:?} {}",
+                context.operation(),
+                context.attribute("tag"),
+                result.is_ok()
+            ));
+        }
+    }
+
+    #[test]
+    fn empty() {
+        let interceptors = SqlInterceptors::default();
+        let context = interceptors
+            .before(SqlOperation::Execute, |_| panic!("not called"))
+            .unwrap();
+        assert!(context.is_none());
+    }
+
+    #[tokio::test]
+    async fn intercept() {
+        let recorder = Arc::new(Recorder::default());
+        let mut interceptors = SqlInterceptors::default();
+        interceptors.add(recorder.clone());
+
+        let context = interceptors
+            .before(SqlOperation::StartTransaction, |context| {
+                context.set_transaction_option(&TransactionOption::new())
+            })
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            Some(&"tenant".to_string()),
+            context.transaction_option().unwrap().transaction_label()
+        );
+
+        let result = interceptors
+            .intercept(Some(context), async {
+                Err::<(), _>(client_error!("error"))
+            })
+            .await;
+        assert!(result.is_err());
+
+        let events = recorder.events.lock().unwrap();
+        assert_eq!(
+            vec![
+                "before StartTransaction".to_string(),
+                "after StartTransaction Some(\"t1\") false".to_string()
+            ],
+            *events
+        );
+    }
+
+    #[tokio::test]
+    async fn intercept_job() {
+        let recorder = Arc::new(Recorder::default());
+        let mut interceptors = SqlInterceptors::default();
+        interceptors.add(recorder.clone());
+
+        let context = interceptors
+            .before(SqlOperation::Execute, |context| context.set_sql("select 1"))
+            .unwrap();
+        let job = interceptors
+            .intercept_job(context, async { Ok(Job::returns("test", 1)) })
+            .await
+            .unwrap();
+        assert_eq!(1, recorder.events.lock().unwrap().len());
+
+        assert_eq!(1, job.await.unwrap());
+        assert_eq!(
+            "after Execute Some(\"t1\") true",
+            recorder.events.lock().unwrap()[1]
+        );
+    }
+
+    #[test]
+    fn veto() {
+        let recorder = Arc::new(Recorder {
+            veto: true,
+            ..Default::default()
+        });
+        let mut interceptors = SqlInterceptors::default();
+        interceptors.add(recorder.clone());
+
+        let result = interceptors.before(SqlOperation::Commit, |_| {});
+        assert!(result.is_err());
+        assert_eq!(1, recorder.events.lock().unwrap().len());
+    }
+}
@@ -0,0 +1,807 @@
+//! Schema migration.
+//!
+//! Applies versioned SQL scripts in order, and records the applied versions and checksums
+//! in a history table.
+//!
+//! since 0.11.0
+
+use std::{
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use log::{trace, warn};
+use sha1::{Digest, Sha1};
+
+use crate::{
+    client_error,
+    error::TgError,
+    io_error,
+    prelude::{
+        CommitOption, SqlClient, SqlParameter, SqlParameterOf, SqlPlaceholder, SqlQueryResultFetch,
+        TgTimePointWithTimeZone, TransactionOption, TransactionOptionSetter, TransactionType,
+    },
+    transaction::Transaction,
+};
+
+use super::sql_text::split_statements;
+
+/// The default name of the migration history table.
+///
+/// since 0.11.0
+pub const DEFAULT_MIGRATION_HISTORY_TABLE: &str = "tsubakuro_schema_history";
+
+/// Versioned SQL script.
+///
+/// A script file is named `V<version>__<description>.sql` (for example, `V1__create_tables.sql`),
+/// and contains SQL statements separated by `;`.
+///
+/// since 0.11.0
+#[derive(Debug, Clone)]
+pub struct Migration {
+    version: i64,
+    description: String,
+    script: String,
+    checksum: String,
+    path: Option<PathBuf>,
+}
+
+impl Migration {
+    /// Creates a new instance.
+    ///
+    /// since 0.11.0
+    pub fn new(version: i64, description: &str, script: &str) -> Migration {
+        Migration {
+            version,
+            description: description.to_string(),
+            script: script.to_string(),
+            checksum: checksum(script),
+            path: None,
+        }
+    }
+
+    /// Reads a script file.
+    ///
+    /// since 0.11.0
+    pub fn from_file<T: AsRef<Path>>(path: T) -> Result<Migration, TgError> {
+        let path = path.as_ref();
+        let file_name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default();
+        let (version, description) = parse_file_name(file_name).ok_or_else(|| {
+            client_error!(format!(
+                "invalid migration file name (expected V<version>__<description>.sql): {}",
+                path.display()
+            ))
+        })?;
+        let script = std::fs::read_to_string(path)
+            .map_err(|e| io_error!(format!("migration file read error. {}", path.display()), e))?;
+
+        let mut migration = Migration::new(version, &description, &script);
+        migration.path = Some(path.to_path_buf());
+        Ok(migration)
+    }
+
+    /// Reads the script files (`*.sql`) in the directory, ordered by version.
+    ///
+    /// since 0.11.0
+    pub fn load_dir<T: AsRef<Path>>(dir: T) -> Result<Vec<Migration>, TgError> {
+        let dir = dir.as_ref();
+        let entries = std::fs::read_dir(dir).map_err(|e| {
+            io_error!(
+                format!("migration directory read error. {}", dir.display()),
+                e
+            )
+        })?;
+
+        let mut migrations = Vec::new();
+        for entry in entries {
+            let path = entry
+                .map_err(|e| {
+                    io_error!(
+                        format!("migration directory read error. {}", dir.display()),
+                        e
+                    )
+                })?
+                .path();
+            if path.is_file() && path.extension().is_some_and(|extension| extension == "sql") {
+                migrations.push(Migration::from_file(path)?);
+            }
+        }
+
+        migrations.sort_by_key(|migration| migration.version);
+        for pair in migrations.windows(2) {
+            if pair[0].version == pair[1].version {
+                return Err(client_error!(format!(
+                    "duplicate migration version {}. {:?}, {:?}",
+                    pair[0].version, pair[0].path, pair[1].path
+                )));
+            }
+        }
+        Ok(migrations)
+    }
+
+    /// Returns the version.
+    ///
+    /// since 0.11.0
+    pub fn version(&self) -> i64 {
+        self.version
+    }
+
+    /// Returns the description.
+    ///
+    /// since 0.11.0
+    pub fn description(&self) -> &String {
+        &self.description
+    }
+
+    /// Returns the SQL script.
+    ///
+    /// since 0.11.0
+    pub fn script(&self) -> &String {
+        &self.script
+    }
+
+    /// Returns the checksum (SHA-1 of the script, line endings normalized).
+    ///
+    /// since 0.11.0
+    pub fn checksum(&self) -> &String {
+        &self.checksum
+    }
+
+    /// Returns the file path.
+    ///
+    /// since 0.11.0
+    pub fn path(&self) -> Option<&PathBuf> {
+        self.path.as_ref()
+    }
+
+    /// Returns the SQL statements of the script.
+    ///
+    /// since 0.11.0
+    pub fn statements(&self) -> Vec<&str> {
+        split_statements(&self.script)
+    }
+}
+
+fn parse_file_name(file_name: &str) -> Option<(i64, String)> {
+    let name = file_name.strip_suffix(".sql")?;
+    let name = name.strip_prefix('V').or_else(|| name.strip_prefix('v'))?;
+    let (version, description) = name.split_once("__")?;
+    let version = version.parse().ok()?;
+    Some((version, description.replace('_', " ")))
+}
+
+fn checksum(script: &str) -> String {
+    let mut hasher = Sha1::new();
+    hasher.update(script.replace("\r\n", "\n").as_bytes());
+    hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+/// Migration recorded in the history table.
+///
+/// since 0.11.0
+#[derive(Debug, Clone, PartialEq)]
+pub struct AppliedMigration {
+    version: i64,
+    description: String,
+    checksum: String,
+    applied_at: Option<TgTimePointWithTimeZone>,
+}
+
+impl AppliedMigration {
+    /// Returns the version.
+    ///
+    /// since 0.11.0
+    pub fn version(&self) -> i64 {
+        self.version
+    }
+
+    /// Returns the description.
+    ///
+    /// since 0.11.0
+    pub fn description(&self) -> &String {
+        &self.description
+    }
+
+    /// Returns the checksum of the applied script.
+    ///
+    /// since 0.11.0
+    pub fn checksum(&self) -> &String {
+        &self.checksum
+    }
+
+    /// Returns the time when the migration was applied.
+    ///
+    /// since 0.11.0
+    pub fn applied_at(&self) -> Option<&TgTimePointWithTimeZone> {
+        self.applied_at.as_ref()
+    }
+}
+
+/// State of a migration.
+///
+/// since 0.11.0
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MigrationState {
+    /// Not applied yet.
+    Pending,
+    /// Applied.
+    Applied,
+    /// Applied, but the script has been changed since then.
+    ChecksumMismatch,
+    /// Not applied, but a later version has been applied.
+    OutOfOrder,
+    /// Applied, but the script is not found.
+    Missing,
+}
+
+/// Status of a migration.
+///
+/// since 0.11.0
+#[derive(Debug, Clone)]
+pub struct MigrationStatus {
+    version: i64,
+    description: String,
+    state: MigrationState,
+    applied: Option<AppliedMigration>,
+}
+
+impl MigrationStatus {
+    /// Returns the version.
+    ///
+    /// since 0.11.0
+    pub fn version(&self) -> i64 {
+        self.version
+    }
+
+    /// Returns the description.
+    ///
+    /// since 0.11.0
+    pub fn description(&self) -> &String {
+        &self.description
+    }
+
+    /// Returns the state.
+    ///
+    /// since 0.11.0
+    pub fn state(&self) -> MigrationState {
+        self.state
+    }
+
+    /// Returns the history record, if applied.
+    ///
+    /// since 0.11.0
+    pub fn applied(&self) -> Option<&AppliedMigration> {
+        self.applied.as_ref()
+    }
+}
+
+/// Schema migration runner.
+///
+/// Each migration is executed in a transaction with `modifies_definitions`,
+/// together with the insertion of the history record.
+///
+/// # Examples
+/// ```
+/// use tsubakuro_rust_core::prelude::*;
+///
+/// async fn example(client: &SqlClient) -> Result<(), TgError> {
+///     let migrator = Migrator::from_dir("migrations")?;
+///
+///     for migration in migrator.dry_run(client).await? {
+///         println!("pending: V{} {}", migration.version(), migration.description());
+///     }
+///
+///     let applied = migrator.migrate(client).await?;
+///     println!("{} migrations applied", applied.len());
+///     Ok(())
+/// }
+/// ```
+///
+/// since 0.11.0
+#[derive(Debug, Clone)]
+pub struct Migrator {
+    migrations: Vec<Migration>,
+    history_table: String,
+    transaction_option: TransactionOption,
+}
+
+impl Migrator {
+    /// Creates a new instance.
+    ///
+    /// since 0.11.0
+    pub fn new(mut migrations: Vec<Migration>) -> Migrator {
+        migrations.sort_by_key(|migration| migration.version);
+
+        let mut transaction_option = TransactionOption::from(TransactionType::Short);
+        transaction_option.set_modifies_definitions(true);
+        Migrator {
+            migrations,
+            history_table: DEFAULT_MIGRATION_HISTORY_TABLE.to_string(),
+            transaction_option,
+        }
+    }
+
+    /// Creates a new instance with the script files in the directory.
+    ///
+    /// since 0.11.0
+    pub fn from_dir<T: AsRef<Path>>(dir: T) -> Result<Migrator, TgError> {
+        Ok(Migrator::new(Migration::load_dir(dir)?))
+    }
+
+    /// Returns the migrations.
+    ///
+    /// since 0.11.0
+    pub fn migrations(&self) -> &Vec<Migration> {
+        &self.migrations
+    }
+
+    /// Set the history table name.
+    ///
+    /// since 0.11.0
+    pub fn set_history_table(&mut self, table_name: &str) {
+        self.history_table = table_name.to_string();
+    }
+
+    /// Get the history table name.
+    ///
+    /// since 0.11.0
+    pub fn history_table(&self) -> &String {
+        &self.history_table
+    }
+
+    /// Set the transaction option for migrations.
+    ///
+    /// `modifies_definitions` is always set.
+    ///
+    /// since 0.11.0
+    pub fn set_transaction_option(&mut self, transaction_option: TransactionOption) {
+        let mut transaction_option = transaction_option;
+        transaction_option.set_modifies_definitions(true);
+        self.transaction_option = transaction_option;
+    }
+
+    /// Get the transaction option for migrations.
+    ///
+    /// since 0.11.0
+    pub fn transaction_option(&self) -> &TransactionOption {
+        &self.transaction_option
+    }
+
+    /// Returns the status of the migrations, ordered by version.
+    ///
+    /// since 0.11.0
+    pub async fn status(&self, client: &SqlClient) -> Result<Vec<MigrationStatus>, TgError> {
+        let applied = self.applied_migrations(client).await?;
+        Ok(self.compare(applied))
+    }
+
+    /// Returns the migrations to be applied, without applying them.
+    ///
+    /// Fails if an applied script has been changed, or a pending migration is out of order.
+    ///
+    /// since 0.11.0
+    pub async fn dry_run(&self, client: &SqlClient) -> Result<Vec<&Migration>, TgError> {
+        let status = self.status(client).await?;
+        self.pending(&status)
+    }
+
+    /// Applies the pending migrations, and returns the applied versions.
+    ///
+    /// Fails without applying any migration if an applied script has been changed,
+    /// or a pending migration is out of order.
+    /// If a migration fails, the migrations before it remain applied.
+    /// The error of the failed statement is returned as is. (The version and the statement are logged)
+    ///
+    /// since 0.11.0
+    pub async fn migrate(&self, client: &SqlClient) -> Result<Vec<i64>, TgError> {
+        const FUNCTION_NAME: &str = "migrate()";
+        trace!("{} start", FUNCTION_NAME);
+
+        self.create_history_table(client).await?;
+        let status = self.status(client).await?;
+        let pending = self.pending(&status)?;
+
+        let mut applied = Vec::with_capacity(pending.len());
+        for migration in pending {
+            self.apply(client, migration).await?;
+            applied.push(migration.version);
+        }
+
+        trace!("{} end", FUNCTION_NAME);
+        Ok(applied)
+    }
+
+    fn pending<'a>(&'a self, status: &[MigrationStatus]) -> Result<Vec<&'a Migration>, TgError> {
+        for s in status {
+            match s.state {
+                MigrationState::ChecksumMismatch => {
+                    return Err(client_error!(format!(
+                        "migration V{} ({}) has been changed after applied",
+                        s.version, s.description
+                    )));
+                }
+                MigrationState::OutOfOrder => {
+                    return Err(client_error!(format!(
+                        "migration V{} ({}) is older than the latest applied migration",
+                        s.version, s.description
+                    )));
+                }
+                _ => {}
+            }
+        }
+
+        let pending = self
+            .migrations
+            .iter()
+            .filter(|migration| {
+                status
+                    .iter()
+                    .any(|s| s.version == migration.version && s.state == MigrationState::Pending)
+            })
+            .collect();
+        Ok(pending)
+    }
+
+    fn compare(&self, applied: Vec<AppliedMigration>) -> Vec<MigrationStatus> {
+        let latest = applied.iter().map(|a| a.version).max();
+
+        let mut status: Vec<MigrationStatus> = self
+            .migrations
+            .iter()
+            .map(|migration| {
+                let applied = applied.iter().find(|a| a.version == migration.version);
+                let state = match applied {
+                    Some(applied) if applied.checksum == migration.checksum => {
+                        MigrationState::Applied
+                    }
+                    Some(_) => MigrationState::ChecksumMismatch,
+                    None if latest.is_some_and(|latest| migration.version < latest) => {
+                        MigrationState::OutOfOrder
+                    }
+                    None => MigrationState::Pending,
+                };
+                MigrationStatus {
+                    version: migration.version,
+                    description: migration.description.clone(),
+                    state,
+                    applied: applied.cloned(),
+                }
+            })
+            .collect();
+
+        for applied in applied {
+            if !self.migrations.iter().any(|m| m.version == applied.version) {
+                status.push(MigrationStatus {
+                    version: applied.version,
+                    description: applied.description.clone(),
+                    state: MigrationState::Missing,
+                    applied: Some(applied),
+                });
+            }
+        }
+
+        status.sort_by_key(|s| s.version);
+        status
+    }
+
+    async fn create_history_table(&self, client: &SqlClient) -> Result<(), TgError> {
+        if client
+            .resolve_table_metadata(&self.history_table)
+            .await?
+            .is_some()
+        {
+            return Ok(());
+        }
+
+        let sql = format!(
+            "create table {} (version bigint primary key, description varchar(*), checksum varchar(40) not null, applied_at timestamp with time zone)",
+            self.history_table
+        );
+        self.execute_in_transaction(client, "create migration history table", |tx| {
+            let sql = sql.clone();
+            async move {
+                client.execute(&tx, &sql).await?;
+                Ok(tx)
+            }
+        })
+        .await
+    }
+
+    async fn applied_migrations(
+        &self,
+        client: &SqlClient,
+    ) -> Result<Vec<AppliedMigration>, TgError> {
+        if client
+            .resolve_table_metadata(&self.history_table)
+            .await?
+            .is_none()
+        {
+            return Ok(vec![]);
+        }
+
+        let transaction = client
+            .start_transaction(&TransactionOption::from(TransactionType::ReadOnly))
+            .await?;
+        let result = self.query_applied_migrations(client, &transaction).await;
+        transaction.close().await?;
+        result
+    }
+
+    async fn query_applied_migrations(
+        &self,
+        client: &SqlClient,
+        transaction: &Transaction,
+    ) -> Result<Vec<AppliedMigration>, TgError> {
+        let sql = format!(
+            "select version, description, checksum, applied_at from {} order by version",
+            self.history_table
+        );
+        let mut query_result = client.query(transaction, &sql).await?;
+
+        let mut applied = Vec::new();
+        while query_result.next_row().await? {
+            query_result.next_column().await?;
+            let version: i64 = query_result.fetch().await?;
+            query_result.next_column().await?;
+            let description: String = if query_result.is_null()? {
+                String::new()
+            } else {
+                query_result.fetch().await?
+            };
+            query_result.next_column().await?;
+            let checksum: String = query_result.fetch().await?;
+            query_result.next_column().await?;
+            let applied_at: Option<TgTimePointWithTimeZone> = if query_result.is_null()? {
+                None
+            } else {
+                Some(query_result.fetch().await?)
+            };
+            applied.push(AppliedMigration {
+                version,
+                description,
+                checksum,
+                applied_at,
+            });
+        }
+        query_result.close().await?;
+
+        let commit_option = CommitOption::new();
+        client.commit(transaction, &commit_option).await?;
+        Ok(applied)
+    }
+
+    async fn apply(&self, client: &SqlClient, migration: &Migration) -> Result<(), TgError> {
+        const FUNCTION_NAME: &str = "apply()";
+        trace!("{} start. version={}", FUNCTION_NAME, migration.version);
+
+        let label = format!("migration V{}", migration.version);
+        let insert = format!(
+            "insert into {} (version, description, checksum, applied_at) values (:version, :description, :checksum, :applied_at)",
+            self.history_table
+        );
+        let placeholders = vec![
+            SqlPlaceholder::of::<i64>("version"),
+            SqlPlaceholder::of::<String>("description"),
+            SqlPlaceholder::of::<String>("checksum"),
+            SqlPlaceholder::of::<TgTimePointWithTimeZone>("applied_at"),
+        ];
+        let prepared_statement = client.prepare(&insert, placeholders).await?;
+
+        let result = self
+            .execute_in_transaction(client, &label, |tx| {
+                let prepared_statement = &prepared_statement;
+                async move {
+                    for (i, sql) in migration.statements().iter().enumerate() {
+                        if let Err(e) = client.execute(&tx, sql).await {
+                            warn!(
+                                "migration V{} failed at statement {}. {}",
+                                migration.version,
+                                i + 1,
+                                e
+                            );
+                            let _ = client.rollback(&tx).await;
+                            let _ = tx.close().await;
+                            return Err(e);
+                        }
+                    }
+
+                    let parameters = vec![
+                        SqlParameter::of("version", migration.version),
+                        SqlParameter::of("description", migration.description.as_str()),
+                        SqlParameter::of("checksum", migration.checksum.as_str()),
+                        SqlParameter::of("applied_at", now()),
+                    ];
+                    client
+                        .prepared_execute(&tx, prepared_statement, parameters)
+                        .await?;
+                    Ok(tx)
+                }
+            })
+            .await;
+        prepared_statement.close().await?;
+
+        trace!("{} end", FUNCTION_NAME);
+        result
+    }
+
+    /// Starts a transaction, executes `f`, and commits.
+    ///
+    /// `f` returns the transaction back to be committed.
+    async fn execute_in_transaction<'a, F, Fut>(
+        &self,
+        client: &SqlClient,
+        label: &str,
+        f: F,
+    ) -> Result<(), TgError>
+    where
+        F: FnOnce(Transaction) -> Fut,
+        Fut: std::future::Future<Output = Result<Transaction, TgError>> + 'a,
+    {
+        let mut transaction_option = self.transaction_option.clone();
+        transaction_option.set_transaction_label(label);
+        let transaction = client.start_transaction(&transaction_option).await?;
+
+        let transaction = f(transaction).await?;
+        let commit_option = CommitOption::new();
+        let result = client.commit(&transaction, &commit_option).await;
+        transaction.close().await?;
+        result
+    }
+}
+
+fn now() -> TgTimePointWithTimeZone {
+    let now = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default();
+    TgTimePointWithTimeZone::new(now.as_secs() as i64, now.subsec_nanos(), 0)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn applied(version: i64, script: &str) -> AppliedMigration {
+        AppliedMigration {
+            version,
+            description: format!("v{version}"),
+            checksum: checksum(script),
+            applied_at: None,
+        }
+    }
+
+    #[test]
+    fn file_name() {
+        assert_eq!(
+            Some((1, "create tables".to_string())),
+            parse_file_name("V1__create_tables.sql")
+        );
+        assert_eq!(
+            Some((20250116, "add column".to_string())),
+            parse_file_name("v20250116__add_column.sql")
+        );
+        assert_eq!(None, parse_file_name("V1_create_tables.sql"));
+        assert_eq!(None, parse_file_name("Vx__create_tables.sql"));
+        assert_eq!(None, parse_file_name("V1__create_tables.txt"));
+        assert_eq!(None, parse_file_name("R__view.sql"));
+    }
+
+    #[test]
+    fn checksum_line_ending() {
+        let lf = Migration::new(
+            1,
+            "a",
+            "create table a (x int);\ninsert into a values (1);\n",
+        );
+        let crlf = Migration::new(
+            1,
+            "a",
+            "create table a (x int);\r\ninsert into a values (1);\r\n",
+        );
+        assert_eq!(lf.checksum(), crlf.checksum());
+        assert_eq!(40, lf.checksum().len());
+        assert_eq!(
+            vec!["create table a (x int)", "insert into a values (1)"],
+            lf.statements()
+        );
+
+        let changed = Migration::new(1, "a", "create table a (x bigint);");
+        assert_ne!(lf.checksum(), changed.checksum());
+    }
+
+    #[test]
+    fn load_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("V2__second.sql"), "select 2").unwrap();
+        std::fs::write(dir.path().join("V10__third.sql"), "select 10").unwrap();
+        std::fs::write(dir.path().join("V1__first.sql"), "select 1").unwrap();
+        std::fs::write(dir.path().join("README.md"), "readme").unwrap();
+
+        let migrations = Migration::load_dir(dir.path()).unwrap();
+        let versions: Vec<i64> = migrations.iter().map(|m| m.version()).collect();
+        assert_eq!(vec![1, 2, 10], versions);
+        assert_eq!("first", migrations[0].description());
+        assert_eq!(
+            Some(&dir.path().join("V1__first.sql")),
+            migrations[0].path()
+        );
+
+        std::fs::write(dir.path().join("V01__duplicate.sql"), "select 1").unwrap();
+        assert!(Migration::load_dir(dir.path()).is_err());
+
+        std::fs::write(dir.path().join("invalid.sql"), "select 1").unwrap();
+        assert!(Migration::load_dir(dir.path()).is_err());
+    }
+
+    #[test]
+    fn compare() {
+        let migrator = Migrator::new(vec![
+            Migration::new(3, "v3", "select 3"),
+            Migration::new(1, "v1", "select 1"),
+            Migration::new(2, "v2", "select 2"),
+            Migration::new(5, "v5", "select 5"),
+        ]);
+
+        let status = migrator.compare(vec![]);
+        assert!(status.iter().all(|s| s.state() == MigrationState::Pending));
+        assert_eq!(4, migrator.pending(&status).unwrap().len());
+
+        let status = migrator.compare(vec![
+            applied(1, "select 1"),
+            applied(3, "select 3"),
+            applied(4, "select 4"),
+        ]);
+        let states: Vec<(i64, MigrationState)> =
+            status.iter().map(|s| (s.version(), s.state())).collect();
+        assert_eq!(
+            vec![
+                (1, MigrationState::Applied),
+                (2, MigrationState::OutOfOrder),
+                (3, MigrationState::Applied),
+                (4, MigrationState::Missing),
+                (5, MigrationState::Pending),
+            ],
+            states
+        );
+        assert!(migrator.pending(&status).is_err());
+
+        let status = migrator.compare(vec![
+            applied(1, "select 1"),
+            applied(2, "select 2"),
+            applied(3, "select 3"),
+        ]);
+        let pending = migrator.pending(&status).unwrap();
+        assert_eq!(1, pending.len());
+        assert_eq!(5, pending[0].version());
+    }
+
+    #[test]
+    fn checksum_mismatch() {
+        let migrator = Migrator::new(vec![
+            Migration::new(1, "v1", "select 1"),
+            Migration::new(2, "v2", "select 2"),
+        ]);
+        let status = migrator.compare(vec![applied(1, "select 100")]);
+        assert_eq!(MigrationState::ChecksumMismatch, status[0].state());
+        assert!(migrator.pending(&status).is_err());
+    }
+
+    #[test]
+    fn transaction_option() {
+        let mut migrator = Migrator::new(vec![]);
+        assert!(migrator.transaction_option().modifies_definitions());
+
+        migrator.set_transaction_option(TransactionOption::from(TransactionType::Long));
+        assert!(migrator.transaction_option().modifies_definitions());
+        assert_eq!(
+            TransactionType::Long,
+            migrator.transaction_option().transaction_type()
+        );
+    }
+}
@@ -2,10 +2,16 @@ pub mod column;
 pub(crate) mod error;
 pub mod execute_result;
 pub mod explain;
+pub mod interceptor;
+pub mod migration;
 pub mod name;
 pub mod prepare;
 pub mod query_result;
+pub mod script;
+pub mod search_path;
 pub mod sql_client;
+pub mod sql_text;
+pub mod table_diff;
 pub mod table_list;
 pub mod table_metadata;
 pub mod r#type;
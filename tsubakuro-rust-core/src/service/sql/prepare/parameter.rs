@@ -1,3 +1,4 @@
+use crate::jogasaki::proto::sql::common::AtomType;
 use crate::jogasaki::proto::sql::request::parameter::{Placement, Value};
 use crate::jogasaki::proto::sql::request::Parameter as SqlParameter;
 use crate::jogasaki::proto::sql::request::{
@@ -47,6 +48,39 @@ impl SqlParameter {
     pub fn value(&self) -> Option<&Value> {
         self.value.as_ref()
     }
+
+    /// Get AtomType inferred from the value.
+    ///
+    /// Returns `None` if the value is null (or has no corresponding AtomType).
+    ///
+    /// since 0.11.0
+    pub fn atom_type(&self) -> Option<AtomType> {
+        let atom_type = match self.value.as_ref()? {
+            Value::BooleanValue(_) => AtomType::Boolean,
+            Value::Int4Value(_) => AtomType::Int4,
+            Value::Int8Value(_) => AtomType::Int8,
+            Value::Float4Value(_) => AtomType::Float4,
+            Value::Float8Value(_) => AtomType::Float8,
+            Value::DecimalValue(_) => AtomType::Decimal,
+            Value::CharacterValue(_) => AtomType::Character,
+            Value::OctetValue(_) => AtomType::Octet,
+            Value::BitValue(_) => AtomType::Bit,
+            Value::DateValue(_) => AtomType::Date,
+            Value::TimeOfDayValue(_) => AtomType::TimeOfDay,
+            Value::TimePointValue(_) => AtomType::TimePoint,
+            Value::DatetimeIntervalValue(_) => AtomType::DatetimeInterval,
+            Value::TimeOfDayWithTimeZoneValue(_) => AtomType::TimeOfDayWithTimeZone,
+            Value::TimePointWithTimeZoneValue(_) => AtomType::TimePointWithTimeZone,
+            Value::Clob(_) | Value::LargeObjectInfoClob(_) => AtomType::Clob,
+            Value::Blob(_) | Value::LargeObjectInfoBlob(_) => AtomType::Blob,
+            _ => return None,
+        };
+        Some(atom_type)
+    }
+
+    pub(crate) fn rename(self, name: &str) -> SqlParameter {
+        SqlParameter::new(name, self.value)
+    }
 }
 
 /// `of` method for [SqlParameter].
@@ -326,6 +360,38 @@ mod test {
         assert_eq!(target0, target);
     }
 
+    #[test]
+    fn atom_type() {
+        assert_eq!(None, SqlParameter::null("test").atom_type());
+        assert_eq!(
+            Some(AtomType::Boolean),
+            SqlParameter::of("test", true).atom_type()
+        );
+        assert_eq!(
+            Some(AtomType::Int4),
+            SqlParameter::of("test", 1).atom_type()
+        );
+        assert_eq!(
+            Some(AtomType::Int8),
+            SqlParameter::of("test", 1_i64).atom_type()
+        );
+        assert_eq!(
+            Some(AtomType::Character),
+            SqlParameter::of("test", "abc").atom_type()
+        );
+        assert_eq!(
+            Some(AtomType::Date),
+            SqlParameter::of("test", TgDate::new(1)).atom_type()
+        );
+    }
+
+    #[test]
+    fn rename() {
+        let target = SqlParameter::of("test", 123).rename("p1");
+        assert_eq!("p1", target.name().unwrap());
+        assert_eq!(&Value::Int4Value(123), target.value().unwrap());
+    }
+
     #[test]
     fn bool() {
         bool_test(true);
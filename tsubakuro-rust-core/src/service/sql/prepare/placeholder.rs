@@ -1,5 +1,6 @@
 use crate::jogasaki::proto::sql::common::AtomType;
 use crate::jogasaki::proto::sql::request::placeholder::{Placement, TypeInfo};
+use crate::jogasaki::proto::sql::request::Parameter as SqlParameter;
 use crate::jogasaki::proto::sql::request::Placeholder as SqlPlaceholder;
 use crate::prelude::{
     TgBlob, TgClob, TgDate, TgDecimal, TgDecimalI128, TgTimeOfDay, TgTimeOfDayWithTimeZone,
@@ -46,6 +47,20 @@ impl SqlPlaceholder {
         let atom_type = T::atom_type();
         SqlPlaceholder::of_atom_type(name, atom_type)
     }
+
+    /// Creates a new instance from the name and value of the parameter.
+    ///
+    /// The AtomType is inferred from the parameter value.
+    /// If the parameter is null, the AtomType is [`AtomType::Unknown`].
+    ///
+    /// Returns `None` if the parameter has no name.
+    ///
+    /// since 0.11.0
+    pub fn of_parameter(parameter: &SqlParameter) -> Option<SqlPlaceholder> {
+        let name = parameter.name()?;
+        let atom_type = parameter.atom_type().unwrap_or(AtomType::Unknown);
+        Some(SqlPlaceholder::of_atom_type(name, atom_type))
+    }
 }
 
 /// AtomType provider for [SqlPlaceholder].
@@ -270,6 +285,26 @@ impl SqlPlaceholderBind for String {
 mod test {
     use super::*;
 
+    #[test]
+    fn of_parameter() {
+        use crate::prelude::SqlParameterOf;
+
+        let parameter = SqlParameter::of("test", 123_i64);
+        let target = SqlPlaceholder::of_parameter(&parameter).unwrap();
+        assert_eq!(SqlPlaceholder::of::<i64>("test"), target);
+
+        let parameter = SqlParameter::of("test", "abc");
+        let target = SqlPlaceholder::of_parameter(&parameter).unwrap();
+        assert_eq!(SqlPlaceholder::of::<String>("test"), target);
+
+        let parameter = SqlParameter::null("test");
+        let target = SqlPlaceholder::of_parameter(&parameter).unwrap();
+        assert_eq!(
+            SqlPlaceholder::of_atom_type("test", AtomType::Unknown),
+            target
+        );
+    }
+
     #[test]
     fn bool() {
         let target0 = SqlPlaceholder::of_atom_type("test", AtomType::Boolean);
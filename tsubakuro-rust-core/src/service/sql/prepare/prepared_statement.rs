@@ -43,6 +43,7 @@ use crate::{
 #[derive(Debug)]
 pub struct SqlPreparedStatement {
     session: Arc<Session>,
+    sql: String,
     prepare_handle: u64,
     has_result_records: bool,
     close_timeout: Duration,
@@ -53,6 +54,7 @@ pub struct SqlPreparedStatement {
 impl SqlPreparedStatement {
     fn new(
         session: Arc<Session>,
+        sql: &str,
         prepare_handle: u64,
         has_result_records: bool,
         close_timeout: Duration,
@@ -60,6 +62,7 @@ impl SqlPreparedStatement {
         let fail_on_drop_error = session.fail_on_drop_error();
         SqlPreparedStatement {
             session,
+            sql: sql.to_string(),
             prepare_handle,
             has_result_records,
             close_timeout,
@@ -72,6 +75,13 @@ impl SqlPreparedStatement {
         self.prepare_handle
     }
 
+    /// Get the SQL statement.
+    ///
+    /// since 0.11.0
+    pub fn sql(&self) -> &String {
+        &self.sql
+    }
+
     /// Check whether ResultRecords are returned as a result of executing this statement.
     pub fn has_result_records(&self) -> bool {
         self.has_result_records
@@ -175,6 +185,7 @@ impl Drop for SqlPreparedStatement {
 
 pub(crate) fn prepare_processor(
     session: Arc<Session>,
+    sql: &str,
     response: WireResponse,
     close_timeout: Duration,
 ) -> Result<SqlPreparedStatement, TgError> {
@@ -191,6 +202,7 @@ pub(crate) fn prepare_processor(
                 crate::jogasaki::proto::sql::response::prepare::Result::PreparedStatementHandle(ps),
             ) => Ok(SqlPreparedStatement::new(
                 session,
+                sql,
                 ps.handle,
                 ps.has_result_records,
                 close_timeout,
@@ -0,0 +1,665 @@
+use std::time::Duration;
+
+use crate::{
+    broken_relation_error, client_error,
+    error::TgError,
+    prelude::{
+        AtomType, SqlColumn, SqlQueryResult, TgBlobReference, TgClobReference, TgDate,
+        TgDecimalResult, TgTimeOfDay, TgTimeOfDayWithTimeZone, TgTimePoint,
+        TgTimePointWithTimeZone,
+    },
+    util::Timeout,
+};
+
+use super::value_stream::ResultSetValueStream;
+
+/// Null bitmap of [SqlColumnVector].
+///
+/// The bit of the row is set if the value is `NULL`.
+/// The bits are packed into `u64` words from the least significant bit. (row `i` is bit `i % 64` of word `i / 64`)
+///
+/// since 0.11.0
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SqlNullBitmap {
+    words: Vec<u64>,
+    len: usize,
+    null_count: usize,
+}
+
+impl SqlNullBitmap {
+    pub(crate) fn with_capacity(capacity: usize) -> SqlNullBitmap {
+        SqlNullBitmap {
+            words: Vec::with_capacity(capacity.div_ceil(64)),
+            len: 0,
+            null_count: 0,
+        }
+    }
+
+    pub(crate) fn truncate(&mut self, len: usize) {
+        while self.len > len {
+            self.len -= 1;
+            let bit = self.len % 64;
+            let word = self.words.last_mut().unwrap();
+            if *word & (1 << bit) != 0 {
+                *word &= !(1 << bit);
+                self.null_count -= 1;
+            }
+            if bit == 0 {
+                self.words.pop();
+            }
+        }
+    }
+
+    pub(crate) fn push(&mut self, is_null: bool) {
+        let bit = self.len % 64;
+        if bit == 0 {
+            self.words.push(0);
+        }
+        if is_null {
+            *self.words.last_mut().unwrap() |= 1 << bit;
+            self.null_count += 1;
+        }
+        self.len += 1;
+    }
+
+    /// Get the number of rows.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether there are no rows.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Whether the value of the row is `NULL`.
+    ///
+    /// # Panics
+    /// Panics if `index` is out of range.
+    pub fn is_null(&self, index: usize) -> bool {
+        assert!(
+            index < self.len,
+            "index out of range. index={index}, len={}",
+            self.len
+        );
+        self.words[index / 64] & (1 << (index % 64)) != 0
+    }
+
+    /// Get the number of `NULL` values.
+    pub fn null_count(&self) -> usize {
+        self.null_count
+    }
+
+    /// Get the packed bits.
+    pub fn words(&self) -> &[u64] {
+        &self.words
+    }
+}
+
+/// Values of [SqlColumnVector].
+///
+/// since 0.11.0
+#[derive(Debug, Clone)]
+pub enum SqlColumnValues {
+    /// `BOOLEAN`
+    Boolean(Vec<Option<bool>>),
+    /// `INT`
+    Int4(Vec<Option<i32>>),
+    /// `BIGINT`
+    Int8(Vec<Option<i64>>),
+    /// `REAL`
+    Float4(Vec<Option<f32>>),
+    /// `DOUBLE`
+    Float8(Vec<Option<f64>>),
+    /// `DECIMAL`
+    Decimal(Vec<Option<TgDecimalResult>>),
+    /// `CHAR`, `VARCHAR`
+    Character(Vec<Option<String>>),
+    /// `BINARY`, `VARBINARY`
+    Octet(Vec<Option<Vec<u8>>>),
+    /// `DATE`
+    Date(Vec<Option<TgDate>>),
+    /// `TIME`
+    TimeOfDay(Vec<Option<TgTimeOfDay>>),
+    /// `TIMESTAMP`
+    TimePoint(Vec<Option<TgTimePoint>>),
+    /// `TIME WITH TIME ZONE`
+    TimeOfDayWithTimeZone(Vec<Option<TgTimeOfDayWithTimeZone>>),
+    /// `TIMESTAMP WITH TIME ZONE`
+    TimePointWithTimeZone(Vec<Option<TgTimePointWithTimeZone>>),
+    /// `BLOB`
+    Blob(Vec<Option<TgBlobReference>>),
+    /// `CLOB`
+    Clob(Vec<Option<TgClobReference>>),
+}
+
+impl SqlColumnValues {
+    fn with_capacity(atom_type: AtomType, capacity: usize) -> Option<SqlColumnValues> {
+        let values = match atom_type {
+            AtomType::Boolean => SqlColumnValues::Boolean(Vec::with_capacity(capacity)),
+            AtomType::Int4 => SqlColumnValues::Int4(Vec::with_capacity(capacity)),
+            AtomType::Int8 => SqlColumnValues::Int8(Vec::with_capacity(capacity)),
+            AtomType::Float4 => SqlColumnValues::Float4(Vec::with_capacity(capacity)),
+            AtomType::Float8 => SqlColumnValues::Float8(Vec::with_capacity(capacity)),
+            AtomType::Decimal => SqlColumnValues::Decimal(Vec::with_capacity(capacity)),
+            AtomType::Character => SqlColumnValues::Character(Vec::with_capacity(capacity)),
+            AtomType::Octet => SqlColumnValues::Octet(Vec::with_capacity(capacity)),
+            AtomType::Date => SqlColumnValues::Date(Vec::with_capacity(capacity)),
+            AtomType::TimeOfDay => SqlColumnValues::TimeOfDay(Vec::with_capacity(capacity)),
+            AtomType::TimePoint => SqlColumnValues::TimePoint(Vec::with_capacity(capacity)),
+            AtomType::TimeOfDayWithTimeZone => {
+                SqlColumnValues::TimeOfDayWithTimeZone(Vec::with_capacity(capacity))
+            }
+            AtomType::TimePointWithTimeZone => {
+                SqlColumnValues::TimePointWithTimeZone(Vec::with_capacity(capacity))
+            }
+            AtomType::Blob => SqlColumnValues::Blob(Vec::with_capacity(capacity)),
+            AtomType::Clob => SqlColumnValues::Clob(Vec::with_capacity(capacity)),
+            _ => return None,
+        };
+        Some(values)
+    }
+
+    /// Get the number of rows.
+    pub fn len(&self) -> usize {
+        match self {
+            SqlColumnValues::Boolean(values) => values.len(),
+            SqlColumnValues::Int4(values) => values.len(),
+            SqlColumnValues::Int8(values) => values.len(),
+            SqlColumnValues::Float4(values) => values.len(),
+            SqlColumnValues::Float8(values) => values.len(),
+            SqlColumnValues::Decimal(values) => values.len(),
+            SqlColumnValues::Character(values) => values.len(),
+            SqlColumnValues::Octet(values) => values.len(),
+            SqlColumnValues::Date(values) => values.len(),
+            SqlColumnValues::TimeOfDay(values) => values.len(),
+            SqlColumnValues::TimePoint(values) => values.len(),
+            SqlColumnValues::TimeOfDayWithTimeZone(values) => values.len(),
+            SqlColumnValues::TimePointWithTimeZone(values) => values.len(),
+            SqlColumnValues::Blob(values) => values.len(),
+            SqlColumnValues::Clob(values) => values.len(),
+        }
+    }
+
+    /// Whether there are no rows.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Values of a column in [SqlQueryResultBatch].
+///
+/// since 0.11.0
+#[derive(Debug, Clone)]
+pub struct SqlColumnVector {
+    name: String,
+    atom_type: AtomType,
+    values: SqlColumnValues,
+    null_bitmap: SqlNullBitmap,
+}
+
+impl SqlColumnVector {
+    pub(crate) fn for_column(
+        column: &SqlColumn,
+        capacity: usize,
+    ) -> Result<SqlColumnVector, TgError> {
+        const FUNCTION_NAME: &str = "fetch_batch()";
+
+        let atom_type = column.atom_type();
+        let values =
+            atom_type.and_then(|atom_type| SqlColumnValues::with_capacity(atom_type, capacity));
+        match (atom_type, values) {
+            (Some(atom_type), Some(values)) => Ok(SqlColumnVector {
+                name: column.name().clone(),
+                atom_type,
+                values,
+                null_bitmap: SqlNullBitmap::with_capacity(capacity),
+            }),
+            _ => Err(client_error!(format!(
+                "{FUNCTION_NAME}: unsupported column type. column={}, type={:?}",
+                column.name(),
+                column.type_info
+            ))),
+        }
+    }
+
+    /// Get column name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get AtomType.
+    pub fn atom_type(&self) -> AtomType {
+        self.atom_type
+    }
+
+    /// Get values.
+    pub fn values(&self) -> &SqlColumnValues {
+        &self.values
+    }
+
+    /// Get values.
+    pub fn into_values(self) -> SqlColumnValues {
+        self.values
+    }
+
+    /// Get null bitmap.
+    pub fn null_bitmap(&self) -> &SqlNullBitmap {
+        &self.null_bitmap
+    }
+
+    /// Get the number of rows.
+    pub fn len(&self) -> usize {
+        self.null_bitmap.len()
+    }
+
+    /// Whether there are no rows.
+    pub fn is_empty(&self) -> bool {
+        self.null_bitmap.is_empty()
+    }
+
+    /// Shrinks to the first `len` rows.
+    fn truncate(&mut self, len: usize) {
+        match &mut self.values {
+            SqlColumnValues::Boolean(values) => values.truncate(len),
+            SqlColumnValues::Int4(values) => values.truncate(len),
+            SqlColumnValues::Int8(values) => values.truncate(len),
+            SqlColumnValues::Float4(values) => values.truncate(len),
+            SqlColumnValues::Float8(values) => values.truncate(len),
+            SqlColumnValues::Decimal(values) => values.truncate(len),
+            SqlColumnValues::Character(values) => values.truncate(len),
+            SqlColumnValues::Octet(values) => values.truncate(len),
+            SqlColumnValues::Date(values) => values.truncate(len),
+            SqlColumnValues::TimeOfDay(values) => values.truncate(len),
+            SqlColumnValues::TimePoint(values) => values.truncate(len),
+            SqlColumnValues::TimeOfDayWithTimeZone(values) => values.truncate(len),
+            SqlColumnValues::TimePointWithTimeZone(values) => values.truncate(len),
+            SqlColumnValues::Blob(values) => values.truncate(len),
+            SqlColumnValues::Clob(values) => values.truncate(len),
+        }
+        self.null_bitmap.truncate(len);
+    }
+
+    /// Decodes the value on the column of the cursor position into the vector.
+    async fn push_from(
+        &mut self,
+        value_stream: &mut ResultSetValueStream,
+        timeout: &Timeout,
+    ) -> Result<(), TgError> {
+        let is_null = value_stream.is_null()?;
+        macro_rules! push {
+            ($values:expr, $value:expr) => {
+                $values.push(if is_null { None } else { Some($value) })
+            };
+        }
+
+        match &mut self.values {
+            SqlColumnValues::Boolean(values) => {
+                push!(values, value_stream.fetch_boolean_value(timeout).await?)
+            }
+            SqlColumnValues::Int4(values) => {
+                push!(values, value_stream.fetch_int4_value(timeout).await?)
+            }
+            SqlColumnValues::Int8(values) => {
+                push!(values, value_stream.fetch_int8_value(timeout).await?)
+            }
+            SqlColumnValues::Float4(values) => {
+                push!(values, value_stream.fetch_float4_value(timeout).await?)
+            }
+            SqlColumnValues::Float8(values) => {
+                push!(values, value_stream.fetch_float8_value(timeout).await?)
+            }
+            SqlColumnValues::Decimal(values) => push!(values, {
+                let (coefficient_bytes, coefficient, exponent) =
+                    value_stream.fetch_decimal_value(timeout).await?;
+                TgDecimalResult::new(
+                    coefficient_bytes.as_deref().map(<[u8]>::to_vec),
+                    coefficient,
+                    exponent,
+                )
+            }),
+            SqlColumnValues::Character(values) => {
+                push!(values, value_stream.fetch_character_value(timeout).await?)
+            }
+            SqlColumnValues::Octet(values) => {
+                push!(
+                    values,
+                    value_stream.fetch_octet_value(timeout).await?.to_vec()
+                )
+            }
+            SqlColumnValues::Date(values) => {
+                push!(
+                    values,
+                    TgDate::new(value_stream.fetch_date_value(timeout).await?)
+                )
+            }
+            SqlColumnValues::TimeOfDay(values) => push!(
+                values,
+                TgTimeOfDay::new(value_stream.fetch_time_of_day_value(timeout).await?)
+            ),
+            SqlColumnValues::TimePoint(values) => push!(values, {
+                let (epoch_seconds, nanos) = value_stream.fetch_time_point_value(timeout).await?;
+                TgTimePoint::new(epoch_seconds, nanos)
+            }),
+            SqlColumnValues::TimeOfDayWithTimeZone(values) => push!(values, {
+                let (nanos, offset) = value_stream
+                    .fetch_time_of_day_with_time_zone_value(timeout)
+                    .await?;
+                TgTimeOfDayWithTimeZone::new(nanos, offset)
+            }),
+            SqlColumnValues::TimePointWithTimeZone(values) => push!(values, {
+                let (epoch_seconds, nanos, offset) = value_stream
+                    .fetch_time_point_with_time_zone_value(timeout)
+                    .await?;
+                TgTimePointWithTimeZone::new(epoch_seconds, nanos, offset)
+            }),
+            SqlColumnValues::Blob(values) => push!(values, {
+                let (provider, object_id, reference_tag) = value_stream.fetch_blob(timeout).await?;
+                TgBlobReference::new(provider, object_id, reference_tag)
+            }),
+            SqlColumnValues::Clob(values) => push!(values, {
+                let (provider, object_id, reference_tag) = value_stream.fetch_clob(timeout).await?;
+                TgClobReference::new(provider, object_id, reference_tag)
+            }),
+        }
+        self.null_bitmap.push(is_null);
+        Ok(())
+    }
+}
+
+/// Decodes up to `max_rows` rows into `columns`.
+///
+/// Returns the number of complete rows, together with the error if one occurred.
+/// On error, the columns may also hold values of the incomplete row.
+async fn read_rows(
+    value_stream: &mut ResultSetValueStream,
+    columns: &mut [SqlColumnVector],
+    max_rows: usize,
+    timeout: Duration,
+) -> (usize, Result<(), TgError>) {
+    const FUNCTION_NAME: &str = "fetch_batch()";
+
+    let mut row_count = 0;
+    while row_count < max_rows {
+        match value_stream.next_row(&Timeout::new(timeout)).await {
+            Ok(true) => {}
+            Ok(false) => break,
+            Err(e) => return (row_count, Err(e)),
+        }
+        for column in columns.iter_mut() {
+            let timeout = Timeout::new(timeout);
+            match value_stream.next_column(&timeout).await {
+                Ok(true) => {}
+                Ok(false) => {
+                    let e = broken_relation_error!(
+                        FUNCTION_NAME,
+                        format!("column not found. column={}", column.name())
+                    );
+                    return (row_count, Err(e));
+                }
+                Err(e) => return (row_count, Err(e)),
+            }
+            if let Err(e) = column.push_from(value_stream, &timeout).await {
+                return (row_count, Err(e));
+            }
+        }
+        row_count += 1;
+    }
+    (row_count, Ok(()))
+}
+
+/// Rows of [SqlQueryResult] in columnar format.
+///
+/// See [SqlQueryResult::fetch_batch()].
+///
+/// since 0.11.0
+#[derive(Debug, Clone)]
+pub struct SqlQueryResultBatch {
+    row_count: usize,
+    columns: Vec<SqlColumnVector>,
+}
+
+impl SqlQueryResultBatch {
+    /// Get the number of rows.
+    pub fn row_count(&self) -> usize {
+        self.row_count
+    }
+
+    /// Whether there are no rows.
+    pub fn is_empty(&self) -> bool {
+        self.row_count == 0
+    }
+
+    /// Get columns.
+    pub fn columns(&self) -> &[SqlColumnVector] {
+        &self.columns
+    }
+
+    /// Get the column by index.
+    pub fn column(&self, index: usize) -> Option<&SqlColumnVector> {
+        self.columns.get(index)
+    }
+
+    /// Get the column by name.
+    pub fn column_by_name(&self, name: &str) -> Option<&SqlColumnVector> {
+        self.columns.iter().find(|column| column.name() == name)
+    }
+
+    /// Get columns.
+    pub fn into_columns(self) -> Vec<SqlColumnVector> {
+        self.columns
+    }
+}
+
+/// Upper limit of the initial capacity of column vectors.
+const MAX_INITIAL_CAPACITY: usize = 1024;
+
+impl SqlQueryResult {
+    /// Retrieves up to `max_rows` rows in columnar format.
+    ///
+    /// The column types are decided by [SqlQueryResultMetadata](crate::prelude::SqlQueryResultMetadata).
+    /// Returns an empty batch if there are no more rows.
+    ///
+    /// If the cursor is in the middle of a row, the rest of the row is skipped.
+    ///
+    /// If an error occurs after some rows have been read, those rows are returned
+    /// and the error is returned by the next call of this method.
+    ///
+    /// # Examples
+    /// ```
+    /// use tsubakuro_rust_core::prelude::*;
+    ///
+    /// async fn example(query_result: &mut SqlQueryResult) -> Result<i64, TgError> {
+    ///     let mut total = 0;
+    ///     loop {
+    ///         let batch = query_result.fetch_batch(1000).await?;
+    ///         if batch.is_empty() {
+    ///             break;
+    ///         }
+    ///
+    ///         let column = batch.column(0).unwrap();
+    ///         if let SqlColumnValues::Int8(values) = column.values() {
+    ///             total += values.iter().flatten().sum::<i64>();
+    ///         }
+    ///     }
+    ///     Ok(total)
+    /// }
+    /// ```
+    ///
+    /// since 0.11.0
+    pub async fn fetch_batch(&mut self, max_rows: usize) -> Result<SqlQueryResultBatch, TgError> {
+        self.fetch_batch_for(max_rows, self.default_timeout).await
+    }
+
+    /// Retrieves up to `max_rows` rows in columnar format.
+    ///
+    /// `timeout` is applied to each read operation.
+    ///
+    /// See [`Self::fetch_batch`].
+    ///
+    /// since 0.11.0
+    pub async fn fetch_batch_for(
+        &mut self,
+        max_rows: usize,
+        timeout: Duration,
+    ) -> Result<SqlQueryResultBatch, TgError> {
+        const FUNCTION_NAME: &str = "fetch_batch()";
+
+        if let Some(e) = self.batch_error.take() {
+            return Err(e);
+        }
+
+        let capacity = max_rows.min(MAX_INITIAL_CAPACITY);
+        let mut columns = match self.get_metadata() {
+            Some(metadata) => metadata
+                .columns()
+                .iter()
+                .map(|column| SqlColumnVector::for_column(column, capacity))
+                .collect::<Result<Vec<_>, _>>()?,
+            None => {
+                return Err(client_error!(format!(
+                    "{FUNCTION_NAME}: metadata not found"
+                )))
+            }
+        };
+
+        let (row_count, result) =
+            read_rows(&mut self.value_stream, &mut columns, max_rows, timeout).await;
+        if let Err(e) = result {
+            if row_count == 0 {
+                return Err(e);
+            }
+            for column in columns.iter_mut() {
+                column.truncate(row_count);
+            }
+            self.batch_error = Some(e);
+        }
+
+        Ok(SqlQueryResultBatch { row_count, columns })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::session::wire::data_channel::test::data_channel;
+
+    use super::*;
+
+    fn column(name: &str, atom_type: AtomType) -> SqlColumn {
+        SqlColumn {
+            name: name.to_string(),
+            type_info: Some(
+                crate::jogasaki::proto::sql::common::column::TypeInfo::AtomType(atom_type.into()),
+            ),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn null_bitmap() {
+        let mut bitmap = SqlNullBitmap::with_capacity(100);
+        for i in 0..100 {
+            bitmap.push(i % 3 == 0);
+        }
+
+        assert_eq!(100, bitmap.len());
+        assert_eq!(34, bitmap.null_count());
+        assert_eq!(2, bitmap.words().len());
+        assert!(bitmap.is_null(0));
+        assert!(!bitmap.is_null(1));
+        assert!(bitmap.is_null(63));
+        assert!(!bitmap.is_null(64));
+        assert!(bitmap.is_null(99));
+    }
+
+    #[test]
+    fn for_column() {
+        let vector = SqlColumnVector::for_column(&column("pk", AtomType::Int8), 10).unwrap();
+        assert_eq!("pk", vector.name());
+        assert_eq!(AtomType::Int8, vector.atom_type());
+        assert!(matches!(vector.values(), SqlColumnValues::Int8(_)));
+        assert!(vector.is_empty());
+
+        let vector = SqlColumnVector::for_column(&column("c", AtomType::Character), 10).unwrap();
+        assert!(matches!(vector.values(), SqlColumnValues::Character(_)));
+
+        assert!(SqlColumnVector::for_column(&column("x", AtomType::Bit), 10).is_err());
+        assert!(SqlColumnVector::for_column(&SqlColumn::default(), 10).is_err());
+    }
+
+    #[test]
+    fn null_bitmap_truncate() {
+        let mut bitmap = SqlNullBitmap::with_capacity(100);
+        for i in 0..100 {
+            bitmap.push(i % 3 == 0);
+        }
+
+        bitmap.truncate(64);
+        assert_eq!(64, bitmap.len());
+        assert_eq!(22, bitmap.null_count());
+        assert_eq!(1, bitmap.words().len());
+        assert!(bitmap.is_null(63));
+
+        bitmap.truncate(0);
+        assert!(bitmap.is_empty());
+        assert_eq!(0, bitmap.null_count());
+        assert!(bitmap.words().is_empty());
+    }
+
+    fn columns() -> Vec<SqlColumnVector> {
+        vec![
+            SqlColumnVector::for_column(&column("pk", AtomType::Int8), 10).unwrap(),
+            SqlColumnVector::for_column(&column("value", AtomType::Character), 10).unwrap(),
+        ]
+    }
+
+    #[tokio::test]
+    async fn read_rows_test() {
+        // row(2): 1, "a"; row(2): 2, NULL; row(2): 3, "bc"; end of contents
+        let chunks: Vec<&'static [u8]> =
+            vec![b"\x81\x01\x40a\x81\x02", b"\xe8\x81\x03\x41b", b"c\xfe"];
+        let mut value_stream = ResultSetValueStream::new(data_channel(chunks));
+        let mut columns = columns();
+        let timeout = Duration::from_secs(10);
+
+        let (row_count, result) = read_rows(&mut value_stream, &mut columns, 2, timeout).await;
+        result.unwrap();
+        assert_eq!(2, row_count);
+        assert!(
+            matches!(columns[0].values(), SqlColumnValues::Int8(v) if v == &[Some(1), Some(2)])
+        );
+        assert!(matches!(columns[1].values(),
+            SqlColumnValues::Character(v) if v == &[Some("a".to_string()), None]));
+        assert_eq!(1, columns[1].null_bitmap().null_count());
+        assert!(columns[1].null_bitmap().is_null(1));
+
+        let mut columns = self::columns();
+        let (row_count, result) = read_rows(&mut value_stream, &mut columns, 2, timeout).await;
+        result.unwrap();
+        assert_eq!(1, row_count);
+        assert!(matches!(columns[1].values(),
+            SqlColumnValues::Character(v) if v == &[Some("bc".to_string())]));
+    }
+
+    #[tokio::test]
+    async fn read_rows_error() {
+        // row(2): 1, "a"; row(2): 2, 3 (type mismatch); end of contents
+        let chunks: Vec<&'static [u8]> = vec![b"\x81\x01\x40a\x81\x02\x03\xfe"];
+        let mut value_stream = ResultSetValueStream::new(data_channel(chunks));
+        let mut columns = columns();
+        let timeout = Duration::from_secs(10);
+
+        let (row_count, result) = read_rows(&mut value_stream, &mut columns, 10, timeout).await;
+        assert!(result.is_err());
+        assert_eq!(1, row_count);
+        assert_eq!(2, columns[0].len());
+        assert_eq!(1, columns[1].len());
+
+        for column in columns.iter_mut() {
+            column.truncate(row_count);
+        }
+        assert!(matches!(columns[0].values(), SqlColumnValues::Int8(v) if v == &[Some(1)]));
+        assert_eq!(1, columns[0].len());
+        assert_eq!(1, columns[1].len());
+    }
+}
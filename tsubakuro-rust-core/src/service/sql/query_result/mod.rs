@@ -1,7 +1,12 @@
+mod batch;
 pub mod metadata;
+mod prefetch;
 #[allow(clippy::module_inception)]
 mod query_result;
 mod value_stream;
 mod variant;
 
+pub use batch::{SqlColumnValues, SqlColumnVector, SqlNullBitmap, SqlQueryResultBatch};
+pub(crate) use prefetch::PrefetchCounters;
+pub use prefetch::{SqlQueryPrefetchOption, SqlQueryPrefetchStatistics};
 pub use query_result::*;
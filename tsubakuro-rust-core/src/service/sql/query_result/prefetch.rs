@@ -0,0 +1,219 @@
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+/// Option for prefetching rows of [SqlQueryResult](crate::prelude::SqlQueryResult).
+///
+/// When prefetch is enabled, a background task reads the result set data from the server ahead of
+/// [SqlQueryResult::next_row()](crate::prelude::SqlQueryResult::next_row) into a bounded buffer.
+/// When the buffer is full, the background task waits until the application consumes the data.
+///
+/// See [ConnectionOption::set_query_prefetch_option()](crate::prelude::ConnectionOption::set_query_prefetch_option),
+/// [SqlClient::set_query_prefetch_option()](crate::prelude::SqlClient::set_query_prefetch_option),
+/// [SqlQueryResult::start_prefetch()](crate::prelude::SqlQueryResult::start_prefetch).
+///
+/// # Examples
+/// ```
+/// use tsubakuro_rust_core::prelude::*;
+///
+/// fn example(connection_option: &mut ConnectionOption) {
+///     let mut prefetch_option = SqlQueryPrefetchOption::new();
+///     prefetch_option.set_buffer_size(128);
+///     connection_option.set_query_prefetch_option(Some(prefetch_option));
+/// }
+/// ```
+///
+/// since 0.11.0
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SqlQueryPrefetchOption {
+    buffer_size: usize,
+}
+
+impl Default for SqlQueryPrefetchOption {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SqlQueryPrefetchOption {
+    /// The default buffer size.
+    pub const DEFAULT_BUFFER_SIZE: usize = 64;
+
+    /// Creates a new instance.
+    pub fn new() -> SqlQueryPrefetchOption {
+        SqlQueryPrefetchOption {
+            buffer_size: Self::DEFAULT_BUFFER_SIZE,
+        }
+    }
+
+    /// Set buffer size.
+    ///
+    /// The buffer size is the number of data chunks (payloads sent by the server) to read ahead.
+    /// If `0` is specified, `1` is used.
+    pub fn set_buffer_size(&mut self, buffer_size: usize) {
+        self.buffer_size = buffer_size.max(1);
+    }
+
+    /// Get buffer size.
+    pub fn buffer_size(&self) -> usize {
+        self.buffer_size
+    }
+}
+
+/// Statistics of prefetching rows of [SqlQueryResult](crate::prelude::SqlQueryResult).
+///
+/// See [SqlQueryResult::prefetch_statistics()](crate::prelude::SqlQueryResult::prefetch_statistics).
+///
+/// since 0.11.0
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SqlQueryPrefetchStatistics {
+    buffer_size: usize,
+    received_chunks: u64,
+    received_bytes: u64,
+    consumed_chunks: u64,
+    buffered_chunks: usize,
+    max_buffered_chunks: usize,
+    consumer_waits: u64,
+    producer_waits: u64,
+}
+
+impl SqlQueryPrefetchStatistics {
+    /// Get buffer size.
+    pub fn buffer_size(&self) -> usize {
+        self.buffer_size
+    }
+
+    /// Get the number of data chunks read from the server.
+    pub fn received_chunks(&self) -> u64 {
+        self.received_chunks
+    }
+
+    /// Get the number of bytes read from the server.
+    pub fn received_bytes(&self) -> u64 {
+        self.received_bytes
+    }
+
+    /// Get the number of data chunks consumed by the application.
+    pub fn consumed_chunks(&self) -> u64 {
+        self.consumed_chunks
+    }
+
+    /// Get the number of data chunks currently in the buffer.
+    pub fn buffered_chunks(&self) -> usize {
+        self.buffered_chunks
+    }
+
+    /// Get the maximum number of data chunks which were in the buffer at the same time.
+    pub fn max_buffered_chunks(&self) -> usize {
+        self.max_buffered_chunks
+    }
+
+    /// Get the number of times the application waited because the buffer was empty.
+    ///
+    /// A large value means that the network is the bottleneck.
+    pub fn consumer_waits(&self) -> u64 {
+        self.consumer_waits
+    }
+
+    /// Get the number of times the background task waited because the buffer was full.
+    ///
+    /// A large value means that the application is the bottleneck.
+    pub fn producer_waits(&self) -> u64 {
+        self.producer_waits
+    }
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct PrefetchCounters {
+    buffer_size: usize,
+    received_chunks: AtomicU64,
+    received_bytes: AtomicU64,
+    consumed_chunks: AtomicU64,
+    buffered_chunks: AtomicUsize,
+    max_buffered_chunks: AtomicUsize,
+    consumer_waits: AtomicU64,
+    producer_waits: AtomicU64,
+}
+
+impl PrefetchCounters {
+    pub(crate) fn new(buffer_size: usize) -> PrefetchCounters {
+        PrefetchCounters {
+            buffer_size,
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn add_received(&self, bytes: usize) {
+        self.received_chunks.fetch_add(1, Ordering::Relaxed);
+        self.received_bytes
+            .fetch_add(bytes as u64, Ordering::Relaxed);
+        let buffered = self.buffered_chunks.fetch_add(1, Ordering::Relaxed) + 1;
+        self.max_buffered_chunks
+            .fetch_max(buffered, Ordering::Relaxed);
+    }
+
+    pub(crate) fn add_consumed(&self) {
+        self.consumed_chunks.fetch_add(1, Ordering::Relaxed);
+        self.buffered_chunks.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn add_consumer_wait(&self) {
+        self.consumer_waits.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn add_producer_wait(&self) {
+        self.producer_waits.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn statistics(&self) -> SqlQueryPrefetchStatistics {
+        SqlQueryPrefetchStatistics {
+            buffer_size: self.buffer_size,
+            received_chunks: self.received_chunks.load(Ordering::Relaxed),
+            received_bytes: self.received_bytes.load(Ordering::Relaxed),
+            consumed_chunks: self.consumed_chunks.load(Ordering::Relaxed),
+            buffered_chunks: self.buffered_chunks.load(Ordering::Relaxed),
+            max_buffered_chunks: self.max_buffered_chunks.load(Ordering::Relaxed),
+            consumer_waits: self.consumer_waits.load(Ordering::Relaxed),
+            producer_waits: self.producer_waits.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn option() {
+        let mut option = SqlQueryPrefetchOption::new();
+        assert_eq!(
+            SqlQueryPrefetchOption::DEFAULT_BUFFER_SIZE,
+            option.buffer_size()
+        );
+
+        option.set_buffer_size(8);
+        assert_eq!(8, option.buffer_size());
+
+        option.set_buffer_size(0);
+        assert_eq!(1, option.buffer_size());
+    }
+
+    #[test]
+    fn counters() {
+        let counters = PrefetchCounters::new(4);
+        counters.add_received(10);
+        counters.add_received(20);
+        counters.add_consumed();
+        counters.add_received(30);
+        counters.add_consumer_wait();
+        counters.add_producer_wait();
+
+        let statistics = counters.statistics();
+        assert_eq!(4, statistics.buffer_size());
+        assert_eq!(3, statistics.received_chunks());
+        assert_eq!(60, statistics.received_bytes());
+        assert_eq!(1, statistics.consumed_chunks());
+        assert_eq!(2, statistics.buffered_chunks());
+        assert_eq!(2, statistics.max_buffered_chunks());
+        assert_eq!(1, statistics.consumer_waits());
+        assert_eq!(1, statistics.producer_waits());
+    }
+}
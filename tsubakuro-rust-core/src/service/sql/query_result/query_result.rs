@@ -1,4 +1,5 @@
 use crate::{
+    broken_encoding_error,
     error::TgError,
     invalid_response_error,
     jogasaki::proto::sql::response::{
@@ -6,19 +7,25 @@ use crate::{
         ResultSetMetadata as SqlQueryResultMetadata,
     },
     prelude::{
-        convert_sql_response, TgBlobReference, TgClobReference, TgDate, TgDecimalI128,
+        convert_sql_response, SqlPreparedStatement, SqlQueryPrefetchOption,
+        SqlQueryPrefetchStatistics, TgBlobReference, TgClobReference, TgDate, TgDecimalI128,
         TgDecimalResult, TgTimeOfDay, TgTimeOfDayWithTimeZone, TgTimePoint,
         TgTimePointWithTimeZone,
     },
     prost_decode_error,
-    session::wire::{response::WireResponse, response_box::SlotEntryHandle, Wire},
+    session::wire::{
+        resource_registry::{ResourceRegistration, TrackedResource},
+        response::WireResponse,
+        response_box::SlotEntryHandle,
+        Wire,
+    },
     util::Timeout,
 };
 use async_trait::async_trait;
 use prost::{bytes::BytesMut, Message};
 use std::{sync::Arc, time::Duration};
 
-use super::value_stream::ResultSetValueStream;
+use super::value_stream::{push_utf8_lossy, ResultSetValueStream};
 
 #[cfg(feature = "with_bigdecimal")]
 mod bigdecimal;
@@ -67,6 +74,10 @@ pub struct SqlQueryResult {
     pub(crate) value_stream: ResultSetValueStream,
     pub(crate) default_timeout: Duration,
     close_timeout: Duration,
+    prepared_statement: Option<SqlPreparedStatement>,
+    borrowed_value: BytesMut,
+    pub(crate) batch_error: Option<TgError>,
+    registration: ResourceRegistration,
 }
 
 impl std::fmt::Debug for SqlQueryResult {
@@ -87,17 +98,32 @@ impl SqlQueryResult {
         value_stream: ResultSetValueStream,
         default_timeout: Duration,
     ) -> SqlQueryResult {
+        let registration = wire
+            .resource_registry()
+            .register(TrackedResource::QueryResult {
+                slot_handle: slot_handle.clone(),
+                name: name.clone(),
+            });
         SqlQueryResult {
             wire,
             slot_handle: Some(slot_handle),
+            registration,
             name,
             metadata,
             value_stream,
             default_timeout,
             close_timeout: default_timeout,
+            prepared_statement: None,
+            borrowed_value: BytesMut::new(),
+            batch_error: None,
         }
     }
 
+    /// Set the prepared statement to be disposed together with this query result.
+    pub(crate) fn set_prepared_statement(&mut self, prepared_statement: SqlPreparedStatement) {
+        self.prepared_statement = Some(prepared_statement);
+    }
+
     /// Set default timeout.
     pub fn set_default_timeout(&mut self, timeout: Duration) {
         self.default_timeout = timeout;
@@ -149,6 +175,57 @@ impl SqlQueryResult {
     pub fn get_metadata(&self) -> Option<&SqlQueryResultMetadata> {
         self.metadata.as_ref()
     }
+
+    /// Starts prefetching rows on a background task.
+    ///
+    /// The background task reads the result set data ahead into a bounded buffer,
+    /// so that the network wait overlaps with the processing of the rows.
+    /// If prefetch has already been started, this method does nothing.
+    ///
+    /// Prefetch is started automatically if [SqlClient::set_query_prefetch_option()](crate::prelude::SqlClient::set_query_prefetch_option)
+    /// is specified.
+    ///
+    /// # Examples
+    /// ```
+    /// use tsubakuro_rust_core::prelude::*;
+    ///
+    /// async fn example(client: &SqlClient, transaction: &Transaction) -> Result<(), TgError> {
+    ///     let mut query_result = client.query(transaction, "select * from large_table").await?;
+    ///     query_result.start_prefetch(&SqlQueryPrefetchOption::new());
+    ///
+    ///     while query_result.next_row().await? {
+    ///         // ...
+    ///     }
+    ///
+    ///     if let Some(statistics) = query_result.prefetch_statistics() {
+    ///         println!("{:?}", statistics);
+    ///     }
+    ///     query_result.close().await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// since 0.11.0
+    pub fn start_prefetch(&mut self, option: &SqlQueryPrefetchOption) {
+        self.value_stream.data_channel_mut().start_prefetch(option);
+    }
+
+    /// Whether prefetch is started.
+    ///
+    /// since 0.11.0
+    pub fn is_prefetch(&self) -> bool {
+        self.value_stream.data_channel().is_prefetch()
+    }
+
+    /// Get prefetch statistics.
+    ///
+    /// Returns `None` if prefetch is not started.
+    ///
+    /// since 0.11.0
+    pub fn prefetch_statistics(&self) -> Option<SqlQueryPrefetchStatistics> {
+        self.value_stream.data_channel().prefetch_statistics()
+    }
 }
 
 pub(crate) fn query_result_processor(
@@ -156,12 +233,16 @@ pub(crate) fn query_result_processor(
     slot_handle: Arc<SlotEntryHandle>,
     response: WireResponse,
     default_timeout: Duration,
+    prefetch_option: Option<&SqlQueryPrefetchOption>,
 ) -> Result<SqlQueryResult, TgError> {
     // const FUNCTION_NAME: &str = "query_result_processor()";
 
     let (dc_name, metadata) = read_result_set_metadata(response)?;
 
-    let data_channel = wire.create_data_channel(&dc_name)?;
+    let mut data_channel = wire.create_data_channel(&dc_name)?;
+    if let Some(prefetch_option) = prefetch_option {
+        data_channel.start_prefetch(prefetch_option);
+    }
     let value_stream = ResultSetValueStream::new(data_channel);
     let query_result = SqlQueryResult::new(
         wire,
@@ -251,6 +332,171 @@ impl SqlQueryResult {
     }
 }
 
+impl SqlQueryResult {
+    /// Retrieves a `CHARACTER` value on the column of the cursor position without copying.
+    ///
+    /// The returned value refers to the buffer of this query result,
+    /// and is valid until the next operation on this query result (e.g. [`Self::next_column`]).
+    ///
+    /// Invalid UTF-8 sequences are replaced with `U+FFFD`, as with the other `CHARACTER` fetch methods.
+    /// In that case only, the value is copied.
+    ///
+    /// You can only take once to retrieve the value on the column.
+    ///
+    /// This method can only be used while the transaction is alive.
+    ///
+    /// # Examples
+    /// ```
+    /// use tsubakuro_rust_core::prelude::*;
+    ///
+    /// async fn example(query_result: &mut SqlQueryResult) -> Result<usize, TgError> {
+    ///     let mut total = 0;
+    ///     while query_result.next_row().await? {
+    ///         if query_result.next_column().await? && !query_result.is_null()? {
+    ///             let value: &str = query_result.fetch_str().await?;
+    ///             total += value.len();
+    ///         }
+    ///     }
+    ///     Ok(total)
+    /// }
+    /// ```
+    ///
+    /// since 0.11.0
+    pub async fn fetch_str(&mut self) -> Result<&str, TgError> {
+        self.fetch_str_for(self.default_timeout).await
+    }
+
+    /// Retrieves a `CHARACTER` value on the column of the cursor position without copying.
+    ///
+    /// See [`Self::fetch_str`].
+    ///
+    /// since 0.11.0
+    pub async fn fetch_str_for(&mut self, timeout: Duration) -> Result<&str, TgError> {
+        const FUNCTION_NAME: &str = "fetch_str()";
+
+        let timeout = Timeout::new(timeout);
+        self.borrowed_value = self.value_stream.fetch_character_bytes(&timeout).await?;
+        if std::str::from_utf8(&self.borrowed_value).is_err() {
+            let mut value = String::with_capacity(self.borrowed_value.len());
+            push_utf8_lossy(&mut value, &self.borrowed_value);
+            self.borrowed_value = BytesMut::from(value.as_bytes());
+        }
+        std::str::from_utf8(&self.borrowed_value)
+            .map_err(|e| broken_encoding_error!(FUNCTION_NAME, e))
+    }
+
+    /// Retrieves a `OCTET` value on the column of the cursor position without copying.
+    ///
+    /// The returned value refers to the buffer of this query result,
+    /// and is valid until the next operation on this query result (e.g. [`Self::next_column`]).
+    ///
+    /// You can only take once to retrieve the value on the column.
+    ///
+    /// This method can only be used while the transaction is alive.
+    ///
+    /// since 0.11.0
+    pub async fn fetch_bytes(&mut self) -> Result<&[u8], TgError> {
+        self.fetch_bytes_for(self.default_timeout).await
+    }
+
+    /// Retrieves a `OCTET` value on the column of the cursor position without copying.
+    ///
+    /// See [`Self::fetch_bytes`].
+    ///
+    /// since 0.11.0
+    pub async fn fetch_bytes_for(&mut self, timeout: Duration) -> Result<&[u8], TgError> {
+        let timeout = Timeout::new(timeout);
+        self.borrowed_value = self.value_stream.fetch_octet_value(&timeout).await?;
+        Ok(&self.borrowed_value)
+    }
+}
+
+/// `fetch_into` method for [SqlQueryResult].
+///
+/// Retrieves a value into the buffer given by the caller, so that the buffer can be reused for each row.
+///
+/// For `String`, invalid UTF-8 sequences are replaced with `U+FFFD` (same as [String::from_utf8_lossy]).
+///
+/// # Examples
+/// ```
+/// use tsubakuro_rust_core::prelude::*;
+///
+/// async fn example(query_result: &mut SqlQueryResult) -> Result<(), TgError> {
+///     let mut value = String::new();
+///     while query_result.next_row().await? {
+///         if query_result.next_column().await? && !query_result.is_null()? {
+///             query_result.fetch_into(&mut value).await?;
+///             println!("{}", value);
+///         }
+///     }
+///     Ok(())
+/// }
+/// ```
+///
+/// since 0.11.0
+#[async_trait(?Send)] // thread unsafe
+pub trait SqlQueryResultFetchInto<T: ?Sized> {
+    /// Retrieves a value on the column of the cursor position into `value`.
+    ///
+    /// The previous content of `value` is cleared.
+    ///
+    /// You can only take once to retrieve the value on the column.
+    ///
+    /// This method can only be used while the transaction is alive.
+    async fn fetch_into(&mut self, value: &mut T) -> Result<(), TgError>;
+
+    /// Retrieves a value on the column of the cursor position into `value`.
+    ///
+    /// The previous content of `value` is cleared.
+    ///
+    /// You can only take once to retrieve the value on the column.
+    ///
+    /// This method can only be used while the transaction is alive.
+    async fn fetch_into_for(&mut self, value: &mut T, timeout: Duration) -> Result<(), TgError>;
+}
+
+#[async_trait(?Send)] // thread unsafe
+impl SqlQueryResultFetchInto<String> for SqlQueryResult {
+    /// Retrieves a `CHARACTER` value on the column of the cursor position into `value`.
+    async fn fetch_into(&mut self, value: &mut String) -> Result<(), TgError> {
+        self.fetch_into_for(value, self.default_timeout).await
+    }
+
+    /// Retrieves a `CHARACTER` value on the column of the cursor position into `value`.
+    async fn fetch_into_for(
+        &mut self,
+        value: &mut String,
+        timeout: Duration,
+    ) -> Result<(), TgError> {
+        let timeout = Timeout::new(timeout);
+        let buffer = self.value_stream.fetch_character_bytes(&timeout).await?;
+        value.clear();
+        push_utf8_lossy(value, &buffer);
+        Ok(())
+    }
+}
+
+#[async_trait(?Send)] // thread unsafe
+impl SqlQueryResultFetchInto<Vec<u8>> for SqlQueryResult {
+    /// Retrieves a `OCTET` value on the column of the cursor position into `value`.
+    async fn fetch_into(&mut self, value: &mut Vec<u8>) -> Result<(), TgError> {
+        self.fetch_into_for(value, self.default_timeout).await
+    }
+
+    /// Retrieves a `OCTET` value on the column of the cursor position into `value`.
+    async fn fetch_into_for(
+        &mut self,
+        value: &mut Vec<u8>,
+        timeout: Duration,
+    ) -> Result<(), TgError> {
+        let timeout = Timeout::new(timeout);
+        let buffer = self.value_stream.fetch_octet_value(&timeout).await?;
+        value.clear();
+        value.extend_from_slice(&buffer);
+        Ok(())
+    }
+}
+
 /// `fetch` method for [SqlQueryResult].
 #[async_trait(?Send)] // thread unsafe
 pub trait SqlQueryResultFetch<T> {
@@ -439,6 +685,8 @@ impl SqlQueryResultFetch<TgDecimalI128> for SqlQueryResult {
 impl SqlQueryResultFetch<String> for SqlQueryResult {
     /// Retrieves a `CHARACTER` value on the column of the cursor position.
     ///
+    /// Invalid UTF-8 sequences are replaced with `U+FFFD`.
+    ///
     /// You can only take once to retrieve the value on the column.
     ///
     /// This method can only be used while the transaction is alive.
@@ -739,19 +987,32 @@ impl SqlQueryResult {
     pub async fn close_for(&mut self, timeout: Duration) -> Result<(), TgError> {
         const FUNCTION_NAME: &str = "close()";
 
+        let mut result = Ok(());
         let slot_handle = self.slot_handle.take();
         if let Some(slot_handle) = slot_handle {
-            let timeout = Timeout::new(timeout);
-            let response = self.wire.pull_response(&slot_handle, &timeout).await?;
-            convert_sql_response(FUNCTION_NAME, &response)?;
+            // the response has been pulled if closed by Session::drain()
+            if self.registration.close() {
+                let timeout = Timeout::new(timeout);
+                result = match self.wire.pull_response(&slot_handle, &timeout).await {
+                    Ok(response) => convert_sql_response(FUNCTION_NAME, &response).map(|_| ()),
+                    Err(e) => Err(e),
+                };
+            }
         }
-        Ok(())
+
+        // the prepared statement is closed even if closing the result set fails
+        let prepared_statement = self.prepared_statement.take();
+        if let Some(prepared_statement) = prepared_statement {
+            let close_result = prepared_statement.close_for(timeout).await;
+            result = result.and(close_result);
+        }
+        result
     }
 
     /// Check if this resource is closed.
     ///
     /// since 0.3.0
     pub fn is_closed(&self) -> bool {
-        self.slot_handle.is_none()
+        self.slot_handle.is_none() || self.registration.is_closed()
     }
 }
@@ -272,6 +272,14 @@ impl ResultSetValueStream {
         }
     }
 
+    pub(crate) fn data_channel_mut(&mut self) -> &mut DataChannel {
+        &mut self.data_channel
+    }
+
+    pub(crate) fn data_channel(&self) -> &DataChannel {
+        &self.data_channel
+    }
+
     pub(crate) async fn next_row(&mut self, timeout: &Timeout) -> Result<bool, TgError> {
         self.discard_top_level_row(timeout).await?;
 
@@ -428,6 +436,16 @@ impl ResultSetValueStream {
         Ok(value)
     }
 
+    pub(crate) async fn fetch_character_bytes(
+        &mut self,
+        timeout: &Timeout,
+    ) -> Result<BytesMut, TgError> {
+        self.require_column_type(EntryType::Character)?;
+        let value = self.read_character_bytes(timeout).await?;
+        self.column_consumed()?;
+        Ok(value)
+    }
+
     pub(crate) async fn fetch_octet_value(
         &mut self,
         timeout: &Timeout,
@@ -732,6 +750,14 @@ impl ResultSetValueStream {
     }
 
     async fn read_character(&mut self, timeout: &Timeout) -> Result<String, TgError> {
+        let buffer = self.read_character_bytes(timeout).await?;
+
+        let mut s = String::with_capacity(buffer.len());
+        push_utf8_lossy(&mut s, &buffer);
+        Ok(s)
+    }
+
+    async fn read_character_bytes(&mut self, timeout: &Timeout) -> Result<BytesMut, TgError> {
         self.require(EntryType::Character)?;
         let size = self.read_character_size(timeout).await?;
 
@@ -743,10 +769,7 @@ impl ResultSetValueStream {
                 return Err(client_error!("saw unexpected eof"));
             }
         };
-
-        let s = String::from_utf8_lossy(&buffer);
-        let s = String::from(s);
-        Ok(s)
+        Ok(buffer)
     }
 
     async fn read_character_size(&mut self, timeout: &Timeout) -> Result<usize, TgError> {
@@ -1026,3 +1049,64 @@ impl ResultSetValueStream {
         self.kind_stack.is_empty()
     }
 }
+
+/// Appends `bytes` to `value` as UTF-8,
+/// replacing invalid sequences with `U+FFFD` in the same way as [String::from_utf8_lossy].
+pub(crate) fn push_utf8_lossy(value: &mut String, bytes: &[u8]) {
+    for chunk in bytes.utf8_chunks() {
+        value.push_str(chunk.valid());
+        if !chunk.invalid().is_empty() {
+            value.push(char::REPLACEMENT_CHARACTER);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use crate::session::wire::data_channel::test::data_channel;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn character_and_octet() {
+        // row(2): "abc", [1, 2]; end of contents
+        let chunks: Vec<&'static [u8]> = vec![b"\x81\x42ab", b"c\xd1\x01\x02\xfe"];
+        let mut value_stream = ResultSetValueStream::new(data_channel(chunks));
+        let timeout = Timeout::new(Duration::from_secs(10));
+
+        assert!(value_stream.next_row(&timeout).await.unwrap());
+        assert!(value_stream.next_column(&timeout).await.unwrap());
+        let value = value_stream.fetch_character_bytes(&timeout).await.unwrap();
+        assert_eq!(&b"abc"[..], &value[..]);
+        assert!(value_stream.next_column(&timeout).await.unwrap());
+        let value = value_stream.fetch_octet_value(&timeout).await.unwrap();
+        assert_eq!(&[1u8, 2][..], &value[..]);
+        assert!(!value_stream.next_column(&timeout).await.unwrap());
+        assert!(!value_stream.next_row(&timeout).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn character_invalid_utf8() {
+        // row(1): "a\xffc"; end of contents
+        let chunks: Vec<&'static [u8]> = vec![b"\x81\x42a\xffc\xfe"];
+        let mut value_stream = ResultSetValueStream::new(data_channel(chunks));
+        let timeout = Timeout::new(Duration::from_secs(10));
+
+        assert!(value_stream.next_row(&timeout).await.unwrap());
+        assert!(value_stream.next_column(&timeout).await.unwrap());
+        let value = value_stream.fetch_character_value(&timeout).await.unwrap();
+        assert_eq!("a\u{fffd}c", value);
+    }
+
+    #[test]
+    fn push_utf8_lossy_test() {
+        let mut value = String::from("x");
+        push_utf8_lossy(&mut value, b"a\xf0\x9f\x98\x80\xe3\x81b");
+        assert_eq!(
+            String::from_utf8_lossy(b"xa\xf0\x9f\x98\x80\xe3\x81b"),
+            value
+        );
+    }
+}
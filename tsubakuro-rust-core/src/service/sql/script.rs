@@ -0,0 +1,281 @@
+use crate::{error::TgError, prelude::SqlExecuteResult};
+
+/// Transaction mode of SQL script execution.
+///
+/// See [SqlScriptOption::set_transaction_mode()].
+///
+/// since 0.11.0
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SqlScriptTransactionMode {
+    /// Each statement is executed in its own transaction.
+    #[default]
+    PerStatement,
+    /// All statements are executed in one transaction.
+    ///
+    /// The transaction is rolled back at the first error,
+    /// so the remaining statements are skipped regardless of [SqlScriptErrorMode].
+    Single,
+}
+
+/// Behavior of SQL script execution when a statement fails.
+///
+/// See [SqlScriptOption::set_error_mode()].
+///
+/// since 0.11.0
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SqlScriptErrorMode {
+    /// Stop at the first error. The remaining statements are skipped.
+    #[default]
+    Stop,
+    /// Continue with the next statement.
+    Continue,
+}
+
+/// Option for SQL script execution.
+///
+/// See [SqlClient::execute_script_with()](crate::prelude::SqlClient::execute_script_with).
+///
+/// since 0.11.0
+#[derive(Debug, Clone, Default)]
+pub struct SqlScriptOption {
+    transaction_mode: SqlScriptTransactionMode,
+    error_mode: SqlScriptErrorMode,
+}
+
+impl SqlScriptOption {
+    /// Creates a new instance.
+    pub fn new() -> SqlScriptOption {
+        SqlScriptOption::default()
+    }
+
+    /// Set transaction mode.
+    pub fn set_transaction_mode(&mut self, transaction_mode: SqlScriptTransactionMode) {
+        self.transaction_mode = transaction_mode;
+    }
+
+    /// Get transaction mode.
+    pub fn transaction_mode(&self) -> SqlScriptTransactionMode {
+        self.transaction_mode
+    }
+
+    /// Set error mode.
+    pub fn set_error_mode(&mut self, error_mode: SqlScriptErrorMode) {
+        self.error_mode = error_mode;
+    }
+
+    /// Get error mode.
+    pub fn error_mode(&self) -> SqlScriptErrorMode {
+        self.error_mode
+    }
+}
+
+/// Status of a statement in SQL script.
+///
+/// since 0.11.0
+#[derive(Debug)]
+pub enum SqlStatementStatus {
+    /// The statement was executed successfully.
+    ///
+    /// In [SqlScriptTransactionMode::Single], it may be rolled back. (See [SqlScriptResult::is_committed()])
+    Succeeded(SqlExecuteResult),
+    /// The statement failed.
+    Failed(TgError),
+    /// The statement was not executed because a preceding statement failed.
+    Skipped,
+}
+
+/// Result of a statement in SQL script.
+///
+/// since 0.11.0
+#[derive(Debug)]
+pub struct SqlStatementResult {
+    index: usize,
+    sql: String,
+    status: SqlStatementStatus,
+}
+
+impl SqlStatementResult {
+    pub(crate) fn new(index: usize, sql: &str, status: SqlStatementStatus) -> SqlStatementResult {
+        SqlStatementResult {
+            index,
+            sql: sql.to_string(),
+            status,
+        }
+    }
+
+    /// Get the index of the statement in the script (0-origin).
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Get the SQL statement.
+    pub fn sql(&self) -> &str {
+        &self.sql
+    }
+
+    /// Get the status.
+    pub fn status(&self) -> &SqlStatementStatus {
+        &self.status
+    }
+
+    /// Get the execute result if the statement succeeded.
+    pub fn execute_result(&self) -> Option<&SqlExecuteResult> {
+        match &self.status {
+            SqlStatementStatus::Succeeded(execute_result) => Some(execute_result),
+            _ => None,
+        }
+    }
+
+    /// Get the error if the statement failed.
+    pub fn error(&self) -> Option<&TgError> {
+        match &self.status {
+            SqlStatementStatus::Failed(error) => Some(error),
+            _ => None,
+        }
+    }
+
+    /// Whether the statement succeeded.
+    pub fn is_succeeded(&self) -> bool {
+        matches!(self.status, SqlStatementStatus::Succeeded(_))
+    }
+
+    /// Whether the statement failed.
+    pub fn is_failed(&self) -> bool {
+        matches!(self.status, SqlStatementStatus::Failed(_))
+    }
+
+    /// Whether the statement was skipped.
+    pub fn is_skipped(&self) -> bool {
+        matches!(self.status, SqlStatementStatus::Skipped)
+    }
+}
+
+/// Result of SQL script execution.
+///
+/// since 0.11.0
+#[derive(Debug)]
+pub struct SqlScriptResult {
+    statements: Vec<SqlStatementResult>,
+    committed: bool,
+    commit_error: Option<TgError>,
+}
+
+impl SqlScriptResult {
+    pub(crate) fn new(
+        statements: Vec<SqlStatementResult>,
+        committed: bool,
+        commit_error: Option<TgError>,
+    ) -> SqlScriptResult {
+        SqlScriptResult {
+            statements,
+            committed,
+            commit_error,
+        }
+    }
+
+    /// Get the results of the statements, in the order of the script.
+    pub fn statements(&self) -> &[SqlStatementResult] {
+        &self.statements
+    }
+
+    /// Get the results of the statements.
+    pub fn into_statements(self) -> Vec<SqlStatementResult> {
+        self.statements
+    }
+
+    /// Whether the succeeded statements are committed.
+    ///
+    /// Always true in [SqlScriptTransactionMode::PerStatement].
+    pub fn is_committed(&self) -> bool {
+        self.committed
+    }
+
+    /// Get the commit error in [SqlScriptTransactionMode::Single].
+    pub fn commit_error(&self) -> Option<&TgError> {
+        self.commit_error.as_ref()
+    }
+
+    /// Whether all statements succeeded and are committed.
+    pub fn is_success(&self) -> bool {
+        self.committed && self.statements.iter().all(|s| s.is_succeeded())
+    }
+
+    /// Get the number of succeeded statements.
+    pub fn succeeded_count(&self) -> usize {
+        self.statements.iter().filter(|s| s.is_succeeded()).count()
+    }
+
+    /// Get the number of failed statements.
+    pub fn failed_count(&self) -> usize {
+        self.statements.iter().filter(|s| s.is_failed()).count()
+    }
+
+    /// Get the number of skipped statements.
+    pub fn skipped_count(&self) -> usize {
+        self.statements.iter().filter(|s| s.is_skipped()).count()
+    }
+
+    /// Get the first error. (statement error or commit error)
+    pub fn first_error(&self) -> Option<&TgError> {
+        self.statements
+            .iter()
+            .find_map(|s| s.error())
+            .or(self.commit_error.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::client_error;
+
+    fn succeeded() -> SqlStatementStatus {
+        let success = crate::jogasaki::proto::sql::response::execute_result::Success::default();
+        SqlStatementStatus::Succeeded(SqlExecuteResult::new(success))
+    }
+
+    #[test]
+    fn option() {
+        let mut option = SqlScriptOption::new();
+        assert_eq!(
+            SqlScriptTransactionMode::PerStatement,
+            option.transaction_mode()
+        );
+        assert_eq!(SqlScriptErrorMode::Stop, option.error_mode());
+
+        option.set_transaction_mode(SqlScriptTransactionMode::Single);
+        option.set_error_mode(SqlScriptErrorMode::Continue);
+        assert_eq!(SqlScriptTransactionMode::Single, option.transaction_mode());
+        assert_eq!(SqlScriptErrorMode::Continue, option.error_mode());
+    }
+
+    #[test]
+    fn result() {
+        let statements = vec![
+            SqlStatementResult::new(0, "create table t (k int)", succeeded()),
+            SqlStatementResult::new(
+                1,
+                "insert into t values (x)",
+                SqlStatementStatus::Failed(client_error!("error")),
+            ),
+            SqlStatementResult::new(2, "insert into t values (1)", SqlStatementStatus::Skipped),
+        ];
+        let result = SqlScriptResult::new(statements, true, None);
+        assert!(!result.is_success());
+        assert_eq!(1, result.succeeded_count());
+        assert_eq!(1, result.failed_count());
+        assert_eq!(1, result.skipped_count());
+        assert!(result.first_error().is_some());
+        assert_eq!("insert into t values (x)", result.statements()[1].sql());
+        assert!(result.statements()[0].execute_result().is_some());
+
+        let statements = vec![SqlStatementResult::new(0, "delete from t", succeeded())];
+        let result = SqlScriptResult::new(statements, false, Some(client_error!("commit")));
+        assert!(!result.is_success());
+        assert!(result.first_error().is_some());
+
+        let statements = vec![SqlStatementResult::new(0, "delete from t", succeeded())];
+        let result = SqlScriptResult::new(statements, true, None);
+        assert!(result.is_success());
+    }
+}
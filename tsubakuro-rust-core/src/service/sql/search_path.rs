@@ -0,0 +1,187 @@
+use std::sync::Arc;
+
+use crate::{
+    error::TgError,
+    invalid_response_error,
+    jogasaki::proto::sql::response::{response::Response as SqlResponseType, Name},
+    prelude::convert_sql_response,
+    session::wire::{response::WireResponse, response_box::SlotEntryHandle},
+    sql_service_error,
+};
+
+use super::{name::TName, table_list::TableList};
+
+/// Represents the schema search path.
+///
+/// Unqualified table names are resolved by searching the schemas in order.
+///
+/// since 0.11.0
+#[derive(Debug, Clone)]
+pub struct SearchPath {
+    schema_names: Vec<TName>,
+}
+
+impl SearchPath {
+    /// Creates a new instance.
+    pub fn new(schema_names: Vec<TName>) -> SearchPath {
+        SearchPath { schema_names }
+    }
+
+    pub(crate) fn from(
+        success: crate::jogasaki::proto::sql::response::get_search_path::Success,
+    ) -> SearchPath {
+        let schema_names: Vec<Name> = success.search_paths;
+        let schema_names: Vec<TName> = schema_names.iter().map(TName::from).collect();
+        SearchPath { schema_names }
+    }
+
+    /// Returns the schema names in search order.
+    pub fn schema_names(&self) -> &Vec<TName> {
+        &self.schema_names
+    }
+
+    /// Resolves a table name in the table list.
+    ///
+    /// A qualified name (`schema.table`) matches the table whose trailing identifiers are equal to it.
+    /// An unqualified name is looked up in each schema of the search path in order,
+    /// and then as a table without schema.
+    ///
+    /// ## Returns
+    /// the first matching table name, or `None` if not found.
+    pub fn resolve<'a>(&self, table_list: &'a TableList, table_name: &str) -> Option<&'a TName> {
+        let identifiers: Vec<&str> = table_name.split('.').collect();
+        let table_names = table_list.table_names();
+
+        if identifiers.len() > 1 {
+            return table_names
+                .iter()
+                .find(|name| ends_with(name.identifiers(), &identifiers));
+        }
+
+        for schema_name in &self.schema_names {
+            let mut qualified: Vec<&str> = schema_name
+                .identifiers()
+                .iter()
+                .map(String::as_str)
+                .collect();
+            qualified.push(table_name);
+            if let Some(name) = table_names
+                .iter()
+                .find(|name| ends_with(name.identifiers(), &qualified))
+            {
+                return Some(name);
+            }
+        }
+
+        table_names
+            .iter()
+            .find(|name| name.identifiers().len() == 1 && name.identifiers()[0] == table_name)
+    }
+}
+
+fn ends_with(identifiers: &[String], suffix: &[&str]) -> bool {
+    if identifiers.len() < suffix.len() {
+        return false;
+    }
+    let start = identifiers.len() - suffix.len();
+    identifiers[start..]
+        .iter()
+        .zip(suffix)
+        .all(|(identifier, s)| identifier == s)
+}
+
+impl std::fmt::Display for SearchPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s: Vec<String> = self.schema_names.iter().map(TName::to_string).collect();
+        write!(f, "{}", s.join(", "))
+    }
+}
+
+pub(crate) fn search_path_processor(
+    _: Arc<SlotEntryHandle>,
+    response: WireResponse,
+) -> Result<SearchPath, TgError> {
+    const FUNCTION_NAME: &str = "search_path_processor()";
+
+    let (sql_response, _) = convert_sql_response(FUNCTION_NAME, &response)?;
+    let message = sql_response.ok_or(invalid_response_error!(
+        FUNCTION_NAME,
+        format!("response {:?} is not ResponseSessionPayload", response),
+    ))?;
+    match message.response {
+        Some(SqlResponseType::GetSearchPath(get_search_path)) => match get_search_path.result {
+            Some(crate::jogasaki::proto::sql::response::get_search_path::Result::Success(
+                success,
+            )) => Ok(SearchPath::from(success)),
+            Some(crate::jogasaki::proto::sql::response::get_search_path::Result::Error(error)) => {
+                Err(sql_service_error!(FUNCTION_NAME, error))
+            }
+            None => Err(invalid_response_error!(
+                FUNCTION_NAME,
+                format!("response GetSearchPath.result is None"),
+            )),
+        },
+        _ => Err(invalid_response_error!(
+            FUNCTION_NAME,
+            format!("response {:?} is not GetSearchPath", message.response),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::jogasaki::proto::sql::response::{list_tables, Identifier};
+
+    fn proto_name(s: &str) -> Name {
+        let identifiers = s
+            .split('.')
+            .map(|label| Identifier {
+                label: label.to_string(),
+            })
+            .collect();
+        Name { identifiers }
+    }
+
+    fn table_list(names: &[&str]) -> TableList {
+        let success = list_tables::Success {
+            table_path_names: names.iter().map(|s| proto_name(s)).collect(),
+        };
+        TableList::new(success)
+    }
+
+    fn tname(s: &str) -> TName {
+        TName::new(s.split('.').map(String::from).collect())
+    }
+
+    #[test]
+    fn resolve() {
+        let search_path = SearchPath::new(vec![tname("db.s1"), tname("db.s2")]);
+        let tables = table_list(&["db.s2.t1", "db.s1.t1", "db.s2.t2", "t3", "db.s3.t3"]);
+
+        let name = |s| search_path.resolve(&tables, s).map(TName::to_string);
+        assert_eq!(Some("db.s1.t1".to_string()), name("t1"));
+        assert_eq!(Some("db.s2.t2".to_string()), name("t2"));
+        assert_eq!(Some("t3".to_string()), name("t3"));
+        assert_eq!(Some("db.s2.t1".to_string()), name("s2.t1"));
+        assert_eq!(Some("db.s3.t3".to_string()), name("db.s3.t3"));
+        assert_eq!(None, name("t4"));
+        assert_eq!(None, name("s1.t2"));
+    }
+
+    #[test]
+    fn resolve_empty_search_path() {
+        let search_path = SearchPath::new(vec![]);
+        let tables = table_list(&["s1.t1", "t1"]);
+
+        let name = |s| search_path.resolve(&tables, s).map(TName::to_string);
+        assert_eq!(Some("t1".to_string()), name("t1"));
+        assert_eq!(Some("s1.t1".to_string()), name("s1.t1"));
+    }
+
+    #[test]
+    fn display() {
+        let search_path = SearchPath::new(vec![tname("db.s1"), tname("s2")]);
+        assert_eq!("db.s1, s2", search_path.to_string());
+    }
+}
@@ -5,12 +5,12 @@ use std::{
     time::Duration,
 };
 
-use log::trace;
+use log::{trace, warn};
 
 use crate::{
     client_error,
     error::TgError,
-    invalid_response_error, io_error,
+    illegal_argument_error, invalid_response_error, io_error,
     job::Job,
     jogasaki::proto::sql::{
         common::Transaction as ProtoTransaction,
@@ -26,18 +26,30 @@ use crate::{
         list_tables_processor, prepare_dispose_processor, prepare_processor,
         query_result_processor,
         r#type::large_object::TgLargeObjectCache,
+        search_path::{search_path_processor, SearchPath},
         table_metadata_processor, transaction_status_processor, CommitOption, ServiceClient,
-        SqlExecuteResult, SqlParameter, SqlPlaceholder, SqlQueryResult, TableList, TableMetadata,
-        TgBlobReference, TgClobReference, TransactionStatusWithMessage,
+        SqlExecuteResult, SqlParameter, SqlPlaceholder, SqlQueryPrefetchOption, SqlQueryResult,
+        TableList, TableMetadata, TgBlobReference, TgClobReference, TransactionStatusWithMessage,
     },
     prost_decode_error,
     service::{
         lob::{
             downloader::{BlobDownloader, ClobDownloader},
             lob_client::{create_lob_client, LobClient, LobClientMethod, RemoteLob},
+            upload_option::LobUploadOption,
             uploader::{BlobUploader, ClobUploader},
         },
-        sql::r#type::{blob::TgBlob, clob::TgClob},
+        sql::{
+            interceptor::{SqlInterceptor, SqlInterceptors, SqlOperation},
+            r#type::{blob::TgBlob, clob::TgClob},
+            script::{
+                SqlScriptErrorMode, SqlScriptOption, SqlScriptResult, SqlScriptTransactionMode,
+                SqlStatementResult, SqlStatementStatus,
+            },
+            sql_text::{
+                positional_placeholder_name, replace_positional_placeholders, split_statements,
+            },
+        },
         ServiceMessageVersion,
     },
     session::{
@@ -46,10 +58,12 @@ use crate::{
     },
     sql_service_error,
     tateyama::proto::framework::common::BlobInfo,
+    transaction::{commit_handle::CommitHandle, status_watch::TransactionStatusWatchOption},
     transaction::{
         option::TransactionOption, transaction_begin_processor, transaction_commit_processor,
         transaction_dispose_processor, transaction_rollback_processor, Transaction,
     },
+    util::Timeout,
 };
 
 use prost::{alloc::string::String as ProstString, Message};
@@ -110,15 +124,21 @@ pub struct SqlClient {
     session: Arc<Session>,
     lob_client: tokio::sync::OnceCell<Box<dyn LobClient>>,
     default_timeout: Duration,
+    query_prefetch_option: Option<SqlQueryPrefetchOption>,
+    interceptors: SqlInterceptors,
 }
 
 impl ServiceClient for SqlClient {
     fn new(session: Arc<Session>) -> Self {
         let default_timeout = session.default_timeout();
+        let query_prefetch_option = session.query_prefetch_option().cloned();
+        let interceptors = session.sql_interceptor_list();
         SqlClient {
             session,
             lob_client: tokio::sync::OnceCell::new(),
             default_timeout,
+            query_prefetch_option,
+            interceptors,
         }
     }
 }
@@ -148,6 +168,32 @@ impl SqlClient {
     pub fn default_timeout(&self) -> Duration {
         self.default_timeout
     }
+
+    /// Set query prefetch option.
+    ///
+    /// If `Some` is specified, the rows of [SqlQueryResult] returned by this client are prefetched.
+    /// The default value is taken from [ConnectionOption::set_query_prefetch_option()](crate::prelude::ConnectionOption::set_query_prefetch_option).
+    ///
+    /// since 0.11.0
+    pub fn set_query_prefetch_option(&mut self, option: Option<SqlQueryPrefetchOption>) {
+        self.query_prefetch_option = option;
+    }
+
+    /// Get query prefetch option.
+    ///
+    /// since 0.11.0
+    pub fn query_prefetch_option(&self) -> Option<&SqlQueryPrefetchOption> {
+        self.query_prefetch_option.as_ref()
+    }
+
+    /// Add an interceptor.
+    ///
+    /// See [SqlInterceptor].
+    ///
+    /// since 0.11.0
+    pub fn add_interceptor(&mut self, interceptor: Arc<dyn SqlInterceptor>) {
+        self.interceptors.add(interceptor);
+    }
 }
 
 impl SqlClient {
@@ -283,6 +329,129 @@ impl SqlClient {
         SqlCommand::DescribeTable(request)
     }
 
+    /// Retrieves the schema search path.
+    ///
+    /// # Examples
+    /// ```
+    /// use tsubakuro_rust_core::prelude::*;
+    ///
+    /// async fn example(client: &SqlClient) -> Result<(), TgError> {
+    ///     let search_path = client.get_search_path().await?;
+    ///     for schema_name in search_path.schema_names() {
+    ///         println!("{}", schema_name);
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// since 0.11.0
+    pub async fn get_search_path(&self) -> Result<SearchPath, TgError> {
+        let timeout = self.default_timeout;
+        self.get_search_path_for(timeout).await
+    }
+
+    /// Retrieves the schema search path.
+    ///
+    /// since 0.11.0
+    pub async fn get_search_path_for(&self, timeout: Duration) -> Result<SearchPath, TgError> {
+        const FUNCTION_NAME: &str = "get_search_path()";
+        trace!("{} start", FUNCTION_NAME);
+
+        let command = Self::search_path_command();
+        let (slot_handle, response) = self.send_and_pull_response(command, None, timeout).await?;
+        let search_path = search_path_processor(slot_handle, response)?;
+
+        trace!("{} end", FUNCTION_NAME);
+        Ok(search_path)
+    }
+
+    /// Retrieves the schema search path.
+    ///
+    /// since 0.11.0
+    pub async fn get_search_path_async(&self) -> Result<Job<SearchPath>, TgError> {
+        const FUNCTION_NAME: &str = "get_search_path_async()";
+        trace!("{} start", FUNCTION_NAME);
+
+        let command = Self::search_path_command();
+        let job = self
+            .send_and_pull_async(
+                "GetSearchPath",
+                command,
+                None,
+                Box::new(search_path_processor),
+            )
+            .await?;
+
+        trace!("{} end", FUNCTION_NAME);
+        Ok(job)
+    }
+
+    fn search_path_command() -> SqlCommand {
+        let request = crate::jogasaki::proto::sql::request::GetSearchPath {};
+        SqlCommand::GetSearchPath(request)
+    }
+
+    /// Resolves a table name using the search path, and retrieves metadata for the table.
+    ///
+    /// See [SearchPath::resolve] for the resolution rules.
+    ///
+    /// ## Returns
+    /// `None` if the table is not found.
+    ///
+    /// # Examples
+    /// ```
+    /// use tsubakuro_rust_core::prelude::*;
+    ///
+    /// async fn example(client: &SqlClient) -> Result<(), TgError> {
+    ///     if let Some(table_metadata) = client.resolve_table_metadata("customer").await? {
+    ///         println!(
+    ///             "schema name={}, table name={}",
+    ///             table_metadata.schema_name(),
+    ///             table_metadata.table_name()
+    ///         );
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// since 0.11.0
+    pub async fn resolve_table_metadata(
+        &self,
+        table_name: &str,
+    ) -> Result<Option<TableMetadata>, TgError> {
+        let timeout = self.default_timeout;
+        self.resolve_table_metadata_for(table_name, timeout).await
+    }
+
+    /// Resolves a table name using the search path, and retrieves metadata for the table.
+    ///
+    /// `timeout` applies to each request.
+    ///
+    /// since 0.11.0
+    pub async fn resolve_table_metadata_for(
+        &self,
+        table_name: &str,
+        timeout: Duration,
+    ) -> Result<Option<TableMetadata>, TgError> {
+        const FUNCTION_NAME: &str = "resolve_table_metadata()";
+        trace!("{} start", FUNCTION_NAME);
+
+        let search_path = self.get_search_path_for(timeout).await?;
+        let table_list = self.list_tables_for(timeout).await?;
+        let metadata = match search_path.resolve(&table_list, table_name) {
+            Some(name) => {
+                let name = name.to_string();
+                Some(self.get_table_metadata_for(&name, timeout).await?)
+            }
+            None => None,
+        };
+
+        trace!("{} end", FUNCTION_NAME);
+        Ok(metadata)
+    }
+
     /// Prepares a SQL statement.
     ///
     /// Note: Should invoke [`SqlPreparedStatement::close`] before [`SqlPreparedStatement::drop`] to dispose the prepared statement.
@@ -325,12 +494,20 @@ impl SqlClient {
         const FUNCTION_NAME: &str = "prepare()";
         trace!("{} start", FUNCTION_NAME);
 
-        let command = Self::prepare_command(sql, placeholders);
-        let (_, response) = self.send_and_pull_response(command, None, timeout).await?;
-
-        let session = self.session.clone();
-        let close_timeout = self.default_timeout;
-        let ps = prepare_processor(session, response, close_timeout)?;
+        let context = self.interceptors.before(SqlOperation::Prepare, |context| {
+            context.set_sql(sql);
+        })?;
+        let ps = self
+            .interceptors
+            .intercept(context, async {
+                let command = Self::prepare_command(sql, placeholders);
+                let (_, response) = self.send_and_pull_response(command, None, timeout).await?;
+
+                let session = self.session.clone();
+                let close_timeout = self.default_timeout;
+                prepare_processor(session, sql, response, close_timeout)
+            })
+            .await?;
 
         trace!("{} end", FUNCTION_NAME);
         Ok(ps)
@@ -347,18 +524,26 @@ impl SqlClient {
         const FUNCTION_NAME: &str = "prepare_async()";
         trace!("{} start", FUNCTION_NAME);
 
-        let command = Self::prepare_command(sql, placeholders);
-        let session = self.session.clone();
-        let close_timeout = self.default_timeout;
+        let context = self.interceptors.before(SqlOperation::Prepare, |context| {
+            context.set_sql(sql);
+        })?;
         let job = self
-            .send_and_pull_async(
-                "Prepare",
-                command,
-                None,
-                Box::new(move |_, response| {
-                    prepare_processor(session.clone(), response, close_timeout)
-                }),
-            )
+            .interceptors
+            .intercept_job(context, async {
+                let command = Self::prepare_command(sql, placeholders);
+                let session = self.session.clone();
+                let sql = sql.to_string();
+                let close_timeout = self.default_timeout;
+                self.send_and_pull_async(
+                    "Prepare",
+                    command,
+                    None,
+                    Box::new(move |_, response| {
+                        prepare_processor(session.clone(), &sql, response, close_timeout)
+                    }),
+                )
+                .await
+            })
             .await?;
 
         trace!("{} end", FUNCTION_NAME);
@@ -595,14 +780,32 @@ impl SqlClient {
         const FUNCTION_NAME: &str = "start_transaction()";
         trace!("{} start", FUNCTION_NAME);
 
-        let command = Self::begin_transaction_command(transaction_option);
-        let (_, response) = self.send_and_pull_response(command, None, timeout).await?;
-
-        let session = self.session.clone();
-        let close_timeout = transaction_option
-            .close_timeout()
-            .unwrap_or(self.default_timeout);
-        let transaction = transaction_begin_processor(session, response, close_timeout)?;
+        let mut context = self
+            .interceptors
+            .before(SqlOperation::StartTransaction, |context| {
+                context.set_transaction_option(transaction_option);
+            })?;
+        let intercepted_option = context
+            .as_ref()
+            .and_then(|context| context.transaction_option().cloned());
+        let transaction_option = intercepted_option.as_ref().unwrap_or(transaction_option);
+
+        let result = async {
+            let command = Self::begin_transaction_command(transaction_option);
+            let (_, response) = self.send_and_pull_response(command, None, timeout).await?;
+
+            let session = self.session.clone();
+            let close_timeout = transaction_option
+                .close_timeout()
+                .unwrap_or(self.default_timeout);
+            transaction_begin_processor(session, response, close_timeout)
+        }
+        .await;
+        if let (Some(context), Ok(transaction)) = (context.as_mut(), &result) {
+            context.set_transaction(transaction);
+        }
+        self.interceptors.after(context.as_ref(), &result);
+        let transaction = result?;
 
         trace!("{} end", FUNCTION_NAME);
         Ok(transaction)
@@ -618,19 +821,33 @@ impl SqlClient {
         const FUNCTION_NAME: &str = "start_transaction_async()";
         trace!("{} start", FUNCTION_NAME);
 
+        let context = self
+            .interceptors
+            .before(SqlOperation::StartTransaction, |context| {
+                context.set_transaction_option(transaction_option);
+            })?;
+        let intercepted_option = context
+            .as_ref()
+            .and_then(|context| context.transaction_option().cloned());
+        let transaction_option = intercepted_option.as_ref().unwrap_or(transaction_option);
+
         let command = Self::begin_transaction_command(transaction_option);
         let session = self.session.clone();
         let close_timeout = transaction_option
             .close_timeout()
             .unwrap_or(self.default_timeout);
         let job = self
-            .send_and_pull_async(
-                "StartTransaction",
-                command,
-                None,
-                Box::new(move |_, response| {
-                    transaction_begin_processor(session.clone(), response, close_timeout)
-                }),
+            .interceptors
+            .intercept_job(
+                context,
+                self.send_and_pull_async(
+                    "StartTransaction",
+                    command,
+                    None,
+                    Box::new(move |_, response| {
+                        transaction_begin_processor(session.clone(), response, close_timeout)
+                    }),
+                ),
             )
             .await?;
 
@@ -760,14 +977,28 @@ impl SqlClient {
         const FUNCTION_NAME: &str = "get_transaction_status()";
         trace!("{} start", FUNCTION_NAME);
 
-        let command = Self::transaction_status_command(transaction.transaction_handle()?);
-        let (slot_handle, response) = self.send_and_pull_response(command, None, timeout).await?;
-        let status = transaction_status_processor(slot_handle, response)?;
+        let wire = self.wire();
+        let status =
+            Self::transaction_status_by_handle(&wire, transaction.transaction_handle()?, timeout)
+                .await?;
 
         trace!("{} end", FUNCTION_NAME);
         Ok(status)
     }
 
+    pub(crate) async fn transaction_status_by_handle(
+        wire: &Wire,
+        transaction_handle: &ProtoTransaction,
+        timeout: Duration,
+    ) -> Result<TransactionStatusWithMessage, TgError> {
+        let command = Self::transaction_status_command(transaction_handle);
+        let request = Self::new_request(command);
+        let (slot_handle, response) = wire
+            .send_and_pull_response(SERVICE_ID_SQL, request, None, timeout)
+            .await?;
+        transaction_status_processor(slot_handle, response)
+    }
+
     /// Get the transaction status on the server.
     ///
     /// since 0.2.0
@@ -832,11 +1063,21 @@ impl SqlClient {
         const FUNCTION_NAME: &str = "execute()";
         trace!("{} start", FUNCTION_NAME);
 
-        let tx_handle = transaction.transaction_handle()?;
-
-        let command = Self::execute_statement_command(tx_handle, sql);
-        let (slot_handle, response) = self.send_and_pull_response(command, None, timeout).await?;
-        let execute_result = execute_result_processor(slot_handle, response)?;
+        let context = self.interceptors.before(SqlOperation::Execute, |context| {
+            context.set_sql(sql);
+            context.set_transaction(transaction);
+        })?;
+        let execute_result = self
+            .interceptors
+            .intercept(context, async {
+                let tx_handle = transaction.transaction_handle()?;
+
+                let command = Self::execute_statement_command(tx_handle, sql);
+                let (slot_handle, response) =
+                    self.send_and_pull_response(command, None, timeout).await?;
+                execute_result_processor(slot_handle, response)
+            })
+            .await?;
 
         trace!("{} end", FUNCTION_NAME);
         Ok(execute_result)
@@ -851,11 +1092,24 @@ impl SqlClient {
         const FUNCTION_NAME: &str = "execute_async()";
         trace!("{} start", FUNCTION_NAME);
 
-        let tx_handle = transaction.transaction_handle()?;
-
-        let command = Self::execute_statement_command(tx_handle, sql);
+        let context = self.interceptors.before(SqlOperation::Execute, |context| {
+            context.set_sql(sql);
+            context.set_transaction(transaction);
+        })?;
         let job = self
-            .send_and_pull_async("Execute", command, None, Box::new(execute_result_processor))
+            .interceptors
+            .intercept_job(context, async {
+                let tx_handle = transaction.transaction_handle()?;
+
+                let command = Self::execute_statement_command(tx_handle, sql);
+                self.send_and_pull_async(
+                    "Execute",
+                    command,
+                    None,
+                    Box::new(execute_result_processor),
+                )
+                .await
+            })
             .await?;
 
         trace!("{} end", FUNCTION_NAME);
@@ -911,13 +1165,29 @@ impl SqlClient {
         const FUNCTION_NAME: &str = "prepared_execute()";
         trace!("{} start", FUNCTION_NAME);
 
-        let tx_handle = transaction.transaction_handle()?;
-        let (parameters, lobs) = self.convert_lob_parameters(parameters, timeout).await?;
-
-        let command =
-            Self::execute_prepared_statement_command(tx_handle, prepared_statement, parameters);
-        let (slot_handle, response) = self.send_and_pull_response(command, lobs, timeout).await?;
-        let execute_result = execute_result_processor(slot_handle, response)?;
+        let context = self
+            .interceptors
+            .before(SqlOperation::PreparedExecute, |context| {
+                context.set_sql(prepared_statement.sql());
+                context.set_transaction(transaction);
+                context.set_parameters(&parameters);
+            })?;
+        let execute_result = self
+            .interceptors
+            .intercept(context, async {
+                let tx_handle = transaction.transaction_handle()?;
+                let (parameters, lobs) = self.convert_lob_parameters(parameters, timeout).await?;
+
+                let command = Self::execute_prepared_statement_command(
+                    tx_handle,
+                    prepared_statement,
+                    parameters,
+                );
+                let (slot_handle, response) =
+                    self.send_and_pull_response(command, lobs, timeout).await?;
+                execute_result_processor(slot_handle, response)
+            })
+            .await?;
 
         trace!("{} end", FUNCTION_NAME);
         Ok(execute_result)
@@ -933,14 +1203,33 @@ impl SqlClient {
         const FUNCTION_NAME: &str = "prepared_execute_async()";
         trace!("{} start", FUNCTION_NAME);
 
-        let tx_handle = transaction.transaction_handle()?;
-        let timeout = self.default_timeout;
-        let (parameters, lobs) = self.convert_lob_parameters(parameters, timeout).await?;
-
-        let command =
-            Self::execute_prepared_statement_command(tx_handle, prepared_statement, parameters);
+        let context = self
+            .interceptors
+            .before(SqlOperation::PreparedExecute, |context| {
+                context.set_sql(prepared_statement.sql());
+                context.set_transaction(transaction);
+                context.set_parameters(&parameters);
+            })?;
         let job = self
-            .send_and_pull_async("Execute", command, lobs, Box::new(execute_result_processor))
+            .interceptors
+            .intercept_job(context, async {
+                let tx_handle = transaction.transaction_handle()?;
+                let timeout = self.default_timeout;
+                let (parameters, lobs) = self.convert_lob_parameters(parameters, timeout).await?;
+
+                let command = Self::execute_prepared_statement_command(
+                    tx_handle,
+                    prepared_statement,
+                    parameters,
+                );
+                self.send_and_pull_async(
+                    "Execute",
+                    command,
+                    lobs,
+                    Box::new(execute_result_processor),
+                )
+                .await
+            })
             .await?;
 
         trace!("{} end", FUNCTION_NAME);
@@ -1010,14 +1299,31 @@ impl SqlClient {
         const FUNCTION_NAME: &str = "query()";
         trace!("{} start", FUNCTION_NAME);
 
-        let tx_handle = transaction.transaction_handle()?;
-
-        let command = Self::execute_query_command(tx_handle, sql);
-        let (slot_handle, response) = self.send_and_pull_response(command, None, timeout).await?;
-
-        let wire = self.wire().clone();
-        let default_timeout = self.default_timeout;
-        let query_result = query_result_processor(wire, slot_handle, response, default_timeout)?;
+        let context = self.interceptors.before(SqlOperation::Query, |context| {
+            context.set_sql(sql);
+            context.set_transaction(transaction);
+        })?;
+        let query_result = self
+            .interceptors
+            .intercept(context, async {
+                let tx_handle = transaction.transaction_handle()?;
+
+                let command = Self::execute_query_command(tx_handle, sql);
+                let (slot_handle, response) =
+                    self.send_and_pull_response(command, None, timeout).await?;
+
+                let wire = self.wire().clone();
+                let default_timeout = self.default_timeout;
+                let prefetch_option = self.query_prefetch_option.as_ref();
+                query_result_processor(
+                    wire,
+                    slot_handle,
+                    response,
+                    default_timeout,
+                    prefetch_option,
+                )
+            })
+            .await?;
 
         trace!("{} end", FUNCTION_NAME);
         Ok(query_result)
@@ -1032,20 +1338,35 @@ impl SqlClient {
         const FUNCTION_NAME: &str = "query_async()";
         trace!("{} start", FUNCTION_NAME);
 
-        let tx_handle = transaction.transaction_handle()?;
-
-        let command = Self::execute_query_command(tx_handle, sql);
-        let wire = self.wire().clone();
-        let default_timeout = self.default_timeout;
+        let context = self.interceptors.before(SqlOperation::Query, |context| {
+            context.set_sql(sql);
+            context.set_transaction(transaction);
+        })?;
         let job = self
-            .send_and_pull_async(
-                "Query",
-                command,
-                None,
-                Box::new(move |slot_handle, response| {
-                    query_result_processor(wire.clone(), slot_handle, response, default_timeout)
-                }),
-            )
+            .interceptors
+            .intercept_job(context, async {
+                let tx_handle = transaction.transaction_handle()?;
+
+                let command = Self::execute_query_command(tx_handle, sql);
+                let wire = self.wire().clone();
+                let default_timeout = self.default_timeout;
+                let prefetch_option = self.query_prefetch_option.clone();
+                self.send_and_pull_async(
+                    "Query",
+                    command,
+                    None,
+                    Box::new(move |slot_handle, response| {
+                        query_result_processor(
+                            wire.clone(),
+                            slot_handle,
+                            response,
+                            default_timeout,
+                            prefetch_option.as_ref(),
+                        )
+                    }),
+                )
+                .await
+            })
             .await?;
 
         trace!("{} end", FUNCTION_NAME);
@@ -1110,16 +1431,36 @@ impl SqlClient {
         const FUNCTION_NAME: &str = "prepared_query()";
         trace!("{} start", FUNCTION_NAME);
 
-        let tx_handle = transaction.transaction_handle()?;
-        let (parameters, lobs) = self.convert_lob_parameters(parameters, timeout).await?;
-
-        let command =
-            Self::execute_prepared_query_command(tx_handle, prepared_statement, parameters);
-        let (slot_handle, response) = self.send_and_pull_response(command, lobs, timeout).await?;
-
-        let wire = self.wire().clone();
-        let default_timeout = self.default_timeout;
-        let query_result = query_result_processor(wire, slot_handle, response, default_timeout)?;
+        let context = self
+            .interceptors
+            .before(SqlOperation::PreparedQuery, |context| {
+                context.set_sql(prepared_statement.sql());
+                context.set_transaction(transaction);
+                context.set_parameters(&parameters);
+            })?;
+        let query_result = self
+            .interceptors
+            .intercept(context, async {
+                let tx_handle = transaction.transaction_handle()?;
+                let (parameters, lobs) = self.convert_lob_parameters(parameters, timeout).await?;
+
+                let command =
+                    Self::execute_prepared_query_command(tx_handle, prepared_statement, parameters);
+                let (slot_handle, response) =
+                    self.send_and_pull_response(command, lobs, timeout).await?;
+
+                let wire = self.wire().clone();
+                let default_timeout = self.default_timeout;
+                let prefetch_option = self.query_prefetch_option.as_ref();
+                query_result_processor(
+                    wire,
+                    slot_handle,
+                    response,
+                    default_timeout,
+                    prefetch_option,
+                )
+            })
+            .await?;
 
         trace!("{} end", FUNCTION_NAME);
         Ok(query_result)
@@ -1135,44 +1476,425 @@ impl SqlClient {
         const FUNCTION_NAME: &str = "prepared_query_async()";
         trace!("{} start", FUNCTION_NAME);
 
-        let tx_handle = transaction.transaction_handle()?;
+        let context = self
+            .interceptors
+            .before(SqlOperation::PreparedQuery, |context| {
+                context.set_sql(prepared_statement.sql());
+                context.set_transaction(transaction);
+                context.set_parameters(&parameters);
+            })?;
+        let job = self
+            .interceptors
+            .intercept_job(context, async {
+                let tx_handle = transaction.transaction_handle()?;
+                let timeout = self.default_timeout;
+                let (parameters, lobs) = self.convert_lob_parameters(parameters, timeout).await?;
+
+                let command =
+                    Self::execute_prepared_query_command(tx_handle, prepared_statement, parameters);
+                let wire = self.wire().clone();
+                let default_timeout = self.default_timeout;
+                let prefetch_option = self.query_prefetch_option.clone();
+                self.send_and_pull_async(
+                    "Query",
+                    command,
+                    lobs,
+                    Box::new(move |slot_handle, response| {
+                        query_result_processor(
+                            wire.clone(),
+                            slot_handle,
+                            response,
+                            default_timeout,
+                            prefetch_option.as_ref(),
+                        )
+                    }),
+                )
+                .await
+            })
+            .await?;
+
+        trace!("{} end", FUNCTION_NAME);
+        Ok(job)
+    }
+
+    fn execute_prepared_query_command(
+        transaction_handle: &ProtoTransaction,
+        prepared_statement: &SqlPreparedStatement,
+        parameters: Vec<SqlParameter>,
+    ) -> SqlCommand {
+        let ps_handle = crate::jogasaki::proto::sql::common::PreparedStatement {
+            handle: prepared_statement.prepare_handle(),
+            has_result_records: prepared_statement.has_result_records(),
+        };
+        let request = crate::jogasaki::proto::sql::request::ExecutePreparedQuery {
+            transaction_handle: Some(*transaction_handle),
+            prepared_statement_handle: Some(ps_handle),
+            parameters,
+        };
+        SqlCommand::ExecutePreparedQuery(request)
+    }
+
+    /// Executes a SQL statement with parameters.
+    ///
+    /// The statement is prepared with placeholders derived from the parameters,
+    /// executed, and then disposed.
+    /// The SQL may contain positional markers (`?`) instead of named placeholders.
+    /// In that case, the parameters are bound in order and their names are ignored.
+    ///
+    /// # Examples
+    /// ```
+    /// use tsubakuro_rust_core::prelude::*;
+    ///
+    /// async fn example(client: &SqlClient, transaction: &Transaction) -> Result<(), TgError> {
+    ///     let sql = "insert into customer values(:id, :name, :age)";
+    ///     let parameters = vec![
+    ///         SqlParameter::of("id", 4_i64),
+    ///         SqlParameter::of("name", "example"),
+    ///         SqlParameter::of("age", 20),
+    ///     ];
+    ///     let execute_result = client.execute_with(&transaction, sql, parameters).await?;
+    ///     println!("inserted rows={}", execute_result.inserted_rows());
+    ///
+    ///     let sql = "update customer set c_age = ? where c_id = ?";
+    ///     let parameters = vec![SqlParameter::of("", 21), SqlParameter::of("", 4_i64)];
+    ///     let execute_result = client.execute_with(&transaction, sql, parameters).await?;
+    ///     println!("updated rows={}", execute_result.updated_rows());
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// since 0.11.0
+    pub async fn execute_with(
+        &self,
+        transaction: &Transaction,
+        sql: &str,
+        parameters: Vec<SqlParameter>,
+    ) -> Result<SqlExecuteResult, TgError> {
         let timeout = self.default_timeout;
-        let (parameters, lobs) = self.convert_lob_parameters(parameters, timeout).await?;
+        self.execute_with_for(transaction, sql, parameters, timeout)
+            .await
+    }
 
-        let command =
-            Self::execute_prepared_query_command(tx_handle, prepared_statement, parameters);
-        let wire = self.wire().clone();
-        let default_timeout = self.default_timeout;
-        let job = self
-            .send_and_pull_async(
-                "Query",
-                command,
-                lobs,
-                Box::new(move |slot_handle, response| {
-                    query_result_processor(wire.clone(), slot_handle, response, default_timeout)
-                }),
-            )
+    /// Executes a SQL statement with parameters.
+    ///
+    /// since 0.11.0
+    pub async fn execute_with_for(
+        &self,
+        transaction: &Transaction,
+        sql: &str,
+        parameters: Vec<SqlParameter>,
+        timeout: Duration,
+    ) -> Result<SqlExecuteResult, TgError> {
+        const FUNCTION_NAME: &str = "execute_with()";
+        trace!("{} start", FUNCTION_NAME);
+
+        let (prepared_statement, parameters) = self
+            .prepare_with_parameters(sql, parameters, timeout)
+            .await?;
+        let result = self
+            .prepared_execute_for(transaction, &prepared_statement, parameters, timeout)
+            .await;
+        let close_result = prepared_statement.close_for(timeout).await;
+        let execute_result = result?;
+        close_result?;
+
+        trace!("{} end", FUNCTION_NAME);
+        Ok(execute_result)
+    }
+
+    /// Executes a SQL statement with parameters and retrieve its result.
+    ///
+    /// The statement is prepared with placeholders derived from the parameters,
+    /// and is disposed when the query result is closed.
+    /// The SQL may contain positional markers (`?`) instead of named placeholders.
+    /// In that case, the parameters are bound in order and their names are ignored.
+    ///
+    /// # Examples
+    /// ```
+    /// use tsubakuro_rust_core::prelude::*;
+    ///
+    /// async fn example(client: &SqlClient, transaction: &Transaction) -> Result<(), TgError> {
+    ///     let sql = "select c_id, c_name, c_age from customer where c_id = :id";
+    ///     let parameters = vec![SqlParameter::of("id", 3_i64)];
+    ///     let mut query_result = client.query_with(&transaction, sql, parameters).await?;
+    ///
+    ///     while query_result.next_row().await? {
+    ///         if query_result.next_column().await? {
+    ///             let id: i64 = query_result.fetch().await?;
+    ///         }
+    ///         if query_result.next_column().await? {
+    ///             let name: Option<String> = query_result.fetch().await?;
+    ///         }
+    ///         if query_result.next_column().await? {
+    ///             let age: Option<i32> = query_result.fetch().await?;
+    ///         }
+    ///     }
+    ///
+    ///     query_result.close().await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// since 0.11.0
+    pub async fn query_with(
+        &self,
+        transaction: &Transaction,
+        sql: &str,
+        parameters: Vec<SqlParameter>,
+    ) -> Result<SqlQueryResult, TgError> {
+        let timeout = self.default_timeout;
+        self.query_with_for(transaction, sql, parameters, timeout)
+            .await
+    }
+
+    /// Executes a SQL statement with parameters and retrieve its result.
+    ///
+    /// since 0.11.0
+    pub async fn query_with_for(
+        &self,
+        transaction: &Transaction,
+        sql: &str,
+        parameters: Vec<SqlParameter>,
+        timeout: Duration,
+    ) -> Result<SqlQueryResult, TgError> {
+        const FUNCTION_NAME: &str = "query_with()";
+        trace!("{} start", FUNCTION_NAME);
+
+        let (prepared_statement, parameters) = self
+            .prepare_with_parameters(sql, parameters, timeout)
             .await?;
+        let result = self
+            .prepared_query_for(transaction, &prepared_statement, parameters, timeout)
+            .await;
+        let mut query_result = match result {
+            Ok(query_result) => query_result,
+            Err(e) => {
+                if let Err(close_error) = prepared_statement.close_for(timeout).await {
+                    warn!(
+                        "{} prepared statement close error. {}",
+                        FUNCTION_NAME, close_error
+                    );
+                }
+                return Err(e);
+            }
+        };
+        query_result.set_prepared_statement(prepared_statement);
+
+        trace!("{} end", FUNCTION_NAME);
+        Ok(query_result)
+    }
+
+    /// Executes a SQL script which contains multiple statements separated by `;`.
+    ///
+    /// Each statement is executed in its own transaction, and the execution stops at the first error.
+    /// `;` in string literals, quoted identifiers and comments are not treated as separators.
+    ///
+    /// See [Self::execute_script_with].
+    ///
+    /// # Examples
+    /// ```
+    /// use tsubakuro_rust_core::prelude::*;
+    ///
+    /// async fn example(client: &SqlClient) -> Result<(), TgError> {
+    ///     let script = "
+    ///       create table customer (c_id bigint primary key, c_name varchar(30));
+    ///       -- seed data
+    ///       insert into customer values (1, 'a;b');
+    ///       insert into customer values (2, 'c');
+    ///     ";
+    ///     let result = client.execute_script(&TransactionOption::default(), script).await?;
+    ///     for statement in result.statements() {
+    ///         println!("{} {:?}", statement.sql(), statement.status());
+    ///     }
+    ///     if let Some(e) = result.first_error() {
+    ///         println!("error: {}", e);
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// since 0.11.0
+    pub async fn execute_script(
+        &self,
+        transaction_option: &TransactionOption,
+        script: &str,
+    ) -> Result<SqlScriptResult, TgError> {
+        self.execute_script_with(transaction_option, script, &SqlScriptOption::default())
+            .await
+    }
+
+    /// Executes a SQL script which contains multiple statements separated by `;`.
+    ///
+    /// Returns `Err` only if the transaction cannot be started in [SqlScriptTransactionMode::Single].
+    /// The errors of the statements are reported in [SqlScriptResult].
+    ///
+    /// # Examples
+    /// ```
+    /// use tsubakuro_rust_core::prelude::*;
+    ///
+    /// async fn example(client: &SqlClient, script: &str) -> Result<(), TgError> {
+    ///     let mut script_option = SqlScriptOption::new();
+    ///     script_option.set_transaction_mode(SqlScriptTransactionMode::Single);
+    ///
+    ///     let transaction_option = TransactionOption::from(TransactionType::Long);
+    ///     let result = client.execute_script_with(&transaction_option, script, &script_option).await?;
+    ///     println!("committed={}, succeeded={}", result.is_committed(), result.succeeded_count());
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// since 0.11.0
+    pub async fn execute_script_with(
+        &self,
+        transaction_option: &TransactionOption,
+        script: &str,
+        script_option: &SqlScriptOption,
+    ) -> Result<SqlScriptResult, TgError> {
+        const FUNCTION_NAME: &str = "execute_script()";
+        trace!("{} start", FUNCTION_NAME);
+
+        let statements = split_statements(script);
+        let result = match script_option.transaction_mode() {
+            SqlScriptTransactionMode::PerStatement => {
+                self.execute_script_per_statement(transaction_option, &statements, script_option)
+                    .await
+            }
+            SqlScriptTransactionMode::Single => {
+                self.execute_script_in_transaction(transaction_option, &statements)
+                    .await?
+            }
+        };
+
+        trace!("{} end", FUNCTION_NAME);
+        Ok(result)
+    }
+
+    async fn execute_script_per_statement(
+        &self,
+        transaction_option: &TransactionOption,
+        statements: &[&str],
+        script_option: &SqlScriptOption,
+    ) -> SqlScriptResult {
+        let mut results = Vec::with_capacity(statements.len());
+        let mut stopped = false;
+        for (index, sql) in statements.iter().enumerate() {
+            let status = if stopped {
+                SqlStatementStatus::Skipped
+            } else {
+                match self.execute_in_transaction(transaction_option, sql).await {
+                    Ok(execute_result) => SqlStatementStatus::Succeeded(execute_result),
+                    Err(e) => {
+                        stopped = script_option.error_mode() == SqlScriptErrorMode::Stop;
+                        SqlStatementStatus::Failed(e)
+                    }
+                }
+            };
+            results.push(SqlStatementResult::new(index, sql, status));
+        }
+        SqlScriptResult::new(results, true, None)
+    }
+
+    async fn execute_in_transaction(
+        &self,
+        transaction_option: &TransactionOption,
+        sql: &str,
+    ) -> Result<SqlExecuteResult, TgError> {
+        let transaction = self.start_transaction(transaction_option).await?;
+        let result = match self.execute(&transaction, sql).await {
+            Ok(execute_result) => self
+                .commit(&transaction, &CommitOption::default())
+                .await
+                .map(|_| execute_result),
+            Err(e) => Err(e),
+        };
+        let close_result = transaction.close().await;
+        let execute_result = result?;
+        close_result?;
+        Ok(execute_result)
+    }
+
+    async fn execute_script_in_transaction(
+        &self,
+        transaction_option: &TransactionOption,
+        statements: &[&str],
+    ) -> Result<SqlScriptResult, TgError> {
+        const FUNCTION_NAME: &str = "execute_script()";
+
+        let transaction = self.start_transaction(transaction_option).await?;
+
+        let mut results = Vec::with_capacity(statements.len());
+        let mut failed = false;
+        for (index, sql) in statements.iter().enumerate() {
+            let status = if failed {
+                SqlStatementStatus::Skipped
+            } else {
+                match self.execute(&transaction, sql).await {
+                    Ok(execute_result) => SqlStatementStatus::Succeeded(execute_result),
+                    Err(e) => {
+                        failed = true;
+                        SqlStatementStatus::Failed(e)
+                    }
+                }
+            };
+            results.push(SqlStatementResult::new(index, sql, status));
+        }
 
-        trace!("{} end", FUNCTION_NAME);
-        Ok(job)
+        let commit_error = if failed {
+            if let Err(e) = self.rollback(&transaction).await {
+                warn!("{} rollback error. {}", FUNCTION_NAME, e);
+            }
+            None
+        } else {
+            self.commit(&transaction, &CommitOption::default())
+                .await
+                .err()
+        };
+        let committed = !failed && commit_error.is_none();
+        if let Err(e) = transaction.close().await {
+            warn!("{} transaction close error. {}", FUNCTION_NAME, e);
+        }
+
+        Ok(SqlScriptResult::new(results, committed, commit_error))
     }
 
-    fn execute_prepared_query_command(
-        transaction_handle: &ProtoTransaction,
-        prepared_statement: &SqlPreparedStatement,
+    async fn prepare_with_parameters(
+        &self,
+        sql: &str,
         parameters: Vec<SqlParameter>,
-    ) -> SqlCommand {
-        let ps_handle = crate::jogasaki::proto::sql::common::PreparedStatement {
-            handle: prepared_statement.prepare_handle(),
-            has_result_records: prepared_statement.has_result_records(),
-        };
-        let request = crate::jogasaki::proto::sql::request::ExecutePreparedQuery {
-            transaction_handle: Some(*transaction_handle),
-            prepared_statement_handle: Some(ps_handle),
-            parameters,
+        timeout: Duration,
+    ) -> Result<(SqlPreparedStatement, Vec<SqlParameter>), TgError> {
+        let (sql, count) = replace_positional_placeholders(sql);
+        let parameters = if count > 0 {
+            if parameters.len() != count {
+                return Err(illegal_argument_error!(format!(
+                    "parameter count mismatch. positional markers={}, parameters={}",
+                    count,
+                    parameters.len()
+                )));
+            }
+            parameters
+                .into_iter()
+                .enumerate()
+                .map(|(i, parameter)| parameter.rename(&positional_placeholder_name(i + 1)))
+                .collect()
+        } else {
+            parameters
         };
-        SqlCommand::ExecutePreparedQuery(request)
+
+        let placeholders = parameters
+            .iter()
+            .map(|parameter| {
+                SqlPlaceholder::of_parameter(parameter)
+                    .ok_or_else(|| illegal_argument_error!("parameter name is not specified"))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let prepared_statement = self.prepare_for(&sql, placeholders, timeout).await?;
+        Ok((prepared_statement, parameters))
     }
 
     /// Check whether LOB operation is supported in the current lob transfer type.
@@ -1259,8 +1981,18 @@ impl SqlClient {
         const FUNCTION_NAME: &str = "upload_blob_file()";
         trace!("{} start", FUNCTION_NAME);
 
-        let lob_client = self.get_lob_client().await?;
-        let lob = lob_client.upload_lob_file(path.as_ref(), timeout).await?;
+        let context = self
+            .interceptors
+            .before(SqlOperation::UploadLob, |context| {
+                context.set_path(path.as_ref());
+            })?;
+        let lob = self
+            .interceptors
+            .intercept(context, async {
+                let lob_client = self.get_lob_client().await?;
+                lob_client.upload_lob_file(path.as_ref(), timeout).await
+            })
+            .await?;
 
         trace!("{} end", FUNCTION_NAME);
         Ok(TgBlob::from_remote_lob(lob))
@@ -1276,9 +2008,19 @@ impl SqlClient {
         const FUNCTION_NAME: &str = "upload_blob_file_async()";
         trace!("{} start", FUNCTION_NAME);
 
-        let lob_client = self.get_lob_client().await?;
-        let job = lob_client.upload_lob_file_async(path.as_ref()).await?;
-        let job = job.convert("TgBlob", Box::new(|lob| Ok(TgBlob::from_remote_lob(lob))));
+        let context = self
+            .interceptors
+            .before(SqlOperation::UploadLob, |context| {
+                context.set_path(path.as_ref());
+            })?;
+        let job = self
+            .interceptors
+            .intercept_job(context, async {
+                let lob_client = self.get_lob_client().await?;
+                let job = lob_client.upload_lob_file_async(path.as_ref()).await?;
+                Ok(job.convert("TgBlob", Box::new(|lob| Ok(TgBlob::from_remote_lob(lob)))))
+            })
+            .await?;
 
         trace!("{} end", FUNCTION_NAME);
         Ok(job)
@@ -1325,8 +2067,18 @@ impl SqlClient {
         const FUNCTION_NAME: &str = "upload_clob_file()";
         trace!("{} start", FUNCTION_NAME);
 
-        let lob_client = self.get_lob_client().await?;
-        let lob = lob_client.upload_lob_file(path.as_ref(), timeout).await?;
+        let context = self
+            .interceptors
+            .before(SqlOperation::UploadLob, |context| {
+                context.set_path(path.as_ref());
+            })?;
+        let lob = self
+            .interceptors
+            .intercept(context, async {
+                let lob_client = self.get_lob_client().await?;
+                lob_client.upload_lob_file(path.as_ref(), timeout).await
+            })
+            .await?;
 
         trace!("{} end", FUNCTION_NAME);
         Ok(TgClob::from_remote_lob(lob))
@@ -1342,14 +2094,143 @@ impl SqlClient {
         const FUNCTION_NAME: &str = "upload_clob_file_async()";
         trace!("{} start", FUNCTION_NAME);
 
-        let lob_client = self.get_lob_client().await?;
-        let job = lob_client.upload_lob_file_async(path.as_ref()).await?;
-        let job = job.convert("TgClob", Box::new(|lob| Ok(TgClob::from_remote_lob(lob))));
+        let context = self
+            .interceptors
+            .before(SqlOperation::UploadLob, |context| {
+                context.set_path(path.as_ref());
+            })?;
+        let job = self
+            .interceptors
+            .intercept_job(context, async {
+                let lob_client = self.get_lob_client().await?;
+                let job = lob_client.upload_lob_file_async(path.as_ref()).await?;
+                Ok(job.convert("TgClob", Box::new(|lob| Ok(TgClob::from_remote_lob(lob)))))
+            })
+            .await?;
 
         trace!("{} end", FUNCTION_NAME);
         Ok(job)
     }
 
+    /// Uploads files as BLOBs.
+    ///
+    /// Each file is retried according to `option` if the upload fails,
+    /// and the files are uploaded concurrently when using the blob relay service.
+    /// The retry restarts the upload of the file from the beginning,
+    /// because the blob relay service does not support resuming an upload.
+    ///
+    /// ## Returns
+    /// the result of each file in the same order as `paths`.
+    /// A failure of one file does not affect the other files,
+    /// so only the failed files need to be uploaded again.
+    ///
+    /// # Examples
+    /// ```
+    /// use tsubakuro_rust_core::prelude::*;
+    ///
+    /// async fn example(client: &SqlClient, paths: &[&str]) -> Result<Vec<TgBlob>, TgError> {
+    ///     let mut option = LobUploadOption::new();
+    ///     option.set_max_retries(5);
+    ///
+    ///     let results = client.upload_blob_files(paths, &option).await?;
+    ///     results.into_iter().collect()
+    /// }
+    /// ```
+    ///
+    /// since 0.11.0
+    pub async fn upload_blob_files<T: AsRef<Path>>(
+        &self,
+        paths: &[T],
+        option: &LobUploadOption,
+    ) -> Result<Vec<Result<TgBlob, TgError>>, TgError> {
+        let timeout = self.default_timeout;
+        self.upload_blob_files_for(paths, option, timeout).await
+    }
+
+    /// Uploads files as BLOBs.
+    ///
+    /// `timeout` applies to each attempt of each file.
+    ///
+    /// since 0.11.0
+    pub async fn upload_blob_files_for<T: AsRef<Path>>(
+        &self,
+        paths: &[T],
+        option: &LobUploadOption,
+        timeout: Duration,
+    ) -> Result<Vec<Result<TgBlob, TgError>>, TgError> {
+        const FUNCTION_NAME: &str = "upload_blob_files()";
+        trace!("{} start", FUNCTION_NAME);
+
+        let context = self.interceptors.before(SqlOperation::UploadLob, |_| {})?;
+        let results = self
+            .interceptors
+            .intercept(context, async {
+                let lob_client = self.get_lob_client().await?;
+                let paths: Vec<PathBuf> = paths
+                    .iter()
+                    .map(|path| path.as_ref().to_path_buf())
+                    .collect();
+                let results = lob_client.upload_lob_files(&paths, option, timeout).await;
+                Ok(results
+                    .into_iter()
+                    .map(|result| result.map(TgBlob::from_remote_lob))
+                    .collect())
+            })
+            .await?;
+
+        trace!("{} end", FUNCTION_NAME);
+        Ok(results)
+    }
+
+    /// Uploads files as CLOBs.
+    ///
+    /// See [`Self::upload_blob_files`].
+    ///
+    /// since 0.11.0
+    pub async fn upload_clob_files<T: AsRef<Path>>(
+        &self,
+        paths: &[T],
+        option: &LobUploadOption,
+    ) -> Result<Vec<Result<TgClob, TgError>>, TgError> {
+        let timeout = self.default_timeout;
+        self.upload_clob_files_for(paths, option, timeout).await
+    }
+
+    /// Uploads files as CLOBs.
+    ///
+    /// `timeout` applies to each attempt of each file.
+    ///
+    /// since 0.11.0
+    pub async fn upload_clob_files_for<T: AsRef<Path>>(
+        &self,
+        paths: &[T],
+        option: &LobUploadOption,
+        timeout: Duration,
+    ) -> Result<Vec<Result<TgClob, TgError>>, TgError> {
+        const FUNCTION_NAME: &str = "upload_clob_files()";
+        trace!("{} start", FUNCTION_NAME);
+
+        let context = self.interceptors.before(SqlOperation::UploadLob, |_| {})?;
+        let results = self
+            .interceptors
+            .intercept(context, async {
+                let lob_client = self.get_lob_client().await?;
+                let paths: Vec<PathBuf> = paths
+                    .iter()
+                    .map(|path| path.as_ref().to_path_buf())
+                    .collect();
+                let results = lob_client.upload_lob_files(&paths, option, timeout).await;
+                Ok(results
+                    .into_iter()
+                    .map(|result| result.map(TgClob::from_remote_lob))
+                    .collect())
+            })
+            .await?;
+
+        trace!("{} end", FUNCTION_NAME);
+        Ok(results)
+    }
+
     /// Uploads a BLOB.
     ///
     /// # Examples
@@ -1391,8 +2272,14 @@ impl SqlClient {
         const FUNCTION_NAME: &str = "upload_blob()";
         trace!("{} start", FUNCTION_NAME);
 
-        let lob_client = self.get_lob_client().await?;
-        let lob = lob_client.upload_lob(value, timeout).await?;
+        let context = self.interceptors.before(SqlOperation::UploadLob, |_| {})?;
+        let lob = self
+            .interceptors
+            .intercept(context, async {
+                let lob_client = self.get_lob_client().await?;
+                lob_client.upload_lob(value, timeout).await
+            })
+            .await?;
 
         trace!("{} end", FUNCTION_NAME);
         Ok(TgBlob::from_remote_lob(lob))
@@ -1405,9 +2292,15 @@ impl SqlClient {
         const FUNCTION_NAME: &str = "upload_blob_async()";
         trace!("{} start", FUNCTION_NAME);
 
-        let lob_client = self.get_lob_client().await?;
-        let job = lob_client.upload_lob_async(value).await?;
-        let job = job.convert("TgBlob", Box::new(|lob| Ok(TgBlob::from_remote_lob(lob))));
+        let context = self.interceptors.before(SqlOperation::UploadLob, |_| {})?;
+        let job = self
+            .interceptors
+            .intercept_job(context, async {
+                let lob_client = self.get_lob_client().await?;
+                let job = lob_client.upload_lob_async(value).await?;
+                Ok(job.convert("TgBlob", Box::new(|lob| Ok(TgBlob::from_remote_lob(lob)))))
+            })
+            .await?;
 
         trace!("{} end", FUNCTION_NAME);
         Ok(job)
@@ -1450,10 +2343,16 @@ impl SqlClient {
         const FUNCTION_NAME: &str = "upload_clob()";
         trace!("{} start", FUNCTION_NAME);
 
-        let value = value.as_bytes();
+        let context = self.interceptors.before(SqlOperation::UploadLob, |_| {})?;
+        let lob = self
+            .interceptors
+            .intercept(context, async {
+                let value = value.as_bytes();
 
-        let lob_client = self.get_lob_client().await?;
-        let lob = lob_client.upload_lob(value, timeout).await?;
+                let lob_client = self.get_lob_client().await?;
+                lob_client.upload_lob(value, timeout).await
+            })
+            .await?;
 
         trace!("{} end", FUNCTION_NAME);
         Ok(TgClob::from_remote_lob(lob))
@@ -1466,10 +2365,16 @@ impl SqlClient {
         const FUNCTION_NAME: &str = "upload_clob_async()";
         trace!("{} start", FUNCTION_NAME);
 
-        let lob_client = self.get_lob_client().await?;
-        let value = value.as_bytes();
-        let job = lob_client.upload_lob_async(value).await?;
-        let job = job.convert("TgClob", Box::new(|lob| Ok(TgClob::from_remote_lob(lob))));
+        let context = self.interceptors.before(SqlOperation::UploadLob, |_| {})?;
+        let job = self
+            .interceptors
+            .intercept_job(context, async {
+                let lob_client = self.get_lob_client().await?;
+                let value = value.as_bytes();
+                let job = lob_client.upload_lob_async(value).await?;
+                Ok(job.convert("TgClob", Box::new(|lob| Ok(TgClob::from_remote_lob(lob)))))
+            })
+            .await?;
 
         trace!("{} end", FUNCTION_NAME);
         Ok(job)
@@ -1529,11 +2434,19 @@ impl SqlClient {
         const FUNCTION_NAME: &str = "open_blob()";
         trace!("{} start", FUNCTION_NAME);
 
-        let lob_client = self.get_lob_client().await?;
-        let client_path = lob_client
-            .download_lob_file(transaction, blob, timeout)
+        let context = self.interceptors.before(SqlOperation::OpenLob, |context| {
+            context.set_transaction(transaction);
+        })?;
+        let file = self
+            .interceptors
+            .intercept(context, async {
+                let lob_client = self.get_lob_client().await?;
+                let client_path = lob_client
+                    .download_lob_file(transaction, blob, timeout)
+                    .await?;
+                Self::open_lob_file(client_path)
+            })
             .await?;
-        let file = Self::open_lob_file(client_path)?;
 
         trace!("{} end", FUNCTION_NAME);
         Ok(file)
@@ -1548,11 +2461,19 @@ impl SqlClient {
         const FUNCTION_NAME: &str = "open_blob_async()";
         trace!("{} start", FUNCTION_NAME);
 
-        let lob_client = self.get_lob_client().await?;
-        let job = lob_client
-            .download_lob_file_async(transaction, blob)
+        let context = self.interceptors.before(SqlOperation::OpenLob, |context| {
+            context.set_transaction(transaction);
+        })?;
+        let job = self
+            .interceptors
+            .intercept_job(context, async {
+                let lob_client = self.get_lob_client().await?;
+                let job = lob_client
+                    .download_lob_file_async(transaction, blob)
+                    .await?;
+                Ok(job.convert("File", Box::new(Self::open_lob_file)))
+            })
             .await?;
-        let job = job.convert("File", Box::new(Self::open_lob_file));
 
         trace!("{} end", FUNCTION_NAME);
         Ok(job)
@@ -1594,11 +2515,19 @@ impl SqlClient {
         const FUNCTION_NAME: &str = "open_clob()";
         trace!("{} start", FUNCTION_NAME);
 
-        let lob_client = self.get_lob_client().await?;
-        let client_path = lob_client
-            .download_lob_file(transaction, clob, timeout)
+        let context = self.interceptors.before(SqlOperation::OpenLob, |context| {
+            context.set_transaction(transaction);
+        })?;
+        let file = self
+            .interceptors
+            .intercept(context, async {
+                let lob_client = self.get_lob_client().await?;
+                let client_path = lob_client
+                    .download_lob_file(transaction, clob, timeout)
+                    .await?;
+                Self::open_lob_file(client_path)
+            })
             .await?;
-        let file = Self::open_lob_file(client_path)?;
 
         trace!("{} end", FUNCTION_NAME);
         Ok(file)
@@ -1613,11 +2542,19 @@ impl SqlClient {
         const FUNCTION_NAME: &str = "open_clob_async()";
         trace!("{} start", FUNCTION_NAME);
 
-        let lob_client = self.get_lob_client().await?;
-        let job = lob_client
-            .download_lob_file_async(transaction, clob)
+        let context = self.interceptors.before(SqlOperation::OpenLob, |context| {
+            context.set_transaction(transaction);
+        })?;
+        let job = self
+            .interceptors
+            .intercept_job(context, async {
+                let lob_client = self.get_lob_client().await?;
+                let job = lob_client
+                    .download_lob_file_async(transaction, clob)
+                    .await?;
+                Ok(job.convert("File", Box::new(Self::open_lob_file)))
+            })
             .await?;
-        let job = job.convert("File", Box::new(Self::open_lob_file));
 
         trace!("{} end", FUNCTION_NAME);
         Ok(job)
@@ -1665,15 +2602,27 @@ impl SqlClient {
         const FUNCTION_NAME: &str = "get_blob_cache()";
         trace!("{} start", FUNCTION_NAME);
 
-        let lob_client = self.get_lob_client().await?;
-        let cache = if lob_client.supports_method(LobClientMethod::DownloadLobFile) {
-            let client_path = lob_client
-                .download_lob_file(transaction, blob, timeout)
-                .await?;
-            Self::create_large_object_cache(client_path)?
-        } else {
-            TgLargeObjectCache::new(None)
-        };
+        let context = self
+            .interceptors
+            .before(SqlOperation::GetLobCache, |context| {
+                context.set_transaction(transaction);
+            })?;
+        let cache = self
+            .interceptors
+            .intercept(context, async {
+                let lob_client = self.get_lob_client().await?;
+                Ok(
+                    if lob_client.supports_method(LobClientMethod::DownloadLobFile) {
+                        let client_path = lob_client
+                            .download_lob_file(transaction, blob, timeout)
+                            .await?;
+                        Self::create_large_object_cache(client_path)?
+                    } else {
+                        TgLargeObjectCache::new(None)
+                    },
+                )
+            })
+            .await?;
 
         trace!("{} end", FUNCTION_NAME);
         Ok(cache)
@@ -1690,18 +2639,30 @@ impl SqlClient {
         const FUNCTION_NAME: &str = "get_blob_cache_async()";
         trace!("{} start", FUNCTION_NAME);
 
-        let lob_client = self.get_lob_client().await?;
-        let job = if lob_client.supports_method(LobClientMethod::DownloadLobFile) {
-            let job = lob_client
-                .download_lob_file_async(transaction, blob)
-                .await?;
-            job.convert(
-                "LargeObjectCache",
-                Box::new(Self::create_large_object_cache),
-            )
-        } else {
-            Job::returns("LargeObjectCache", TgLargeObjectCache::new(None))
-        };
+        let context = self
+            .interceptors
+            .before(SqlOperation::GetLobCache, |context| {
+                context.set_transaction(transaction);
+            })?;
+        let job = self
+            .interceptors
+            .intercept_job(context, async {
+                let lob_client = self.get_lob_client().await?;
+                Ok(
+                    if lob_client.supports_method(LobClientMethod::DownloadLobFile) {
+                        let job = lob_client
+                            .download_lob_file_async(transaction, blob)
+                            .await?;
+                        job.convert(
+                            "LargeObjectCache",
+                            Box::new(Self::create_large_object_cache),
+                        )
+                    } else {
+                        Job::returns("LargeObjectCache", TgLargeObjectCache::new(None))
+                    },
+                )
+            })
+            .await?;
 
         trace!("{} end", FUNCTION_NAME);
         Ok(job)
@@ -1745,15 +2706,27 @@ impl SqlClient {
         const FUNCTION_NAME: &str = "get_clob_cache()";
         trace!("{} start", FUNCTION_NAME);
 
-        let lob_client = self.get_lob_client().await?;
-        let cache = if lob_client.supports_method(LobClientMethod::DownloadLobFile) {
-            let client_path = lob_client
-                .download_lob_file(transaction, clob, timeout)
-                .await?;
-            Self::create_large_object_cache(client_path)?
-        } else {
-            TgLargeObjectCache::new(None)
-        };
+        let context = self
+            .interceptors
+            .before(SqlOperation::GetLobCache, |context| {
+                context.set_transaction(transaction);
+            })?;
+        let cache = self
+            .interceptors
+            .intercept(context, async {
+                let lob_client = self.get_lob_client().await?;
+                Ok(
+                    if lob_client.supports_method(LobClientMethod::DownloadLobFile) {
+                        let client_path = lob_client
+                            .download_lob_file(transaction, clob, timeout)
+                            .await?;
+                        Self::create_large_object_cache(client_path)?
+                    } else {
+                        TgLargeObjectCache::new(None)
+                    },
+                )
+            })
+            .await?;
 
         trace!("{} end", FUNCTION_NAME);
         Ok(cache)
@@ -1770,18 +2743,30 @@ impl SqlClient {
         const FUNCTION_NAME: &str = "get_clob_cache_async()";
         trace!("{} start", FUNCTION_NAME);
 
-        let lob_client = self.get_lob_client().await?;
-        let job = if lob_client.supports_method(LobClientMethod::DownloadLobFile) {
-            let job = lob_client
-                .download_lob_file_async(transaction, clob)
-                .await?;
-            job.convert(
-                "LargeObjectCache",
-                Box::new(Self::create_large_object_cache),
-            )
-        } else {
-            Job::returns("LargeObjectCache", TgLargeObjectCache::new(None))
-        };
+        let context = self
+            .interceptors
+            .before(SqlOperation::GetLobCache, |context| {
+                context.set_transaction(transaction);
+            })?;
+        let job = self
+            .interceptors
+            .intercept_job(context, async {
+                let lob_client = self.get_lob_client().await?;
+                Ok(
+                    if lob_client.supports_method(LobClientMethod::DownloadLobFile) {
+                        let job = lob_client
+                            .download_lob_file_async(transaction, clob)
+                            .await?;
+                        job.convert(
+                            "LargeObjectCache",
+                            Box::new(Self::create_large_object_cache),
+                        )
+                    } else {
+                        Job::returns("LargeObjectCache", TgLargeObjectCache::new(None))
+                    },
+                )
+            })
+            .await?;
 
         trace!("{} end", FUNCTION_NAME);
         Ok(job)
@@ -1829,8 +2814,16 @@ impl SqlClient {
         const FUNCTION_NAME: &str = "read_blob()";
         trace!("{} start", FUNCTION_NAME);
 
-        let lob_client = self.get_lob_client().await?;
-        let buf = lob_client.download_lob(transaction, blob, timeout).await?;
+        let context = self.interceptors.before(SqlOperation::ReadLob, |context| {
+            context.set_transaction(transaction);
+        })?;
+        let buf = self
+            .interceptors
+            .intercept(context, async {
+                let lob_client = self.get_lob_client().await?;
+                lob_client.download_lob(transaction, blob, timeout).await
+            })
+            .await?;
 
         trace!("{} end", FUNCTION_NAME);
         Ok(buf)
@@ -1847,8 +2840,16 @@ impl SqlClient {
         const FUNCTION_NAME: &str = "read_blob_async()";
         trace!("{} start", FUNCTION_NAME);
 
-        let lob_client = self.get_lob_client().await?;
-        let job = lob_client.download_lob_async(transaction, blob).await?;
+        let context = self.interceptors.before(SqlOperation::ReadLob, |context| {
+            context.set_transaction(transaction);
+        })?;
+        let job = self
+            .interceptors
+            .intercept_job(context, async {
+                let lob_client = self.get_lob_client().await?;
+                lob_client.download_lob_async(transaction, blob).await
+            })
+            .await?;
 
         trace!("{} end", FUNCTION_NAME);
         Ok(job)
@@ -1891,10 +2892,17 @@ impl SqlClient {
         const FUNCTION_NAME: &str = "read_clob()";
         trace!("{} start", FUNCTION_NAME);
 
-        let lob_client = self.get_lob_client().await?;
-        let buf = lob_client.download_lob(transaction, clob, timeout).await?;
-        let buf =
-            String::from_utf8(buf).map_err(|e| io_error!("CLOB data is not valid UTF-8: {}", e))?;
+        let context = self.interceptors.before(SqlOperation::ReadLob, |context| {
+            context.set_transaction(transaction);
+        })?;
+        let buf = self
+            .interceptors
+            .intercept(context, async {
+                let lob_client = self.get_lob_client().await?;
+                let buf = lob_client.download_lob(transaction, clob, timeout).await?;
+                String::from_utf8(buf).map_err(|e| io_error!("CLOB data is not valid UTF-8: {}", e))
+            })
+            .await?;
 
         trace!("{} end", FUNCTION_NAME);
         Ok(buf)
@@ -1911,14 +2919,23 @@ impl SqlClient {
         const FUNCTION_NAME: &str = "read_clob_async()";
         trace!("{} start", FUNCTION_NAME);
 
-        let lob_client = self.get_lob_client().await?;
-        let job = lob_client.download_lob_async(transaction, clob).await?;
-        let job = job.convert(
-            "CLOB",
-            Box::new(|buf| {
-                String::from_utf8(buf).map_err(|e| io_error!("CLOB data is not valid UTF-8: {}", e))
-            }),
-        );
+        let context = self.interceptors.before(SqlOperation::ReadLob, |context| {
+            context.set_transaction(transaction);
+        })?;
+        let job = self
+            .interceptors
+            .intercept_job(context, async {
+                let lob_client = self.get_lob_client().await?;
+                let job = lob_client.download_lob_async(transaction, clob).await?;
+                Ok(job.convert(
+                    "CLOB",
+                    Box::new(|buf| {
+                        String::from_utf8(buf)
+                            .map_err(|e| io_error!("CLOB data is not valid UTF-8: {}", e))
+                    }),
+                ))
+            })
+            .await?;
 
         trace!("{} end", FUNCTION_NAME);
         Ok(job)
@@ -1959,7 +2976,15 @@ impl SqlClient {
         const FUNCTION_NAME: &str = "copy_blob_to()";
         trace!("{} start", FUNCTION_NAME);
 
-        self.copy_lob_to(transaction, blob, destination.as_ref(), timeout)
+        let context = self.interceptors.before(SqlOperation::CopyLob, |context| {
+            context.set_transaction(transaction);
+            context.set_path(destination.as_ref());
+        })?;
+        self.interceptors
+            .intercept(context, async {
+                self.copy_lob_to(transaction, blob, destination.as_ref(), timeout)
+                    .await
+            })
             .await?;
 
         trace!("{} end", FUNCTION_NAME);
@@ -1976,8 +3001,16 @@ impl SqlClient {
         const FUNCTION_NAME: &str = "copy_blob_to_async()";
         trace!("{} start", FUNCTION_NAME);
 
+        let context = self.interceptors.before(SqlOperation::CopyLob, |context| {
+            context.set_transaction(transaction);
+            context.set_path(destination.as_ref());
+        })?;
         let job = self
-            .copy_lob_to_async(transaction, blob, destination.as_ref())
+            .interceptors
+            .intercept_job(context, async {
+                self.copy_lob_to_async(transaction, blob, destination.as_ref())
+                    .await
+            })
             .await?;
 
         trace!("{} end", FUNCTION_NAME);
@@ -2019,7 +3052,15 @@ impl SqlClient {
         const FUNCTION_NAME: &str = "copy_clob_to()";
         trace!("{} start", FUNCTION_NAME);
 
-        self.copy_lob_to(transaction, clob, destination.as_ref(), timeout)
+        let context = self.interceptors.before(SqlOperation::CopyLob, |context| {
+            context.set_transaction(transaction);
+            context.set_path(destination.as_ref());
+        })?;
+        self.interceptors
+            .intercept(context, async {
+                self.copy_lob_to(transaction, clob, destination.as_ref(), timeout)
+                    .await
+            })
             .await?;
 
         trace!("{} end", FUNCTION_NAME);
@@ -2036,8 +3077,16 @@ impl SqlClient {
         const FUNCTION_NAME: &str = "copy_clob_to_async()";
         trace!("{} start", FUNCTION_NAME);
 
+        let context = self.interceptors.before(SqlOperation::CopyLob, |context| {
+            context.set_transaction(transaction);
+            context.set_path(destination.as_ref());
+        })?;
         let job = self
-            .copy_lob_to_async(transaction, clob, destination.as_ref())
+            .interceptors
+            .intercept_job(context, async {
+                self.copy_lob_to_async(transaction, clob, destination.as_ref())
+                    .await
+            })
             .await?;
 
         trace!("{} end", FUNCTION_NAME);
@@ -2163,11 +3212,19 @@ impl SqlClient {
         const FUNCTION_NAME: &str = "commit()";
         trace!("{} start", FUNCTION_NAME);
 
-        let tx_handle = transaction.transaction_handle()?;
-
-        let command = Self::commit_command(tx_handle, commit_option);
-        let (slot_handle, response) = self.send_and_pull_response(command, None, timeout).await?;
-        transaction_commit_processor(slot_handle, response)?;
+        let context = self.interceptors.before(SqlOperation::Commit, |context| {
+            context.set_transaction(transaction);
+        })?;
+        self.interceptors
+            .intercept(context, async {
+                let tx_handle = transaction.transaction_handle()?;
+
+                let command = Self::commit_command(tx_handle, commit_option);
+                let (slot_handle, response) =
+                    self.send_and_pull_response(command, None, timeout).await?;
+                transaction_commit_processor(slot_handle, response)
+            })
+            .await?;
 
         trace!("{} end", FUNCTION_NAME);
         Ok(())
@@ -2182,22 +3239,111 @@ impl SqlClient {
         const FUNCTION_NAME: &str = "commit_async()";
         trace!("{} start", FUNCTION_NAME);
 
-        let tx_handle = transaction.transaction_handle()?;
-
-        let command = Self::commit_command(tx_handle, commit_option);
+        let context = self.interceptors.before(SqlOperation::Commit, |context| {
+            context.set_transaction(transaction);
+        })?;
         let job = self
-            .send_and_pull_async(
-                "Commit",
-                command,
-                None,
-                Box::new(transaction_commit_processor),
-            )
+            .interceptors
+            .intercept_job(context, async {
+                let tx_handle = transaction.transaction_handle()?;
+
+                let command = Self::commit_command(tx_handle, commit_option);
+                self.send_and_pull_async(
+                    "Commit",
+                    command,
+                    None,
+                    Box::new(transaction_commit_processor),
+                )
+                .await
+            })
             .await?;
 
         trace!("{} end", FUNCTION_NAME);
         Ok(job)
     }
 
+    /// Request commit to the SQL service, and returns a handle which notifies each durability stage.
+    ///
+    /// The commit request is sent before this method returns,
+    /// and the commit response is received on a background task.
+    /// Use [CommitHandle::committed()] to wait for the commit response (the same timing as [Self::commit()]),
+    /// and [CommitHandle::available()], [CommitHandle::stored()] etc. to wait for each stage.
+    ///
+    /// # Examples
+    /// ```
+    /// use tsubakuro_rust_core::prelude::*;
+    ///
+    /// async fn example(client: &SqlClient, transaction: &Transaction) -> Result<(), TgError> {
+    ///     let commit_option = CommitOption::from(CommitType::Available);
+    ///     let handle = client.commit_with_handle(transaction, &commit_option).await?;
+    ///
+    ///     handle.available().await?;
+    ///     println!("available");
+    ///
+    ///     handle.stored().await?;
+    ///     println!("stored");
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// since 0.11.0
+    pub async fn commit_with_handle(
+        &self,
+        transaction: &Transaction,
+        commit_option: &CommitOption,
+    ) -> Result<CommitHandle, TgError> {
+        const FUNCTION_NAME: &str = "commit_with_handle()";
+        trace!("{} start", FUNCTION_NAME);
+
+        let context = self.interceptors.before(SqlOperation::Commit, |context| {
+            context.set_transaction(transaction);
+        })?;
+        let wire = self.wire();
+        let sent = async {
+            let tx_handle = *transaction.transaction_handle()?;
+
+            let command = Self::commit_command(&tx_handle, commit_option);
+            self.check_draining(&command)?;
+            let request = Self::new_request(command);
+            let slot_handle = wire.send_only(SERVICE_ID_SQL, request, None).await?;
+            Ok((tx_handle, slot_handle))
+        };
+        let (tx_handle, slot_handle) = match sent.await {
+            Ok(sent) => sent,
+            Err(e) => {
+                let result = Err(e);
+                self.interceptors.after(context.as_ref(), &result);
+                return result;
+            }
+        };
+
+        let handle = CommitHandle::start(
+            wire,
+            tx_handle,
+            slot_handle,
+            commit_option.commit_type(),
+            self.default_timeout,
+            self.default_timeout,
+            TransactionStatusWatchOption::default(),
+            self.interceptors.clone(),
+            context,
+        );
+
+        trace!("{} end", FUNCTION_NAME);
+        Ok(handle)
+    }
+
+    pub(crate) async fn commit_response(
+        wire: &Wire,
+        slot_handle: Arc<SlotEntryHandle>,
+        timeout: Duration,
+    ) -> Result<(), TgError> {
+        let timeout = Timeout::new(timeout);
+        let response = wire.pull_response(&slot_handle, &timeout).await?;
+        transaction_commit_processor(slot_handle, response)
+    }
+
     fn commit_command(
         transaction_handle: &ProtoTransaction,
         commit_option: &CommitOption,
@@ -2237,11 +3383,21 @@ impl SqlClient {
         const FUNCTION_NAME: &str = "rollback()";
         trace!("{} start", FUNCTION_NAME);
 
-        let tx_handle = transaction.transaction_handle()?;
-
-        let command = Self::rollback_command(tx_handle);
-        let (slot_handle, response) = self.send_and_pull_response(command, None, timeout).await?;
-        transaction_rollback_processor(slot_handle, response)?;
+        let context = self
+            .interceptors
+            .before(SqlOperation::Rollback, |context| {
+                context.set_transaction(transaction);
+            })?;
+        self.interceptors
+            .intercept(context, async {
+                let tx_handle = transaction.transaction_handle()?;
+
+                let command = Self::rollback_command(tx_handle);
+                let (slot_handle, response) =
+                    self.send_and_pull_response(command, None, timeout).await?;
+                transaction_rollback_processor(slot_handle, response)
+            })
+            .await?;
 
         trace!("{} end", FUNCTION_NAME);
         Ok(())
@@ -2252,16 +3408,25 @@ impl SqlClient {
         const FUNCTION_NAME: &str = "rollback_async()";
         trace!("{} start", FUNCTION_NAME);
 
-        let tx_handle = transaction.transaction_handle()?;
-
-        let command = Self::rollback_command(tx_handle);
+        let context = self
+            .interceptors
+            .before(SqlOperation::Rollback, |context| {
+                context.set_transaction(transaction);
+            })?;
         let job = self
-            .send_and_pull_async(
-                "Rollback",
-                command,
-                None,
-                Box::new(transaction_rollback_processor),
-            )
+            .interceptors
+            .intercept_job(context, async {
+                let tx_handle = transaction.transaction_handle()?;
+
+                let command = Self::rollback_command(tx_handle);
+                self.send_and_pull_async(
+                    "Rollback",
+                    command,
+                    None,
+                    Box::new(transaction_rollback_processor),
+                )
+                .await
+            })
             .await?;
 
         trace!("{} end", FUNCTION_NAME);
@@ -2305,6 +3470,30 @@ impl SqlClient {
         Ok(())
     }
 
+    pub(crate) async fn rollback_by_handle(
+        wire: &Wire,
+        transaction_handle: &ProtoTransaction,
+        timeout: Duration,
+    ) -> Result<(), TgError> {
+        let request = Self::new_request(Self::rollback_command(transaction_handle));
+        let (slot_handle, response) = wire
+            .send_and_pull_response(SERVICE_ID_SQL, request, None, timeout)
+            .await?;
+        transaction_rollback_processor(slot_handle, response)
+    }
+
+    pub(crate) async fn dispose_transaction_by_handle(
+        wire: &Wire,
+        transaction_handle: &ProtoTransaction,
+        timeout: Duration,
+    ) -> Result<(), TgError> {
+        let request = Self::new_request(Self::dispose_transaction_command(transaction_handle));
+        let (_, response) = wire
+            .send_and_pull_response(SERVICE_ID_SQL, request, None, timeout)
+            .await?;
+        transaction_dispose_processor(response)
+    }
+
     fn dispose_transaction_command(transaction_handle: &ProtoTransaction) -> SqlCommand {
         let request = crate::jogasaki::proto::sql::request::DisposeTransaction {
             transaction_handle: Some(*transaction_handle),
@@ -2433,7 +3622,32 @@ impl SqlClient {
         self.session.wire()
     }
 
+    /// Rejects a request which starts new work while the session is draining.
+    ///
+    /// Requests which close resources (e.g. rollback, dispose) are allowed.
+    fn check_draining(&self, command: &SqlCommand) -> Result<(), TgError> {
+        let new_work = matches!(
+            command,
+            SqlCommand::Begin(_)
+                | SqlCommand::Prepare(_)
+                | SqlCommand::ExecuteStatement(_)
+                | SqlCommand::ExecuteQuery(_)
+                | SqlCommand::ExecutePreparedStatement(_)
+                | SqlCommand::ExecutePreparedQuery(_)
+                | SqlCommand::Batch(_)
+                | SqlCommand::ExecuteDump(_)
+                | SqlCommand::ExecuteDumpByText(_)
+                | SqlCommand::ExecuteLoad(_)
+                | SqlCommand::Commit(_)
+        );
+        if new_work && self.session.is_draining() {
+            return Err(client_error!("session is draining"));
+        }
+        Ok(())
+    }
+
     async fn send_only(&self, command: SqlCommand) -> Result<Arc<SlotEntryHandle>, TgError> {
+        self.check_draining(&command)?;
         let request = Self::new_request(command);
         self.wire().send_only(SERVICE_ID_SQL, request, None).await
     }
@@ -2444,6 +3658,7 @@ impl SqlClient {
         lobs: Option<Vec<BlobInfo>>,
         timeout: Duration,
     ) -> Result<(Arc<SlotEntryHandle>, WireResponse), TgError> {
+        self.check_draining(&command)?;
         let request = Self::new_request(command);
         self.wire()
             .send_and_pull_response(SERVICE_ID_SQL, request, lobs, timeout)
@@ -2459,6 +3674,7 @@ impl SqlClient {
             dyn Fn(Arc<SlotEntryHandle>, WireResponse) -> Result<T, TgError> + Send + Sync,
         >,
     ) -> Result<Job<T>, TgError> {
+        self.check_draining(&command)?;
         let request = Self::new_request(command);
         self.wire()
             .send_and_pull_async(
@@ -2585,4 +3801,148 @@ mod test {
         assert_send::<SqlClient>();
         assert_sync::<SqlClient>();
     }
+
+    #[tokio::test]
+    async fn commit_with_handle_sends_request() {
+        use std::io::Read;
+
+        use crate::{
+            prelude::{CommitType, ConnectionOption, CredentialSource, Endpoint},
+            session::{
+                tcp::{link::TcpLink, wire::TcpWire},
+                wire::DelegateWire,
+            },
+        };
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let endpoint = Endpoint::Tcp("127.0.0.1".to_string(), port);
+
+        let connection_option = ConnectionOption::new();
+        let link = TcpLink::connect(&connection_option, &endpoint)
+            .await
+            .unwrap();
+        let (mut server, _) = listener.accept().unwrap();
+        server
+            .set_read_timeout(Some(Duration::from_secs(1)))
+            .unwrap();
+
+        let wire = Wire::new(DelegateWire::Tcp(Arc::new(TcpWire::new(link))));
+        let session = Session::new(
+            wire,
+            endpoint,
+            CredentialSource::None,
+            &connection_option,
+            Duration::from_secs(1),
+        );
+        let client: SqlClient = session.make_client();
+        let transaction = Transaction::new(
+            session.clone(),
+            ProtoTransaction {
+                handle: 1,
+                ..Default::default()
+            },
+            "tx1".to_string(),
+            Duration::ZERO,
+        );
+
+        let commit_option = CommitOption::from(CommitType::Stored);
+        let _handle = client
+            .commit_with_handle(&transaction, &commit_option)
+            .await
+            .unwrap();
+
+        // the background tasks have not run yet on this (current thread) runtime
+        let mut buffer = [0; 1024];
+        let size = server.read(&mut buffer).unwrap();
+        assert!(size > 0);
+    }
+
+    #[tokio::test]
+    async fn intercept_prepared_statement_sql() {
+        use std::sync::Mutex;
+
+        use prost::Message;
+
+        use crate::{
+            jogasaki::proto::sql::{
+                common::PreparedStatement as ProtoPreparedStatement,
+                response::{
+                    prepare, response::Response as SqlResponseType, Prepare as PrepareResponse,
+                    Response as SqlResponse,
+                },
+            },
+            prelude::{ConnectionOption, SqlInterceptContext, SqlInterceptor, SqlOperation},
+            session::tcp::test_server::TestServer,
+        };
+
+        #[derive(Default)]
+        struct Recorder {
+            sql: Mutex<Vec<(SqlOperation, Option<String>)>>,
+        }
+
+        impl SqlInterceptor for Recorder {
+            fn before(&self, context: &mut SqlInterceptContext) -> Result<(), TgError> {
+                self.sql
+                    .lock()
+                    .unwrap()
+                    .push((context.operation(), context.sql().cloned()));
+                match context.operation() {
+                    SqlOperation::Prepare => Ok(()),
+                    _ => Err(client_error!("veto")),
+                }
+            }
+        }
+
+        let server = TestServer::start().await;
+        let (session, mut connection) = server.connect(&ConnectionOption::new()).await;
+        let recorder = Arc::new(Recorder::default());
+        let mut client: SqlClient = session.make_client();
+        client.add_interceptor(recorder.clone());
+
+        let sql = "select * from test where pk = :pk";
+        let (prepared_statement, _) = tokio::join!(client.prepare(sql, vec![]), async {
+            let (slot, _) = connection.read_sql_request().await;
+            let response = SqlResponse {
+                response: Some(SqlResponseType::Prepare(PrepareResponse {
+                    result: Some(prepare::Result::PreparedStatementHandle(
+                        ProtoPreparedStatement {
+                            handle: 1,
+                            has_result_records: true,
+                        },
+                    )),
+                })),
+            };
+            connection
+                .write_response(slot, &response.encode_length_delimited_to_vec())
+                .await;
+        });
+        let prepared_statement = prepared_statement.unwrap();
+        assert_eq!(sql, prepared_statement.sql());
+
+        let transaction = Transaction::new(
+            session.clone(),
+            ProtoTransaction {
+                handle: 1,
+                ..Default::default()
+            },
+            "tx1".to_string(),
+            Duration::ZERO,
+        );
+        let result = client
+            .prepared_execute(&transaction, &prepared_statement, vec![])
+            .await;
+        assert!(result.is_err());
+        let result = client
+            .prepared_query(&transaction, &prepared_statement, vec![])
+            .await;
+        assert!(result.is_err());
+
+        let expected = vec![
+            (SqlOperation::Prepare, Some(sql.to_string())),
+            (SqlOperation::PreparedExecute, Some(sql.to_string())),
+            (SqlOperation::PreparedQuery, Some(sql.to_string())),
+        ];
+        assert_eq!(expected, *recorder.sql.lock().unwrap());
+    }
 }
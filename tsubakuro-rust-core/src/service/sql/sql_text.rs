@@ -0,0 +1,260 @@
+//! Utilities for SQL text.
+
+use std::{iter::Peekable, str::CharIndices};
+
+/// The prefix of placeholder names generated from positional markers (`?`).
+pub(crate) const POSITIONAL_PLACEHOLDER_PREFIX: &str = "p";
+
+/// Iterator over the characters of SQL text which are outside of
+/// string literals, quoted identifiers and comments.
+///
+/// Yields byte index and character.
+///
+/// # Examples
+/// ```
+/// use tsubakuro_rust_core::prelude::*;
+///
+/// let code: String = SqlCodeChars::new("select 'a;b' /* c */ from t")
+///     .map(|(_, c)| c)
+///     .collect();
+/// assert_eq!("select   from t", code);
+/// ```
+///
+/// since 0.11.0
+pub struct SqlCodeChars<'a> {
+    chars: Peekable<CharIndices<'a>>,
+    unterminated: bool,
+}
+
+impl<'a> SqlCodeChars<'a> {
+    /// Creates a new instance.
+    pub fn new(sql: &'a str) -> SqlCodeChars<'a> {
+        SqlCodeChars {
+            chars: sql.char_indices().peekable(),
+            unterminated: false,
+        }
+    }
+
+    /// Check if the text ends inside a string literal, a quoted identifier or a block comment.
+    ///
+    /// Valid after the iteration reaches the end.
+    pub fn is_unterminated(&self) -> bool {
+        self.unterminated
+    }
+
+    fn skip_quoted(&mut self, quote: char) {
+        while let Some((_, c)) = self.chars.next() {
+            if c == quote {
+                // doubled quote is an escaped quote
+                match self.chars.peek() {
+                    Some((_, c)) if *c == quote => {
+                        self.chars.next();
+                    }
+                    _ => return,
+                }
+            }
+        }
+        self.unterminated = true;
+    }
+
+    fn skip_line_comment(&mut self) {
+        for (_, c) in self.chars.by_ref() {
+            if c == '\n' {
+                return;
+            }
+        }
+    }
+
+    fn skip_block_comment(&mut self) {
+        while let Some((_, c)) = self.chars.next() {
+            if c == '*' {
+                if let Some((_, '/')) = self.chars.peek() {
+                    self.chars.next();
+                    return;
+                }
+            }
+        }
+        self.unterminated = true;
+    }
+}
+
+impl Iterator for SqlCodeChars<'_> {
+    type Item = (usize, char);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (i, c) = self.chars.next()?;
+            match c {
+                '\'' | '"' => self.skip_quoted(c),
+                '-' if matches!(self.chars.peek(), Some((_, '-'))) => {
+                    self.chars.next();
+                    self.skip_line_comment();
+                }
+                '/' if matches!(self.chars.peek(), Some((_, '*'))) => {
+                    self.chars.next();
+                    self.skip_block_comment();
+                }
+                _ => return Some((i, c)),
+            }
+        }
+    }
+}
+
+/// Replaces positional markers (`?`) with named placeholders (`:p1`, `:p2`, ...).
+///
+/// Markers in string literals, quoted identifiers and comments are not replaced.
+/// Returns the rewritten SQL and the number of replaced markers.
+///
+/// # Examples
+/// ```
+/// use tsubakuro_rust_core::prelude::*;
+///
+/// let (sql, count) = replace_positional_placeholders("select * from t where a = ? and b = '?'");
+/// assert_eq!("select * from t where a = :p1 and b = '?'", sql);
+/// assert_eq!(1, count);
+/// assert_eq!("p1", positional_placeholder_name(1));
+/// ```
+///
+/// since 0.11.0
+pub fn replace_positional_placeholders(sql: &str) -> (String, usize) {
+    let mut result = String::with_capacity(sql.len() + 8);
+    let mut count = 0;
+    let mut last = 0;
+    for (i, c) in SqlCodeChars::new(sql) {
+        if c == '?' {
+            count += 1;
+            result.push_str(&sql[last..i]);
+            result.push(':');
+            result.push_str(&positional_placeholder_name(count));
+            last = i + 1;
+        }
+    }
+    result.push_str(&sql[last..]);
+
+    (result, count)
+}
+
+/// Splits SQL text into statements by `;` outside of string literals, quoted identifiers and comments.
+///
+/// The statements are trimmed, and those without code (only whitespace and comments) are omitted.
+///
+/// # Examples
+/// ```
+/// use tsubakuro_rust_core::prelude::*;
+///
+/// let statements = split_statements("insert into t values ('a;b'); -- comment\nselect * from t");
+/// assert_eq!(
+///     vec!["insert into t values ('a;b')", "-- comment\nselect * from t"],
+///     statements
+/// );
+/// ```
+///
+/// since 0.11.0
+pub fn split_statements(sql: &str) -> Vec<&str> {
+    let mut statements = Vec::new();
+    let mut start = 0;
+    let mut has_code = false;
+    for (i, c) in SqlCodeChars::new(sql) {
+        if c == ';' {
+            if has_code {
+                statements.push(sql[start..i].trim());
+            }
+            start = i + 1;
+            has_code = false;
+        } else if !c.is_whitespace() {
+            has_code = true;
+        }
+    }
+    if has_code {
+        statements.push(sql[start..].trim());
+    }
+    statements
+}
+
+/// Returns the placeholder name of the positional marker.
+///
+/// `position` is 1-origin.
+///
+/// since 0.11.0
+pub fn positional_placeholder_name(position: usize) -> String {
+    format!("{POSITIONAL_PLACEHOLDER_PREFIX}{position}")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn replace_positional() {
+        let (sql, count) =
+            replace_positional_placeholders("select * from tb where a = ? and b = ?");
+        assert_eq!("select * from tb where a = :p1 and b = :p2", sql);
+        assert_eq!(2, count);
+    }
+
+    #[test]
+    fn replace_positional_nothing() {
+        let (sql, count) = replace_positional_placeholders("select * from tb where a = :a");
+        assert_eq!("select * from tb where a = :a", sql);
+        assert_eq!(0, count);
+    }
+
+    #[test]
+    fn replace_positional_skip_literal() {
+        let (sql, count) = replace_positional_placeholders(
+            r#"select '?', 'it''s ?', "a?""b" from tb where a = ?"#,
+        );
+        assert_eq!(
+            r#"select '?', 'it''s ?', "a?""b" from tb where a = :p1"#,
+            sql
+        );
+        assert_eq!(1, count);
+    }
+
+    #[test]
+    fn replace_positional_skip_comment() {
+        let (sql, count) =
+            replace_positional_placeholders("select /* ? */ a -- ?\nfrom tb where a = ?/**/");
+        assert_eq!("select /* ? */ a -- ?\nfrom tb where a = :p1/**/", sql);
+        assert_eq!(1, count);
+    }
+
+    #[test]
+    fn split() {
+        let sql = "create table a (x int); -- first;\ninsert into a values (1) ;\n\n/* ; */ ;";
+        assert_eq!(
+            vec![
+                "create table a (x int)",
+                "-- first;\ninsert into a values (1)"
+            ],
+            split_statements(sql)
+        );
+    }
+
+    #[test]
+    fn split_literal() {
+        let sql = "insert into a values ('a;b', \"c;\"\"d\")\nselect 1";
+        assert_eq!(vec![sql], split_statements(sql));
+        assert!(split_statements("  ;\n-- comment").is_empty());
+    }
+
+    #[test]
+    fn code_chars_unterminated() {
+        let is_unterminated = |sql: &str| {
+            let mut chars = SqlCodeChars::new(sql);
+            chars.by_ref().for_each(drop);
+            chars.is_unterminated()
+        };
+        assert!(!is_unterminated("select 'a' /* b */ -- c"));
+        assert!(is_unterminated("select 'a"));
+        assert!(is_unterminated("select \"a"));
+        assert!(is_unterminated("select /* a"));
+    }
+
+    #[test]
+    fn code_chars_multibyte() {
+        let sql = "select 'あ?', ? from 表";
+        let chars: String = SqlCodeChars::new(sql).map(|(_, c)| c).collect();
+        assert_eq!("select , ? from 表", chars);
+    }
+}
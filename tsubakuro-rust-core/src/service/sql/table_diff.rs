@@ -0,0 +1,409 @@
+use crate::{
+    client_error,
+    error::TgError,
+    prelude::{SqlColumn, TableMetadata},
+};
+
+use super::table_metadata::{column_definition, quote_identifier};
+
+/// Change of a table definition.
+///
+/// since 0.11.0
+#[derive(Debug, Clone, PartialEq)]
+pub enum TableChange {
+    /// The table description is changed.
+    AlterTableDescription(Option<String>),
+    /// The column is added.
+    AddColumn(SqlColumn),
+    /// The column is dropped.
+    DropColumn(String),
+    /// The column type is changed.
+    AlterColumnType {
+        column_name: String,
+        old_type: Option<String>,
+        new_type: Option<String>,
+    },
+    /// The nullability of the column is changed.
+    AlterColumnNullable { column_name: String, nullable: bool },
+    /// The column description is changed.
+    AlterColumnDescription {
+        column_name: String,
+        description: Option<String>,
+    },
+    /// The primary key is changed.
+    AlterPrimaryKey {
+        old_keys: Vec<String>,
+        new_keys: Vec<String>,
+    },
+}
+
+/// Differences between two table definitions.
+///
+/// # Examples
+/// ```
+/// use tsubakuro_rust_core::prelude::*;
+///
+/// async fn example(client: &SqlClient, other: &SqlClient) -> Result<(), TgError> {
+///     let old = other.get_table_metadata("tb1").await?;
+///     let new = client.get_table_metadata("tb1").await?;
+///
+///     let diff = old.diff(&new);
+///     for sql in diff.to_alter_sql()? {
+///         println!("{sql};");
+///     }
+///     Ok(())
+/// }
+/// ```
+///
+/// since 0.11.0
+#[derive(Debug, Clone)]
+pub struct TableDiff {
+    table_name: String,
+    changes: Vec<TableChange>,
+}
+
+impl TableDiff {
+    /// Compares the table definitions.
+    ///
+    /// The changes turn `old` into `new`. Columns are matched by name, and the column order is not compared.
+    ///
+    /// since 0.11.0
+    pub fn new(old: &TableMetadata, new: &TableMetadata) -> TableDiff {
+        let mut changes = Vec::new();
+
+        if old.description() != new.description() {
+            changes.push(TableChange::AlterTableDescription(
+                new.description().cloned(),
+            ));
+        }
+
+        for old_column in old.columns() {
+            if find_column(new, old_column.name()).is_none() {
+                changes.push(TableChange::DropColumn(old_column.name().clone()));
+            }
+        }
+        for new_column in new.columns() {
+            match find_column(old, new_column.name()) {
+                Some(old_column) => diff_column(old_column, new_column, &mut changes),
+                None => changes.push(TableChange::AddColumn(new_column.clone())),
+            }
+        }
+
+        if old.primary_keys() != new.primary_keys() {
+            changes.push(TableChange::AlterPrimaryKey {
+                old_keys: old.primary_keys().clone(),
+                new_keys: new.primary_keys().clone(),
+            });
+        }
+
+        TableDiff {
+            table_name: new.qualified_table_name(),
+            changes,
+        }
+    }
+
+    /// Returns the table name.
+    ///
+    /// since 0.11.0
+    pub fn table_name(&self) -> &String {
+        &self.table_name
+    }
+
+    /// Returns the changes.
+    ///
+    /// since 0.11.0
+    pub fn changes(&self) -> &Vec<TableChange> {
+        &self.changes
+    }
+
+    /// Whether the table definitions are the same.
+    ///
+    /// since 0.11.0
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Returns the changes as `ALTER TABLE` (and `COMMENT ON`) statements.
+    ///
+    /// The statements describe the changes in standard SQL; they may not be executable on Tsurugi.
+    /// Every identifier is delimited by `"`.
+    ///
+    /// Returns an error if the type of an added or altered column cannot be written in SQL.
+    ///
+    /// since 0.11.0
+    pub fn to_alter_sql(&self) -> Result<Vec<String>, TgError> {
+        let table_name = &self.table_name;
+        let mut statements = Vec::with_capacity(self.changes.len());
+        for change in &self.changes {
+            match change {
+                TableChange::AlterTableDescription(description) => statements.push(format!(
+                    "COMMENT ON TABLE {} IS {}",
+                    table_name,
+                    string_literal(description)
+                )),
+                TableChange::AddColumn(column) => {
+                    let definition = column_definition(column)?;
+                    statements.push(format!(
+                        "ALTER TABLE {} ADD COLUMN {}",
+                        table_name, definition
+                    ));
+                }
+                TableChange::DropColumn(column_name) => statements.push(format!(
+                    "ALTER TABLE {} DROP COLUMN {}",
+                    table_name,
+                    quote_identifier(column_name)
+                )),
+                TableChange::AlterColumnType {
+                    column_name,
+                    new_type,
+                    ..
+                } => {
+                    let new_type = new_type.as_deref().ok_or_else(|| {
+                        client_error!(format!("unsupported column type. column={column_name}"))
+                    })?;
+                    statements.push(format!(
+                        "ALTER TABLE {} ALTER COLUMN {} SET DATA TYPE {}",
+                        table_name,
+                        quote_identifier(column_name),
+                        new_type
+                    ));
+                }
+                TableChange::AlterColumnNullable {
+                    column_name,
+                    nullable,
+                } => statements.push(format!(
+                    "ALTER TABLE {} ALTER COLUMN {} {} NOT NULL",
+                    table_name,
+                    quote_identifier(column_name),
+                    if *nullable { "DROP" } else { "SET" }
+                )),
+                TableChange::AlterColumnDescription {
+                    column_name,
+                    description,
+                } => statements.push(format!(
+                    "COMMENT ON COLUMN {}.{} IS {}",
+                    table_name,
+                    quote_identifier(column_name),
+                    string_literal(description)
+                )),
+                TableChange::AlterPrimaryKey { old_keys, new_keys } => {
+                    if !old_keys.is_empty() {
+                        statements.push(format!("ALTER TABLE {} DROP PRIMARY KEY", table_name));
+                    }
+                    if !new_keys.is_empty() {
+                        let keys: Vec<String> =
+                            new_keys.iter().map(|key| quote_identifier(key)).collect();
+                        statements.push(format!(
+                            "ALTER TABLE {} ADD PRIMARY KEY ({})",
+                            table_name,
+                            keys.join(", ")
+                        ));
+                    }
+                }
+            }
+        }
+        Ok(statements)
+    }
+}
+
+impl TableMetadata {
+    /// Compares with the other table definition.
+    ///
+    /// The changes turn this table into `other`. See [`TableDiff::new`].
+    ///
+    /// since 0.11.0
+    pub fn diff(&self, other: &TableMetadata) -> TableDiff {
+        TableDiff::new(self, other)
+    }
+}
+
+fn find_column<'a>(table_metadata: &'a TableMetadata, name: &str) -> Option<&'a SqlColumn> {
+    table_metadata
+        .columns()
+        .iter()
+        .find(|column| column.name() == name)
+}
+
+fn diff_column(old: &SqlColumn, new: &SqlColumn, changes: &mut Vec<TableChange>) {
+    let column_name = new.name();
+
+    let old_type = old.sql_type();
+    let new_type = new.sql_type();
+    if old_type != new_type {
+        changes.push(TableChange::AlterColumnType {
+            column_name: column_name.clone(),
+            old_type,
+            new_type,
+        });
+    }
+
+    let old_nullable = old.nullable().unwrap_or(true);
+    let new_nullable = new.nullable().unwrap_or(true);
+    if old_nullable != new_nullable {
+        changes.push(TableChange::AlterColumnNullable {
+            column_name: column_name.clone(),
+            nullable: new_nullable,
+        });
+    }
+
+    if old.description() != new.description() {
+        changes.push(TableChange::AlterColumnDescription {
+            column_name: column_name.clone(),
+            description: new.description().cloned(),
+        });
+    }
+}
+
+fn string_literal(value: &Option<String>) -> String {
+    match value {
+        Some(value) => format!("'{}'", value.replace('\'', "''")),
+        None => "NULL".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::jogasaki::proto::sql::{
+        common::column::{DescriptionOpt, NullableOpt, TypeInfo},
+        common::AtomType,
+        response::describe_table::Success,
+    };
+
+    fn column(name: &str, atom_type: AtomType, nullable: bool) -> SqlColumn {
+        SqlColumn {
+            name: name.to_string(),
+            type_info: Some(TypeInfo::AtomType(atom_type.into())),
+            nullable_opt: Some(NullableOpt::Nullable(nullable)),
+            ..Default::default()
+        }
+    }
+
+    fn table(columns: Vec<SqlColumn>, primary_keys: &[&str]) -> TableMetadata {
+        let describe_table = Success {
+            table_name: "example".to_string(),
+            columns,
+            primary_key: primary_keys.iter().map(|key| key.to_string()).collect(),
+            ..Default::default()
+        };
+        TableMetadata::new(describe_table)
+    }
+
+    #[test]
+    fn same() {
+        let old = table(vec![column("foo", AtomType::Int4, false)], &["foo"]);
+        let new = table(vec![column("foo", AtomType::Int4, false)], &["foo"]);
+
+        let diff = old.diff(&new);
+        assert!(diff.is_empty());
+        assert!(diff.to_alter_sql().unwrap().is_empty());
+    }
+
+    #[test]
+    fn diff() {
+        let old = table(
+            vec![
+                column("foo", AtomType::Int4, false),
+                column("bar", AtomType::Int4, true),
+                column("zzz", AtomType::Date, true),
+            ],
+            &["foo"],
+        );
+        let mut description = column("baz", AtomType::Character, true);
+        description.description_opt = Some(DescriptionOpt::Description("it's baz".to_string()));
+        let new = table(
+            vec![
+                column("foo", AtomType::Int4, false),
+                column("bar", AtomType::Int8, false),
+                description,
+            ],
+            &["foo", "bar"],
+        );
+
+        let diff = TableDiff::new(&old, &new);
+        assert_eq!("\"example\"", diff.table_name());
+        assert_eq!(
+            &vec![
+                TableChange::DropColumn("zzz".to_string()),
+                TableChange::AlterColumnType {
+                    column_name: "bar".to_string(),
+                    old_type: Some("INT".to_string()),
+                    new_type: Some("BIGINT".to_string()),
+                },
+                TableChange::AlterColumnNullable {
+                    column_name: "bar".to_string(),
+                    nullable: false,
+                },
+                TableChange::AddColumn(new.columns()[2].clone()),
+                TableChange::AlterPrimaryKey {
+                    old_keys: vec!["foo".to_string()],
+                    new_keys: vec!["foo".to_string(), "bar".to_string()],
+                },
+            ],
+            diff.changes()
+        );
+        assert_eq!(
+            vec![
+                r#"ALTER TABLE "example" DROP COLUMN "zzz""#,
+                r#"ALTER TABLE "example" ALTER COLUMN "bar" SET DATA TYPE BIGINT"#,
+                r#"ALTER TABLE "example" ALTER COLUMN "bar" SET NOT NULL"#,
+                r#"ALTER TABLE "example" ADD COLUMN "baz" CHAR"#,
+                r#"ALTER TABLE "example" DROP PRIMARY KEY"#,
+                r#"ALTER TABLE "example" ADD PRIMARY KEY ("foo", "bar")"#,
+            ],
+            diff.to_alter_sql().unwrap()
+        );
+
+        let diff = new.diff(&old);
+        assert_eq!(
+            r#"COMMENT ON COLUMN "example"."baz" IS NULL"#,
+            TableDiff {
+                table_name: "\"example\"".to_string(),
+                changes: vec![TableChange::AlterColumnDescription {
+                    column_name: "baz".to_string(),
+                    description: None,
+                }],
+            }
+            .to_alter_sql()
+            .unwrap()[0]
+        );
+        assert!(diff
+            .changes()
+            .contains(&TableChange::DropColumn("baz".to_string())));
+    }
+
+    #[test]
+    fn description() {
+        let old = table(vec![], &[]);
+        let new = TableMetadata::new(Success {
+            table_name: "example".to_string(),
+            description_opt: Some(
+                crate::jogasaki::proto::sql::response::describe_table::success::DescriptionOpt::Description(
+                    "it's new".to_string(),
+                ),
+            ),
+            ..Default::default()
+        });
+
+        assert_eq!(
+            vec![r#"COMMENT ON TABLE "example" IS 'it''s new'"#],
+            old.diff(&new).to_alter_sql().unwrap()
+        );
+    }
+
+    #[test]
+    fn unsupported_type() {
+        let old = table(vec![column("foo", AtomType::Int4, false)], &[]);
+        let new = table(vec![column("foo", AtomType::Unknown, false)], &[]);
+        assert!(old.diff(&new).to_alter_sql().is_err());
+
+        let new = table(
+            vec![
+                column("foo", AtomType::Int4, false),
+                column("bar", AtomType::Unknown, true),
+            ],
+            &[],
+        );
+        assert!(old.diff(&new).to_alter_sql().is_err());
+    }
+}
@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use crate::{
+    client_error,
     error::TgError,
     invalid_response_error,
     jogasaki::proto::sql::response::response::Response as SqlResponseType,
@@ -68,6 +69,105 @@ impl TableMetadata {
     pub fn primary_keys(&self) -> &Vec<String> {
         &self.describe_table.primary_key
     }
+
+    /// Returns the table name qualified by the schema name (if exists).
+    ///
+    /// The names are delimited by `"`.
+    ///
+    /// since 0.11.0
+    pub fn qualified_table_name(&self) -> String {
+        let table_name = quote_identifier(self.table_name());
+        if self.schema_name().is_empty() {
+            table_name
+        } else {
+            format!("{}.{}", quote_identifier(self.schema_name()), table_name)
+        }
+    }
+
+    /// Returns `CREATE TABLE` statement of the table.
+    ///
+    /// The descriptions of the table and the columns are written as documentation comments (`/** ... */`).
+    /// Default values are not included, because table metadata does not provide them.
+    /// Every identifier is delimited by `"`.
+    ///
+    /// # Examples
+    /// ```
+    /// use tsubakuro_rust_core::prelude::*;
+    ///
+    /// async fn example(client: &SqlClient) -> Result<(), TgError> {
+    ///     let table_metadata = client.get_table_metadata("tb1").await?;
+    ///     println!("{}", table_metadata.to_create_table_sql()?);
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// since 0.11.0
+    pub fn to_create_table_sql(&self) -> Result<String, TgError> {
+        let mut sql = String::new();
+        if let Some(description) = self.description() {
+            sql.push_str(&doc_comment(description, ""));
+        }
+        sql.push_str(&format!("CREATE TABLE {} (\n", self.qualified_table_name()));
+
+        let mut definitions = Vec::with_capacity(self.columns().len() + 1);
+        for column in self.columns() {
+            let mut definition = String::new();
+            if let Some(description) = column.description() {
+                definition.push_str(&doc_comment(description, "    "));
+            }
+            definition.push_str("    ");
+            definition.push_str(&column_definition(column)?);
+            definitions.push(definition);
+        }
+        if !self.primary_keys().is_empty() {
+            let keys: Vec<String> = self
+                .primary_keys()
+                .iter()
+                .map(|key| quote_identifier(key))
+                .collect();
+            definitions.push(format!("    PRIMARY KEY ({})", keys.join(", ")));
+        }
+        sql.push_str(&definitions.join(",\n"));
+        sql.push_str("\n)");
+
+        Ok(sql)
+    }
+}
+
+/// Returns the identifier delimited by `"`.
+///
+/// Every identifier is delimited, so that reserved words (e.g. `value`) and case are kept as is.
+pub(crate) fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+pub(crate) fn column_definition(column: &SqlColumn) -> Result<String, TgError> {
+    let sql_type = column.sql_type().ok_or_else(|| {
+        client_error!(format!(
+            "unsupported column type. column={}, type={:?}",
+            column.name(),
+            column.type_info
+        ))
+    })?;
+    let mut definition = format!("{} {}", quote_identifier(column.name()), sql_type);
+    if column.nullable() == Some(false) {
+        definition.push_str(" NOT NULL");
+    }
+    Ok(definition)
+}
+
+fn doc_comment(description: &str, indent: &str) -> String {
+    let description = description.replace("*/", "* /");
+    if description.contains('\n') {
+        let mut s = format!("{indent}/**\n");
+        for line in description.lines() {
+            s.push_str(&format!("{indent} * {line}\n"));
+        }
+        s.push_str(&format!("{indent} */\n"));
+        s
+    } else {
+        format!("{indent}/** {description} */\n")
+    }
 }
 
 pub(crate) fn table_metadata_processor(
@@ -100,3 +200,93 @@ pub(crate) fn table_metadata_processor(
         )),
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::jogasaki::proto::sql::{
+        common::column::{LengthOpt, NullableOpt, TypeInfo, VaryingOpt},
+        common::AtomType,
+        response::describe_table::{success::DescriptionOpt, Success},
+    };
+
+    fn column(name: &str, atom_type: AtomType, nullable: bool) -> SqlColumn {
+        SqlColumn {
+            name: name.to_string(),
+            type_info: Some(TypeInfo::AtomType(atom_type.into())),
+            nullable_opt: Some(NullableOpt::Nullable(nullable)),
+            ..Default::default()
+        }
+    }
+
+    fn varchar(name: &str, length: u32) -> SqlColumn {
+        let mut column = column(name, AtomType::Character, true);
+        column.length_opt = Some(LengthOpt::Length(length));
+        column.varying_opt = Some(VaryingOpt::Varying(true));
+        column
+    }
+
+    fn table(columns: Vec<SqlColumn>, primary_keys: &[&str]) -> TableMetadata {
+        let describe_table = Success {
+            table_name: "example".to_string(),
+            columns,
+            primary_key: primary_keys.iter().map(|key| key.to_string()).collect(),
+            ..Default::default()
+        };
+        TableMetadata::new(describe_table)
+    }
+
+    #[test]
+    fn to_create_table_sql() {
+        let mut value = varchar("value", 10);
+        value.description_opt = Some(
+            crate::jogasaki::proto::sql::common::column::DescriptionOpt::Description(
+                "value of foo".to_string(),
+            ),
+        );
+        let mut table_metadata = table(
+            vec![
+                column("foo", AtomType::Int4, false),
+                column("bar", AtomType::Int8, true),
+                value,
+                column("Zzz Zzz", AtomType::Date, true),
+            ],
+            &["foo"],
+        );
+        table_metadata.describe_table.description_opt = Some(DescriptionOpt::Description(
+            "example table\nsecond line".to_string(),
+        ));
+
+        let expected = r#"/**
+ * example table
+ * second line
+ */
+CREATE TABLE "example" (
+    "foo" INT NOT NULL,
+    "bar" BIGINT,
+    /** value of foo */
+    "value" VARCHAR(10),
+    "Zzz Zzz" DATE,
+    PRIMARY KEY ("foo")
+)"#;
+        assert_eq!(expected, table_metadata.to_create_table_sql().unwrap());
+
+        table_metadata.describe_table.schema_name = "s1".to_string();
+        assert_eq!("\"s1\".\"example\"", table_metadata.qualified_table_name());
+    }
+
+    #[test]
+    fn to_create_table_sql_unsupported() {
+        let table_metadata = table(vec![column("foo", AtomType::Unknown, true)], &[]);
+        assert!(table_metadata.to_create_table_sql().is_err());
+    }
+
+    #[test]
+    fn quote() {
+        assert_eq!("\"foo_1\"", quote_identifier("foo_1"));
+        assert_eq!("\"value\"", quote_identifier("value"));
+        assert_eq!("\"1foo\"", quote_identifier("1foo"));
+        assert_eq!("\"a\"\"b\"", quote_identifier("a\"b"));
+        assert_eq!("\"\"", quote_identifier(""));
+    }
+}
@@ -28,6 +28,7 @@ impl From<crate::tateyama::proto::system::response::Error> for DiagnosticCode {
         let name = code.as_str_name();
 
         DiagnosticCode::new(SERVICE_ID_SYSTEM, "SYS", code_number, name)
+            .with_supplemental_text(&value.supplemental_text)
     }
 }
 
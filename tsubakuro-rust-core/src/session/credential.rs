@@ -1,4 +1,4 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use crate::{client_error, error::TgError, io_error};
 
@@ -91,6 +91,113 @@ impl Default for Credential {
     }
 }
 
+/// The environment variable name of authentication token.
+pub(crate) const ENV_AUTH_TOKEN: &str = "TSURUGI_AUTH_TOKEN";
+
+/// The environment variable name of credential file path.
+pub(crate) const ENV_CREDENTIALS: &str = "TSURUGI_CREDENTIALS";
+
+/// The default credential file path relative to the user's home directory.
+const DEFAULT_CREDENTIAL_FILE: &str = ".tsurugidb/credentials.key";
+
+/// Source of the credential found by [Credential::discover()].
+///
+/// since 0.11.0
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CredentialSource {
+    /// Specified explicitly.
+    Explicit,
+    /// Environment variable.
+    Environment(
+        /// environment variable name
+        String,
+    ),
+    /// Credential file.
+    File(
+        /// file path
+        PathBuf,
+    ),
+    /// No credential was found.
+    None,
+}
+
+impl std::fmt::Display for CredentialSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CredentialSource::Explicit => write!(f, "explicit"),
+            CredentialSource::Environment(name) => write!(f, "environment variable {name}"),
+            CredentialSource::File(path) => write!(f, "file {}", path.display()),
+            CredentialSource::None => write!(f, "none"),
+        }
+    }
+}
+
+impl Credential {
+    /// Discover credential.
+    ///
+    /// The credential is searched in the following order.
+    /// 1. environment variable `TSURUGI_AUTH_TOKEN` (authentication token)
+    /// 2. environment variable `TSURUGI_CREDENTIALS` (path to credential file)
+    /// 3. default credential file (`$HOME/.tsurugidb/credentials.key`)
+    ///
+    /// Returns [Credential::Null] with [CredentialSource::None] if no credential is found.
+    ///
+    /// since 0.11.0
+    pub fn discover() -> Result<(Credential, CredentialSource), TgError> {
+        Self::discover_with(&Credential::Null)
+    }
+
+    /// Discover credential.
+    ///
+    /// Returns `explicit` if it is not [Credential::Null], otherwise same as [Self::discover()].
+    ///
+    /// since 0.11.0
+    pub fn discover_with(explicit: &Credential) -> Result<(Credential, CredentialSource), TgError> {
+        Self::discover_by(explicit, |name| std::env::var(name).ok())
+    }
+
+    fn discover_by<F>(
+        explicit: &Credential,
+        get_env: F,
+    ) -> Result<(Credential, CredentialSource), TgError>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        if !matches!(explicit, Credential::Null) {
+            return Ok((explicit.clone(), CredentialSource::Explicit));
+        }
+
+        let get_env = |name: &str| get_env(name).filter(|s| !s.is_empty());
+
+        if let Some(token) = get_env(ENV_AUTH_TOKEN) {
+            let credential = Credential::from_auth_token(token);
+            return Ok((
+                credential,
+                CredentialSource::Environment(ENV_AUTH_TOKEN.to_string()),
+            ));
+        }
+
+        if let Some(path) = get_env(ENV_CREDENTIALS) {
+            let credential = Credential::load(&path)?;
+            return Ok((
+                credential,
+                CredentialSource::Environment(ENV_CREDENTIALS.to_string()),
+            ));
+        }
+
+        let home = get_env("HOME").or_else(|| get_env("USERPROFILE"));
+        if let Some(home) = home {
+            let path = Path::new(&home).join(DEFAULT_CREDENTIAL_FILE);
+            if path.is_file() {
+                let credential = Credential::load(&path)?;
+                return Ok((credential, CredentialSource::File(path)));
+            }
+        }
+
+        Ok((Credential::Null, CredentialSource::None))
+    }
+}
+
 /// The format version field name in credential file.
 const KEY_FORMAT_VERSION: &str = "format_version";
 
@@ -168,4 +275,60 @@ mod test {
             r#"{"format_version":1,"user":"user","password":"password","expiration_date":"2025-08-28T23:59:59.123456Z"}"#
         );
     }
+
+    #[test]
+    fn discover_explicit() {
+        let explicit = Credential::from_user_password("user", Some("password"));
+        let (credential, source) =
+            Credential::discover_by(&explicit, |_| Some("token".to_string())).unwrap();
+        assert!(matches!(credential, Credential::UserPassword { user, .. } if user == "user"));
+        assert_eq!(CredentialSource::Explicit, source);
+    }
+
+    #[test]
+    fn discover_env() {
+        let (credential, source) = Credential::discover_by(&Credential::Null, |name| match name {
+            ENV_AUTH_TOKEN => Some("token".to_string()),
+            _ => None,
+        })
+        .unwrap();
+        assert!(matches!(credential, Credential::AuthToken(token) if token == "token"));
+        assert_eq!(
+            CredentialSource::Environment(ENV_AUTH_TOKEN.to_string()),
+            source
+        );
+    }
+
+    #[test]
+    fn discover_default_file() {
+        let home = tempfile::tempdir().unwrap();
+        let path = home.path().join(DEFAULT_CREDENTIAL_FILE);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, "encrypted\ncomment\n").unwrap();
+
+        let home_str = home.path().to_str().unwrap().to_string();
+        let get_env = |name: &str| match name {
+            ENV_AUTH_TOKEN => Some("".to_string()),
+            "HOME" => Some(home_str.clone()),
+            _ => None,
+        };
+        let (credential, source) = Credential::discover_by(&Credential::Null, get_env).unwrap();
+        assert!(
+            matches!(credential, Credential::File { encrypted, .. } if encrypted == "encrypted")
+        );
+        assert_eq!(CredentialSource::File(path), source);
+    }
+
+    #[test]
+    fn discover_none() {
+        let home = tempfile::tempdir().unwrap();
+        let home_str = home.path().to_str().unwrap().to_string();
+        let (credential, source) = Credential::discover_by(&Credential::Null, |name| match name {
+            "HOME" => Some(home_str.clone()),
+            _ => None,
+        })
+        .unwrap();
+        assert!(matches!(credential, Credential::Null));
+        assert_eq!(CredentialSource::None, source);
+    }
 }
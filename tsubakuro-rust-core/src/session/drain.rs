@@ -0,0 +1,495 @@
+use std::{sync::Arc, time::Duration};
+
+use log::{debug, trace};
+
+use tokio::task::JoinHandle;
+
+use crate::{
+    client_error,
+    error::TgError,
+    prelude::{endpoint::endpoint_broker::EndpointBroker, Session, SqlClient},
+    session::wire::{resource_registry::TrackedResource, Wire},
+    util::Timeout,
+};
+
+/// Summary of [Session::drain()].
+///
+/// Holds what was closed or cancelled by force.
+///
+/// since 0.11.0
+#[derive(Debug, Default)]
+pub struct SessionDrainSummary {
+    completed_requests: usize,
+    cancelled_requests: usize,
+    closed_query_results: Vec<String>,
+    rolled_back_transactions: Vec<String>,
+    disposed_transactions: Vec<String>,
+    errors: Vec<TgError>,
+    timeout: bool,
+}
+
+impl SessionDrainSummary {
+    /// Get the number of in-flight requests which completed while draining.
+    pub fn completed_requests(&self) -> usize {
+        self.completed_requests
+    }
+
+    /// Get the number of in-flight requests which were cancelled because they did not complete in time.
+    pub fn cancelled_requests(&self) -> usize {
+        self.cancelled_requests
+    }
+
+    /// Get the names of the query results which were closed.
+    pub fn closed_query_results(&self) -> &[String] {
+        &self.closed_query_results
+    }
+
+    /// Get the ids of the transactions which were rolled back.
+    ///
+    /// Committed transactions which were not closed are not included. (They are only disposed)
+    pub fn rolled_back_transactions(&self) -> &[String] {
+        &self.rolled_back_transactions
+    }
+
+    /// Get the ids of the transactions which were disposed.
+    pub fn disposed_transactions(&self) -> &[String] {
+        &self.disposed_transactions
+    }
+
+    /// Get the errors which occurred while closing the resources.
+    pub fn errors(&self) -> &[TgError] {
+        &self.errors
+    }
+
+    /// Whether the timeout expired while draining.
+    pub fn is_timeout(&self) -> bool {
+        self.timeout
+    }
+
+    /// Whether nothing was closed or cancelled by force, and no error occurred.
+    pub fn is_clean(&self) -> bool {
+        self.cancelled_requests == 0
+            && self.closed_query_results.is_empty()
+            && self.disposed_transactions.is_empty()
+            && self.errors.is_empty()
+            && !self.timeout
+    }
+}
+
+impl Session {
+    /// Drains and closes this session.
+    ///
+    /// The session is closed in the following order.
+    /// 1. Stop accepting new work. (New requests such as execute, query, prepare, begin and commit fail with an error.
+    ///    Closing resources, e.g. [Transaction::close()](crate::prelude::Transaction::close), is still allowed.)
+    /// 2. Wait for the in-flight requests (e.g. [Job](crate::prelude::Job)) to complete.
+    ///    The requests which do not complete in time are cancelled.
+    /// 3. Close the open [SqlQueryResult](crate::prelude::SqlQueryResult)s.
+    /// 4. Roll back and dispose the open [Transaction](crate::prelude::Transaction)s.
+    /// 5. Close the session.
+    ///
+    /// `timeout` is the time limit of the whole drain. If `timeout` is zero, waits without limit.
+    /// After the timeout, the remaining resources are not closed on the server, and the session is closed.
+    ///
+    /// The resources closed by this method can be dropped (or closed) afterwards without error.
+    ///
+    /// Returns an error only if closing the session fails.
+    /// The errors while closing the resources are stored in [SessionDrainSummary::errors()].
+    ///
+    /// # Examples
+    /// ```
+    /// use std::{future::Future, sync::Arc, time::Duration};
+    /// use tsubakuro_rust_core::prelude::*;
+    ///
+    /// // e.g. shutdown_signal = tokio::signal::ctrl_c()
+    /// async fn example<F: Future>(session: Arc<Session>, shutdown_signal: F) -> Result<(), TgError> {
+    ///     shutdown_signal.await;
+    ///
+    ///     let summary = session.drain(Duration::from_secs(30)).await?;
+    ///     if !summary.is_clean() {
+    ///         println!("cancelled requests: {}", summary.cancelled_requests());
+    ///         println!("closed query results: {:?}", summary.closed_query_results());
+    ///         println!("rolled back transactions: {:?}", summary.rolled_back_transactions());
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// since 0.11.0
+    pub async fn drain(&self, timeout: Duration) -> Result<SessionDrainSummary, TgError> {
+        const FUNCTION_NAME: &str = "drain()";
+        trace!("{} start", FUNCTION_NAME);
+
+        let wire = self.wire();
+        wire.resource_registry().set_draining();
+
+        let timeout = Timeout::new(timeout);
+        let mut summary = SessionDrainSummary::default();
+
+        Self::drain_requests(&wire, &timeout, &mut summary).await;
+
+        let resources = wire.resource_registry().take_open_resources();
+        for resource in resources {
+            Self::drain_resource(&wire, resource, &timeout, &mut summary).await;
+        }
+
+        self.close().await?;
+
+        trace!("{} end. summary={:?}", FUNCTION_NAME, summary);
+        Ok(summary)
+    }
+
+    /// Check if the session is draining (or drained).
+    ///
+    /// since 0.11.0
+    pub fn is_draining(&self) -> bool {
+        self.wire().is_draining()
+    }
+
+    async fn drain_requests(
+        wire: &Arc<Wire>,
+        timeout: &Timeout,
+        summary: &mut SessionDrainSummary,
+    ) {
+        let in_flight = |wire: &Wire| {
+            let query_result_slots = wire.resource_registry().query_result_slots();
+            wire.in_flight_slot_handles(&query_result_slots)
+        };
+
+        // The link is read on a separate task, so that the wait can be bounded by the timeout
+        // without cutting off a frame in the middle.
+        let mut pull_task: Option<JoinHandle<Result<bool, TgError>>> = None;
+
+        let initial = in_flight(wire).len();
+        loop {
+            let slot_handles = in_flight(wire);
+            if slot_handles.is_empty() {
+                break;
+            }
+
+            if timeout.is_timeout() {
+                summary.timeout = true;
+                for slot_handle in &slot_handles {
+                    slot_handle.set_cancelled();
+                    // send only (do not wait for the response)
+                    if let Err(e) = EndpointBroker::cancel(wire, slot_handle.slot()).await {
+                        debug!(
+                            "Session.drain() cancel error. slot={}, {}",
+                            slot_handle.slot(),
+                            e
+                        );
+                    }
+                }
+                summary.cancelled_requests = slot_handles.len();
+                break;
+            }
+
+            let task = pull_task.get_or_insert_with(|| {
+                let wire = wire.clone();
+                tokio::spawn(async move { wire.pull_once().await })
+            });
+            let wait = async {
+                tokio::select! {
+                    result = task => Some(result),
+                    _ = wire.wait_response_notification() => None,
+                }
+            };
+            let result = match timeout.remaining() {
+                Some(remaining) => tokio::time::timeout(remaining, wait).await.unwrap_or(None),
+                None => wait.await,
+            };
+
+            if let Some(result) = result {
+                pull_task = None;
+                let result =
+                    result.unwrap_or_else(|e| Err(client_error!("Session.drain() pull error", e)));
+                if let Err(e) = result {
+                    summary.errors.push(e);
+                    summary.cancelled_requests = slot_handles.len();
+                    break;
+                }
+            }
+            tokio::task::yield_now().await;
+        }
+        summary.completed_requests = initial.saturating_sub(summary.cancelled_requests);
+
+        if let Some(mut task) = pull_task {
+            // wait for the frame being read, within the timeout
+            let result = match timeout.remaining() {
+                Some(remaining) => tokio::time::timeout(remaining, &mut task).await.ok(),
+                None => Some((&mut task).await),
+            };
+            match result {
+                Some(Ok(Ok(_))) => {}
+                Some(Ok(Err(e))) => summary.errors.push(e),
+                Some(Err(e)) => summary
+                    .errors
+                    .push(client_error!("Session.drain() pull error", e)),
+                None => {
+                    // the frame being read is lost, and the session is closed after this
+                    summary.timeout = true;
+                    task.abort();
+                    wire.set_broken();
+                }
+            }
+        }
+    }
+
+    async fn drain_resource(
+        wire: &Wire,
+        resource: TrackedResource,
+        timeout: &Timeout,
+        summary: &mut SessionDrainSummary,
+    ) {
+        const FUNCTION_NAME: &str = "Session.drain()";
+
+        match resource {
+            TrackedResource::QueryResult { slot_handle, name } => {
+                if let Some(remaining) = Self::remaining(timeout, summary) {
+                    let timeout = Timeout::new(remaining);
+                    if let Err(e) = wire.pull_response(&slot_handle, &timeout).await {
+                        summary.errors.push(e);
+                    }
+                }
+                summary.closed_query_results.push(name);
+            }
+            TrackedResource::Transaction {
+                transaction_handle,
+                transaction_id,
+            } => {
+                if let Some(remaining) = Self::remaining(timeout, summary) {
+                    match SqlClient::rollback_by_handle(wire, &transaction_handle, remaining).await
+                    {
+                        Ok(_) => summary
+                            .rolled_back_transactions
+                            .push(transaction_id.clone()),
+                        // e.g. already committed
+                        Err(e) => debug!("{FUNCTION_NAME} rollback error. {}", e),
+                    }
+                }
+                if let Some(remaining) = Self::remaining(timeout, summary) {
+                    if let Err(e) = SqlClient::dispose_transaction_by_handle(
+                        wire,
+                        &transaction_handle,
+                        remaining,
+                    )
+                    .await
+                    {
+                        summary.errors.push(e);
+                    }
+                }
+                summary.disposed_transactions.push(transaction_id);
+            }
+        }
+    }
+
+    /// Returns the remaining time, or None if the timeout expired.
+    fn remaining(timeout: &Timeout, summary: &mut SessionDrainSummary) -> Option<Duration> {
+        match timeout.remaining() {
+            Some(remaining) if remaining.is_zero() => {
+                summary.timeout = true;
+                None
+            }
+            Some(remaining) => Some(remaining),
+            None => Some(Duration::ZERO),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use tokio::{io::AsyncWriteExt, net::TcpListener};
+
+    use super::*;
+    use crate::{
+        jogasaki::proto::sql::{
+            common::Transaction as ProtoTransaction, request::request::Request as SqlCommand,
+        },
+        prelude::{ConnectionOption, Endpoint, Transaction},
+        session::{
+            tcp::{
+                fault_injection::{LinkFault, LinkFaultInjector, LinkFaultRule, LinkFaultTarget},
+                link::TcpLink,
+                test_server::TestServer,
+                wire::TcpWire,
+            },
+            wire::DelegateWire,
+        },
+    };
+
+    #[test]
+    fn summary() {
+        let summary = SessionDrainSummary::default();
+        assert!(summary.is_clean());
+
+        let summary = SessionDrainSummary {
+            completed_requests: 2,
+            ..Default::default()
+        };
+        assert!(summary.is_clean());
+
+        let summary = SessionDrainSummary {
+            cancelled_requests: 1,
+            ..Default::default()
+        };
+        assert!(!summary.is_clean());
+
+        let summary = SessionDrainSummary {
+            rolled_back_transactions: vec!["tx1".to_string()],
+            disposed_transactions: vec!["tx1".to_string()],
+            ..Default::default()
+        };
+        assert!(!summary.is_clean());
+        assert_eq!(&["tx1".to_string()], summary.rolled_back_transactions());
+    }
+
+    #[test]
+    fn remaining() {
+        let mut summary = SessionDrainSummary::default();
+        let timeout = Timeout::new(Duration::ZERO);
+        assert_eq!(
+            Some(Duration::ZERO),
+            Session::remaining(&timeout, &mut summary)
+        );
+        assert!(!summary.is_timeout());
+
+        let timeout = Timeout::new(Duration::from_nanos(1));
+        std::thread::sleep(Duration::from_millis(1));
+        assert_eq!(None, Session::remaining(&timeout, &mut summary));
+        assert!(summary.is_timeout());
+    }
+
+    /// Connects a wire to a dummy server, and makes a request in flight.
+    async fn in_flight_wire(
+        fault_injector: &Arc<LinkFaultInjector>,
+    ) -> (Arc<Wire>, tokio::net::TcpStream) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let endpoint = Endpoint::Tcp("127.0.0.1".to_string(), port);
+
+        let mut connection_option = ConnectionOption::new();
+        connection_option.set_link_fault_injector(Some(fault_injector.clone()));
+        let link = TcpLink::connect(&connection_option, &endpoint)
+            .await
+            .unwrap();
+        let (mut server, _) = listener.accept().await.unwrap();
+
+        let tcp_wire = Arc::new(TcpWire::new(link));
+        let slot = tcp_wire.response_box().create_slot_handle().slot();
+        let wire = Wire::new(DelegateWire::Tcp(tcp_wire));
+
+        // RESPONSE_SESSION_PAYLOAD: info(1) + slot(2) + length(4) + payload
+        let mut frame = vec![1, slot as u8, (slot >> 8) as u8, 3, 0, 0, 0];
+        frame.extend_from_slice(b"abc");
+        server.write_all(&frame).await.unwrap();
+
+        (wire, server)
+    }
+
+    #[tokio::test]
+    async fn drain_requests() {
+        let fault_injector = Arc::new(LinkFaultInjector::new());
+        let (wire, _server) = in_flight_wire(&fault_injector).await;
+        assert_eq!(1, wire.in_flight_slot_handles(&[]).len());
+
+        let timeout = Timeout::new(Duration::from_secs(10));
+        let mut summary = SessionDrainSummary::default();
+        Session::drain_requests(&wire, &timeout, &mut summary).await;
+
+        assert_eq!(1, summary.completed_requests());
+        assert_eq!(0, summary.cancelled_requests());
+        assert!(!summary.is_timeout());
+        assert!(!wire.is_broken());
+    }
+
+    #[tokio::test]
+    async fn drain_requests_stalled_frame() {
+        // the frame stalls while being received, and recv_timeout is not set
+        let fault_injector = Arc::new(LinkFaultInjector::new());
+        fault_injector.add_rule(LinkFaultRule::new(
+            LinkFaultTarget::Recv,
+            LinkFault::Delay(Duration::from_secs(60)),
+        ));
+        let (wire, _server) = in_flight_wire(&fault_injector).await;
+
+        let timeout = Timeout::new(Duration::from_millis(100));
+        let mut summary = SessionDrainSummary::default();
+        tokio::time::timeout(
+            Duration::from_secs(10),
+            Session::drain_requests(&wire, &timeout, &mut summary),
+        )
+        .await
+        .expect("drain_requests() must not exceed the timeout");
+
+        assert_eq!(0, summary.completed_requests());
+        assert_eq!(1, summary.cancelled_requests());
+        assert!(summary.is_timeout());
+        assert!(wire.is_broken());
+    }
+
+    #[tokio::test]
+    async fn close_transaction_while_draining() {
+        let server = TestServer::start().await;
+        let (session, mut connection) = server.connect(&ConnectionOption::new()).await;
+        let client: SqlClient = session.make_client();
+        let new_transaction = |handle| {
+            let transaction_handle = ProtoTransaction {
+                handle,
+                ..Default::default()
+            };
+            Transaction::new(
+                session.clone(),
+                transaction_handle,
+                format!("tx{handle}"),
+                Duration::from_secs(5),
+            )
+        };
+        let transaction1 = new_transaction(1);
+        let transaction2 = new_transaction(2);
+        transaction2.set_fail_on_drop_error(true);
+
+        // a request in flight, which drain() waits for
+        let job = client
+            .get_transaction_status_async(&transaction1)
+            .await
+            .unwrap();
+        let (status_slot, _) = connection.read_sql_request().await;
+
+        let drain = tokio::spawn({
+            let session = session.clone();
+            async move { session.drain(Duration::from_secs(10)).await }
+        });
+        while !session.is_draining() {
+            tokio::task::yield_now().await;
+        }
+
+        // new work is rejected
+        let error = client
+            .execute(&transaction1, "insert into test values(1)")
+            .await
+            .unwrap_err();
+        assert_eq!("session is draining", error.message());
+
+        let server_task = tokio::spawn(async move {
+            for _ in 0..2 {
+                let (slot, command) = connection.read_sql_request().await;
+                assert!(matches!(command, SqlCommand::DisposeTransaction(_)));
+                connection.write_sql_success(slot).await;
+            }
+            connection.write_sql_success(status_slot).await;
+        });
+
+        // dispose on drop must not fail
+        drop(transaction2);
+        transaction1.close().await.unwrap();
+
+        let summary = drain.await.unwrap().unwrap();
+        server_task.await.unwrap();
+        assert_eq!(1, summary.completed_requests());
+        assert!(summary.disposed_transactions().is_empty());
+        assert!(summary.errors().is_empty());
+        assert!(session.is_closed());
+        drop(job);
+    }
+}
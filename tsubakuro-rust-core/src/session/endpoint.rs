@@ -56,6 +56,25 @@ impl Endpoint {
             ))),
         }
     }
+
+    /// parse comma-separated endpoint urls.
+    ///
+    /// # Parameters
+    /// - `endpoints` - comma-separated endpoint urls. (e.g. `tcp://primary:12345,tcp://standby:12345`)
+    ///
+    /// since 0.11.0
+    pub fn parse_list(endpoints: &str) -> Result<Vec<Endpoint>, TgError> {
+        let list = endpoints
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(Endpoint::parse)
+            .collect::<Result<Vec<_>, _>>()?;
+        if list.is_empty() {
+            return Err(client_error!("endpoint parse error. endpoint is empty"));
+        }
+        Ok(list)
+    }
 }
 
 impl std::fmt::Display for Endpoint {
@@ -73,6 +92,31 @@ impl std::fmt::Debug for Endpoint {
     }
 }
 
+/// Policy to select an endpoint when multiple endpoints are specified.
+///
+/// See [ConnectionOption::set_endpoint_selection_policy()](crate::prelude::ConnectionOption::set_endpoint_selection_policy).
+///
+/// since 0.11.0
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EndpointSelectionPolicy {
+    /// Try the endpoints in the specified order (failover).
+    #[default]
+    InOrder,
+    /// Try the endpoints in random order (load balancing).
+    Random,
+}
+
+impl EndpointSelectionPolicy {
+    pub(crate) fn arrange<'a>(&self, endpoints: &'a [Endpoint]) -> Vec<&'a Endpoint> {
+        let mut list: Vec<&Endpoint> = endpoints.iter().collect();
+        if let EndpointSelectionPolicy::Random = self {
+            use rand::seq::SliceRandom;
+            list.shuffle(&mut rand::thread_rng());
+        }
+        list
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -106,6 +150,40 @@ mod test {
         let _ = Endpoint::parse("tcp://localhost").unwrap_err();
     }
 
+    #[test]
+    fn parse_list() {
+        let actual = Endpoint::parse_list("tcp://primary:12345, tcp://standby:12346").unwrap();
+        assert_eq!(
+            vec![
+                Endpoint::Tcp("primary".to_string(), 12345),
+                Endpoint::Tcp("standby".to_string(), 12346),
+            ],
+            actual
+        );
+
+        let actual = Endpoint::parse_list("tcp://localhost:12345").unwrap();
+        assert_eq!(vec![Endpoint::Tcp("localhost".to_string(), 12345)], actual);
+    }
+
+    #[test]
+    fn parse_list_error() {
+        let _ = Endpoint::parse_list("").unwrap_err();
+        let _ = Endpoint::parse_list("tcp://localhost:12345,tcp://localhost").unwrap_err();
+    }
+
+    #[test]
+    fn selection_policy() {
+        let endpoints = Endpoint::parse_list("tcp://a:1,tcp://b:2,tcp://c:3").unwrap();
+
+        let actual = EndpointSelectionPolicy::InOrder.arrange(&endpoints);
+        assert_eq!(endpoints.iter().collect::<Vec<_>>(), actual);
+
+        let mut actual = EndpointSelectionPolicy::Random.arrange(&endpoints);
+        assert_eq!(3, actual.len());
+        actual.sort_by_key(|e| e.to_string());
+        assert_eq!(endpoints.iter().collect::<Vec<_>>(), actual);
+    }
+
     #[test]
     fn ng_schema() {
         let e = Endpoint::parse("ipc::tsurugi").unwrap_err();
@@ -1,7 +1,11 @@
 pub mod credential;
+pub mod drain;
 pub mod endpoint;
 pub mod lob_transfer_type;
 pub mod option;
+pub(crate) mod option_parser;
+pub mod reconnect_option;
+pub mod resilient_session;
 #[allow(clippy::module_inception)]
 pub mod session;
 pub(crate) mod tcp;
@@ -0,0 +1,215 @@
+use std::{sync::Arc, time::Duration};
+
+/// Event of [ResilientSession](crate::prelude::ResilientSession).
+///
+/// See [ReconnectOption::set_event_callback()].
+///
+/// since 0.11.0
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReconnectEvent {
+    /// The connection is detected as broken or expired.
+    Disconnected { generation: u64 },
+    /// Trying to connect.
+    ///
+    /// `attempt` is 0-origin.
+    Reconnecting { attempt: u32 },
+    /// Failed to connect.
+    ReconnectFailed { attempt: u32, message: String },
+    /// Connected.
+    ///
+    /// `generation` is the number of reconnections so far.
+    Reconnected { attempt: u32, generation: u64 },
+    /// Gave up reconnecting, because the number of attempts reached [ReconnectOption::max_attempts()].
+    GaveUp { attempts: u32 },
+    /// The registered statement was prepared on the new connection.
+    StatementReprepared { sql: String },
+    /// Failed to prepare the registered statement on the new connection.
+    ///
+    /// The statement is prepared again on its next use.
+    StatementReprepareFailed { sql: String, message: String },
+    /// The in-flight transaction is lost by the reconnection.
+    TransactionLost { transaction_id: String },
+}
+
+/// Callback to receive [ReconnectEvent].
+///
+/// since 0.11.0
+pub type ReconnectEventCallback = Arc<dyn Fn(&ReconnectEvent) + Send + Sync>;
+
+/// Option for reconnection of [ResilientSession](crate::prelude::ResilientSession).
+///
+/// # Examples
+/// ```
+/// use std::{sync::Arc, time::Duration};
+/// use tsubakuro_rust_core::prelude::*;
+///
+/// fn example() -> ReconnectOption {
+///     let mut option = ReconnectOption::new();
+///     option.set_max_attempts(10);
+///     option.set_initial_backoff(Duration::from_millis(500));
+///     option.set_max_backoff(Duration::from_secs(30));
+///     option.set_event_callback(Arc::new(|event: &ReconnectEvent| {
+///         println!("{:?}", event);
+///     }));
+///     option
+/// }
+/// ```
+///
+/// since 0.11.0
+#[derive(Clone)]
+pub struct ReconnectOption {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    event_callback: Option<ReconnectEventCallback>,
+}
+
+impl std::fmt::Debug for ReconnectOption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ReconnectOption")
+            .field("max_attempts", &self.max_attempts)
+            .field("initial_backoff", &self.initial_backoff)
+            .field("max_backoff", &self.max_backoff)
+            .field("event_callback", &self.event_callback.is_some())
+            .finish()
+    }
+}
+
+impl Default for ReconnectOption {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ReconnectOption {
+    /// Creates a new instance.
+    pub fn new() -> ReconnectOption {
+        ReconnectOption {
+            max_attempts: 5,
+            initial_backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(10),
+            event_callback: None,
+        }
+    }
+
+    /// Set the maximum number of connection attempts for each reconnection.
+    ///
+    /// `0` means unlimited.
+    pub fn set_max_attempts(&mut self, max_attempts: u32) {
+        self.max_attempts = max_attempts;
+    }
+
+    /// Get the maximum number of connection attempts for each reconnection.
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// Set the wait time after the first failed attempt.
+    ///
+    /// The wait time doubles for each attempt up to [`Self::max_backoff`].
+    pub fn set_initial_backoff(&mut self, backoff: Duration) {
+        self.initial_backoff = backoff;
+    }
+
+    /// Get the wait time after the first failed attempt.
+    pub fn initial_backoff(&self) -> Duration {
+        self.initial_backoff
+    }
+
+    /// Set the maximum wait time between attempts.
+    pub fn set_max_backoff(&mut self, backoff: Duration) {
+        self.max_backoff = backoff;
+    }
+
+    /// Get the maximum wait time between attempts.
+    pub fn max_backoff(&self) -> Duration {
+        self.max_backoff
+    }
+
+    /// Set the callback to receive reconnection events.
+    ///
+    /// The callback may be called from multiple threads concurrently.
+    pub fn set_event_callback(&mut self, callback: ReconnectEventCallback) {
+        self.event_callback = Some(callback);
+    }
+
+    /// Get the callback to receive reconnection events.
+    pub fn event_callback(&self) -> Option<&ReconnectEventCallback> {
+        self.event_callback.as_ref()
+    }
+
+    pub(crate) fn notify_event(&self, event: ReconnectEvent) {
+        if let Some(callback) = &self.event_callback {
+            callback(&event);
+        }
+    }
+
+    /// Returns true if another attempt is allowed after `attempt` (0-origin) failed.
+    pub(crate) fn can_retry(&self, attempt: u32) -> bool {
+        self.max_attempts == 0 || attempt + 1 < self.max_attempts
+    }
+
+    /// Returns the wait time before the next attempt.
+    ///
+    /// `attempt` is the number of the failed attempt (0-origin).
+    pub(crate) fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 1u32.checked_shl(attempt).unwrap_or(u32::MAX);
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Mutex;
+
+    use super::*;
+
+    #[test]
+    fn backoff() {
+        let mut option = ReconnectOption::new();
+        option.set_initial_backoff(Duration::from_millis(100));
+        option.set_max_backoff(Duration::from_millis(500));
+
+        assert_eq!(Duration::from_millis(100), option.backoff(0));
+        assert_eq!(Duration::from_millis(200), option.backoff(1));
+        assert_eq!(Duration::from_millis(400), option.backoff(2));
+        assert_eq!(Duration::from_millis(500), option.backoff(3));
+        assert_eq!(Duration::from_millis(500), option.backoff(100));
+    }
+
+    #[test]
+    fn can_retry() {
+        let mut option = ReconnectOption::new();
+        option.set_max_attempts(3);
+        assert!(option.can_retry(0));
+        assert!(option.can_retry(1));
+        assert!(!option.can_retry(2));
+
+        option.set_max_attempts(0);
+        assert!(option.can_retry(1000));
+    }
+
+    #[test]
+    fn notify_event() {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let mut option = ReconnectOption::new();
+        option.notify_event(ReconnectEvent::GaveUp { attempts: 1 });
+
+        let events_clone = events.clone();
+        option.set_event_callback(Arc::new(move |event: &ReconnectEvent| {
+            events_clone.lock().unwrap().push(event.clone());
+        }));
+        option.notify_event(ReconnectEvent::Reconnecting { attempt: 0 });
+        option.notify_event(ReconnectEvent::Reconnected {
+            attempt: 0,
+            generation: 1,
+        });
+
+        let events = events.lock().unwrap();
+        assert_eq!(2, events.len());
+        assert_eq!(ReconnectEvent::Reconnecting { attempt: 0 }, events[0]);
+        assert!(format!("{:?}", option).contains("event_callback: true"));
+    }
+}
@@ -0,0 +1,918 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, Mutex, RwLock, Weak,
+};
+
+use log::{debug, warn};
+
+use crate::{
+    client_error,
+    error::TgError,
+    prelude::{
+        CommitOption, CoreServiceCode, SqlClient, SqlExecuteResult, SqlParameter, SqlPlaceholder,
+        SqlPreparedStatement, SqlQueryResult, TransactionOption,
+    },
+    transaction::Transaction,
+};
+
+use super::{
+    option::ConnectionOption,
+    reconnect_option::{ReconnectEvent, ReconnectOption},
+    Session,
+};
+
+/// Session which reconnects automatically when the connection is broken or expired.
+///
+/// The connection is checked before each operation,
+/// and reconnected with the original [ConnectionOption] if it is broken or expired.
+/// Statements prepared by [Self::prepare] are prepared again on the new connection.
+///
+/// SQL interceptors added to the session are taken over to the new connection.
+///
+/// Transactions cannot be recovered.
+/// Operations of a [ResilientTransaction] started before the reconnection fail with `ClientError`,
+/// and the application should retry the whole transaction.
+///
+/// # Examples
+/// ```
+/// use std::sync::Arc;
+/// use tsubakuro_rust_core::prelude::*;
+///
+/// async fn example(connection_option: &ConnectionOption) -> Result<(), TgError> {
+///     let mut reconnect_option = ReconnectOption::new();
+///     reconnect_option.set_max_attempts(10);
+///     reconnect_option.set_event_callback(Arc::new(|event: &ReconnectEvent| {
+///         println!("{:?}", event);
+///     }));
+///
+///     let session = ResilientSession::connect(connection_option, reconnect_option).await?;
+///     let statement = session
+///         .prepare(
+///             "insert into tb values(:pk, :value)",
+///             vec![
+///                 SqlPlaceholder::of_atom_type("pk", AtomType::Int4),
+///                 SqlPlaceholder::of_atom_type("value", AtomType::Character),
+///             ],
+///         )
+///         .await?;
+///
+///     let transaction = session.start_transaction(&TransactionOption::default()).await?;
+///     let parameters = vec![
+///         SqlParameter::of("pk", 1),
+///         SqlParameter::of("value", "abc"),
+///     ];
+///     let result = match transaction.prepared_execute(&statement, parameters).await {
+///         Ok(_) => transaction.commit(&CommitOption::default()).await,
+///         Err(e) => Err(e),
+///     };
+///     transaction.close().await?;
+///     result?;
+///
+///     statement.close().await?;
+///     session.close().await
+/// }
+/// ```
+///
+/// since 0.11.0
+pub struct ResilientSession {
+    connection_option: ConnectionOption,
+    reconnect_option: ReconnectOption,
+    current: RwLock<CurrentSession>,
+    reconnect_lock: tokio::sync::Mutex<()>,
+    statements: Mutex<Vec<Weak<StatementState>>>,
+    closed: AtomicBool,
+}
+
+#[derive(Clone)]
+struct CurrentSession {
+    session: Arc<Session>,
+    generation: u64,
+    expired: bool,
+}
+
+impl CurrentSession {
+    fn needs_reconnect(&self) -> bool {
+        self.expired || self.session.is_broken() || self.session.is_closed()
+    }
+}
+
+impl std::fmt::Debug for ResilientSession {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let current = self.current();
+        f.debug_struct("ResilientSession")
+            .field("session", &current.session)
+            .field("generation", &current.generation)
+            .field("expired", &current.expired)
+            .field("reconnect_option", &self.reconnect_option)
+            .field("closed", &self.closed)
+            .finish()
+    }
+}
+
+impl ResilientSession {
+    /// Connect to Tsurugi server.
+    pub async fn connect(
+        connection_option: &ConnectionOption,
+        reconnect_option: ReconnectOption,
+    ) -> Result<Arc<ResilientSession>, TgError> {
+        let session = Session::connect(connection_option).await?;
+        Ok(Arc::new(ResilientSession {
+            connection_option: connection_option.clone(),
+            reconnect_option,
+            current: RwLock::new(CurrentSession {
+                session,
+                generation: 0,
+                expired: false,
+            }),
+            reconnect_lock: tokio::sync::Mutex::new(()),
+            statements: Mutex::new(Vec::new()),
+            closed: AtomicBool::new(false),
+        }))
+    }
+
+    /// Get the connection option.
+    pub fn connection_option(&self) -> &ConnectionOption {
+        &self.connection_option
+    }
+
+    /// Get the reconnect option.
+    pub fn reconnect_option(&self) -> &ReconnectOption {
+        &self.reconnect_option
+    }
+
+    /// Get the number of reconnections so far.
+    pub fn generation(&self) -> u64 {
+        self.current().generation
+    }
+
+    fn current(&self) -> CurrentSession {
+        self.current.read().unwrap().clone()
+    }
+
+    /// Get the current session.
+    ///
+    /// Reconnect if the connection is broken or expired.
+    ///
+    /// Clients created from the returned session are not recovered by reconnection.
+    /// Pass their errors to [Self::report_error] so that the next operation reconnects.
+    pub async fn session(&self) -> Result<Arc<Session>, TgError> {
+        let (session, _) = self.connected_session().await?;
+        Ok(session)
+    }
+
+    /// Create a SQL client on the current session.
+    ///
+    /// See [Self::session].
+    pub async fn make_sql_client(&self) -> Result<SqlClient, TgError> {
+        let session = self.session().await?;
+        Ok(session.make_client())
+    }
+
+    /// Report the error of an operation on the current session.
+    ///
+    /// If the error means that the connection is lost (e.g. `SESSION_EXPIRED`),
+    /// the next operation reconnects.
+    pub fn report_error(&self, error: &TgError) {
+        let generation = self.generation();
+        self.report_error_on(error, generation);
+    }
+
+    fn report_error_on(&self, error: &TgError, generation: u64) {
+        if !is_connection_lost(error) {
+            return;
+        }
+
+        let mut current = self.current.write().unwrap();
+        if current.generation == generation && !current.expired {
+            debug!(
+                "ResilientSession: connection lost. generation={}, error={}",
+                generation, error
+            );
+            current.expired = true;
+        }
+    }
+
+    /// Reconnect to Tsurugi server regardless of the state of the current connection.
+    pub async fn reconnect(&self) -> Result<(), TgError> {
+        let generation = self.generation();
+        self.reconnect_from(generation).await?;
+        Ok(())
+    }
+
+    async fn connected_session(&self) -> Result<(Arc<Session>, u64), TgError> {
+        self.check_closed()?;
+
+        let current = self.current();
+        if !current.needs_reconnect() {
+            return Ok((current.session, current.generation));
+        }
+        self.reconnect_from(current.generation).await
+    }
+
+    async fn reconnect_from(&self, generation: u64) -> Result<(Arc<Session>, u64), TgError> {
+        let _guard = self.reconnect_lock.lock().await;
+        self.check_closed()?;
+
+        let current = self.current();
+        if current.generation != generation {
+            // already reconnected by another task
+            return Ok((current.session, current.generation));
+        }
+
+        self.reconnect_option
+            .notify_event(ReconnectEvent::Disconnected { generation });
+        let generation = generation + 1;
+        let session = self.connect_with_retry(generation).await?;
+        for interceptor in current.session.sql_interceptors() {
+            session.add_sql_interceptor(interceptor);
+        }
+
+        let old = {
+            let mut current = self.current.write().unwrap();
+            std::mem::replace(
+                &mut *current,
+                CurrentSession {
+                    session: session.clone(),
+                    generation,
+                    expired: false,
+                },
+            )
+        };
+        if let Err(e) = old.session.close().await {
+            debug!("ResilientSession: old session close error. {}", e);
+        }
+
+        self.reprepare_statements(&session, generation).await;
+
+        Ok((session, generation))
+    }
+
+    async fn connect_with_retry(&self, generation: u64) -> Result<Arc<Session>, TgError> {
+        let option = &self.reconnect_option;
+        let mut attempt = 0;
+        loop {
+            option.notify_event(ReconnectEvent::Reconnecting { attempt });
+            match Session::connect(&self.connection_option).await {
+                Ok(session) => {
+                    option.notify_event(ReconnectEvent::Reconnected {
+                        attempt,
+                        generation,
+                    });
+                    return Ok(session);
+                }
+                Err(e) => {
+                    option.notify_event(ReconnectEvent::ReconnectFailed {
+                        attempt,
+                        message: e.to_string(),
+                    });
+                    if !option.can_retry(attempt) {
+                        option.notify_event(ReconnectEvent::GaveUp {
+                            attempts: attempt + 1,
+                        });
+                        return Err(e);
+                    }
+
+                    let backoff = option.backoff(attempt);
+                    warn!(
+                        "ResilientSession: connect failed. retry after {:?}. attempt={}, error={}",
+                        backoff, attempt, e
+                    );
+                    tokio::time::sleep(backoff).await;
+                    attempt += 1;
+                }
+            }
+        }
+    }
+
+    async fn reprepare_statements(&self, session: &Arc<Session>, generation: u64) {
+        let statements: Vec<Arc<StatementState>> = {
+            let mut statements = self.statements.lock().unwrap();
+            statements.retain(|state| state.strong_count() > 0);
+            statements
+                .iter()
+                .filter_map(|state| state.upgrade())
+                .collect()
+        };
+
+        for state in statements {
+            if state.is_closed() {
+                continue;
+            }
+            match state.prepare_on(session, generation).await {
+                Ok(_) => self
+                    .reconnect_option
+                    .notify_event(ReconnectEvent::StatementReprepared {
+                        sql: state.sql.clone(),
+                    }),
+                Err(e) => {
+                    warn!(
+                        "ResilientSession: re-prepare failed. sql={}, error={}",
+                        state.sql, e
+                    );
+                    self.reconnect_option
+                        .notify_event(ReconnectEvent::StatementReprepareFailed {
+                            sql: state.sql.clone(),
+                            message: e.to_string(),
+                        });
+                }
+            }
+        }
+    }
+
+    /// Prepare a SQL statement.
+    ///
+    /// The statement is prepared again on the new connection after reconnection.
+    pub async fn prepare(
+        self: &Arc<Self>,
+        sql: &str,
+        placeholders: Vec<SqlPlaceholder>,
+    ) -> Result<ResilientPreparedStatement, TgError> {
+        let state = Arc::new(StatementState {
+            sql: sql.to_string(),
+            placeholders,
+            prepared: tokio::sync::Mutex::new(None),
+            closed: AtomicBool::new(false),
+        });
+
+        let (session, generation) = self.connected_session().await?;
+        state
+            .prepare_on(&session, generation)
+            .await
+            .map_err(|e| self.map_error(e, generation))?;
+
+        {
+            let mut statements = self.statements.lock().unwrap();
+            statements.retain(|state| state.strong_count() > 0);
+            statements.push(Arc::downgrade(&state));
+        }
+
+        Ok(ResilientPreparedStatement {
+            session: self.clone(),
+            state,
+        })
+    }
+
+    /// Start a new transaction.
+    ///
+    /// The transaction is bound to the current connection, and cannot be recovered after reconnection.
+    pub async fn start_transaction(
+        self: &Arc<Self>,
+        transaction_option: &TransactionOption,
+    ) -> Result<ResilientTransaction, TgError> {
+        let (session, generation) = self.connected_session().await?;
+        let client: SqlClient = session.make_client();
+        let transaction = client
+            .start_transaction(transaction_option)
+            .await
+            .map_err(|e| self.map_error(e, generation))?;
+
+        Ok(ResilientTransaction {
+            session: self.clone(),
+            client,
+            transaction,
+            generation,
+            lost: AtomicBool::new(false),
+        })
+    }
+
+    fn map_error(&self, error: TgError, generation: u64) -> TgError {
+        self.report_error_on(&error, generation);
+        error
+    }
+
+    /// Disposes the current session and the prepared statements.
+    pub async fn close(&self) -> Result<(), TgError> {
+        if self
+            .closed
+            .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+            .is_err()
+        {
+            return Ok(());
+        }
+
+        let _guard = self.reconnect_lock.lock().await;
+        let statements: Vec<Arc<StatementState>> = {
+            let mut statements = self.statements.lock().unwrap();
+            statements
+                .drain(..)
+                .filter_map(|state| state.upgrade())
+                .collect()
+        };
+        for state in statements {
+            if let Err(e) = state.close().await {
+                debug!("ResilientSession: statement close error. {}", e);
+            }
+        }
+
+        let current = self.current();
+        current.session.close().await
+    }
+
+    /// Check if the session is closed.
+    pub fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::SeqCst)
+    }
+
+    fn check_closed(&self) -> Result<(), TgError> {
+        if self.is_closed() {
+            Err(client_error!("ResilientSession already closed"))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Returns true if the error means that the connection can no longer be used.
+fn is_connection_lost(error: &TgError) -> bool {
+    match error {
+        TgError::IoError(..) => true,
+        _ => matches!(
+            error.core_service_code(),
+            Some(CoreServiceCode::SessionClosed | CoreServiceCode::SessionExpired)
+        ),
+    }
+}
+
+struct StatementState {
+    sql: String,
+    placeholders: Vec<SqlPlaceholder>,
+    prepared: tokio::sync::Mutex<Option<(u64, Arc<SqlPreparedStatement>)>>,
+    closed: AtomicBool,
+}
+
+impl StatementState {
+    /// Returns the prepared statement of the generation.
+    ///
+    /// Returns None if the statement has been prepared on a newer connection.
+    async fn prepare_on(
+        &self,
+        session: &Arc<Session>,
+        generation: u64,
+    ) -> Result<Option<Arc<SqlPreparedStatement>>, TgError> {
+        let mut prepared = self.prepared.lock().await;
+        if self.is_closed() {
+            return Err(client_error!("ResilientPreparedStatement already closed"));
+        }
+        match &*prepared {
+            Some((g, statement)) if *g == generation => return Ok(Some(statement.clone())),
+            Some((g, _)) if *g > generation => return Ok(None),
+            _ => {}
+        }
+
+        if let Some((_, old)) = prepared.take() {
+            if let Err(e) = old.close().await {
+                debug!(
+                    "ResilientPreparedStatement: old statement close error. {}",
+                    e
+                );
+            }
+        }
+
+        let client: SqlClient = session.make_client();
+        let statement = Arc::new(client.prepare(&self.sql, self.placeholders.clone()).await?);
+        *prepared = Some((generation, statement.clone()));
+        Ok(Some(statement))
+    }
+
+    async fn close(&self) -> Result<(), TgError> {
+        let mut prepared = self.prepared.lock().await;
+        self.closed.store(true, Ordering::SeqCst);
+        match prepared.take() {
+            Some((_, statement)) => statement.close().await,
+            None => Ok(()),
+        }
+    }
+
+    fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::SeqCst)
+    }
+}
+
+/// Prepared statement of [ResilientSession].
+///
+/// The statement is prepared again on the new connection after reconnection.
+///
+/// since 0.11.0
+pub struct ResilientPreparedStatement {
+    session: Arc<ResilientSession>,
+    state: Arc<StatementState>,
+}
+
+impl std::fmt::Debug for ResilientPreparedStatement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ResilientPreparedStatement")
+            .field("sql", &self.state.sql)
+            .field("placeholders", &self.state.placeholders)
+            .field("closed", &self.state.closed)
+            .finish()
+    }
+}
+
+impl ResilientPreparedStatement {
+    /// Get the SQL statement.
+    pub fn sql(&self) -> &str {
+        &self.state.sql
+    }
+
+    /// Get the placeholders.
+    pub fn placeholders(&self) -> &Vec<SqlPlaceholder> {
+        &self.state.placeholders
+    }
+
+    /// Get the prepared statement on the current session.
+    ///
+    /// Reconnect if the connection is broken or expired, and prepare the statement again if necessary.
+    pub async fn prepared_statement(&self) -> Result<Arc<SqlPreparedStatement>, TgError> {
+        loop {
+            let (session, generation) = self.session.connected_session().await?;
+            match self.state.prepare_on(&session, generation).await {
+                Ok(Some(statement)) => return Ok(statement),
+                Ok(None) => continue,
+                Err(e) => return Err(self.session.map_error(e, generation)),
+            }
+        }
+    }
+
+    /// Disposes the prepared statement.
+    pub async fn close(&self) -> Result<(), TgError> {
+        self.state.close().await
+    }
+
+    /// Check if the prepared statement is closed.
+    pub fn is_closed(&self) -> bool {
+        self.state.is_closed()
+    }
+}
+
+/// Transaction of [ResilientSession].
+///
+/// The transaction is bound to the connection on which it was started.
+/// If the connection is lost, all operations fail with `ClientError`,
+/// because the transaction cannot be recovered.
+///
+/// Note: Should invoke [`Self::close`] before [`Self::drop`] to dispose the transaction.
+///
+/// since 0.11.0
+pub struct ResilientTransaction {
+    session: Arc<ResilientSession>,
+    client: SqlClient,
+    transaction: Transaction,
+    generation: u64,
+    lost: AtomicBool,
+}
+
+impl std::fmt::Debug for ResilientTransaction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ResilientTransaction")
+            .field("transaction", &self.transaction)
+            .field("generation", &self.generation)
+            .field("lost", &self.lost)
+            .finish()
+    }
+}
+
+impl ResilientTransaction {
+    /// Get the transaction.
+    pub fn transaction(&self) -> &Transaction {
+        &self.transaction
+    }
+
+    /// Get transaction id.
+    pub fn transaction_id(&self) -> &String {
+        self.transaction.transaction_id()
+    }
+
+    /// Get the SQL client bound to the connection of the transaction.
+    pub fn client(&self) -> &SqlClient {
+        &self.client
+    }
+
+    /// Check if the connection of the transaction is lost.
+    pub fn is_lost(&self) -> bool {
+        if self.lost.load(Ordering::SeqCst) {
+            return true;
+        }
+
+        let current = self.session.current();
+        if current.generation != self.generation || current.needs_reconnect() {
+            self.set_lost();
+            return true;
+        }
+        false
+    }
+
+    fn set_lost(&self) {
+        if !self.lost.swap(true, Ordering::SeqCst) {
+            self.session
+                .reconnect_option
+                .notify_event(ReconnectEvent::TransactionLost {
+                    transaction_id: self.transaction_id().clone(),
+                });
+        }
+    }
+
+    fn lost_error(&self, cause: Option<TgError>) -> TgError {
+        let message = format!(
+            "ResilientTransaction: connection lost. the transaction cannot be recovered. transaction_id={}",
+            self.transaction_id()
+        );
+        match cause {
+            Some(cause) => client_error!(message, cause),
+            None => client_error!(message),
+        }
+    }
+
+    fn check_lost(&self) -> Result<(), TgError> {
+        if self.is_lost() {
+            Err(self.lost_error(None))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn map_error(&self, error: TgError) -> TgError {
+        if is_connection_lost(&error) {
+            self.session.report_error_on(&error, self.generation);
+            self.set_lost();
+            self.lost_error(Some(error))
+        } else {
+            error
+        }
+    }
+
+    async fn prepared_statement(
+        &self,
+        statement: &ResilientPreparedStatement,
+    ) -> Result<Arc<SqlPreparedStatement>, TgError> {
+        let session = self.session.current().session;
+        match statement.state.prepare_on(&session, self.generation).await {
+            Ok(Some(statement)) => Ok(statement),
+            Ok(None) => {
+                self.set_lost();
+                Err(self.lost_error(None))
+            }
+            Err(e) => Err(self.map_error(e)),
+        }
+    }
+
+    /// Executes a SQL statement.
+    pub async fn execute(&self, sql: &str) -> Result<SqlExecuteResult, TgError> {
+        self.check_lost()?;
+        self.client
+            .execute(&self.transaction, sql)
+            .await
+            .map_err(|e| self.map_error(e))
+    }
+
+    /// Executes a SQL statement and retrieve its result.
+    pub async fn query(&self, sql: &str) -> Result<SqlQueryResult, TgError> {
+        self.check_lost()?;
+        self.client
+            .query(&self.transaction, sql)
+            .await
+            .map_err(|e| self.map_error(e))
+    }
+
+    /// Executes a prepared statement.
+    pub async fn prepared_execute(
+        &self,
+        prepared_statement: &ResilientPreparedStatement,
+        parameters: Vec<SqlParameter>,
+    ) -> Result<SqlExecuteResult, TgError> {
+        self.check_lost()?;
+        let statement = self.prepared_statement(prepared_statement).await?;
+        self.client
+            .prepared_execute(&self.transaction, &statement, parameters)
+            .await
+            .map_err(|e| self.map_error(e))
+    }
+
+    /// Executes a prepared statement and retrieve its result.
+    pub async fn prepared_query(
+        &self,
+        prepared_statement: &ResilientPreparedStatement,
+        parameters: Vec<SqlParameter>,
+    ) -> Result<SqlQueryResult, TgError> {
+        self.check_lost()?;
+        let statement = self.prepared_statement(prepared_statement).await?;
+        self.client
+            .prepared_query(&self.transaction, &statement, parameters)
+            .await
+            .map_err(|e| self.map_error(e))
+    }
+
+    /// Request commit.
+    pub async fn commit(&self, commit_option: &CommitOption) -> Result<(), TgError> {
+        self.check_lost()?;
+        self.client
+            .commit(&self.transaction, commit_option)
+            .await
+            .map_err(|e| self.map_error(e))
+    }
+
+    /// Request rollback.
+    pub async fn rollback(&self) -> Result<(), TgError> {
+        self.check_lost()?;
+        self.client
+            .rollback(&self.transaction)
+            .await
+            .map_err(|e| self.map_error(e))
+    }
+
+    /// Disposes the transaction.
+    ///
+    /// If the connection is lost, the transaction is discarded without error.
+    pub async fn close(&self) -> Result<(), TgError> {
+        if self.is_lost() {
+            if let Err(e) = self.transaction.close().await {
+                debug!("ResilientTransaction: close error. {}", e);
+            }
+            return Ok(());
+        }
+        self.transaction
+            .close()
+            .await
+            .map_err(|e| self.map_error(e))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        io_error,
+        jogasaki::proto::sql::{
+            common::{
+                PreparedStatement as ProtoPreparedStatement, Transaction as ProtoTransaction,
+                TransactionId,
+            },
+            request::request::Request as SqlCommand,
+            response::{
+                begin, prepare, response::Response as SqlResponseType, Begin as BeginResponse,
+                Prepare as PrepareResponse,
+            },
+        },
+        session::tcp::test_server::{TestConnection, TestServer},
+        timeout_error,
+    };
+
+    #[test]
+    fn connection_lost() {
+        assert!(is_connection_lost(&io_error!("TcpLink is broken")));
+        assert!(!is_connection_lost(&timeout_error!("timeout")));
+        assert!(!is_connection_lost(&client_error!("example")));
+    }
+
+    fn record_events(reconnect_option: &mut ReconnectOption) -> Arc<Mutex<Vec<ReconnectEvent>>> {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let sink = events.clone();
+        reconnect_option.set_event_callback(Arc::new(move |event: &ReconnectEvent| {
+            sink.lock().unwrap().push(event.clone());
+        }));
+        events
+    }
+
+    async fn connect(
+        server: &TestServer,
+    ) -> (
+        Arc<ResilientSession>,
+        TestConnection,
+        Arc<Mutex<Vec<ReconnectEvent>>>,
+    ) {
+        let mut connection_option = ConnectionOption::new();
+        connection_option.set_endpoint(server.endpoint().clone());
+        let mut reconnect_option = ReconnectOption::new();
+        let events = record_events(&mut reconnect_option);
+
+        let (session, connection) = tokio::join!(
+            ResilientSession::connect(&connection_option, reconnect_option),
+            server.accept_session(1)
+        );
+        (session.unwrap(), connection, events)
+    }
+
+    async fn answer_prepare(connection: &mut TestConnection, handle: u64) {
+        let (slot, command) = connection.read_sql_request().await;
+        assert!(matches!(command, SqlCommand::Prepare(_)));
+        let response = SqlResponseType::Prepare(PrepareResponse {
+            result: Some(prepare::Result::PreparedStatementHandle(
+                ProtoPreparedStatement {
+                    handle,
+                    has_result_records: false,
+                },
+            )),
+        });
+        connection.write_sql_response(slot, response).await;
+    }
+
+    async fn answer_begin(connection: &mut TestConnection, transaction_id: &str) {
+        let (slot, command) = connection.read_sql_request().await;
+        assert!(matches!(command, SqlCommand::Begin(_)));
+        let response = SqlResponseType::Begin(BeginResponse {
+            result: Some(begin::Result::Success(begin::Success {
+                transaction_handle: Some(ProtoTransaction {
+                    handle: 1,
+                    ..Default::default()
+                }),
+                transaction_id: Some(TransactionId {
+                    id: transaction_id.to_string(),
+                }),
+            })),
+        });
+        connection.write_sql_response(slot, response).await;
+    }
+
+    #[tokio::test]
+    async fn reconnect_after_io_error() {
+        let server = TestServer::start().await;
+        let (session, mut connection, events) = connect(&server).await;
+
+        let sql = "select * from test";
+        let (statement, _) = tokio::join!(
+            session.prepare(sql, vec![]),
+            answer_prepare(&mut connection, 1)
+        );
+        let statement = statement.unwrap();
+        let prepared = statement.prepared_statement().await.unwrap();
+        assert_eq!(1, prepared.prepare_handle());
+
+        // the server goes away
+        drop(connection);
+        let error = session.prepare("select 1", vec![]).await.unwrap_err();
+        assert!(matches!(error, TgError::IoError(..)), "{error:?}");
+        assert_eq!(0, session.generation());
+
+        // the next operation reconnects, and prepares the statement again
+        let (prepared, _connection) = tokio::join!(statement.prepared_statement(), async {
+            let mut connection = server.accept_session(2).await;
+            answer_prepare(&mut connection, 2).await;
+            connection
+        });
+        assert_eq!(2, prepared.unwrap().prepare_handle());
+        assert_eq!(1, session.generation());
+
+        let expected = vec![
+            ReconnectEvent::Disconnected { generation: 0 },
+            ReconnectEvent::Reconnecting { attempt: 0 },
+            ReconnectEvent::Reconnected {
+                attempt: 0,
+                generation: 1,
+            },
+            ReconnectEvent::StatementReprepared {
+                sql: sql.to_string(),
+            },
+        ];
+        assert_eq!(expected, *events.lock().unwrap());
+
+        // prepared on the current generation
+        let prepared = statement.prepared_statement().await.unwrap();
+        assert_eq!(2, prepared.prepare_handle());
+    }
+
+    #[tokio::test]
+    async fn transaction_lost_after_reconnect() {
+        let server = TestServer::start().await;
+        let (session, mut connection, events) = connect(&server).await;
+
+        let transaction_option = TransactionOption::default();
+        let (transaction, _) = tokio::join!(
+            session.start_transaction(&transaction_option),
+            answer_begin(&mut connection, "tx1")
+        );
+        let transaction = transaction.unwrap();
+        assert!(!transaction.is_lost());
+
+        let (result, _connection) = tokio::join!(session.reconnect(), server.accept_session(2));
+        result.unwrap();
+        assert_eq!(1, session.generation());
+
+        // the transaction is not sent to the new connection
+        let error = transaction
+            .execute("update test set value = 1")
+            .await
+            .unwrap_err();
+        assert!(matches!(error, TgError::ClientError(..)), "{error:?}");
+        assert!(error.message().contains("connection lost"), "{error}");
+        assert!(transaction.is_lost());
+        let error = transaction
+            .commit(&CommitOption::default())
+            .await
+            .unwrap_err();
+        assert!(matches!(error, TgError::ClientError(..)), "{error:?}");
+
+        // discarded without error
+        transaction.close().await.unwrap();
+
+        let transaction_lost = ReconnectEvent::TransactionLost {
+            transaction_id: "tx1".to_string(),
+        };
+        let events = events.lock().unwrap();
+        assert_eq!(
+            1,
+            events
+                .iter()
+                .filter(|event| **event == transaction_lost)
+                .count()
+        );
+    }
+}
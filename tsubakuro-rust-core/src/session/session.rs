@@ -9,7 +9,7 @@ use crate::{
     error::TgError,
     illegal_argument_error,
     job::Job,
-    prelude::{Endpoint, ShutdownType},
+    prelude::{CredentialSource, Endpoint, ShutdownType, SqlQueryPrefetchOption},
     service::{
         core::core_service::CoreService,
         lob::{
@@ -17,6 +17,7 @@ use crate::{
             privileged::path_mapping::{LargeObjectRecvPathMapping, LargeObjectSendPathMapping},
             relay::client::RelayLobClientOption,
         },
+        sql::interceptor::{SqlInterceptor, SqlInterceptors},
         ServiceClient,
     },
     session::lob_transfer_type::LobTransferType,
@@ -51,12 +52,16 @@ use super::{option::ConnectionOption, tcp::connector::TcpConnector, wire::Wire};
 #[derive(Debug)]
 pub struct Session {
     wire: Arc<Wire>,
+    endpoint: Endpoint,
+    credential_source: CredentialSource,
     lob_send_path_mapping: Arc<LargeObjectSendPathMapping>,
     lob_recv_path_mapping: Arc<LargeObjectRecvPathMapping>,
     relay_lob_client_option: RelayLobClientOption,
     default_timeout: RwLock<Duration>,
+    query_prefetch_option: Option<SqlQueryPrefetchOption>,
     shutdowned: AtomicBool,
     fail_on_drop_error: AtomicBool,
+    sql_interceptors: RwLock<SqlInterceptors>,
 }
 
 impl Session {
@@ -123,9 +128,39 @@ impl Session {
             .endpoint()
             .ok_or(illegal_argument_error!("endpoint not specified"))?;
 
+        let kind = std::mem::discriminant(endpoint);
+        if option
+            .endpoints()
+            .iter()
+            .any(|e| std::mem::discriminant(e) != kind)
+        {
+            return Err(illegal_argument_error!(
+                "endpoints of different kinds are specified"
+            ));
+        }
+
         Ok(endpoint)
     }
 
+    /// Get the endpoint actually connected.
+    ///
+    /// When multiple endpoints are specified in [`ConnectionOption`], this is the one selected.
+    ///
+    /// since 0.11.0
+    pub fn endpoint(&self) -> &Endpoint {
+        &self.endpoint
+    }
+
+    /// Get the source of the credential used to connect.
+    ///
+    /// When [credential discovery](ConnectionOption::set_credential_discovery) is enabled,
+    /// this tells where the credential was found.
+    ///
+    /// since 0.11.0
+    pub fn credential_source(&self) -> &CredentialSource {
+        &self.credential_source
+    }
+
     /// Get user name.
     ///
     /// since 0.5.0
@@ -175,6 +210,15 @@ impl Session {
         *default_timeout
     }
 
+    /// Get query prefetch option.
+    ///
+    /// See [ConnectionOption::set_query_prefetch_option()].
+    ///
+    /// since 0.11.0
+    pub fn query_prefetch_option(&self) -> Option<&SqlQueryPrefetchOption> {
+        self.query_prefetch_option.as_ref()
+    }
+
     /// Creates a service client.
     ///
     /// # Examples
@@ -302,6 +346,37 @@ impl Session {
         self.wire.is_closed()
     }
 
+    /// Add an interceptor to SqlClients.
+    ///
+    /// The interceptor is applied to SqlClients created by [Self::make_client] after this call.
+    ///
+    /// since 0.11.0
+    pub fn add_sql_interceptor(&self, interceptor: Arc<dyn SqlInterceptor>) {
+        let mut sql_interceptors = self.sql_interceptors.write().unwrap();
+        sql_interceptors.add(interceptor);
+    }
+
+    /// Get the interceptors of SqlClients.
+    ///
+    /// since 0.11.0
+    pub fn sql_interceptors(&self) -> Vec<Arc<dyn SqlInterceptor>> {
+        let sql_interceptors = self.sql_interceptors.read().unwrap();
+        sql_interceptors.list().clone()
+    }
+
+    pub(crate) fn sql_interceptor_list(&self) -> SqlInterceptors {
+        self.sql_interceptors.read().unwrap().clone()
+    }
+
+    /// Check if the connection of the session is broken.
+    ///
+    /// A broken session can no longer be used, and must be reconnected.
+    ///
+    /// since 0.11.0
+    pub fn is_broken(&self) -> bool {
+        self.wire.is_broken()
+    }
+
     /// for debug
     #[doc(hidden)]
     pub fn set_fail_on_drop_error(&self, value: bool) {
@@ -318,6 +393,8 @@ impl Session {
 impl Session {
     pub(crate) fn new(
         wire: Arc<Wire>,
+        endpoint: Endpoint,
+        credential_source: CredentialSource,
         connection_option: &ConnectionOption,
         default_timeout: Duration,
     ) -> Arc<Self> {
@@ -328,6 +405,8 @@ impl Session {
 
         let session = Arc::new(Session {
             wire,
+            endpoint,
+            credential_source,
             lob_send_path_mapping: Arc::new(
                 connection_option
                     .large_object_path_mapping_on_send()
@@ -340,8 +419,10 @@ impl Session {
             ),
             relay_lob_client_option,
             default_timeout: RwLock::new(default_timeout),
+            query_prefetch_option: connection_option.query_prefetch_option().cloned(),
             shutdowned: AtomicBool::new(false),
             fail_on_drop_error: AtomicBool::new(false),
+            sql_interceptors: RwLock::new(SqlInterceptors::default()),
         });
 
         let keep_alive = connection_option.keep_alive();
@@ -352,7 +433,7 @@ impl Session {
                 loop {
                     tokio::time::sleep(keep_alive).await;
 
-                    if wire.is_closed() {
+                    if wire.is_closed() || wire.is_draining() {
                         trace!("session.keep_alive end");
                         break;
                     }
@@ -1,10 +1,11 @@
 use std::sync::Arc;
 use std::time::Duration;
 
-use crate::client_error;
+use log::debug;
+
 use crate::error::TgError;
 use crate::job::Job;
-use crate::prelude::{ConnectionOption, Credential};
+use crate::prelude::{ConnectionOption, Credential, CredentialSource, Endpoint};
 use crate::session::wire::DelegateWire;
 use crate::session::{wire::Wire, Session};
 use crate::tateyama::proto::endpoint::request::BlobTransferMedium;
@@ -12,6 +13,7 @@ use crate::tateyama::proto::endpoint::request::{
     wire_information::StreamInformation, wire_information::WireInformation as WireInformationType,
     ClientInformation, Credential as ProtoCredential, WireInformation,
 };
+use crate::{client_error, illegal_argument_error, io_error};
 
 use crate::service::endpoint::endpoint_broker::EndpointBroker;
 use crate::util::string_to_prost_string;
@@ -27,9 +29,9 @@ impl TcpConnector {
         timeout: Duration,
         default_timeout: Duration,
     ) -> Result<Arc<Session>, TgError> {
-        let wire = TcpConnector::create_wire(connection_option).await?;
+        let (wire, endpoint) = TcpConnector::create_wire(connection_option).await?;
 
-        let client_information =
+        let (client_information, credential_source) =
             Self::create_client_information(&wire, connection_option, timeout).await?;
         let blob_transfer_media = Self::create_blob_transfer_media(connection_option);
         let wire_information = Self::create_wire_information();
@@ -44,16 +46,22 @@ impl TcpConnector {
 
         wire.initialize(result)?;
 
-        Ok(Session::new(wire, connection_option, default_timeout))
+        Ok(Session::new(
+            wire,
+            endpoint,
+            credential_source,
+            connection_option,
+            default_timeout,
+        ))
     }
 
     pub(crate) async fn connect_async(
         connection_option: &ConnectionOption,
         default_timeout: Duration,
     ) -> Result<Job<Arc<Session>>, TgError> {
-        let wire = TcpConnector::create_wire(connection_option).await?;
+        let (wire, endpoint) = TcpConnector::create_wire(connection_option).await?;
 
-        let client_information =
+        let (client_information, credential_source) =
             Self::create_client_information(&wire, connection_option, default_timeout).await?;
         let blob_transfer_media = Self::create_blob_transfer_media(connection_option);
         let wire_information = Self::create_wire_information();
@@ -67,6 +75,8 @@ impl TcpConnector {
                 wire.initialize(result)?;
                 Ok(Session::new(
                     wire.clone(),
+                    endpoint.clone(),
+                    credential_source.clone(),
                     &connection_option,
                     default_timeout,
                 ))
@@ -78,29 +88,63 @@ impl TcpConnector {
         Ok(job)
     }
 
-    async fn create_wire(connection_option: &ConnectionOption) -> Result<Arc<Wire>, TgError> {
-        let link = TcpLink::connect(connection_option).await?;
+    async fn create_wire(
+        connection_option: &ConnectionOption,
+    ) -> Result<(Arc<Wire>, Endpoint), TgError> {
+        let link = Self::connect_link(connection_option).await?;
+        let endpoint = link.endpoint().clone();
         let wire = TcpWire::new(link);
         let wire = Wire::new(DelegateWire::Tcp(Arc::new(wire)));
-        Ok(wire)
+        Ok((wire, endpoint))
+    }
+
+    async fn connect_link(connection_option: &ConnectionOption) -> Result<TcpLink, TgError> {
+        let policy = connection_option.endpoint_selection_policy();
+        let endpoints = policy.arrange(connection_option.endpoints());
+
+        let mut errors = Vec::with_capacity(endpoints.len());
+        for endpoint in endpoints {
+            match TcpLink::connect(connection_option, endpoint).await {
+                Ok(link) => return Ok(link),
+                Err(e) => {
+                    debug!("TcpConnector connect error. endpoint={}, {}", endpoint, e);
+                    errors.push((endpoint, e));
+                }
+            }
+        }
+
+        match errors.len() {
+            0 => Err(illegal_argument_error!("endpoint not specified")),
+            // a single endpoint keeps the original error
+            1 => Err(errors.remove(0).1),
+            _ => {
+                let messages: Vec<String> = errors
+                    .iter()
+                    .map(|(endpoint, e)| format!("{endpoint}: {e}"))
+                    .collect();
+                Err(io_error!(format!(
+                    "TcpLink connect error for all endpoints. [{}]",
+                    messages.join(", ")
+                )))
+            }
+        }
     }
 
     async fn create_client_information(
         wire: &Arc<Wire>,
         option: &ConnectionOption,
         timeout: Duration,
-    ) -> Result<ClientInformation, TgError> {
-        Ok(ClientInformation {
+    ) -> Result<(ClientInformation, CredentialSource), TgError> {
+        let (credential, source) = option.resolve_credential()?;
+        debug!("TcpConnector credential source: {}", source);
+
+        let client_information = ClientInformation {
             connection_label: string_to_prost_string(option.session_label()),
             application_name: string_to_prost_string(option.application_name()),
-            credential: to_proto_credential(
-                wire,
-                option.credential(),
-                option.validity_period(),
-                timeout,
-            )
-            .await?,
-        })
+            credential: to_proto_credential(wire, &credential, option.validity_period(), timeout)
+                .await?,
+        };
+        Ok((client_information, source))
     }
 
     fn create_blob_transfer_media(option: &ConnectionOption) -> Vec<BlobTransferMedium> {
@@ -160,3 +204,67 @@ async fn to_proto_credential(
         })),
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    async fn closed_port() -> u16 {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        listener.local_addr().unwrap().port()
+    }
+
+    #[tokio::test]
+    async fn connect_link_single_endpoint() {
+        let mut option = ConnectionOption::new();
+        option.set_endpoint(Endpoint::Tcp("127.0.0.1".to_string(), closed_port().await));
+
+        let error = TcpConnector::connect_link(&option).await.unwrap_err();
+        match error {
+            TgError::IoError(message, cause) => {
+                assert_eq!("TcpLink connect error", message);
+                assert!(cause.is_some());
+            }
+            e => panic!("unexpected error. {:?}", e),
+        }
+    }
+
+    #[tokio::test]
+    async fn connect_link_multiple_endpoints() {
+        let mut option = ConnectionOption::new();
+        option.add_endpoint(Endpoint::Tcp("127.0.0.1".to_string(), closed_port().await));
+        option.add_endpoint(Endpoint::Tcp("127.0.0.1".to_string(), closed_port().await));
+
+        let error = TcpConnector::connect_link(&option).await.unwrap_err();
+        match error {
+            TgError::IoError(message, _) => {
+                assert!(message.starts_with("TcpLink connect error for all endpoints."));
+            }
+            e => panic!("unexpected error. {:?}", e),
+        }
+    }
+
+    #[tokio::test]
+    async fn connect_credential_source() {
+        use crate::session::tcp::test_server::TestServer;
+
+        let server = TestServer::start().await;
+        let mut option = ConnectionOption::new();
+        option.set_endpoint(server.endpoint().clone());
+
+        let (session, _connection) =
+            tokio::join!(Session::connect(&option), server.accept_session(1));
+        assert_eq!(
+            &CredentialSource::None,
+            session.unwrap().credential_source()
+        );
+
+        option.set_credential(Credential::from_auth_token("token"));
+        let (session, _connection) =
+            tokio::join!(Session::connect(&option), server.accept_session(2));
+        assert_eq!(
+            &CredentialSource::Explicit,
+            session.unwrap().credential_source()
+        );
+    }
+}
@@ -0,0 +1,596 @@
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+    time::Duration,
+};
+
+use log::debug;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+use crate::{
+    error::TgError,
+    io_error,
+    session::{
+        tcp::{r#enum::TcpResponseInfo, wire::TcpWire},
+        wire::link::LinkMessage,
+    },
+};
+
+/// Frames to which a fault is injected.
+///
+/// since 0.11.0
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkFaultTarget {
+    /// Frames sent to the server.
+    Send,
+    /// Frames received from the server, except the result set frames.
+    Recv,
+    /// Result set (data channel) frames received from the server.
+    RecvResultSet,
+}
+
+/// Fault injected into a frame.
+///
+/// since 0.11.0
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkFault {
+    /// Delays the frame.
+    ///
+    /// The delay is included in the send timeout and recv timeout of [ConnectionOption](crate::prelude::ConnectionOption).
+    Delay(Duration),
+    /// Drops the frame.
+    ///
+    /// The frame is not sent, or the received frame is discarded.
+    Drop,
+    /// Truncates the frame to the specified number of bytes.
+    ///
+    /// For received frames, the payload is truncated.
+    Truncate(usize),
+    /// Resets the connection.
+    ///
+    /// The send or recv fails with an I/O error, and the link becomes broken.
+    Reset,
+}
+
+/// Rule of fault injection.
+///
+/// See [LinkFaultInjector::add_rule()].
+///
+/// since 0.11.0
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkFaultRule {
+    target: LinkFaultTarget,
+    fault: LinkFault,
+    skip: usize,
+    times: Option<usize>,
+}
+
+impl LinkFaultRule {
+    /// Creates a new instance.
+    ///
+    /// By default, the fault is injected into every frame of `target`.
+    pub fn new(target: LinkFaultTarget, fault: LinkFault) -> LinkFaultRule {
+        LinkFaultRule {
+            target,
+            fault,
+            skip: 0,
+            times: None,
+        }
+    }
+
+    /// Creates a rule which delays every result set frame.
+    pub fn slow_data_channel(delay: Duration) -> LinkFaultRule {
+        LinkFaultRule::new(LinkFaultTarget::RecvResultSet, LinkFault::Delay(delay))
+    }
+
+    /// Get target.
+    pub fn target(&self) -> LinkFaultTarget {
+        self.target
+    }
+
+    /// Get fault.
+    pub fn fault(&self) -> LinkFault {
+        self.fault
+    }
+
+    /// Set the number of frames to pass before the fault is injected.
+    pub fn set_skip(&mut self, skip: usize) {
+        self.skip = skip;
+    }
+
+    /// Get the number of frames to pass before the fault is injected.
+    pub fn skip(&self) -> usize {
+        self.skip
+    }
+
+    /// Set the number of times the fault is injected.
+    ///
+    /// If `None` is specified, the fault is injected without limit.
+    pub fn set_times(&mut self, times: Option<usize>) {
+        self.times = times;
+    }
+
+    /// Get the number of times the fault is injected.
+    pub fn times(&self) -> Option<usize> {
+        self.times
+    }
+}
+
+#[derive(Debug)]
+struct LinkFaultRuleState {
+    rule: LinkFaultRule,
+    matched: usize,
+    injected: usize,
+}
+
+impl LinkFaultRuleState {
+    fn next(&mut self, target: LinkFaultTarget) -> Option<LinkFault> {
+        if self.rule.target != target {
+            return None;
+        }
+
+        self.matched += 1;
+        if self.matched <= self.rule.skip {
+            return None;
+        }
+        if let Some(times) = self.rule.times {
+            if self.injected >= times {
+                return None;
+            }
+        }
+        self.injected += 1;
+        Some(self.rule.fault)
+    }
+}
+
+/// Injects faults into the TCP link, for tests.
+///
+/// The faults are injected by the rules, and by the byte limits to reset the connection.
+/// The rules and the limits can be changed while the session is in use.
+///
+/// Every rule of the target counts the frame, and the first rule which fires is applied.
+///
+/// Intended for tests only.
+/// Requires the `fault_injection` feature.
+///
+/// See [ConnectionOption::set_link_fault_injector()](crate::prelude::ConnectionOption::set_link_fault_injector).
+///
+/// # Examples
+/// ```no_run
+/// use std::{sync::Arc, time::Duration};
+/// use tsubakuro_rust_core::prelude::*;
+///
+/// #[cfg(feature = "fault_injection")]
+/// async fn example(mut connection_option: ConnectionOption) -> Result<(), TgError> {
+///     let fault_injector = Arc::new(LinkFaultInjector::new());
+///     connection_option.set_link_fault_injector(Some(fault_injector.clone()));
+///     connection_option.set_recv_timeout(Duration::from_secs(1));
+///
+///     let session = Session::connect(&connection_option).await?;
+///     let client: SqlClient = session.make_client();
+///
+///     // the next response is lost
+///     let mut rule = LinkFaultRule::new(LinkFaultTarget::Recv, LinkFault::Drop);
+///     rule.set_times(Some(1));
+///     fault_injector.add_rule(rule);
+///
+///     let result = client.list_tables_for(Duration::from_secs(1)).await;
+///     assert!(matches!(result, Err(TgError::TimeoutError(_))));
+///
+///     Ok(())
+/// }
+/// ```
+///
+/// since 0.11.0
+#[derive(Debug, Default)]
+pub struct LinkFaultInjector {
+    rules: Mutex<Vec<LinkFaultRuleState>>,
+    reset_after_sent_bytes: Mutex<Option<u64>>,
+    reset_after_received_bytes: Mutex<Option<u64>>,
+    sent_bytes: AtomicU64,
+    received_bytes: AtomicU64,
+    injected_count: AtomicU64,
+}
+
+impl LinkFaultInjector {
+    /// Creates a new instance.
+    pub fn new() -> LinkFaultInjector {
+        LinkFaultInjector::default()
+    }
+
+    /// Add a rule.
+    pub fn add_rule(&self, rule: LinkFaultRule) {
+        let mut rules = self.rules.lock().unwrap();
+        rules.push(LinkFaultRuleState {
+            rule,
+            matched: 0,
+            injected: 0,
+        });
+    }
+
+    /// Remove all rules and byte limits.
+    pub fn clear(&self) {
+        self.rules.lock().unwrap().clear();
+        *self.reset_after_sent_bytes.lock().unwrap() = None;
+        *self.reset_after_received_bytes.lock().unwrap() = None;
+    }
+
+    /// Set the number of bytes after which the connection is reset on send.
+    ///
+    /// The number is counted from the start of the connection. (See [Self::sent_bytes()])
+    /// The bytes up to the limit are sent, and the rest of the frame is not.
+    pub fn set_reset_after_sent_bytes(&self, bytes: Option<u64>) {
+        *self.reset_after_sent_bytes.lock().unwrap() = bytes;
+    }
+
+    /// Set the number of bytes after which the connection is reset on recv.
+    ///
+    /// The number is counted from the start of the connection. (See [Self::received_bytes()])
+    /// The frame which exceeds the limit is discarded.
+    pub fn set_reset_after_received_bytes(&self, bytes: Option<u64>) {
+        *self.reset_after_received_bytes.lock().unwrap() = bytes;
+    }
+
+    /// Get the number of bytes sent.
+    pub fn sent_bytes(&self) -> u64 {
+        self.sent_bytes.load(Ordering::SeqCst)
+    }
+
+    /// Get the number of bytes received.
+    pub fn received_bytes(&self) -> u64 {
+        self.received_bytes.load(Ordering::SeqCst)
+    }
+
+    /// Get the number of injected faults.
+    pub fn injected_count(&self) -> u64 {
+        self.injected_count.load(Ordering::SeqCst)
+    }
+
+    fn next_fault(&self, target: LinkFaultTarget) -> Option<LinkFault> {
+        let mut rules = self.rules.lock().unwrap();
+        let mut fault = None;
+        for rule in rules.iter_mut() {
+            let next = rule.next(target);
+            if fault.is_none() {
+                fault = next;
+            }
+        }
+        if fault.is_some() {
+            self.injected_count.fetch_add(1, Ordering::SeqCst);
+        }
+        fault
+    }
+
+    fn reset_error(&self, function_name: &str) -> TgError {
+        self.injected_count.fetch_add(1, Ordering::SeqCst);
+        io_error!(format!(
+            "{function_name}: connection reset by fault injection"
+        ))
+    }
+
+    pub(crate) async fn inject_send<W: AsyncWrite + Unpin>(
+        &self,
+        writer: &mut W,
+        frame: &[&[u8]],
+    ) -> Result<(), TgError> {
+        const FUNCTION_NAME: &str = "TcpLink.send()";
+
+        let length = frame.iter().map(|part| part.len()).sum::<usize>();
+
+        let limit = *self.reset_after_sent_bytes.lock().unwrap();
+        if let Some(limit) = limit {
+            let sent = self.sent_bytes();
+            if sent + length as u64 > limit {
+                let rest = limit.saturating_sub(sent) as usize;
+                debug!("{FUNCTION_NAME}: fault injection. reset after {rest} bytes");
+                self.write_frame(writer, frame, rest).await?;
+                return Err(self.reset_error(FUNCTION_NAME));
+            }
+        }
+
+        let fault = self.next_fault(LinkFaultTarget::Send);
+        debug!("{FUNCTION_NAME}: fault injection. fault={:?}", fault);
+        match fault {
+            None => self.write_frame(writer, frame, length).await,
+            Some(LinkFault::Delay(delay)) => {
+                tokio::time::sleep(delay).await;
+                self.write_frame(writer, frame, length).await
+            }
+            Some(LinkFault::Drop) => Ok(()),
+            Some(LinkFault::Truncate(size)) => {
+                self.write_frame(writer, frame, size.min(length)).await
+            }
+            Some(LinkFault::Reset) => Err(self.reset_error(FUNCTION_NAME)),
+        }
+    }
+
+    async fn write_frame<W: AsyncWrite + Unpin>(
+        &self,
+        writer: &mut W,
+        frame: &[&[u8]],
+        size: usize,
+    ) -> Result<(), TgError> {
+        let mut rest = size;
+        for part in frame {
+            let part = &part[..part.len().min(rest)];
+            writer
+                .write_all(part)
+                .await
+                .map_err(|e| io_error!("TcpLink.send(): send error", e))?;
+            rest -= part.len();
+        }
+        writer
+            .flush()
+            .await
+            .map_err(|e| io_error!("TcpLink.send(): flush error", e))?;
+        self.sent_bytes.fetch_add(size as u64, Ordering::SeqCst);
+        Ok(())
+    }
+
+    pub(crate) async fn inject_recv(
+        &self,
+        message: LinkMessage,
+    ) -> Result<Option<LinkMessage>, TgError> {
+        const FUNCTION_NAME: &str = "TcpLink.recv()";
+
+        let info = TcpResponseInfo::from(message.info());
+        let is_result_set = TcpWire::is_result_set_response(info);
+        let target = if is_result_set {
+            LinkFaultTarget::RecvResultSet
+        } else {
+            LinkFaultTarget::Recv
+        };
+
+        let mut payload = message.take_payload().await;
+        // info(1) + slot(2) + [writer(1)] + length(4) + payload
+        let header_length = if info == TcpResponseInfo::ResponseResultSetPayload {
+            8
+        } else {
+            7
+        };
+        let length = header_length + payload.as_ref().map_or(0, |payload| payload.len());
+
+        let limit = *self.reset_after_received_bytes.lock().unwrap();
+        if let Some(limit) = limit {
+            if self.received_bytes() + length as u64 > limit {
+                debug!("{FUNCTION_NAME}: fault injection. reset");
+                return Err(self.reset_error(FUNCTION_NAME));
+            }
+        }
+        self.received_bytes
+            .fetch_add(length as u64, Ordering::SeqCst);
+
+        let fault = self.next_fault(target);
+        debug!("{FUNCTION_NAME}: fault injection. fault={:?}", fault);
+        match fault {
+            None => {}
+            Some(LinkFault::Delay(delay)) => tokio::time::sleep(delay).await,
+            Some(LinkFault::Drop) => return Ok(None),
+            Some(LinkFault::Truncate(size)) => {
+                if let Some(payload) = payload.as_mut() {
+                    payload.truncate(size);
+                }
+            }
+            Some(LinkFault::Reset) => return Err(self.reset_error(FUNCTION_NAME)),
+        }
+
+        Ok(Some(LinkMessage::new(
+            message.info(),
+            payload,
+            message.slot(),
+            message.writer(),
+        )))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use prost::bytes::BytesMut;
+    use tokio::{io::AsyncReadExt, net::TcpListener};
+
+    use super::*;
+    use crate::{
+        prelude::{ConnectionOption, Endpoint},
+        session::tcp::link::TcpLink,
+    };
+
+    fn response(info: TcpResponseInfo, payload: &[u8]) -> LinkMessage {
+        LinkMessage::new(info.value(), Some(BytesMut::from(payload)), 1, 0)
+    }
+
+    #[test]
+    fn rule() {
+        let mut rule = LinkFaultRule::new(LinkFaultTarget::Send, LinkFault::Drop);
+        rule.set_skip(1);
+        rule.set_times(Some(2));
+        let mut state = LinkFaultRuleState {
+            rule,
+            matched: 0,
+            injected: 0,
+        };
+
+        assert_eq!(None, state.next(LinkFaultTarget::Recv));
+        assert_eq!(None, state.next(LinkFaultTarget::Send));
+        assert_eq!(Some(LinkFault::Drop), state.next(LinkFaultTarget::Send));
+        assert_eq!(Some(LinkFault::Drop), state.next(LinkFaultTarget::Send));
+        assert_eq!(None, state.next(LinkFaultTarget::Send));
+    }
+
+    #[tokio::test]
+    async fn send() {
+        let injector = LinkFaultInjector::new();
+        let mut rule = LinkFaultRule::new(LinkFaultTarget::Send, LinkFault::Truncate(4));
+        rule.set_times(Some(1));
+        injector.add_rule(rule);
+        let mut rule = LinkFaultRule::new(LinkFaultTarget::Send, LinkFault::Drop);
+        rule.set_skip(1);
+        rule.set_times(Some(1));
+        injector.add_rule(rule);
+
+        let mut writer = Vec::new();
+        let frame: &[&[u8]] = &[b"abc", b"def"];
+        injector.inject_send(&mut writer, frame).await.unwrap();
+        assert_eq!(b"abcd", writer.as_slice());
+        injector.inject_send(&mut writer, frame).await.unwrap();
+        assert_eq!(b"abcd", writer.as_slice());
+        injector.inject_send(&mut writer, frame).await.unwrap();
+        assert_eq!(b"abcdabcdef", writer.as_slice());
+        assert_eq!(10, injector.sent_bytes());
+        assert_eq!(2, injector.injected_count());
+
+        injector.set_reset_after_sent_bytes(Some(12));
+        let result = injector.inject_send(&mut writer, frame).await;
+        assert!(matches!(result, Err(TgError::IoError(_, _))));
+        assert_eq!(b"abcdabcdefab", writer.as_slice());
+        assert_eq!(12, injector.sent_bytes());
+
+        injector.clear();
+        injector.inject_send(&mut writer, frame).await.unwrap();
+        assert_eq!(18, injector.sent_bytes());
+    }
+
+    #[tokio::test]
+    async fn recv() {
+        let injector = LinkFaultInjector::new();
+        injector.add_rule(LinkFaultRule::new(
+            LinkFaultTarget::RecvResultSet,
+            LinkFault::Drop,
+        ));
+        let mut rule = LinkFaultRule::new(LinkFaultTarget::Recv, LinkFault::Truncate(2));
+        rule.set_times(Some(1));
+        injector.add_rule(rule);
+
+        let message = injector
+            .inject_recv(response(TcpResponseInfo::ResponseResultSetPayload, b"abc"))
+            .await
+            .unwrap();
+        assert!(message.is_none());
+        assert_eq!(11, injector.received_bytes());
+
+        let message = injector
+            .inject_recv(response(TcpResponseInfo::ResponseSessionPayload, b"abc"))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(b"ab", message.take_payload().await.unwrap().as_ref());
+
+        let message = injector
+            .inject_recv(response(TcpResponseInfo::ResponseSessionPayload, b"abc"))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(b"abc", message.take_payload().await.unwrap().as_ref());
+        assert_eq!(31, injector.received_bytes());
+
+        injector.set_reset_after_received_bytes(Some(32));
+        let result = injector
+            .inject_recv(response(TcpResponseInfo::ResponseSessionPayload, b"abc"))
+            .await;
+        assert!(matches!(result, Err(TgError::IoError(_, _))));
+        assert_eq!(3, injector.injected_count());
+    }
+
+    async fn connect(
+        fault_injector: &Arc<LinkFaultInjector>,
+        configure: impl FnOnce(&mut ConnectionOption),
+    ) -> (TcpLink, tokio::net::TcpStream) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let endpoint = Endpoint::Tcp("127.0.0.1".to_string(), port);
+
+        let mut connection_option = ConnectionOption::new();
+        connection_option.set_link_fault_injector(Some(fault_injector.clone()));
+        configure(&mut connection_option);
+
+        let link = TcpLink::connect(&connection_option, &endpoint)
+            .await
+            .unwrap();
+        let (server, _) = listener.accept().await.unwrap();
+        (link, server)
+    }
+
+    #[tokio::test]
+    async fn link_send_timeout() {
+        let fault_injector = Arc::new(LinkFaultInjector::new());
+        let (link, _server) = connect(&fault_injector, |option| {
+            option.set_send_timeout(Duration::from_millis(10));
+        })
+        .await;
+
+        fault_injector.add_rule(LinkFaultRule::new(
+            LinkFaultTarget::Send,
+            LinkFault::Delay(Duration::from_secs(1)),
+        ));
+        let result = link.send(1, b"header", b"payload").await;
+        assert!(matches!(result, Err(TgError::IoError(_, _))));
+        assert!(link.is_broken());
+    }
+
+    #[tokio::test]
+    async fn link_reset() {
+        let fault_injector = Arc::new(LinkFaultInjector::new());
+        let (link, mut server) = connect(&fault_injector, |_| {}).await;
+
+        fault_injector.set_reset_after_sent_bytes(Some(10));
+        let result = link.send(1, b"header", b"payload").await;
+        assert!(matches!(result, Err(TgError::IoError(_, _))));
+        assert!(link.is_broken());
+
+        let mut buffer = [0u8; 10];
+        server.read_exact(&mut buffer).await.unwrap();
+        assert_eq!(b"hea", &buffer[7..]);
+    }
+
+    #[tokio::test]
+    async fn job_response_dropped() {
+        use crate::{
+            jogasaki::proto::sql::common::Transaction as ProtoTransaction,
+            prelude::{SqlClient, Transaction},
+            session::tcp::test_server::TestServer,
+        };
+
+        let fault_injector = Arc::new(LinkFaultInjector::new());
+        let mut connection_option = ConnectionOption::new();
+        connection_option.set_link_fault_injector(Some(fault_injector.clone()));
+
+        let server = TestServer::start().await;
+        let (session, mut connection) = server.connect(&connection_option).await;
+        let client: SqlClient = session.make_client();
+        let transaction = Transaction::new(
+            session.clone(),
+            ProtoTransaction::default(),
+            "tx1".to_string(),
+            Duration::ZERO,
+        );
+
+        // the response is lost, so the job times out
+        let mut rule = LinkFaultRule::new(LinkFaultTarget::Recv, LinkFault::Drop);
+        rule.set_times(Some(1));
+        fault_injector.add_rule(rule);
+
+        let mut job = client.rollback_async(&transaction).await.unwrap();
+        let (slot, _) = connection.read_sql_request().await;
+        connection.write_sql_success(slot).await;
+        let result = job.take_for(Duration::from_millis(100)).await;
+        assert!(matches!(result, Err(TgError::TimeoutError(_))));
+        assert_eq!(1, fault_injector.injected_count());
+
+        // the slot is released when the cancel completes
+        let mut cancel_job = job.cancel_async().await.unwrap().unwrap();
+        let (cancel_slot, _, _) = connection.read_request().await;
+        assert_eq!(slot, cancel_slot);
+        connection.write_sql_success(slot).await;
+        assert!(cancel_job.wait(Duration::from_secs(10)).await.unwrap());
+        drop(cancel_job);
+
+        let mut job = client.rollback_async(&transaction).await.unwrap();
+        let (next_slot, _) = connection.read_sql_request().await;
+        assert_eq!(slot, next_slot);
+        connection.write_sql_success(next_slot).await;
+        job.take_for(Duration::from_secs(10)).await.unwrap();
+    }
+}
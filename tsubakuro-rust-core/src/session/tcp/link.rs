@@ -15,6 +15,8 @@ use crate::{
     session::{tcp::r#enum::TcpRequestInfo, wire::link::LinkMessage},
 };
 
+#[cfg(any(test, feature = "fault_injection"))]
+use super::fault_injection::LinkFaultInjector;
 use super::r#enum::TcpResponseInfo;
 
 pub(crate) struct TcpLink {
@@ -25,6 +27,8 @@ pub(crate) struct TcpLink {
     recv_timeout: Duration,
     broken: AtomicBool,
     closed: AtomicBool,
+    #[cfg(any(test, feature = "fault_injection"))]
+    fault_injector: Option<std::sync::Arc<LinkFaultInjector>>,
 }
 
 impl std::fmt::Debug for TcpLink {
@@ -40,20 +44,28 @@ impl std::fmt::Debug for TcpLink {
 }
 
 impl TcpLink {
-    pub(crate) async fn connect(connection_option: &ConnectionOption) -> Result<TcpLink, TgError> {
-        let endpoint = connection_option
-            .endpoint()
-            .ok_or(illegal_argument_error!("endpoint not specified"))?;
+    pub(crate) async fn connect(
+        connection_option: &ConnectionOption,
+        endpoint: &Endpoint,
+    ) -> Result<TcpLink, TgError> {
         let addr = if let Endpoint::Tcp(host, port) = endpoint {
             format!("{host}:{port}")
         } else {
             return Err(illegal_argument_error!("endpoint is not TCP"));
         };
 
-        trace!("TcpLink connect start");
-        let stream = TcpStream::connect(addr)
-            .await
-            .map_err(|e| io_error!("TcpLink connect error", e))?;
+        trace!("TcpLink connect start. endpoint={}", endpoint);
+        let connect_timeout = connection_option.connect_timeout();
+        let stream = if connect_timeout.is_zero() {
+            TcpStream::connect(addr)
+                .await
+                .map_err(|e| io_error!("TcpLink connect error", e))?
+        } else {
+            tokio::time::timeout(connect_timeout, TcpStream::connect(addr))
+                .await
+                .map_err(|_| io_error!("TcpLink connect timeout"))?
+                .map_err(|e| io_error!("TcpLink connect error", e))?
+        };
         trace!("TcpLink connect end");
         stream
             .set_nodelay(true)
@@ -68,9 +80,15 @@ impl TcpLink {
             recv_timeout: connection_option.recv_timeout(),
             broken: AtomicBool::new(false),
             closed: AtomicBool::new(false),
+            #[cfg(any(test, feature = "fault_injection"))]
+            fault_injector: connection_option.link_fault_injector().cloned(),
         })
     }
 
+    pub(crate) fn endpoint(&self) -> &Endpoint {
+        &self.endpoint
+    }
+
     pub(crate) async fn send(
         &self,
         slot: i32,
@@ -96,11 +114,12 @@ impl TcpLink {
         let result = {
             let timeout = self.send_timeout;
             if timeout.is_zero() {
-                Self::send_body(writer, &tcp_header, frame_header, payload).await
+                self.send_frame(writer, &tcp_header, frame_header, payload)
+                    .await
             } else {
                 let result = tokio::time::timeout(
                     timeout,
-                    Self::send_body(writer, &tcp_header, frame_header, payload),
+                    self.send_frame(writer, &tcp_header, frame_header, payload),
                 )
                 .await;
                 match result {
@@ -115,6 +134,22 @@ impl TcpLink {
         result
     }
 
+    async fn send_frame(
+        &self,
+        writer: &mut WriteHalf<TcpStream>,
+        tcp_header: &[u8],
+        frame_header: &[u8],
+        payload: &[u8],
+    ) -> Result<(), TgError> {
+        #[cfg(any(test, feature = "fault_injection"))]
+        if let Some(fault_injector) = &self.fault_injector {
+            return fault_injector
+                .inject_send(writer, &[tcp_header, frame_header, payload])
+                .await;
+        }
+        Self::send_body(writer, tcp_header, frame_header, payload).await
+    }
+
     async fn send_body(
         writer: &mut WriteHalf<TcpStream>,
         tcp_header: &[u8],
@@ -158,10 +193,10 @@ impl TcpLink {
         let result = {
             let timeout = self.send_timeout;
             if timeout.is_zero() {
-                Self::send_header_only_body(writer, &tcp_header).await
+                self.send_header_only_frame(writer, &tcp_header).await
             } else {
                 let result =
-                    tokio::time::timeout(timeout, Self::send_header_only_body(writer, &tcp_header))
+                    tokio::time::timeout(timeout, self.send_header_only_frame(writer, &tcp_header))
                         .await;
                 match result {
                     Ok(result) => result,
@@ -175,6 +210,18 @@ impl TcpLink {
         result
     }
 
+    async fn send_header_only_frame(
+        &self,
+        writer: &mut WriteHalf<TcpStream>,
+        tcp_header: &[u8],
+    ) -> Result<(), TgError> {
+        #[cfg(any(test, feature = "fault_injection"))]
+        if let Some(fault_injector) = &self.fault_injector {
+            return fault_injector.inject_send(writer, &[tcp_header]).await;
+        }
+        Self::send_header_only_body(writer, tcp_header).await
+    }
+
     async fn send_header_only_body(
         writer: &mut WriteHalf<TcpStream>,
         tcp_header: &[u8],
@@ -202,9 +249,9 @@ impl TcpLink {
         let result = {
             let timeout = self.recv_timeout;
             if timeout.is_zero() {
-                Self::recv_body(reader).await
+                self.recv_frame(reader).await
             } else {
-                let result = tokio::time::timeout(timeout, Self::recv_body(reader)).await;
+                let result = tokio::time::timeout(timeout, self.recv_frame(reader)).await;
                 match result {
                     Ok(result) => result,
                     Err(_) => Err(io_error!("TcpLink.recv() timeout")),
@@ -217,6 +264,21 @@ impl TcpLink {
         result
     }
 
+    async fn recv_frame(
+        &self,
+        reader: &mut ReadHalf<TcpStream>,
+    ) -> Result<Option<LinkMessage>, TgError> {
+        let link_message = Self::recv_body(reader).await?;
+        #[cfg(any(test, feature = "fault_injection"))]
+        if let Some(fault_injector) = &self.fault_injector {
+            return match link_message {
+                Some(link_message) => fault_injector.inject_recv(link_message).await,
+                None => Ok(None),
+            };
+        }
+        Ok(link_message)
+    }
+
     async fn recv_body(reader: &mut ReadHalf<TcpStream>) -> Result<Option<LinkMessage>, TgError> {
         let info = {
             let result = tokio::time::timeout(Duration::from_nanos(10), reader.read_u8()).await;
@@ -290,11 +352,11 @@ impl TcpLink {
         Ok(Some(link_message))
     }
 
-    fn set_broken(&self) {
+    pub(crate) fn set_broken(&self) {
         self.broken.store(true, std::sync::atomic::Ordering::SeqCst);
     }
 
-    fn is_broken(&self) -> bool {
+    pub(crate) fn is_broken(&self) -> bool {
         self.broken.load(std::sync::atomic::Ordering::SeqCst)
     }
 
@@ -2,6 +2,10 @@ pub(crate) mod connector;
 pub(crate) mod data_channel_box;
 pub(crate) mod data_channel_wire;
 pub(crate) mod r#enum;
+#[cfg(any(test, feature = "fault_injection"))]
+pub(crate) mod fault_injection;
 pub(crate) mod link;
+#[cfg(test)]
+pub(crate) mod test_server;
 #[allow(clippy::module_inception)]
 pub(crate) mod wire;
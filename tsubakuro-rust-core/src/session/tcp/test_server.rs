@@ -0,0 +1,151 @@
+//! A minimal server for unit tests, which speaks the TCP frame format of the session.
+
+use std::sync::Arc;
+
+use prost::Message;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+};
+
+use crate::{
+    jogasaki::proto::sql::{
+        request::{request::Request as SqlCommand, Request as SqlRequest},
+        response::{
+            response::Response as SqlResponseType, result_only, Response as SqlResponse,
+            ResultOnly, Success,
+        },
+    },
+    prelude::{ConnectionOption, CredentialSource, Endpoint, Session},
+    session::{
+        tcp::{link::TcpLink, wire::TcpWire},
+        wire::{DelegateWire, Wire},
+    },
+    tateyama::proto::{
+        endpoint::response::{handshake, Handshake as HandshakeResponse},
+        framework::{
+            request::Header as FrameworkRequestHeader,
+            response::{header::PayloadType, Header as FrameworkResponseHeader},
+        },
+    },
+};
+
+pub(crate) struct TestServer {
+    listener: TcpListener,
+    endpoint: Endpoint,
+}
+
+impl TestServer {
+    pub(crate) async fn start() -> TestServer {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let endpoint = Endpoint::Tcp("127.0.0.1".to_string(), port);
+        TestServer { listener, endpoint }
+    }
+
+    pub(crate) fn endpoint(&self) -> &Endpoint {
+        &self.endpoint
+    }
+
+    pub(crate) async fn accept(&self) -> TestConnection {
+        let (stream, _) = self.listener.accept().await.unwrap();
+        TestConnection { stream }
+    }
+
+    /// Accepts a connection of [Session::connect()], and answers the handshake.
+    pub(crate) async fn accept_session(&self, session_id: u64) -> TestConnection {
+        let mut connection = self.accept().await;
+        let (slot, _, _) = connection.read_request().await;
+        let response = HandshakeResponse {
+            result: Some(handshake::Result::Success(handshake::Success {
+                session_id,
+                ..Default::default()
+            })),
+        };
+        connection
+            .write_response(slot, &response.encode_length_delimited_to_vec())
+            .await;
+        connection
+    }
+
+    /// Connects a session to this server without handshake.
+    pub(crate) async fn connect(
+        &self,
+        connection_option: &ConnectionOption,
+    ) -> (Arc<Session>, TestConnection) {
+        let link = TcpLink::connect(connection_option, &self.endpoint)
+            .await
+            .unwrap();
+        let connection = self.accept().await;
+
+        let wire = Wire::new(DelegateWire::Tcp(Arc::new(TcpWire::new(link))));
+        let session = Session::new(
+            wire,
+            self.endpoint.clone(),
+            CredentialSource::None,
+            connection_option,
+            std::time::Duration::from_secs(5),
+        );
+        (session, connection)
+    }
+}
+
+pub(crate) struct TestConnection {
+    stream: TcpStream,
+}
+
+impl TestConnection {
+    /// Reads a request frame, and returns the slot and the service payload.
+    pub(crate) async fn read_request(&mut self) -> (i32, FrameworkRequestHeader, Vec<u8>) {
+        let mut header = [0u8; 7];
+        self.stream.read_exact(&mut header).await.unwrap();
+        let slot = i32::from(header[1]) | (i32::from(header[2]) << 8);
+        let length = u32::from_le_bytes([header[3], header[4], header[5], header[6]]) as usize;
+
+        let mut body = vec![0u8; length];
+        self.stream.read_exact(&mut body).await.unwrap();
+        let mut slice = &body[..];
+        let framework_header = FrameworkRequestHeader::decode_length_delimited(&mut slice).unwrap();
+        (slot, framework_header, slice.to_vec())
+    }
+
+    /// Reads a request frame of the SQL service.
+    pub(crate) async fn read_sql_request(&mut self) -> (i32, SqlCommand) {
+        let (slot, _, payload) = self.read_request().await;
+        let request = SqlRequest::decode_length_delimited(&payload[..]).unwrap();
+        (slot, request.request.unwrap())
+    }
+
+    /// Writes a response frame with the service payload.
+    pub(crate) async fn write_response(&mut self, slot: i32, payload: &[u8]) {
+        let header = FrameworkResponseHeader {
+            payload_type: PayloadType::ServiceResult.into(),
+            ..Default::default()
+        };
+        let mut body = header.encode_length_delimited_to_vec();
+        body.extend_from_slice(payload);
+
+        // RESPONSE_SESSION_PAYLOAD: info(1) + slot(2) + length(4) + payload
+        let mut frame = vec![1, slot as u8, (slot >> 8) as u8];
+        frame.extend_from_slice(&(body.len() as u32).to_le_bytes());
+        frame.extend_from_slice(&body);
+        self.stream.write_all(&frame).await.unwrap();
+    }
+
+    /// Writes a response of the SQL service.
+    pub(crate) async fn write_sql_response(&mut self, slot: i32, response: SqlResponseType) {
+        let response = SqlResponse {
+            response: Some(response),
+        };
+        self.write_response(slot, &response.encode_length_delimited_to_vec())
+            .await;
+    }
+
+    /// Writes a successful `ResultOnly` response of the SQL service.
+    pub(crate) async fn write_sql_success(&mut self, slot: i32) {
+        let response = SqlResponseType::ResultOnly(ResultOnly {
+            result: Some(result_only::Result::Success(Success {})),
+        });
+        self.write_sql_response(slot, response).await;
+    }
+}
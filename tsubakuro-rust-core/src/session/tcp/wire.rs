@@ -138,10 +138,18 @@ impl TcpWire {
     pub(crate) fn is_closed(&self) -> bool {
         self.link.is_closed()
     }
+
+    pub(crate) fn is_broken(&self) -> bool {
+        self.link.is_broken()
+    }
+
+    pub(crate) fn set_broken(&self) {
+        self.link.set_broken()
+    }
 }
 
 impl TcpWire {
-    fn is_result_set_response(info: TcpResponseInfo) -> bool {
+    pub(crate) fn is_result_set_response(info: TcpResponseInfo) -> bool {
         matches!(
             info,
             TcpResponseInfo::ResponseResultSetHello
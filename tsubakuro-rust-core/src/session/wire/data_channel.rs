@@ -1,13 +1,28 @@
 use std::{
     collections::{HashMap, VecDeque},
     sync::Arc,
+    time::Duration,
 };
 
 use async_trait::async_trait;
+use log::trace;
 use prost::bytes::{Buf, BytesMut};
-use tokio::sync::{Mutex, MutexGuard};
+use tokio::sync::{
+    mpsc::{
+        self,
+        error::{TryRecvError, TrySendError},
+    },
+    Mutex, MutexGuard,
+};
 
-use crate::{error::TgError, return_err_if_timeout, util::Timeout};
+use crate::{
+    error::TgError,
+    prelude::{SqlQueryPrefetchOption, SqlQueryPrefetchStatistics},
+    return_err_if_timeout,
+    service::sql::query_result::PrefetchCounters,
+    timeout_error,
+    util::Timeout,
+};
 
 #[async_trait]
 pub(crate) trait DataChannelWire: std::fmt::Debug + Send + Sync {
@@ -21,6 +36,7 @@ pub(crate) struct DataChannel {
     bytes_list: Mutex<VecDeque<BytesMut>>,
     writer_map: Mutex<HashMap<u8, Vec<BytesMut>>>,
     current: Mutex<Option<BytesMut>>,
+    prefetcher: Option<DataChannelPrefetcher>,
 }
 
 impl std::fmt::Debug for DataChannel {
@@ -40,8 +56,38 @@ impl DataChannel {
             bytes_list: Mutex::new(VecDeque::new()),
             writer_map: Mutex::new(HashMap::new()),
             current: Mutex::new(None),
+            prefetcher: None,
         }
     }
+
+    /// Starts reading ahead on a background task.
+    ///
+    /// The data already received is handed over to the background task.
+    pub(crate) fn start_prefetch(&mut self, option: &SqlQueryPrefetchOption) {
+        if self.prefetcher.is_some() {
+            return;
+        }
+
+        let source = DataChannel {
+            name: self.name.clone(),
+            dc_wire: self.dc_wire.clone(),
+            bytes_list: Mutex::new(std::mem::take(self.bytes_list.get_mut())),
+            writer_map: Mutex::new(std::mem::take(self.writer_map.get_mut())),
+            current: Mutex::new(None),
+            prefetcher: None,
+        };
+        self.prefetcher = Some(DataChannelPrefetcher::start(source, option.buffer_size()));
+    }
+
+    pub(crate) fn is_prefetch(&self) -> bool {
+        self.prefetcher.is_some()
+    }
+
+    pub(crate) fn prefetch_statistics(&self) -> Option<SqlQueryPrefetchStatistics> {
+        self.prefetcher
+            .as_ref()
+            .map(|prefetcher| prefetcher.counters.statistics())
+    }
 }
 
 impl DataChannel {
@@ -130,6 +176,10 @@ impl DataChannel {
 
 impl DataChannel {
     async fn pull(&self, timeout: &Timeout) -> Result<Option<BytesMut>, TgError> {
+        if let Some(prefetcher) = &self.prefetcher {
+            return prefetcher.recv(timeout).await;
+        }
+
         loop {
             let bytes = {
                 let mut bytes_list = self.bytes_list.lock().await;
@@ -171,3 +221,174 @@ impl DataChannel {
         }
     }
 }
+
+/// Interval to check whether the consumer is still alive while the server sends nothing.
+const PREFETCH_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+struct DataChannelPrefetcher {
+    receiver: Mutex<mpsc::Receiver<Result<BytesMut, TgError>>>,
+    counters: Arc<PrefetchCounters>,
+}
+
+impl DataChannelPrefetcher {
+    fn start(source: DataChannel, buffer_size: usize) -> DataChannelPrefetcher {
+        let (sender, receiver) = mpsc::channel(buffer_size);
+        let counters = Arc::new(PrefetchCounters::new(buffer_size));
+
+        let task_counters = counters.clone();
+        tokio::spawn(async move {
+            Self::run(source, sender, task_counters).await;
+        });
+
+        DataChannelPrefetcher {
+            receiver: Mutex::new(receiver),
+            counters,
+        }
+    }
+
+    async fn run(
+        source: DataChannel,
+        sender: mpsc::Sender<Result<BytesMut, TgError>>,
+        counters: Arc<PrefetchCounters>,
+    ) {
+        trace!("DataChannel.prefetch start. name={}", source.name);
+        loop {
+            let timeout = Timeout::new(PREFETCH_POLL_INTERVAL);
+            let item = match source.pull(&timeout).await {
+                Ok(Some(bytes)) => {
+                    counters.add_received(bytes.len());
+                    Ok(bytes)
+                }
+                Ok(None) => break,
+                Err(TgError::TimeoutError(_)) => {
+                    if sender.is_closed() {
+                        break;
+                    }
+                    continue;
+                }
+                Err(e) => Err(e),
+            };
+            let is_err = item.is_err();
+
+            match sender.try_send(item) {
+                Ok(_) => {}
+                Err(TrySendError::Full(item)) => {
+                    counters.add_producer_wait();
+                    if sender.send(item).await.is_err() {
+                        break;
+                    }
+                }
+                Err(TrySendError::Closed(_)) => break,
+            }
+            if is_err {
+                break;
+            }
+        }
+        trace!("DataChannel.prefetch end. name={}", source.name);
+    }
+
+    async fn recv(&self, timeout: &Timeout) -> Result<Option<BytesMut>, TgError> {
+        const FUNCTION_NAME: &str = "DataChannel::pull()";
+
+        let mut receiver = self.receiver.lock().await;
+        let item = match receiver.try_recv() {
+            Ok(item) => Some(item),
+            Err(TryRecvError::Disconnected) => None,
+            Err(TryRecvError::Empty) => {
+                self.counters.add_consumer_wait();
+                match timeout.remaining() {
+                    Some(remaining) => tokio::time::timeout(remaining, receiver.recv())
+                        .await
+                        .map_err(|_| timeout_error!(FUNCTION_NAME))?,
+                    None => receiver.recv().await,
+                }
+            }
+        };
+
+        match item {
+            Some(Ok(bytes)) => {
+                self.counters.add_consumed();
+                Ok(Some(bytes))
+            }
+            Some(Err(e)) => Err(e),
+            None => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+    use super::*;
+
+    #[derive(Debug)]
+    struct TestDataChannelWire {
+        chunks: Vec<&'static [u8]>,
+        index: AtomicUsize,
+        end: AtomicBool,
+    }
+
+    #[async_trait]
+    impl DataChannelWire for TestDataChannelWire {
+        async fn pull1(
+            &self,
+            data_channel: &DataChannel,
+            _timeout: &Timeout,
+        ) -> Result<(), TgError> {
+            let index = self.index.fetch_add(1, Ordering::SeqCst);
+            match self.chunks.get(index) {
+                Some(chunk) => {
+                    data_channel
+                        .add_writer_payload(0, BytesMut::from(*chunk))
+                        .await;
+                    data_channel.flush_writer(0).await;
+                }
+                None => self.end.store(true, Ordering::SeqCst),
+            }
+            Ok(())
+        }
+
+        fn is_end(&self) -> bool {
+            self.end.load(Ordering::SeqCst)
+        }
+    }
+
+    /// Creates a data channel which returns `chunks` in order.
+    pub(crate) fn data_channel(chunks: Vec<&'static [u8]>) -> DataChannel {
+        let dc_wire = TestDataChannelWire {
+            chunks,
+            index: AtomicUsize::new(0),
+            end: AtomicBool::new(false),
+        };
+        DataChannel::new("test", Arc::new(dc_wire))
+    }
+
+    #[tokio::test]
+    async fn prefetch() {
+        let chunks: Vec<&'static [u8]> = vec![b"ab", b"cde", b"f", b"ghij"];
+        let mut data_channel = data_channel(chunks);
+        let timeout = Timeout::new(Duration::from_secs(10));
+
+        assert_eq!(Some(b'a'), data_channel.read_u8(&timeout).await.unwrap());
+        assert!(!data_channel.is_prefetch());
+
+        let mut option = SqlQueryPrefetchOption::new();
+        option.set_buffer_size(1);
+        data_channel.start_prefetch(&option);
+        assert!(data_channel.is_prefetch());
+
+        let bytes = data_channel.read_all(8, &timeout).await.unwrap().unwrap();
+        assert_eq!(&b"bcdefghi"[..], &bytes[..]);
+        assert_eq!(Some(b'j'), data_channel.read_u8(&timeout).await.unwrap());
+        assert_eq!(None, data_channel.read_u8(&timeout).await.unwrap());
+
+        let statistics = data_channel.prefetch_statistics().unwrap();
+        assert_eq!(1, statistics.buffer_size());
+        assert_eq!(3, statistics.received_chunks());
+        assert_eq!(8, statistics.received_bytes());
+        assert_eq!(3, statistics.consumed_chunks());
+        assert_eq!(0, statistics.buffered_chunks());
+        assert!(statistics.max_buffered_chunks() <= 2);
+    }
+}
@@ -2,6 +2,7 @@ pub(crate) mod cancel;
 pub(crate) mod crypto;
 pub(crate) mod data_channel;
 pub(crate) mod link;
+pub(crate) mod resource_registry;
 pub(crate) mod response;
 pub(crate) mod response_box;
 #[allow(clippy::module_inception)]
@@ -0,0 +1,228 @@
+use std::{
+    collections::BTreeMap,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use crate::jogasaki::proto::sql::common::Transaction as ProtoTransaction;
+
+use super::response_box::SlotEntryHandle;
+
+/// Resource which is closed by [Session::drain()](crate::prelude::Session::drain).
+#[derive(Debug)]
+pub(crate) enum TrackedResource {
+    Transaction {
+        transaction_handle: ProtoTransaction,
+        transaction_id: String,
+    },
+    QueryResult {
+        slot_handle: Arc<SlotEntryHandle>,
+        name: String,
+    },
+}
+
+#[derive(Debug)]
+struct TrackedEntry {
+    resource: TrackedResource,
+    closed: Arc<AtomicBool>,
+}
+
+/// Registry of the open resources of a session.
+#[derive(Debug, Default)]
+pub(crate) struct ResourceRegistry {
+    draining: AtomicBool,
+    next_id: AtomicU64,
+    entries: Mutex<BTreeMap<u64, TrackedEntry>>,
+}
+
+impl ResourceRegistry {
+    pub(crate) fn register(self: &Arc<Self>, resource: TrackedResource) -> ResourceRegistration {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let closed = Arc::new(AtomicBool::new(false));
+
+        let mut entries = self.entries.lock().unwrap();
+        entries.insert(
+            id,
+            TrackedEntry {
+                resource,
+                closed: closed.clone(),
+            },
+        );
+
+        ResourceRegistration {
+            id,
+            registry: self.clone(),
+            closed,
+        }
+    }
+
+    fn unregister(&self, id: u64) {
+        let mut entries = self.entries.lock().unwrap();
+        entries.remove(&id);
+    }
+
+    pub(crate) fn set_draining(&self) {
+        self.draining.store(true, Ordering::SeqCst);
+    }
+
+    pub(crate) fn is_draining(&self) -> bool {
+        self.draining.load(Ordering::SeqCst)
+    }
+
+    /// Returns the slots of the open query results.
+    pub(crate) fn query_result_slots(&self) -> Vec<i32> {
+        let entries = self.entries.lock().unwrap();
+        entries
+            .values()
+            .filter(|entry| !entry.closed.load(Ordering::SeqCst))
+            .filter_map(|entry| match &entry.resource {
+                TrackedResource::QueryResult { slot_handle, .. } => Some(slot_handle.slot()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Marks the open resources as closed, and returns them in the order of registration.
+    ///
+    /// The query results are returned before the transactions.
+    pub(crate) fn take_open_resources(&self) -> Vec<TrackedResource> {
+        let mut entries = self.entries.lock().unwrap();
+        let ids: Vec<u64> = entries
+            .iter()
+            .filter(|(_, entry)| {
+                entry
+                    .closed
+                    .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+                    .is_ok()
+            })
+            .map(|(id, _)| *id)
+            .collect();
+
+        let mut resources: Vec<TrackedResource> = ids
+            .iter()
+            .filter_map(|id| entries.remove(id))
+            .map(|entry| entry.resource)
+            .collect();
+        // stable sort keeps the order of registration
+        resources.sort_by_key(|resource| match resource {
+            TrackedResource::QueryResult { .. } => 0,
+            TrackedResource::Transaction { .. } => 1,
+        });
+        resources
+    }
+}
+
+/// Registration of a resource in [ResourceRegistry].
+///
+/// The resource is unregistered when this is dropped.
+pub(crate) struct ResourceRegistration {
+    id: u64,
+    registry: Arc<ResourceRegistry>,
+    closed: Arc<AtomicBool>,
+}
+
+impl std::fmt::Debug for ResourceRegistration {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ResourceRegistration")
+            .field("id", &self.id)
+            .field("closed", &self.closed)
+            .finish()
+    }
+}
+
+impl ResourceRegistration {
+    /// Marks the resource as closed.
+    ///
+    /// Returns false if it was already closed (by the owner or by the drain).
+    pub(crate) fn close(&self) -> bool {
+        self.closed
+            .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+            .is_ok()
+    }
+
+    pub(crate) fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::SeqCst)
+    }
+}
+
+impl Drop for ResourceRegistration {
+    fn drop(&mut self) {
+        self.registry.unregister(self.id);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::session::wire::response_box::ResponseBox;
+
+    fn transaction(transaction_id: &str) -> TrackedResource {
+        TrackedResource::Transaction {
+            transaction_handle: ProtoTransaction::default(),
+            transaction_id: transaction_id.to_string(),
+        }
+    }
+
+    fn resource_name(resource: &TrackedResource) -> &str {
+        match resource {
+            TrackedResource::Transaction { transaction_id, .. } => transaction_id,
+            TrackedResource::QueryResult { name, .. } => name,
+        }
+    }
+
+    #[test]
+    fn take_open_resources() {
+        let registry = Arc::new(ResourceRegistry::default());
+        let tx1 = registry.register(transaction("tx1"));
+        let tx2 = registry.register(transaction("tx2"));
+        let tx3 = registry.register(transaction("tx3"));
+        let tx4 = registry.register(transaction("tx4"));
+
+        assert!(tx2.close());
+        assert!(!tx2.close());
+        drop(tx3);
+
+        let resources = registry.take_open_resources();
+        let ids: Vec<&str> = resources.iter().map(resource_name).collect();
+        assert_eq!(vec!["tx1", "tx4"], ids);
+        assert!(tx1.is_closed());
+        assert!(!tx1.close());
+        assert!(tx4.is_closed());
+
+        assert!(registry.take_open_resources().is_empty());
+    }
+
+    #[test]
+    fn take_open_resources_order() {
+        let registry = Arc::new(ResourceRegistry::default());
+        let response_box = Arc::new(ResponseBox::new());
+        let _tx1 = registry.register(transaction("tx1"));
+        let _rs1 = registry.register(TrackedResource::QueryResult {
+            slot_handle: response_box.clone().create_slot_handle(),
+            name: "rs1".to_string(),
+        });
+        let _tx2 = registry.register(transaction("tx2"));
+        let rs2 = registry.register(TrackedResource::QueryResult {
+            slot_handle: response_box.clone().create_slot_handle(),
+            name: "rs2".to_string(),
+        });
+        assert_eq!(vec![0, 1], registry.query_result_slots());
+
+        rs2.close();
+        assert_eq!(vec![0], registry.query_result_slots());
+
+        let resources = registry.take_open_resources();
+        let ids: Vec<&str> = resources.iter().map(resource_name).collect();
+        assert_eq!(vec!["rs1", "tx1", "tx2"], ids);
+    }
+
+    #[test]
+    fn draining() {
+        let registry = ResourceRegistry::default();
+        assert!(!registry.is_draining());
+        registry.set_draining();
+        assert!(registry.is_draining());
+    }
+}
@@ -1,9 +1,13 @@
 use std::{
     collections::VecDeque,
-    sync::{atomic::AtomicI32, Arc, Mutex},
+    sync::{
+        atomic::{AtomicBool, AtomicI32},
+        Arc, Mutex,
+    },
 };
 
 use log::error;
+use tokio::sync::{futures::Notified, Notify};
 
 use super::response::WireResponse;
 
@@ -25,6 +29,7 @@ pub(crate) struct SlotEntryHandle {
     response_box: Arc<ResponseBox>,
     slot_entry: Option<SlotEntry>,
     response: Mutex<VecDeque<WireResponse>>,
+    cancelled: AtomicBool,
 }
 
 impl std::fmt::Debug for SlotEntryHandle {
@@ -43,6 +48,7 @@ impl SlotEntryHandle {
             response_box,
             slot_entry: Some(slot_entry),
             response: Mutex::new(VecDeque::with_capacity(2)),
+            cancelled: AtomicBool::new(false),
         }
     }
 
@@ -64,6 +70,16 @@ impl SlotEntryHandle {
         let queue = self.response.lock().unwrap();
         !queue.is_empty()
     }
+
+    /// Marks that the request was cancelled by [Session::drain()](crate::prelude::Session::drain).
+    pub(crate) fn set_cancelled(&self) {
+        self.cancelled
+            .store(true, std::sync::atomic::Ordering::SeqCst);
+    }
+
+    pub(crate) fn is_cancelled(&self) -> bool {
+        self.cancelled.load(std::sync::atomic::Ordering::SeqCst)
+    }
 }
 
 impl Drop for SlotEntryHandle {
@@ -79,6 +95,7 @@ pub(crate) struct ResponseBox {
     slot_max: AtomicI32,
     slot_pool: Mutex<VecDeque<SlotEntry>>,
     recv_wait_pool: Mutex<Vec<Option<Arc<SlotEntryHandle>>>>,
+    response_notify: Notify,
 }
 
 impl std::fmt::Debug for ResponseBox {
@@ -99,6 +116,7 @@ impl ResponseBox {
             slot_max: AtomicI32::new(0),
             slot_pool: Mutex::new(VecDeque::with_capacity(slot_size as usize)),
             recv_wait_pool: Mutex::new(Vec::with_capacity(slot_size as usize)),
+            response_notify: Notify::new(),
         };
 
         for _ in 0..slot_size {
@@ -183,6 +201,18 @@ impl ResponseBox {
                 );
             }
         }
+        self.response_notify.notify_waiters();
+    }
+
+    /// Returns a future which completes when the next response is set to a slot handle.
+    pub(crate) fn response_notified(&self) -> Notified<'_> {
+        self.response_notify.notified()
+    }
+
+    /// Returns the slot handles which are waiting for the end of the response.
+    pub(crate) fn waiting_slot_handles(&self) -> Vec<Arc<SlotEntryHandle>> {
+        let recv_wait_pool = self.recv_wait_pool.lock().unwrap();
+        recv_wait_pool.iter().flatten().cloned().collect()
     }
 
     fn release_slot_entry(&self, slot_entry: SlotEntry) {
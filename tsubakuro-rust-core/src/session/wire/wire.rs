@@ -34,6 +34,7 @@ use crate::{
 
 use super::{
     data_channel::{DataChannel, DataChannelWire},
+    resource_registry::ResourceRegistry,
     response::{WireResponse, WireResponseError},
     response_box::{ResponseBox, SlotEntryHandle},
 };
@@ -61,6 +62,7 @@ pub(crate) struct Wire {
     crypto: tokio::sync::Mutex<Option<Crypto>>,
     user_name: Mutex<Option<String>>,
     lob_transfer_info: Mutex<LobTransferInfo>,
+    resource_registry: Arc<ResourceRegistry>,
 }
 
 impl std::fmt::Debug for Wire {
@@ -76,6 +78,7 @@ impl Wire {
             crypto: tokio::sync::Mutex::new(None),
             user_name: Mutex::new(None),
             lob_transfer_info: Mutex::new(LobTransferInfo::NotUse),
+            resource_registry: Arc::new(ResourceRegistry::default()),
         })
     }
 
@@ -102,6 +105,14 @@ impl Wire {
     pub(crate) fn lob_transfer_info(&self) -> LobTransferInfo {
         self.lob_transfer_info.lock().unwrap().clone()
     }
+
+    pub(crate) fn resource_registry(&self) -> &Arc<ResourceRegistry> {
+        &self.resource_registry
+    }
+
+    pub(crate) fn is_draining(&self) -> bool {
+        self.resource_registry.is_draining()
+    }
 }
 
 impl Wire {
@@ -302,6 +313,33 @@ impl Wire {
         }
     }
 
+    /// Returns the slot handles of the requests which are waiting for the response.
+    ///
+    /// The slots in `excludes` are excluded.
+    pub(crate) fn in_flight_slot_handles(&self, excludes: &[i32]) -> Vec<Arc<SlotEntryHandle>> {
+        self.wire
+            .response_box()
+            .waiting_slot_handles()
+            .into_iter()
+            .filter(|slot_handle| {
+                !slot_handle.exists_wire_response() && !excludes.contains(&slot_handle.slot())
+            })
+            .collect()
+    }
+
+    /// Receives a response if exists.
+    pub(crate) async fn pull_once(&self) -> Result<bool, TgError> {
+        self.wire.pull1().await
+    }
+
+    /// Waits until a response is set to any slot handle.
+    ///
+    /// Does not read the link by itself.
+    pub(crate) async fn wait_response_notification(&self) {
+        let response_box = self.wire.response_box();
+        response_box.response_notified().await;
+    }
+
     pub(crate) async fn check_response(
         &self,
         slot_handle: Arc<SlotEntryHandle>,
@@ -346,6 +384,15 @@ impl Wire {
     pub fn is_closed(&self) -> bool {
         self.wire.is_closed()
     }
+
+    pub(crate) fn is_broken(&self) -> bool {
+        self.wire.is_broken()
+    }
+
+    /// Marks the connection as broken, e.g. after a frame was cut off.
+    pub(crate) fn set_broken(&self) {
+        self.wire.set_broken()
+    }
 }
 
 // DelegateWireをトレイトにしたいが、downcastが難しいので、enumにしておく
@@ -423,6 +470,20 @@ impl DelegateWire {
             _ => todo!("DelegateWire"),
         }
     }
+
+    fn is_broken(&self) -> bool {
+        match self {
+            DelegateWire::Tcp(tcp_wire) => tcp_wire.is_broken(),
+            DelegateWire::_Dummy => false,
+        }
+    }
+
+    fn set_broken(&self) {
+        match self {
+            DelegateWire::Tcp(tcp_wire) => tcp_wire.set_broken(),
+            DelegateWire::_Dummy => {}
+        }
+    }
 }
 
 pub(crate) fn skip_framework_header(
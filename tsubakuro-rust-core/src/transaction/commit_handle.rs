@@ -0,0 +1,387 @@
+use std::{sync::Arc, time::Duration};
+
+use log::trace;
+use tokio::sync::watch;
+
+use crate::{
+    client_error,
+    error::TgError,
+    jogasaki::proto::sql::common::Transaction as ProtoTransaction,
+    prelude::{CommitType, SqlClient, TransactionStatus},
+    service::sql::interceptor::{SqlInterceptContext, SqlInterceptors},
+    session::wire::{response_box::SlotEntryHandle, Wire},
+    timeout_error,
+};
+
+use super::status_watch::{is_final_status, TransactionStatusWatchOption};
+
+/// Handle of commit which notifies each durability stage.
+///
+/// The stages are taken from the commit response and `GetTransactionStatus`.
+/// When the commit response is received, the stage specified by [CommitOption::commit_type()](crate::prelude::CommitOption::commit_type) is reached.
+/// The later stages are reported by polling the transaction status on a background task.
+/// (See [TransactionStatusWatchOption])
+///
+/// A stage implies the preceding stages. (e.g. `STORED` implies `ACCEPTED` and `AVAILABLE`)
+///
+/// Polling stops when the stage becomes `PROPAGATED`, the commit fails,
+/// the transaction status is no longer available (e.g. the transaction is disposed), or the handle is dropped.
+/// Waiting for a stage which is not reported before polling stops returns an error.
+///
+/// See [SqlClient::commit_with_handle()].
+///
+/// since 0.11.0
+#[derive(Debug)]
+pub struct CommitHandle {
+    commit_type: CommitType,
+    receiver: watch::Receiver<CommitProgress>,
+}
+
+#[derive(Debug, Default)]
+struct CommitProgress {
+    committed: bool,
+    stage: Option<CommitType>,
+    error: Option<TgError>,
+}
+
+impl CommitProgress {
+    fn reach(&mut self, stage: CommitType) -> bool {
+        if self.stage.is_some_and(|s| s >= stage) {
+            return false;
+        }
+        self.stage = Some(stage);
+        true
+    }
+
+    /// Returns the result if it is decided.
+    fn check(&self, stage: Option<CommitType>) -> Option<Result<(), TgError>> {
+        let reached = match stage {
+            Some(stage) => self.stage.is_some_and(|s| s >= stage),
+            None => self.committed,
+        };
+        if reached {
+            return Some(Ok(()));
+        }
+        self.error.as_ref().map(|e| Err(copy_error(e)))
+    }
+
+    fn is_finished(&self) -> bool {
+        self.error.is_some() || self.stage == Some(CommitType::Propagated)
+    }
+}
+
+fn copy_error(error: &TgError) -> TgError {
+    match error {
+        TgError::ClientError(message, _) => TgError::ClientError(message.clone(), None),
+        TgError::TimeoutError(message) => TgError::TimeoutError(message.clone()),
+        TgError::IoError(message, _) => TgError::IoError(message.clone(), None),
+        TgError::ServerError(function_name, message, code, server_message) => TgError::ServerError(
+            function_name.clone(),
+            message.clone(),
+            code.clone(),
+            server_message.clone(),
+        ),
+    }
+}
+
+fn commit_stage(status: TransactionStatus) -> Option<CommitType> {
+    match status {
+        TransactionStatus::Available => Some(CommitType::Available),
+        TransactionStatus::Stored => Some(CommitType::Stored),
+        TransactionStatus::Propagated => Some(CommitType::Propagated),
+        _ => None,
+    }
+}
+
+impl CommitHandle {
+    /// Starts waiting for the commit response and polling the transaction status.
+    ///
+    /// The commit request must have been sent to `slot_handle` already.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn start(
+        wire: Arc<Wire>,
+        transaction_handle: ProtoTransaction,
+        slot_handle: Arc<SlotEntryHandle>,
+        commit_type: CommitType,
+        timeout: Duration,
+        request_timeout: Duration,
+        watch_option: TransactionStatusWatchOption,
+        interceptors: SqlInterceptors,
+        context: Option<SqlInterceptContext>,
+    ) -> CommitHandle {
+        let (sender, receiver) = watch::channel(CommitProgress::default());
+        let sender = Arc::new(sender);
+
+        {
+            let wire = wire.clone();
+            let sender = sender.clone();
+            tokio::spawn(async move {
+                let result = SqlClient::commit_response(&wire, slot_handle, timeout).await;
+                interceptors.after(context.as_ref(), &result);
+                Self::notify_commit(&sender, commit_type, result);
+            });
+        }
+        tokio::spawn(async move {
+            Self::poll_status(
+                wire,
+                transaction_handle,
+                request_timeout,
+                watch_option,
+                sender,
+            )
+            .await;
+        });
+
+        CommitHandle {
+            commit_type,
+            receiver,
+        }
+    }
+
+    fn notify_commit(
+        sender: &watch::Sender<CommitProgress>,
+        commit_type: CommitType,
+        result: Result<(), TgError>,
+    ) {
+        trace!("CommitHandle commit response. result={:?}", result);
+        sender.send_modify(|progress| match result {
+            Ok(_) => {
+                progress.committed = true;
+                // UNSPECIFIED means the server default, which is at least ACCEPTED
+                let stage = match commit_type {
+                    CommitType::Unspecified => CommitType::Accepted,
+                    commit_type => commit_type,
+                };
+                progress.reach(stage);
+            }
+            Err(e) => progress.error = Some(e),
+        });
+    }
+
+    async fn poll_status(
+        wire: Arc<Wire>,
+        transaction_handle: ProtoTransaction,
+        request_timeout: Duration,
+        option: TransactionStatusWatchOption,
+        sender: Arc<watch::Sender<CommitProgress>>,
+    ) {
+        trace!("CommitHandle polling start");
+
+        let mut interval = option.initial_interval();
+        let mut last_status = None;
+        loop {
+            if sender.borrow().is_finished() {
+                break;
+            }
+
+            let result = SqlClient::transaction_status_by_handle(
+                &wire,
+                &transaction_handle,
+                request_timeout,
+            )
+            .await;
+            let status = match result {
+                Ok(status) => status.status(),
+                Err(e) => {
+                    // the commit error is reported by the commit response
+                    trace!("CommitHandle polling error. {:?}", e);
+                    break;
+                }
+            };
+
+            if let Some(stage) = commit_stage(status) {
+                sender.send_if_modified(|progress| progress.reach(stage));
+            }
+            if is_final_status(status) {
+                break;
+            }
+
+            let changed = last_status != Some(status);
+            last_status = Some(status);
+            interval = option.next_interval(interval, changed);
+            // wait for the interval, or stop if the handle is dropped
+            if tokio::time::timeout(interval, sender.closed())
+                .await
+                .is_ok()
+            {
+                break;
+            }
+        }
+
+        trace!("CommitHandle polling end");
+    }
+
+    /// Get the commit type specified by [CommitOption](crate::prelude::CommitOption).
+    pub fn commit_type(&self) -> CommitType {
+        self.commit_type
+    }
+
+    /// Get the latest stage which has been reached.
+    pub fn current_stage(&self) -> Option<CommitType> {
+        self.receiver.borrow().stage
+    }
+
+    /// Waits for the commit response.
+    ///
+    /// This is the same timing as [SqlClient::commit()].
+    pub async fn committed(&self) -> Result<(), TgError> {
+        self.wait_progress(None, Duration::ZERO).await
+    }
+
+    /// Waits until the commit is `ACCEPTED`.
+    pub async fn accepted(&self) -> Result<(), TgError> {
+        self.wait(CommitType::Accepted).await
+    }
+
+    /// Waits until the commit is `AVAILABLE`.
+    pub async fn available(&self) -> Result<(), TgError> {
+        self.wait(CommitType::Available).await
+    }
+
+    /// Waits until the commit is `STORED`.
+    pub async fn stored(&self) -> Result<(), TgError> {
+        self.wait(CommitType::Stored).await
+    }
+
+    /// Waits until the commit is `PROPAGATED`.
+    pub async fn propagated(&self) -> Result<(), TgError> {
+        self.wait(CommitType::Propagated).await
+    }
+
+    /// Waits until the commit reaches `stage`.
+    ///
+    /// [CommitType::Unspecified] is the same as [Self::committed()].
+    pub async fn wait(&self, stage: CommitType) -> Result<(), TgError> {
+        self.wait_for(stage, Duration::ZERO).await
+    }
+
+    /// Waits until the commit reaches `stage`.
+    ///
+    /// If `timeout` is zero, waits without limit.
+    ///
+    /// [CommitType::Unspecified] is the same as [Self::committed()].
+    pub async fn wait_for(&self, stage: CommitType, timeout: Duration) -> Result<(), TgError> {
+        let stage = match stage {
+            CommitType::Unspecified => None,
+            stage => Some(stage),
+        };
+        self.wait_progress(stage, timeout).await
+    }
+
+    async fn wait_progress(
+        &self,
+        stage: Option<CommitType>,
+        timeout: Duration,
+    ) -> Result<(), TgError> {
+        const FUNCTION_NAME: &str = "CommitHandle.wait()";
+
+        let mut receiver = self.receiver.clone();
+        let future = async move {
+            loop {
+                if let Some(result) = receiver.borrow_and_update().check(stage) {
+                    return result;
+                }
+                if receiver.changed().await.is_err() {
+                    // no more notification
+                    if let Some(result) = receiver.borrow().check(stage) {
+                        return result;
+                    }
+                    return Err(client_error!(format!(
+                        "{FUNCTION_NAME}: commit stage {:?} was not reported",
+                        stage.unwrap_or(CommitType::Unspecified)
+                    )));
+                }
+            }
+        };
+
+        if timeout.is_zero() {
+            future.await
+        } else {
+            match tokio::time::timeout(timeout, future).await {
+                Ok(result) => result,
+                Err(_) => Err(timeout_error!(FUNCTION_NAME)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn progress_reach() {
+        let mut progress = CommitProgress::default();
+        assert!(progress.check(Some(CommitType::Accepted)).is_none());
+        assert!(progress.check(None).is_none());
+
+        assert!(progress.reach(CommitType::Available));
+        assert!(!progress.reach(CommitType::Accepted));
+        assert_eq!(Some(CommitType::Available), progress.stage);
+        assert!(progress.check(Some(CommitType::Accepted)).unwrap().is_ok());
+        assert!(progress.check(Some(CommitType::Available)).unwrap().is_ok());
+        assert!(progress.check(Some(CommitType::Stored)).is_none());
+        assert!(progress.check(None).is_none());
+        assert!(!progress.is_finished());
+
+        progress.committed = true;
+        assert!(progress.check(None).unwrap().is_ok());
+
+        assert!(progress.reach(CommitType::Propagated));
+        assert!(progress.is_finished());
+    }
+
+    #[test]
+    fn progress_error() {
+        let mut progress = CommitProgress::default();
+        progress.reach(CommitType::Available);
+        progress.error = Some(client_error!("error"));
+        assert!(progress.is_finished());
+        assert!(progress.check(Some(CommitType::Available)).unwrap().is_ok());
+        let error = progress
+            .check(Some(CommitType::Stored))
+            .unwrap()
+            .unwrap_err();
+        assert_eq!("error", error.message());
+    }
+
+    #[test]
+    fn stage() {
+        assert_eq!(None, commit_stage(TransactionStatus::Committing));
+        assert_eq!(
+            Some(CommitType::Available),
+            commit_stage(TransactionStatus::Available)
+        );
+        assert_eq!(
+            Some(CommitType::Stored),
+            commit_stage(TransactionStatus::Stored)
+        );
+        assert_eq!(
+            Some(CommitType::Propagated),
+            commit_stage(TransactionStatus::Propagated)
+        );
+    }
+
+    #[tokio::test]
+    async fn wait() {
+        let (sender, receiver) = watch::channel(CommitProgress::default());
+        let handle = CommitHandle {
+            commit_type: CommitType::Available,
+            receiver,
+        };
+
+        CommitHandle::notify_commit(&sender, CommitType::Available, Ok(()));
+        handle.committed().await.unwrap();
+        handle.available().await.unwrap();
+        assert_eq!(Some(CommitType::Available), handle.current_stage());
+
+        let result = handle
+            .wait_for(CommitType::Stored, Duration::from_millis(10))
+            .await;
+        assert!(matches!(result, Err(TgError::TimeoutError(_))));
+
+        drop(sender);
+        let result = handle.stored().await;
+        assert!(matches!(result, Err(TgError::ClientError(_, _))));
+    }
+}
@@ -1,7 +1,9 @@
+pub mod commit_handle;
 pub mod commit_option;
 pub mod error_info;
 pub mod option;
 pub mod status;
+pub mod status_watch;
 #[allow(clippy::module_inception)]
 mod transaction;
 
@@ -0,0 +1,448 @@
+use std::{
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use log::trace;
+use tokio::sync::mpsc;
+
+use crate::{
+    client_error,
+    error::TgError,
+    jogasaki::proto::sql::common::Transaction as ProtoTransaction,
+    prelude::{SqlClient, Transaction, TransactionStatus, TransactionStatusWithMessage},
+    session::wire::Wire,
+    timeout_error,
+    util::Timeout,
+};
+
+/// Option for watching transaction status.
+///
+/// The status is polled with adaptive backoff.
+/// The polling interval starts at [`Self::initial_interval`] and doubles while the status does not change,
+/// up to [`Self::max_interval`]. It is reset when the status changes.
+///
+/// See [Transaction::wait_until_with()], [Transaction::watch_status_with()].
+///
+/// since 0.11.0
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionStatusWatchOption {
+    initial_interval: Duration,
+    max_interval: Duration,
+}
+
+impl Default for TransactionStatusWatchOption {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TransactionStatusWatchOption {
+    /// Creates a new instance.
+    pub fn new() -> TransactionStatusWatchOption {
+        TransactionStatusWatchOption {
+            initial_interval: Duration::from_millis(10),
+            max_interval: Duration::from_secs(1),
+        }
+    }
+
+    /// Set the polling interval after the status changes.
+    pub fn set_initial_interval(&mut self, interval: Duration) {
+        self.initial_interval = interval;
+    }
+
+    /// Get the polling interval after the status changes.
+    pub fn initial_interval(&self) -> Duration {
+        self.initial_interval
+    }
+
+    /// Set the maximum polling interval.
+    pub fn set_max_interval(&mut self, interval: Duration) {
+        self.max_interval = interval;
+    }
+
+    /// Get the maximum polling interval.
+    pub fn max_interval(&self) -> Duration {
+        self.max_interval
+    }
+
+    pub(crate) fn next_interval(&self, interval: Duration, changed: bool) -> Duration {
+        if changed {
+            self.initial_interval.min(self.max_interval)
+        } else {
+            interval.saturating_mul(2).min(self.max_interval)
+        }
+    }
+}
+
+/// Returns true if polling the status is no longer meaningful.
+///
+/// `ABORTING` is included because it always ends in `ABORTED`,
+/// and `UNTRACKED` because the server no longer reports the status.
+pub(crate) fn is_final_status(status: TransactionStatus) -> bool {
+    matches!(
+        status,
+        TransactionStatus::Untracked
+            | TransactionStatus::Propagated
+            | TransactionStatus::Aborting
+            | TransactionStatus::Aborted
+    )
+}
+
+/// Returns true if the transaction will never be committed.
+fn is_failed_status(status: TransactionStatus) -> bool {
+    matches!(
+        status,
+        TransactionStatus::Untracked | TransactionStatus::Aborting | TransactionStatus::Aborted
+    )
+}
+
+/// Returns the result of waiting if it is decided by `status`.
+///
+/// The commit stages are ordered (`AVAILABLE` < `STORED` < `PROPAGATED`),
+/// and polling may skip a stage, so `PROPAGATED` (the last stage) is a success even if `predicate` is not satisfied.
+fn check_wait_status<F>(
+    function_name: &str,
+    status: TransactionStatusWithMessage,
+    predicate: &F,
+) -> Option<Result<TransactionStatusWithMessage, TgError>>
+where
+    F: Fn(&TransactionStatusWithMessage) -> bool,
+{
+    if is_failed_status(status.status()) {
+        return Some(Err(client_error!(format!(
+            "{function_name}: transaction status is {:?}. {}",
+            status.status(),
+            status.message()
+        ))));
+    }
+    if predicate(&status) || status.status() == TransactionStatus::Propagated {
+        return Some(Ok(status));
+    }
+    None
+}
+
+impl Transaction {
+    /// Waits until the transaction status satisfies `predicate`.
+    ///
+    /// The status is polled by `GetTransactionStatus` with adaptive backoff. (See [TransactionStatusWatchOption])
+    /// If `timeout` is zero, waits without limit.
+    /// Each status request is also bounded by the remaining time of `timeout`.
+    /// (If `timeout` is zero, by the default timeout of the transaction)
+    ///
+    /// Returns an error if the status becomes `UNTRACKED`, `ABORTING` or `ABORTED`.
+    ///
+    /// The commit stages are ordered (`AVAILABLE` < `STORED` < `PROPAGATED`),
+    /// and polling may skip a stage (e.g. from `AVAILABLE` to `PROPAGATED`),
+    /// so `predicate` should accept the later stages too.
+    /// When the status becomes `PROPAGATED`, it is returned even if `predicate` is not satisfied.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::time::Duration;
+    /// use tsubakuro_rust_core::prelude::*;
+    ///
+    /// async fn example(client: &SqlClient, transaction: &Transaction) -> Result<(), TgError> {
+    ///     client.commit(transaction, &CommitOption::default()).await?;
+    ///
+    ///     let status = transaction
+    ///         .wait_until(
+    ///             |status| status.status() >= TransactionStatus::Stored,
+    ///             Duration::from_secs(30),
+    ///         )
+    ///         .await?;
+    ///     println!("status={:?}", status.status());
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// since 0.11.0
+    pub async fn wait_until<F>(
+        &self,
+        predicate: F,
+        timeout: Duration,
+    ) -> Result<TransactionStatusWithMessage, TgError>
+    where
+        F: Fn(&TransactionStatusWithMessage) -> bool,
+    {
+        self.wait_until_with(predicate, timeout, &TransactionStatusWatchOption::default())
+            .await
+    }
+
+    /// Waits until the transaction status satisfies `predicate`.
+    ///
+    /// See [`Self::wait_until`].
+    ///
+    /// since 0.11.0
+    pub async fn wait_until_with<F>(
+        &self,
+        predicate: F,
+        timeout: Duration,
+        option: &TransactionStatusWatchOption,
+    ) -> Result<TransactionStatusWithMessage, TgError>
+    where
+        F: Fn(&TransactionStatusWithMessage) -> bool,
+    {
+        const FUNCTION_NAME: &str = "wait_until()";
+        trace!("{} start", FUNCTION_NAME);
+
+        let timeout = Timeout::new(timeout);
+        let wire = self.session().wire();
+
+        let mut interval = option.initial_interval();
+        let mut last_status = None;
+        loop {
+            let request_timeout = match timeout.remaining() {
+                Some(remaining) if remaining.is_zero() => {
+                    return Err(timeout_error!(FUNCTION_NAME));
+                }
+                Some(remaining) => remaining,
+                None => self.default_timeout(),
+            };
+            let status = SqlClient::transaction_status_by_handle(
+                &wire,
+                self.transaction_handle()?,
+                request_timeout,
+            )
+            .await?;
+            let current = status.status();
+            if let Some(result) = check_wait_status(FUNCTION_NAME, status, &predicate) {
+                trace!("{} end", FUNCTION_NAME);
+                return result;
+            }
+
+            let changed = last_status != Some(current);
+            last_status = Some(current);
+            interval = option.next_interval(interval, changed);
+
+            match timeout.remaining() {
+                Some(remaining) if remaining.is_zero() => {
+                    return Err(timeout_error!(FUNCTION_NAME));
+                }
+                Some(remaining) => tokio::time::sleep(interval.min(remaining)).await,
+                None => tokio::time::sleep(interval).await,
+            }
+        }
+    }
+
+    /// Watches the transaction status.
+    ///
+    /// Returns a stream which yields the status each time it changes (including the first status).
+    /// The status is polled by `GetTransactionStatus` with adaptive backoff on a background task.
+    /// (See [TransactionStatusWatchOption])
+    ///
+    /// The stream ends after the status becomes `UNTRACKED`, `PROPAGATED`, `ABORTING` or `ABORTED`, or after an error.
+    /// Polling stops when the stream is dropped.
+    ///
+    /// # Examples
+    /// ```
+    /// use tsubakuro_rust_core::prelude::*;
+    ///
+    /// async fn example(client: &SqlClient, transaction: &Transaction) -> Result<(), TgError> {
+    ///     let mut watch = transaction.watch_status()?;
+    ///     let job = client.commit_async(transaction, &CommitOption::default()).await?;
+    ///
+    ///     while let Some(status) = watch.next().await {
+    ///         let status = status?;
+    ///         println!("status={:?}", status.status());
+    ///         if status.status() == TransactionStatus::Stored {
+    ///             break;
+    ///         }
+    ///     }
+    ///     job.await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// since 0.11.0
+    pub fn watch_status(&self) -> Result<TransactionStatusStream, TgError> {
+        self.watch_status_with(&TransactionStatusWatchOption::default())
+    }
+
+    /// Watches the transaction status.
+    ///
+    /// See [`Self::watch_status`].
+    ///
+    /// since 0.11.0
+    pub fn watch_status_with(
+        &self,
+        option: &TransactionStatusWatchOption,
+    ) -> Result<TransactionStatusStream, TgError> {
+        let transaction_handle = *self.transaction_handle()?;
+        Ok(TransactionStatusStream::start(
+            self.session().wire(),
+            transaction_handle,
+            self.default_timeout(),
+            option.clone(),
+        ))
+    }
+}
+
+/// Stream of transaction status.
+///
+/// Implements [tokio_stream::Stream].
+///
+/// See [Transaction::watch_status()].
+///
+/// since 0.11.0
+#[derive(Debug)]
+pub struct TransactionStatusStream {
+    receiver: mpsc::Receiver<Result<TransactionStatusWithMessage, TgError>>,
+}
+
+impl TransactionStatusStream {
+    fn start(
+        wire: Arc<Wire>,
+        transaction_handle: ProtoTransaction,
+        request_timeout: Duration,
+        option: TransactionStatusWatchOption,
+    ) -> TransactionStatusStream {
+        let (sender, receiver) = mpsc::channel(1);
+        tokio::spawn(async move {
+            Self::run(wire, transaction_handle, request_timeout, option, sender).await;
+        });
+
+        TransactionStatusStream { receiver }
+    }
+
+    async fn run(
+        wire: Arc<Wire>,
+        transaction_handle: ProtoTransaction,
+        request_timeout: Duration,
+        option: TransactionStatusWatchOption,
+        sender: mpsc::Sender<Result<TransactionStatusWithMessage, TgError>>,
+    ) {
+        trace!("TransactionStatusStream start");
+
+        let mut interval = option.initial_interval();
+        let mut last_status = None;
+        loop {
+            let result = SqlClient::transaction_status_by_handle(
+                &wire,
+                &transaction_handle,
+                request_timeout,
+            )
+            .await;
+            let status = match result {
+                Ok(status) => status,
+                Err(e) => {
+                    let _ = sender.send(Err(e)).await;
+                    break;
+                }
+            };
+
+            let current = (status.status(), status.message().clone());
+            let changed = last_status.as_ref() != Some(&current);
+            let is_final = is_final_status(current.0);
+            if changed {
+                if sender.send(Ok(status)).await.is_err() {
+                    break;
+                }
+                last_status = Some(current);
+            }
+            if is_final {
+                break;
+            }
+
+            interval = option.next_interval(interval, changed);
+            // wait for the interval, or stop if the stream is dropped
+            if tokio::time::timeout(interval, sender.closed())
+                .await
+                .is_ok()
+            {
+                break;
+            }
+        }
+
+        trace!("TransactionStatusStream end");
+    }
+
+    /// Returns the next status, or `None` if the stream ended.
+    pub async fn next(&mut self) -> Option<Result<TransactionStatusWithMessage, TgError>> {
+        self.receiver.recv().await
+    }
+}
+
+impl tokio_stream::Stream for TransactionStatusStream {
+    type Item = Result<TransactionStatusWithMessage, TgError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_recv(cx)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::jogasaki::proto::sql::response::get_transaction_status::Success;
+
+    #[test]
+    fn next_interval() {
+        let mut option = TransactionStatusWatchOption::new();
+        option.set_initial_interval(Duration::from_millis(10));
+        option.set_max_interval(Duration::from_millis(50));
+
+        let interval = option.initial_interval();
+        let interval = option.next_interval(interval, false);
+        assert_eq!(Duration::from_millis(20), interval);
+        let interval = option.next_interval(interval, false);
+        assert_eq!(Duration::from_millis(40), interval);
+        let interval = option.next_interval(interval, false);
+        assert_eq!(Duration::from_millis(50), interval);
+        let interval = option.next_interval(interval, true);
+        assert_eq!(Duration::from_millis(10), interval);
+    }
+
+    #[test]
+    fn final_status() {
+        assert!(is_final_status(TransactionStatus::Aborted));
+        assert!(is_final_status(TransactionStatus::Propagated));
+        assert!(is_final_status(TransactionStatus::Aborting));
+        assert!(is_final_status(TransactionStatus::Untracked));
+        assert!(!is_final_status(TransactionStatus::Running));
+        assert!(!is_final_status(TransactionStatus::Stored));
+    }
+
+    fn status(status: TransactionStatus) -> TransactionStatusWithMessage {
+        TransactionStatusWithMessage::new(Success {
+            status: status as i32,
+            message: String::new(),
+        })
+    }
+
+    #[test]
+    fn wait_status() {
+        let predicate =
+            |status: &TransactionStatusWithMessage| status.status() >= TransactionStatus::Stored;
+
+        let result = check_wait_status("test", status(TransactionStatus::Available), &predicate);
+        assert!(result.is_none());
+        let result = check_wait_status("test", status(TransactionStatus::Stored), &predicate);
+        assert_eq!(TransactionStatus::Stored, result.unwrap().unwrap().status());
+
+        // ABORTED is greater than STORED, but it is an error
+        let result = check_wait_status("test", status(TransactionStatus::Aborted), &predicate);
+        assert!(result.unwrap().is_err());
+        let result = check_wait_status("test", status(TransactionStatus::Untracked), &predicate);
+        assert!(result.unwrap().is_err());
+    }
+
+    #[test]
+    fn wait_status_skipped_stage() {
+        let predicate =
+            |status: &TransactionStatusWithMessage| status.status() == TransactionStatus::Stored;
+
+        // polling may skip STORED
+        let result = check_wait_status("test", status(TransactionStatus::Available), &predicate);
+        assert!(result.is_none());
+        let result = check_wait_status("test", status(TransactionStatus::Propagated), &predicate);
+        assert_eq!(
+            TransactionStatus::Propagated,
+            result.unwrap().unwrap().status()
+        );
+    }
+}
@@ -16,7 +16,11 @@ use crate::{
         convert_sql_response, sql::SqlClient, sql_result_only_success_processor, ServiceClient,
         Session,
     },
-    session::wire::{response::WireResponse, response_box::SlotEntryHandle},
+    session::wire::{
+        resource_registry::{ResourceRegistration, TrackedResource},
+        response::WireResponse,
+        response_box::SlotEntryHandle,
+    },
     sql_service_error,
 };
 
@@ -57,7 +61,7 @@ pub struct Transaction {
     transaction_handle: ProtoTransaction,
     transaction_id: String,
     close_timeout: Duration,
-    closed: AtomicBool,
+    registration: ResourceRegistration,
     fail_on_drop_error: AtomicBool,
 }
 
@@ -69,12 +73,20 @@ impl Transaction {
         close_timeout: Duration,
     ) -> Transaction {
         let fail_on_drop_error = session.fail_on_drop_error();
+        let registration =
+            session
+                .wire()
+                .resource_registry()
+                .register(TrackedResource::Transaction {
+                    transaction_handle,
+                    transaction_id: transaction_id.clone(),
+                });
         Transaction {
             session,
             transaction_handle,
             transaction_id,
             close_timeout,
-            closed: AtomicBool::new(false),
+            registration,
             fail_on_drop_error: AtomicBool::new(fail_on_drop_error),
         }
     }
@@ -113,16 +125,7 @@ impl Transaction {
     ///
     /// Note: Should invoke `close_for` before [`Self::drop`] to dispose the transaction.
     pub async fn close_for(&self, timeout: Duration) -> Result<(), TgError> {
-        if self
-            .closed
-            .compare_exchange(
-                false,
-                true,
-                std::sync::atomic::Ordering::SeqCst,
-                std::sync::atomic::Ordering::SeqCst,
-            )
-            .is_ok()
-        {
+        if self.registration.close() {
             let sql_client = SqlClient::new(self.session.clone());
             let tx_handle = &self.transaction_handle;
             sql_client.dispose_transaction(tx_handle, timeout).await?;
@@ -132,7 +135,11 @@ impl Transaction {
 
     /// Check if this resource is closed.
     pub fn is_closed(&self) -> bool {
-        self.closed.load(std::sync::atomic::Ordering::SeqCst)
+        self.registration.is_closed()
+    }
+
+    pub(crate) fn session(&self) -> &Arc<Session> {
+        &self.session
     }
 
     pub(crate) fn default_timeout(&self) -> Duration {
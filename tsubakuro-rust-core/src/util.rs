@@ -25,6 +25,15 @@ impl Timeout {
         }
     }
 
+    /// Returns the remaining time, or `None` if no timeout is set.
+    pub(crate) fn remaining(&self) -> Option<Duration> {
+        if self.timeout.is_zero() {
+            return None;
+        }
+
+        Some(self.timeout.saturating_sub(self.start.elapsed()))
+    }
+
     pub(crate) fn is_timeout(&self) -> bool {
         let timeout = self.timeout;
         if timeout.is_zero() {
@@ -0,0 +1,112 @@
+#[cfg(test)]
+mod test {
+    use crate::test::{commit_and_close, create_table, create_test_sql_client, start_occ};
+    use tokio::test;
+    use tsubakuro_rust_core::prelude::*;
+
+    #[test]
+    async fn execute_with() {
+        let client = create_test_sql_client().await;
+
+        create_table(
+            &client,
+            "test",
+            "create table test (foo int primary key, bar bigint, zzz varchar(10))",
+        )
+        .await;
+
+        insert(&client).await;
+        select(&client).await;
+    }
+
+    async fn insert(client: &SqlClient) {
+        let transaction = start_occ(&client).await;
+
+        let sql = "insert into test values(:foo, :bar, :zzz)";
+        let parameters = vec![
+            SqlParameter::of("foo", 1),
+            SqlParameter::of("bar", 11_i64),
+            SqlParameter::of("zzz", "abc"),
+        ];
+        let r = client
+            .execute_with(&transaction, sql, parameters)
+            .await
+            .unwrap();
+        assert_eq!(1, r.inserted_rows());
+
+        let sql = "insert into test values(?, ?, ?)";
+        let parameters = vec![
+            SqlParameter::of("", 2),
+            SqlParameter::of("", 22_i64),
+            SqlParameter::null(""),
+        ];
+        let r = client
+            .execute_with(&transaction, sql, parameters)
+            .await
+            .unwrap();
+        assert_eq!(1, r.inserted_rows());
+
+        let sql = "insert into test values(?, ?, '?')";
+        let parameters = vec![SqlParameter::of("", 3)];
+        let e = client
+            .execute_with(&transaction, sql, parameters)
+            .await
+            .unwrap_err();
+        assert!(matches!(e, TgError::ClientError(..)));
+
+        commit_and_close(client, &transaction).await;
+    }
+
+    async fn select(client: &SqlClient) {
+        let transaction = start_occ(&client).await;
+
+        {
+            let sql = "select * from test where foo = :foo";
+            let parameters = vec![SqlParameter::of("foo", 1)];
+            let mut query_result = client
+                .query_with(&transaction, sql, parameters)
+                .await
+                .unwrap();
+
+            assert_eq!(true, query_result.next_row().await.unwrap());
+
+            assert_eq!(true, query_result.next_column().await.unwrap());
+            assert_eq!(1, query_result.fetch().await.unwrap());
+            assert_eq!(true, query_result.next_column().await.unwrap());
+            assert_eq!(11_i64, query_result.fetch().await.unwrap());
+            assert_eq!(true, query_result.next_column().await.unwrap());
+            let zzz: String = query_result.fetch().await.unwrap();
+            assert_eq!("abc", zzz);
+            assert_eq!(false, query_result.next_column().await.unwrap());
+
+            assert_eq!(false, query_result.next_row().await.unwrap());
+
+            query_result.close().await.unwrap()
+        }
+
+        {
+            let sql = "select * from test where foo = ?";
+            let parameters = vec![SqlParameter::of("", 2)];
+            let mut query_result = client
+                .query_with(&transaction, sql, parameters)
+                .await
+                .unwrap();
+
+            assert_eq!(true, query_result.next_row().await.unwrap());
+
+            assert_eq!(true, query_result.next_column().await.unwrap());
+            assert_eq!(2, query_result.fetch().await.unwrap());
+            assert_eq!(true, query_result.next_column().await.unwrap());
+            assert_eq!(22_i64, query_result.fetch().await.unwrap());
+            assert_eq!(true, query_result.next_column().await.unwrap());
+            assert_eq!(true, query_result.is_null().unwrap());
+            assert_eq!(false, query_result.next_column().await.unwrap());
+
+            assert_eq!(false, query_result.next_row().await.unwrap());
+
+            query_result.close().await.unwrap()
+        }
+
+        commit_and_close(client, &transaction).await;
+    }
+}
@@ -1,6 +1,8 @@
+mod execute_with;
 mod explain;
 mod prepare;
 mod query_result;
+mod search_path;
 mod table_list;
 mod table_metadata;
 mod r#type;
@@ -0,0 +1,41 @@
+#[cfg(test)]
+mod test {
+    use crate::test::{create_table, create_test_sql_client, drop_table_if_exists};
+    use tokio::test;
+
+    #[test]
+    async fn get_search_path() {
+        let client = create_test_sql_client().await;
+
+        let search_path = client.get_search_path().await.unwrap();
+        let _ = search_path.schema_names();
+    }
+
+    #[test]
+    async fn get_search_path_async() {
+        let client = create_test_sql_client().await;
+
+        let mut job = client.get_search_path_async().await.unwrap();
+        assert_eq!("GetSearchPath", job.name());
+        let search_path = job.take().await.unwrap();
+        let _ = search_path.schema_names();
+    }
+
+    #[test]
+    async fn resolve_table_metadata() {
+        let client = create_test_sql_client().await;
+        drop_table_if_exists(&client, "test").await;
+
+        let metadata = client.resolve_table_metadata("test").await.unwrap();
+        assert!(metadata.is_none());
+
+        create_table(&client, "test", "create table test (pk int primary key)").await;
+
+        let metadata = client
+            .resolve_table_metadata("test")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!("test", metadata.table_name());
+    }
+}
@@ -0,0 +1,284 @@
+//! Conversion between [`Value`] and Arrow arrays.
+
+use std::sync::Arc;
+
+use arrow_array::{
+    Array, ArrayRef, BinaryArray, BooleanArray, Date32Array, Decimal128Array, Float32Array,
+    Float64Array, Int32Array, Int64Array, RecordBatch, StringArray, Time64NanosecondArray,
+    TimestampNanosecondArray, cast::AsArray, types::*,
+};
+use arrow_schema::{DataType, Field, Schema, TimeUnit};
+use bigdecimal::{BigDecimal, ToPrimitive, num_bigint::BigInt};
+use chrono::{DateTime, NaiveDate, NaiveTime, Timelike};
+use tsubakuro_rust_core::prelude::*;
+
+use crate::{error::DataError, value::Value};
+
+const UTC: &str = "+00:00";
+
+fn epoch() -> NaiveDate {
+    NaiveDate::from_ymd_opt(1970, 1, 1).unwrap()
+}
+
+/// Creates the Arrow schema for the columns.
+///
+/// Decimal columns without definite precision and scale, and time of day with time zone columns
+/// are stored as strings.
+pub(crate) fn schema(columns: &[SqlColumn]) -> Result<Schema, DataError> {
+    let mut fields = Vec::with_capacity(columns.len());
+    for column in columns {
+        let data_type = match column.atom_type() {
+            Some(AtomType::Boolean) => DataType::Boolean,
+            Some(AtomType::Int4) => DataType::Int32,
+            Some(AtomType::Int8) => DataType::Int64,
+            Some(AtomType::Float4) => DataType::Float32,
+            Some(AtomType::Float8) => DataType::Float64,
+            Some(AtomType::Decimal) => match (column.precision(), column.scale()) {
+                (Some((precision, false)), Some((scale, false))) if precision <= 38 => {
+                    DataType::Decimal128(precision as u8, scale as i8)
+                }
+                _ => DataType::Utf8,
+            },
+            Some(AtomType::Character) => DataType::Utf8,
+            Some(AtomType::Octet) => DataType::Binary,
+            Some(AtomType::Date) => DataType::Date32,
+            Some(AtomType::TimeOfDay) => DataType::Time64(TimeUnit::Nanosecond),
+            Some(AtomType::TimePoint) => DataType::Timestamp(TimeUnit::Nanosecond, None),
+            Some(AtomType::TimeOfDayWithTimeZone) => DataType::Utf8,
+            Some(AtomType::TimePointWithTimeZone) => {
+                DataType::Timestamp(TimeUnit::Nanosecond, Some(UTC.into()))
+            }
+            atom_type => {
+                return Err(format!(
+                    "unsupported column type. column={}, type={:?}",
+                    column.name(),
+                    atom_type
+                )
+                .into());
+            }
+        };
+        fields.push(Field::new(column.name(), data_type, true));
+    }
+    Ok(Schema::new(fields))
+}
+
+/// Creates a record batch from rows.
+pub(crate) fn to_record_batch(
+    schema: &Arc<Schema>,
+    rows: &[Vec<Value>],
+) -> Result<RecordBatch, DataError> {
+    let mut arrays = Vec::with_capacity(schema.fields().len());
+    for (i, field) in schema.fields().iter().enumerate() {
+        let values = rows.iter().map(|row| &row[i]);
+        arrays.push(to_array(field.data_type(), values)?);
+    }
+    Ok(RecordBatch::try_new(schema.clone(), arrays)?)
+}
+
+fn to_array<'a>(
+    data_type: &DataType,
+    values: impl Iterator<Item = &'a Value>,
+) -> Result<ArrayRef, DataError> {
+    let array: ArrayRef = match data_type {
+        DataType::Boolean => Arc::new(BooleanArray::from_iter(values.map(|v| match v {
+            Value::Boolean(v) => Some(*v),
+            _ => None,
+        }))),
+        DataType::Int32 => Arc::new(Int32Array::from_iter(values.map(|v| match v {
+            Value::Int4(v) => Some(*v),
+            _ => None,
+        }))),
+        DataType::Int64 => Arc::new(Int64Array::from_iter(values.map(|v| match v {
+            Value::Int8(v) => Some(*v),
+            _ => None,
+        }))),
+        DataType::Float32 => Arc::new(Float32Array::from_iter(values.map(|v| match v {
+            Value::Float4(v) => Some(*v),
+            _ => None,
+        }))),
+        DataType::Float64 => Arc::new(Float64Array::from_iter(values.map(|v| match v {
+            Value::Float8(v) => Some(*v),
+            _ => None,
+        }))),
+        DataType::Decimal128(precision, scale) => {
+            let mut unscaled = Vec::new();
+            for value in values {
+                unscaled.push(match value {
+                    Value::Decimal(v) => {
+                        let (v, _) = v.with_scale(*scale as i64).into_bigint_and_exponent();
+                        Some(
+                            v.to_i128()
+                                .ok_or_else(|| format!("decimal overflow: {v}"))?,
+                        )
+                    }
+                    _ => None,
+                });
+            }
+            let array = Decimal128Array::from_iter(unscaled);
+            Arc::new(array.with_precision_and_scale(*precision, *scale)?)
+        }
+        DataType::Utf8 => Arc::new(StringArray::from_iter(values.map(|v| v.to_text()))),
+        DataType::Binary => Arc::new(BinaryArray::from_iter(values.map(|v| match v {
+            Value::Octet(v) => Some(v.as_slice()),
+            _ => None,
+        }))),
+        DataType::Date32 => Arc::new(Date32Array::from_iter(values.map(|v| match v {
+            Value::Date(v) => Some((*v - epoch()).num_days() as i32),
+            _ => None,
+        }))),
+        DataType::Time64(TimeUnit::Nanosecond) => {
+            Arc::new(Time64NanosecondArray::from_iter(values.map(|v| match v {
+                Value::TimeOfDay(v) => Some(nanos_of_day(v)),
+                _ => None,
+            })))
+        }
+        DataType::Timestamp(TimeUnit::Nanosecond, time_zone) => {
+            let mut nanos = Vec::new();
+            for value in values {
+                let date_time = match value {
+                    Value::TimePoint(v) => Some(v.and_utc()),
+                    Value::TimePointWithTimeZone(v) => Some(v.to_utc()),
+                    _ => None,
+                };
+                nanos.push(match date_time {
+                    Some(v) => Some(
+                        v.timestamp_nanos_opt()
+                            .ok_or_else(|| format!("time point out of range: {v}"))?,
+                    ),
+                    None => None,
+                });
+            }
+            let array = TimestampNanosecondArray::from_iter(nanos);
+            Arc::new(array.with_timezone_opt(time_zone.clone()))
+        }
+        data_type => return Err(format!("unsupported data type: {data_type}").into()),
+    };
+    Ok(array)
+}
+
+fn nanos_of_day(time: &NaiveTime) -> i64 {
+    time.num_seconds_from_midnight() as i64 * 1_000_000_000 + time.nanosecond() as i64
+}
+
+/// Retrieves the value of the array at the index.
+pub(crate) fn value_at(array: &dyn Array, index: usize) -> Result<Value, DataError> {
+    if array.is_null(index) {
+        return Ok(Value::Null);
+    }
+
+    let value = match array.data_type() {
+        DataType::Boolean => Value::Boolean(array.as_boolean().value(index)),
+        DataType::Int8 => Value::Int4(array.as_primitive::<Int8Type>().value(index) as i32),
+        DataType::Int16 => Value::Int4(array.as_primitive::<Int16Type>().value(index) as i32),
+        DataType::Int32 => Value::Int4(array.as_primitive::<Int32Type>().value(index)),
+        DataType::Int64 => Value::Int8(array.as_primitive::<Int64Type>().value(index)),
+        DataType::Float32 => Value::Float4(array.as_primitive::<Float32Type>().value(index)),
+        DataType::Float64 => Value::Float8(array.as_primitive::<Float64Type>().value(index)),
+        DataType::Decimal128(_, scale) => {
+            let unscaled = array.as_primitive::<Decimal128Type>().value(index);
+            Value::Decimal(BigDecimal::new(BigInt::from(unscaled), *scale as i64))
+        }
+        DataType::Utf8 => Value::Character(array.as_string::<i32>().value(index).to_string()),
+        DataType::LargeUtf8 => Value::Character(array.as_string::<i64>().value(index).to_string()),
+        DataType::Binary => Value::Octet(array.as_binary::<i32>().value(index).to_vec()),
+        DataType::LargeBinary => Value::Octet(array.as_binary::<i64>().value(index).to_vec()),
+        DataType::Date32 => {
+            let days = array.as_primitive::<Date32Type>().value(index);
+            Value::Date(epoch() + chrono::Duration::days(days as i64))
+        }
+        DataType::Time64(unit) => {
+            let value = match unit {
+                TimeUnit::Microsecond => {
+                    array.as_primitive::<Time64MicrosecondType>().value(index) * 1_000
+                }
+                _ => array.as_primitive::<Time64NanosecondType>().value(index),
+            };
+            let seconds = (value / 1_000_000_000) as u32;
+            let nanos = (value % 1_000_000_000) as u32;
+            let time = NaiveTime::from_num_seconds_from_midnight_opt(seconds, nanos)
+                .ok_or_else(|| format!("invalid time of day: {value}"))?;
+            Value::TimeOfDay(time)
+        }
+        DataType::Timestamp(unit, time_zone) => {
+            let nanos = match unit {
+                TimeUnit::Second => array
+                    .as_primitive::<TimestampSecondType>()
+                    .value(index)
+                    .saturating_mul(1_000_000_000),
+                TimeUnit::Millisecond => array
+                    .as_primitive::<TimestampMillisecondType>()
+                    .value(index)
+                    .saturating_mul(1_000_000),
+                TimeUnit::Microsecond => array
+                    .as_primitive::<TimestampMicrosecondType>()
+                    .value(index)
+                    .saturating_mul(1_000),
+                TimeUnit::Nanosecond => {
+                    array.as_primitive::<TimestampNanosecondType>().value(index)
+                }
+            };
+            let date_time = DateTime::from_timestamp_nanos(nanos);
+            match time_zone {
+                Some(_) => Value::TimePointWithTimeZone(date_time.fixed_offset()),
+                None => Value::TimePoint(date_time.naive_utc()),
+            }
+        }
+        data_type => return Err(format!("unsupported parquet type: {data_type}").into()),
+    };
+    Ok(value)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let date_time = NaiveDate::from_ymd_opt(2025, 1, 16)
+            .unwrap()
+            .and_hms_nano_opt(12, 34, 56, 789)
+            .unwrap();
+        let rows = vec![
+            vec![
+                Value::Boolean(true),
+                Value::Int4(1),
+                Value::Int8(2),
+                Value::Float8(1.5),
+                Value::Decimal("12.30".parse().unwrap()),
+                Value::Character("abc".to_string()),
+                Value::Octet(vec![1, 2, 3]),
+                Value::Date(date_time.date()),
+                Value::TimeOfDay(date_time.time()),
+                Value::TimePoint(date_time),
+                Value::TimePointWithTimeZone(date_time.and_utc().fixed_offset()),
+            ],
+            vec![Value::Null; 11],
+        ];
+        let fields = vec![
+            Field::new("c0", DataType::Boolean, true),
+            Field::new("c1", DataType::Int32, true),
+            Field::new("c2", DataType::Int64, true),
+            Field::new("c3", DataType::Float64, true),
+            Field::new("c4", DataType::Decimal128(10, 2), true),
+            Field::new("c5", DataType::Utf8, true),
+            Field::new("c6", DataType::Binary, true),
+            Field::new("c7", DataType::Date32, true),
+            Field::new("c8", DataType::Time64(TimeUnit::Nanosecond), true),
+            Field::new("c9", DataType::Timestamp(TimeUnit::Nanosecond, None), true),
+            Field::new(
+                "c10",
+                DataType::Timestamp(TimeUnit::Nanosecond, Some(UTC.into())),
+                true,
+            ),
+        ];
+        let schema = Arc::new(Schema::new(fields));
+
+        let batch = to_record_batch(&schema, &rows).unwrap();
+        for (r, row) in rows.iter().enumerate() {
+            for (c, expected) in row.iter().enumerate() {
+                let value = value_at(batch.column(c).as_ref(), r).unwrap();
+                assert_eq!(*expected, value, "row={r}, column={c}");
+            }
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use tsubakuro_rust_core::prelude::*;
+
+/// Error of tgdata.
+#[derive(Debug)]
+pub(crate) enum DataError {
+    /// error from Tsurugi.
+    Tg(Box<TgError>),
+    /// file I/O error.
+    Io(std::io::Error),
+    /// invalid data or usage.
+    Message(String),
+}
+
+impl std::fmt::Display for DataError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DataError::Tg(e) => match e.as_ref() {
+                TgError::ServerError(_, _, code, server_message) => {
+                    write!(f, "{code}: {server_message}")
+                }
+                e => write!(f, "{e}"),
+            },
+            DataError::Io(e) => write!(f, "{e}"),
+            DataError::Message(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+impl std::error::Error for DataError {}
+
+impl From<TgError> for DataError {
+    fn from(e: TgError) -> Self {
+        DataError::Tg(Box::new(e))
+    }
+}
+
+impl From<std::io::Error> for DataError {
+    fn from(e: std::io::Error) -> Self {
+        DataError::Io(e)
+    }
+}
+
+impl From<String> for DataError {
+    fn from(message: String) -> Self {
+        DataError::Message(message)
+    }
+}
+
+impl From<&str> for DataError {
+    fn from(message: &str) -> Self {
+        DataError::Message(message.to_string())
+    }
+}
+
+impl From<csv::Error> for DataError {
+    fn from(e: csv::Error) -> Self {
+        DataError::Message(format!("csv error. {e}"))
+    }
+}
+
+impl From<serde_json::Error> for DataError {
+    fn from(e: serde_json::Error) -> Self {
+        DataError::Message(format!("json error. {e}"))
+    }
+}
+
+impl From<parquet::errors::ParquetError> for DataError {
+    fn from(e: parquet::errors::ParquetError) -> Self {
+        DataError::Message(format!("parquet error. {e}"))
+    }
+}
+
+impl From<arrow_schema::ArrowError> for DataError {
+    fn from(e: arrow_schema::ArrowError) -> Self {
+        DataError::Message(format!("arrow error. {e}"))
+    }
+}
@@ -0,0 +1,82 @@
+use std::path::PathBuf;
+
+use tsubakuro_rust_core::prelude::*;
+
+use crate::{
+    error::DataError, format::FileFormat, progress::Progress, value::Value, writer::create_writer,
+};
+
+/// Options of `tgdata export`.
+#[derive(Debug)]
+pub(crate) struct ExportOption {
+    pub(crate) sql: String,
+    pub(crate) output: PathBuf,
+    pub(crate) format: FileFormat,
+    pub(crate) transaction_type: TransactionType,
+    pub(crate) null_string: String,
+    pub(crate) progress: bool,
+}
+
+/// Exports the query result to the file, and returns the number of rows.
+pub(crate) async fn export(client: &SqlClient, option: &ExportOption) -> Result<u64, DataError> {
+    let transaction_option = TransactionOption::from(option.transaction_type);
+    let transaction = client.start_transaction(&transaction_option).await?;
+
+    let result = export_in(client, &transaction, option).await;
+    let result = match result {
+        Ok(rows) => {
+            let commit_option = CommitOption::new();
+            client
+                .commit(&transaction, &commit_option)
+                .await
+                .map(|_| rows)
+                .map_err(DataError::from)
+        }
+        Err(e) => {
+            let _ = client.rollback(&transaction).await;
+            Err(e)
+        }
+    };
+    transaction.close().await?;
+    result
+}
+
+async fn export_in(
+    client: &SqlClient,
+    transaction: &Transaction,
+    option: &ExportOption,
+) -> Result<u64, DataError> {
+    let mut query_result = client.query(transaction, &option.sql).await?;
+    let result = export_rows(&mut query_result, option).await;
+    query_result.close().await?;
+    result
+}
+
+async fn export_rows(
+    query_result: &mut SqlQueryResult,
+    option: &ExportOption,
+) -> Result<u64, DataError> {
+    let columns: Vec<SqlColumn> = match query_result.get_metadata() {
+        Some(metadata) => metadata.columns().clone(),
+        None => return Err("query result has no metadata".into()),
+    };
+
+    let mut writer = create_writer(option.format, &option.output, &columns, &option.null_string)?;
+    let mut progress = Progress::new("exported", option.progress);
+    let mut row = Vec::with_capacity(columns.len());
+    while query_result.next_row().await? {
+        row.clear();
+        for column in &columns {
+            if !query_result.next_column().await? {
+                return Err(format!("column not found. column={}", column.name()).into());
+            }
+            row.push(Value::fetch(query_result, column).await?);
+        }
+        writer.write_row(&row)?;
+        progress.add(1);
+    }
+    writer.finish()?;
+    progress.finish();
+
+    Ok(progress.rows())
+}
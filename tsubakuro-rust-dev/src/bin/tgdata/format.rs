@@ -0,0 +1,63 @@
+use std::path::Path;
+
+use crate::error::DataError;
+
+/// File format of exported / imported data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum FileFormat {
+    /// CSV with a header line.
+    Csv,
+    /// one JSON object per line.
+    JsonLines,
+    /// Apache Parquet.
+    Parquet,
+}
+
+impl FileFormat {
+    pub(crate) fn parse(s: &str) -> Result<FileFormat, DataError> {
+        let format = match s.to_ascii_lowercase().as_str() {
+            "csv" => FileFormat::Csv,
+            "json" | "jsonl" | "ndjson" => FileFormat::JsonLines,
+            "parquet" => FileFormat::Parquet,
+            _ => return Err(format!("unknown format: {s}").into()),
+        };
+        Ok(format)
+    }
+
+    /// Determines the format from the file extension.
+    pub(crate) fn from_path(path: &Path) -> Result<FileFormat, DataError> {
+        let extension = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .ok_or_else(|| {
+                format!(
+                    "format is not specified, and cannot be determined from {}",
+                    path.display()
+                )
+            })?;
+        FileFormat::parse(extension)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn from_path() {
+        assert_eq!(
+            FileFormat::Csv,
+            FileFormat::from_path(Path::new("a/b.CSV")).unwrap()
+        );
+        assert_eq!(
+            FileFormat::JsonLines,
+            FileFormat::from_path(Path::new("b.jsonl")).unwrap()
+        );
+        assert_eq!(
+            FileFormat::Parquet,
+            FileFormat::from_path(Path::new("b.parquet")).unwrap()
+        );
+        assert!(FileFormat::from_path(Path::new("b")).is_err());
+        assert!(FileFormat::from_path(Path::new("b.txt")).is_err());
+    }
+}
@@ -0,0 +1,377 @@
+use std::path::{Path, PathBuf};
+
+use tsubakuro_rust_core::prelude::*;
+
+use crate::{
+    error::DataError,
+    format::FileFormat,
+    progress::Progress,
+    reader::{RowReader, open_reader},
+};
+
+/// Options of `tgdata import`.
+#[derive(Debug)]
+pub(crate) struct ImportOption {
+    pub(crate) table_name: String,
+    pub(crate) input: PathBuf,
+    pub(crate) format: FileFormat,
+    /// number of statements sent before waiting for their results.
+    pub(crate) batch_size: usize,
+    /// number of rows per transaction.
+    pub(crate) commit_interval: u64,
+    pub(crate) transaction_type: TransactionType,
+    pub(crate) null_string: String,
+    /// skips the rows committed by the previous run.
+    pub(crate) resume: bool,
+    pub(crate) checkpoint: PathBuf,
+    pub(crate) progress: bool,
+}
+
+impl ImportOption {
+    /// Returns the default checkpoint file of the input file.
+    pub(crate) fn default_checkpoint(input: &Path) -> PathBuf {
+        let mut path = input.as_os_str().to_owned();
+        path.push(".checkpoint");
+        PathBuf::from(path)
+    }
+
+    fn transaction_option(&self) -> TransactionOption {
+        let mut option = TransactionOption::from(self.transaction_type);
+        if self.transaction_type == TransactionType::Long {
+            option.set_write_preserve(&[self.table_name.as_str()]);
+        }
+        option.set_transaction_label("tgdata import");
+        option
+    }
+}
+
+struct TargetColumn {
+    name: String,
+    atom_type: AtomType,
+}
+
+/// Imports the file into the table, and returns the number of rows imported by this run.
+pub(crate) async fn import(client: &SqlClient, option: &ImportOption) -> Result<u64, DataError> {
+    if option.transaction_type == TransactionType::ReadOnly {
+        return Err("read only transaction cannot be used for import".into());
+    }
+
+    let metadata = client
+        .resolve_table_metadata(&option.table_name)
+        .await?
+        .ok_or_else(|| format!("table not found: {}", option.table_name))?;
+    let mut reader = open_reader(option.format, &option.input, &option.null_string)?;
+    let columns = target_columns(&metadata, reader.column_names())?;
+
+    let (sql, placeholders) = insert_statement(&option.table_name, &columns);
+    let prepared_statement = client.prepare(&sql, placeholders).await?;
+    let result = import_rows(
+        client,
+        &prepared_statement,
+        reader.as_mut(),
+        &columns,
+        option,
+    )
+    .await;
+    prepared_statement.close().await?;
+    result
+}
+
+fn target_columns(
+    metadata: &TableMetadata,
+    names: &[String],
+) -> Result<Vec<TargetColumn>, DataError> {
+    if names.is_empty() {
+        return Err("input has no columns".into());
+    }
+
+    let mut columns = Vec::with_capacity(names.len());
+    for name in names {
+        let column = metadata
+            .columns()
+            .iter()
+            .find(|column| column.name().eq_ignore_ascii_case(name))
+            .ok_or_else(|| {
+                format!(
+                    "column '{}' not found in table {}",
+                    name,
+                    metadata.table_name()
+                )
+            })?;
+        let atom_type = column
+            .atom_type()
+            .ok_or_else(|| format!("unknown column type. column={}", column.name()))?;
+        columns.push(TargetColumn {
+            name: column.name().clone(),
+            atom_type,
+        });
+    }
+    Ok(columns)
+}
+
+fn placeholder_name(index: usize) -> String {
+    format!("p{index}")
+}
+
+fn insert_statement(table_name: &str, columns: &[TargetColumn]) -> (String, Vec<SqlPlaceholder>) {
+    let names: Vec<&str> = columns.iter().map(|c| c.name.as_str()).collect();
+    let values: Vec<String> = (0..columns.len())
+        .map(|i| format!(":{}", placeholder_name(i)))
+        .collect();
+    let sql = format!(
+        "insert into {} ({}) values ({})",
+        table_name,
+        names.join(", "),
+        values.join(", ")
+    );
+
+    let placeholders = columns
+        .iter()
+        .enumerate()
+        .map(|(i, c)| SqlPlaceholder::of_atom_type(&placeholder_name(i), c.atom_type))
+        .collect();
+    (sql, placeholders)
+}
+
+async fn import_rows(
+    client: &SqlClient,
+    prepared_statement: &SqlPreparedStatement,
+    reader: &mut dyn RowReader,
+    columns: &[TargetColumn],
+    option: &ImportOption,
+) -> Result<u64, DataError> {
+    let checkpoint = Checkpoint::new(&option.checkpoint, &option.input);
+    let skip = if option.resume { checkpoint.load()? } else { 0 };
+    for _ in 0..skip {
+        if reader.next_row()?.is_none() {
+            break;
+        }
+    }
+
+    let mut progress = Progress::new("imported", option.progress);
+    progress.set_rows(skip);
+    let mut committed = skip;
+    let transaction_option = option.transaction_option();
+    loop {
+        let transaction = client.start_transaction(&transaction_option).await?;
+        let result = import_transaction(
+            client,
+            &transaction,
+            prepared_statement,
+            reader,
+            columns,
+            option,
+            &mut progress,
+        )
+        .await;
+        let result = match result {
+            Ok(result) => {
+                let commit_option = CommitOption::new();
+                client
+                    .commit(&transaction, &commit_option)
+                    .await
+                    .map(|_| result)
+                    .map_err(DataError::from)
+            }
+            Err(e) => {
+                let _ = client.rollback(&transaction).await;
+                Err(e)
+            }
+        };
+        transaction.close().await?;
+
+        let (rows, end) = match result {
+            Ok(result) => result,
+            Err(e) => {
+                progress.finish();
+                return Err(format!(
+                    "{e}\n{committed} rows were committed. run again with --resume to continue"
+                )
+                .into());
+            }
+        };
+        committed += rows;
+        checkpoint.save(committed)?;
+        if end {
+            break;
+        }
+    }
+
+    checkpoint.remove()?;
+    progress.finish();
+    Ok(committed - skip)
+}
+
+/// Imports rows up to the commit interval, and returns the number of rows and whether the end of the input is reached.
+async fn import_transaction(
+    client: &SqlClient,
+    transaction: &Transaction,
+    prepared_statement: &SqlPreparedStatement,
+    reader: &mut dyn RowReader,
+    columns: &[TargetColumn],
+    option: &ImportOption,
+    progress: &mut Progress,
+) -> Result<(u64, bool), DataError> {
+    let mut rows = 0;
+    while rows < option.commit_interval {
+        let limit = option
+            .batch_size
+            .min((option.commit_interval - rows) as usize);
+
+        let mut jobs = Vec::with_capacity(limit);
+        let mut end = false;
+        for _ in 0..limit {
+            let Some(row) = reader.next_row()? else {
+                end = true;
+                break;
+            };
+            if row.len() != columns.len() {
+                return Err(format!(
+                    "number of values mismatch. expected={}, actual={}",
+                    columns.len(),
+                    row.len()
+                )
+                .into());
+            }
+
+            let mut parameters = Vec::with_capacity(columns.len());
+            for (i, (value, column)) in row.into_iter().zip(columns).enumerate() {
+                let value = value.coerce(column.atom_type).map_err(|e| {
+                    format!(
+                        "{e}. row={}, column={}",
+                        progress.rows() + jobs.len() as u64 + 1,
+                        column.name
+                    )
+                })?;
+                parameters.push(value.to_parameter(&placeholder_name(i)));
+            }
+            let job = client
+                .prepared_execute_async(transaction, prepared_statement, parameters)
+                .await?;
+            jobs.push(job);
+        }
+
+        let n = jobs.len() as u64;
+        for mut job in jobs {
+            job.take().await?;
+        }
+        rows += n;
+        progress.add(n);
+        if end {
+            return Ok((rows, true));
+        }
+    }
+    Ok((rows, false))
+}
+
+/// Number of committed rows, saved for resuming an interrupted import.
+struct Checkpoint {
+    path: PathBuf,
+    input: String,
+}
+
+impl Checkpoint {
+    fn new(path: &Path, input: &Path) -> Checkpoint {
+        Checkpoint {
+            path: path.to_path_buf(),
+            input: input.display().to_string(),
+        }
+    }
+
+    fn load(&self) -> Result<u64, DataError> {
+        let content = match std::fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut input = None;
+        let mut committed = None;
+        for line in content.lines() {
+            match line.split_once('=') {
+                Some(("input", value)) => input = Some(value),
+                Some(("committed", value)) => committed = value.parse().ok(),
+                _ => {}
+            }
+        }
+        if input != Some(self.input.as_str()) {
+            return Err(format!(
+                "checkpoint {} is not for {}",
+                self.path.display(),
+                self.input
+            )
+            .into());
+        }
+        committed.ok_or_else(|| format!("invalid checkpoint: {}", self.path.display()).into())
+    }
+
+    fn save(&self, committed: u64) -> Result<(), DataError> {
+        // write and rename, not to leave a broken file
+        let mut temp = self.path.as_os_str().to_owned();
+        temp.push(".tmp");
+        std::fs::write(
+            &temp,
+            format!("input={}\ncommitted={}\n", self.input, committed),
+        )?;
+        std::fs::rename(&temp, &self.path)?;
+        Ok(())
+    }
+
+    fn remove(&self) -> Result<(), DataError> {
+        match std::fs::remove_file(&self.path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn insert() {
+        let columns = vec![
+            TargetColumn {
+                name: "id".to_string(),
+                atom_type: AtomType::Int4,
+            },
+            TargetColumn {
+                name: "name".to_string(),
+                atom_type: AtomType::Character,
+            },
+        ];
+        let (sql, placeholders) = insert_statement("example", &columns);
+        assert_eq!("insert into example (id, name) values (:p0, :p1)", sql);
+        assert_eq!(2, placeholders.len());
+        assert_eq!(Some(AtomType::Character), placeholders[1].atom_type());
+    }
+
+    #[test]
+    fn checkpoint() {
+        let dir = std::env::temp_dir().join(format!("tgdata-checkpoint-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("data.csv.checkpoint");
+
+        let checkpoint = Checkpoint::new(&path, Path::new("data.csv"));
+        assert_eq!(0, checkpoint.load().unwrap());
+        checkpoint.save(1000).unwrap();
+        assert_eq!(1000, checkpoint.load().unwrap());
+
+        let other = Checkpoint::new(&path, Path::new("other.csv"));
+        assert!(other.load().is_err());
+
+        checkpoint.remove().unwrap();
+        assert!(!path.exists());
+        checkpoint.remove().unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn default_checkpoint() {
+        assert_eq!(
+            PathBuf::from("/tmp/data.csv.checkpoint"),
+            ImportOption::default_checkpoint(Path::new("/tmp/data.csv"))
+        );
+    }
+}
@@ -0,0 +1,368 @@
+//! Data export / import tool for Tsurugi.
+
+mod columnar;
+mod error;
+mod export;
+mod format;
+mod import;
+mod progress;
+mod reader;
+mod value;
+mod writer;
+
+use std::{
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::Duration,
+};
+
+use tsubakuro_rust_core::prelude::*;
+
+use crate::{
+    error::DataError,
+    export::{ExportOption, export},
+    format::FileFormat,
+    import::{ImportOption, import},
+};
+
+const USAGE: &str = r#"usage: tgdata export [options] [connection-url] (--table <table> | --query <sql>) --output <file>
+       tgdata import [options] [connection-url] --table <table> --input <file>
+
+connection-url:
+  tcp://[user[:password]@]host:port[?key=value&...]
+  (if omitted, TSURUGI_* environment variables are used)
+
+common options:
+  --config <file>              read connection option from file (.toml or .json)
+  --credentials <file>         read credential from file
+  --timeout <duration>         default timeout (e.g. 30s, 500ms)
+  --format <format>            csv, jsonl or parquet (default: from the file extension)
+  --null <string>              string representing null in CSV (default: empty)
+  --transaction <type>         occ, ltx or rtx (default: rtx for export, ltx for import)
+  --quiet                      do not report progress
+  -h, --help                   show this help
+
+import options:
+  --batch-size <n>             statements sent before waiting for the results (default: 100)
+  --commit-interval <n>        rows per transaction (default: 10000)
+  --resume                     skip the rows committed by the previous run
+  --checkpoint <file>          checkpoint file (default: <input>.checkpoint)"#;
+
+#[derive(Debug, PartialEq)]
+enum Mode {
+    Export,
+    Import,
+}
+
+#[derive(Debug)]
+struct Arguments {
+    mode: Option<Mode>,
+    connection_url: Option<String>,
+    config: Option<PathBuf>,
+    credentials: Option<PathBuf>,
+    timeout: Option<Duration>,
+    table: Option<String>,
+    query: Option<String>,
+    output: Option<PathBuf>,
+    input: Option<PathBuf>,
+    format: Option<FileFormat>,
+    null_string: String,
+    transaction_type: Option<TransactionType>,
+    batch_size: usize,
+    commit_interval: u64,
+    resume: bool,
+    checkpoint: Option<PathBuf>,
+    quiet: bool,
+    help: bool,
+}
+
+impl Default for Arguments {
+    fn default() -> Self {
+        Arguments {
+            mode: None,
+            connection_url: None,
+            config: None,
+            credentials: None,
+            timeout: None,
+            table: None,
+            query: None,
+            output: None,
+            input: None,
+            format: None,
+            null_string: String::new(),
+            transaction_type: None,
+            batch_size: 100,
+            commit_interval: 10000,
+            resume: false,
+            checkpoint: None,
+            quiet: false,
+            help: false,
+        }
+    }
+}
+
+impl Arguments {
+    fn parse(args: impl IntoIterator<Item = String>) -> Result<Arguments, DataError> {
+        let mut arguments = Arguments::default();
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| {
+                args.next()
+                    .ok_or_else(|| format!("value is not specified for {name}"))
+            };
+            match arg.as_str() {
+                "--config" => arguments.config = Some(PathBuf::from(value(&arg)?)),
+                "--credentials" => arguments.credentials = Some(PathBuf::from(value(&arg)?)),
+                "--timeout" => arguments.timeout = Some(parse_duration(&value(&arg)?)?),
+                "--table" => arguments.table = Some(value(&arg)?),
+                "--query" => arguments.query = Some(value(&arg)?),
+                "-o" | "--output" => arguments.output = Some(PathBuf::from(value(&arg)?)),
+                "-i" | "--input" => arguments.input = Some(PathBuf::from(value(&arg)?)),
+                "--format" => arguments.format = Some(FileFormat::parse(&value(&arg)?)?),
+                "--null" => arguments.null_string = value(&arg)?,
+                "--transaction" => {
+                    arguments.transaction_type = Some(parse_transaction_type(&value(&arg)?)?);
+                }
+                "--batch-size" => arguments.batch_size = parse_count(&arg, &value(&arg)?)?,
+                "--commit-interval" => {
+                    arguments.commit_interval = parse_count(&arg, &value(&arg)?)?;
+                }
+                "--resume" => arguments.resume = true,
+                "--checkpoint" => arguments.checkpoint = Some(PathBuf::from(value(&arg)?)),
+                "--quiet" => arguments.quiet = true,
+                "-h" | "--help" => arguments.help = true,
+                _ if arg.starts_with('-') => return Err(format!("unknown option: {arg}").into()),
+                "export" if arguments.mode.is_none() => arguments.mode = Some(Mode::Export),
+                "import" if arguments.mode.is_none() => arguments.mode = Some(Mode::Import),
+                _ => {
+                    if arguments.mode.is_none() {
+                        return Err(format!("unknown command: {arg}").into());
+                    }
+                    if arguments.connection_url.is_some() {
+                        return Err(format!("unexpected argument: {arg}").into());
+                    }
+                    arguments.connection_url = Some(arg);
+                }
+            }
+        }
+
+        Ok(arguments)
+    }
+
+    fn connection_option(&self) -> Result<ConnectionOption, DataError> {
+        let mut option = if let Some(config) = &self.config {
+            ConnectionOption::from_file(config)?
+        } else if let Some(url) = &self.connection_url {
+            ConnectionOption::from_url(url)?
+        } else {
+            ConnectionOption::from_env()?
+        };
+
+        if let Some(credentials) = &self.credentials {
+            option.set_credential(Credential::load(credentials)?);
+        }
+        if let Some(timeout) = self.timeout {
+            option.set_default_timeout(timeout);
+        }
+        option.set_application_name("tgdata");
+        Ok(option)
+    }
+
+    fn format(&self, path: &Path) -> Result<FileFormat, DataError> {
+        match self.format {
+            Some(format) => Ok(format),
+            None => FileFormat::from_path(path),
+        }
+    }
+
+    fn export_option(&self) -> Result<ExportOption, DataError> {
+        let sql = match (&self.table, &self.query) {
+            (Some(table), None) => format!("select * from {table}"),
+            (None, Some(query)) => query.clone(),
+            _ => return Err("specify either --table or --query".into()),
+        };
+        let output = self.output.clone().ok_or("--output is not specified")?;
+        Ok(ExportOption {
+            sql,
+            format: self.format(&output)?,
+            output,
+            transaction_type: self.transaction_type.unwrap_or(TransactionType::ReadOnly),
+            null_string: self.null_string.clone(),
+            progress: !self.quiet,
+        })
+    }
+
+    fn import_option(&self) -> Result<ImportOption, DataError> {
+        let table_name = self.table.clone().ok_or("--table is not specified")?;
+        let input = self.input.clone().ok_or("--input is not specified")?;
+        Ok(ImportOption {
+            table_name,
+            format: self.format(&input)?,
+            checkpoint: self
+                .checkpoint
+                .clone()
+                .unwrap_or_else(|| ImportOption::default_checkpoint(&input)),
+            input,
+            batch_size: self.batch_size,
+            commit_interval: self.commit_interval,
+            transaction_type: self.transaction_type.unwrap_or(TransactionType::Long),
+            null_string: self.null_string.clone(),
+            resume: self.resume,
+            progress: !self.quiet,
+        })
+    }
+}
+
+fn parse_transaction_type(s: &str) -> Result<TransactionType, DataError> {
+    let transaction_type = match s.to_ascii_lowercase().as_str() {
+        "occ" | "short" => TransactionType::Short,
+        "ltx" | "long" => TransactionType::Long,
+        "rtx" | "readonly" | "read_only" => TransactionType::ReadOnly,
+        _ => return Err(format!("unknown transaction type: {s}").into()),
+    };
+    Ok(transaction_type)
+}
+
+fn parse_count<T: std::str::FromStr + Default + PartialEq>(
+    name: &str,
+    s: &str,
+) -> Result<T, DataError> {
+    match s.parse() {
+        Ok(value) if value != T::default() => Ok(value),
+        _ => Err(format!("{name} must be a positive number: {s}").into()),
+    }
+}
+
+/// Parses a duration such as `30s`, `500ms`, `1m` (seconds if no unit).
+fn parse_duration(s: &str) -> Result<Duration, DataError> {
+    let s = s.trim();
+    let index = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (number, unit) = s.split_at(index);
+    let number: u64 = number
+        .parse()
+        .map_err(|_| format!("invalid duration: {s}"))?;
+    let duration = match unit {
+        "" | "s" => Duration::from_secs(number),
+        "ms" => Duration::from_millis(number),
+        "m" | "min" => Duration::from_secs(number * 60),
+        _ => return Err(format!("invalid duration unit: {s}").into()),
+    };
+    Ok(duration)
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let arguments = match Arguments::parse(std::env::args().skip(1)) {
+        Ok(arguments) => arguments,
+        Err(e) => {
+            eprintln!("{e}\n\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+    if arguments.help || arguments.mode.is_none() {
+        println!("{USAGE}");
+        return ExitCode::SUCCESS;
+    }
+
+    match run(&arguments).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn run(arguments: &Arguments) -> Result<(), DataError> {
+    let option = arguments.connection_option()?;
+    let session = Session::connect(&option).await?;
+    let mut client: SqlClient = session.make_client();
+    client.set_default_timeout(option.default_timeout());
+
+    let result = match arguments.mode {
+        Some(Mode::Export) => {
+            let option = arguments.export_option()?;
+            export(&client, &option).await.map(|rows| {
+                println!("{} rows exported to {}", rows, option.output.display());
+            })
+        }
+        Some(Mode::Import) => {
+            let option = arguments.import_option()?;
+            import(&client, &option).await.map(|rows| {
+                println!("{} rows imported into {}", rows, option.table_name);
+            })
+        }
+        None => Ok(()),
+    };
+    session.close().await?;
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Arguments, DataError> {
+        Arguments::parse(args.iter().map(|s| s.to_string()))
+    }
+
+    #[test]
+    fn export_arguments() {
+        let arguments = parse(&[
+            "export",
+            "tcp://localhost:12345",
+            "--table",
+            "example",
+            "-o",
+            "example.parquet",
+        ])
+        .unwrap();
+        assert_eq!(Some(Mode::Export), arguments.mode);
+        assert_eq!(
+            Some("tcp://localhost:12345".to_string()),
+            arguments.connection_url
+        );
+
+        let option = arguments.export_option().unwrap();
+        assert_eq!("select * from example", option.sql);
+        assert_eq!(FileFormat::Parquet, option.format);
+        assert_eq!(TransactionType::ReadOnly, option.transaction_type);
+
+        let arguments = parse(&["export", "--table", "t", "--query", "select 1"]).unwrap();
+        assert!(arguments.export_option().is_err());
+    }
+
+    #[test]
+    fn import_arguments() {
+        let arguments = parse(&[
+            "import",
+            "--table",
+            "example",
+            "-i",
+            "/tmp/example.txt",
+            "--format",
+            "csv",
+            "--batch-size",
+            "10",
+            "--transaction",
+            "occ",
+            "--resume",
+        ])
+        .unwrap();
+
+        let option = arguments.import_option().unwrap();
+        assert_eq!(FileFormat::Csv, option.format);
+        assert_eq!(10, option.batch_size);
+        assert_eq!(10000, option.commit_interval);
+        assert_eq!(TransactionType::Short, option.transaction_type);
+        assert!(option.resume);
+        assert_eq!(
+            PathBuf::from("/tmp/example.txt.checkpoint"),
+            option.checkpoint
+        );
+
+        assert!(parse(&["import", "--batch-size", "0"]).is_err());
+        assert!(parse(&["unknown"]).is_err());
+    }
+}
@@ -0,0 +1,65 @@
+use std::{
+    io::Write,
+    time::{Duration, Instant},
+};
+
+const REPORT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Reports the number of processed rows to stderr.
+pub(crate) struct Progress {
+    label: &'static str,
+    enabled: bool,
+    start: Instant,
+    last_report: Instant,
+    rows: u64,
+}
+
+impl Progress {
+    pub(crate) fn new(label: &'static str, enabled: bool) -> Progress {
+        let now = Instant::now();
+        Progress {
+            label,
+            enabled,
+            start: now,
+            last_report: now,
+            rows: 0,
+        }
+    }
+
+    /// Starts from the rows processed before (for resumed imports).
+    pub(crate) fn set_rows(&mut self, rows: u64) {
+        self.rows = rows;
+    }
+
+    pub(crate) fn rows(&self) -> u64 {
+        self.rows
+    }
+
+    pub(crate) fn add(&mut self, rows: u64) {
+        self.rows += rows;
+        if self.enabled && self.last_report.elapsed() >= REPORT_INTERVAL {
+            self.last_report = Instant::now();
+            eprint!("\r{}", self.message());
+            let _ = std::io::stderr().flush();
+        }
+    }
+
+    pub(crate) fn finish(&self) {
+        if self.enabled {
+            eprintln!("\r{}", self.message());
+        }
+    }
+
+    fn message(&self) -> String {
+        let elapsed = self.start.elapsed().as_secs_f64();
+        let rate = if elapsed > 0.0 {
+            self.rows as f64 / elapsed
+        } else {
+            0.0
+        };
+        format!(
+            "{}: {} rows ({:.1}s, {:.0} rows/s)",
+            self.label, self.rows, elapsed, rate
+        )
+    }
+}
@@ -0,0 +1,245 @@
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, Read},
+    path::Path,
+};
+
+use arrow_array::RecordBatch;
+use parquet::arrow::arrow_reader::{ParquetRecordBatchReader, ParquetRecordBatchReaderBuilder};
+
+use crate::{columnar, error::DataError, format::FileFormat, value::Value};
+
+/// Reader of imported rows.
+///
+/// Values are not converted to the column types; see [`Value::coerce`].
+pub(crate) trait RowReader {
+    fn column_names(&self) -> &[String];
+
+    /// Reads the next row, or returns `None` at the end of the file.
+    fn next_row(&mut self) -> Result<Option<Vec<Value>>, DataError>;
+}
+
+/// Opens a reader.
+pub(crate) fn open_reader(
+    format: FileFormat,
+    path: &Path,
+    null_string: &str,
+) -> Result<Box<dyn RowReader>, DataError> {
+    let file = File::open(path)?;
+    let reader: Box<dyn RowReader> = match format {
+        FileFormat::Csv => Box::new(CsvReader::new(file, null_string)?),
+        FileFormat::JsonLines => Box::new(JsonLinesReader::new(file)?),
+        FileFormat::Parquet => Box::new(ParquetReader::new(file)?),
+    };
+    Ok(reader)
+}
+
+pub(crate) struct CsvReader<R: Read> {
+    reader: csv::Reader<R>,
+    names: Vec<String>,
+    null_string: String,
+    record: csv::StringRecord,
+}
+
+impl<R: Read> CsvReader<R> {
+    pub(crate) fn new(reader: R, null_string: &str) -> Result<CsvReader<R>, DataError> {
+        let mut reader = csv::Reader::from_reader(reader);
+        let names = reader.headers()?.iter().map(String::from).collect();
+        Ok(CsvReader {
+            reader,
+            names,
+            null_string: null_string.to_string(),
+            record: csv::StringRecord::new(),
+        })
+    }
+}
+
+impl<R: Read> RowReader for CsvReader<R> {
+    fn column_names(&self) -> &[String] {
+        &self.names
+    }
+
+    fn next_row(&mut self) -> Result<Option<Vec<Value>>, DataError> {
+        if !self.reader.read_record(&mut self.record)? {
+            return Ok(None);
+        }
+        let row = self
+            .record
+            .iter()
+            .map(|field| {
+                if field == self.null_string {
+                    Value::Null
+                } else {
+                    Value::Character(field.to_string())
+                }
+            })
+            .collect();
+        Ok(Some(row))
+    }
+}
+
+/// Reader of JSON Lines.
+///
+/// The column names are taken from the first object.
+pub(crate) struct JsonLinesReader<R: Read> {
+    lines: std::io::Lines<BufReader<R>>,
+    names: Vec<String>,
+    first: Option<serde_json::Map<String, serde_json::Value>>,
+    line_number: usize,
+}
+
+impl<R: Read> JsonLinesReader<R> {
+    pub(crate) fn new(reader: R) -> Result<JsonLinesReader<R>, DataError> {
+        let mut reader = JsonLinesReader {
+            lines: BufReader::new(reader).lines(),
+            names: Vec::new(),
+            first: None,
+            line_number: 0,
+        };
+        if let Some(object) = reader.next_object()? {
+            reader.names = object.keys().cloned().collect();
+            reader.first = Some(object);
+        }
+        Ok(reader)
+    }
+
+    fn next_object(
+        &mut self,
+    ) -> Result<Option<serde_json::Map<String, serde_json::Value>>, DataError> {
+        for line in self.lines.by_ref() {
+            let line = line?;
+            self.line_number += 1;
+            if line.trim().is_empty() {
+                continue;
+            }
+            return match serde_json::from_str(&line)? {
+                serde_json::Value::Object(object) => Ok(Some(object)),
+                _ => Err(format!("not a json object. line={}", self.line_number).into()),
+            };
+        }
+        Ok(None)
+    }
+}
+
+impl<R: Read> RowReader for JsonLinesReader<R> {
+    fn column_names(&self) -> &[String] {
+        &self.names
+    }
+
+    fn next_row(&mut self) -> Result<Option<Vec<Value>>, DataError> {
+        let mut object = match self.first.take() {
+            Some(object) => object,
+            None => match self.next_object()? {
+                Some(object) => object,
+                None => return Ok(None),
+            },
+        };
+
+        let mut row = Vec::with_capacity(self.names.len());
+        for name in &self.names {
+            let value = object.remove(name).unwrap_or(serde_json::Value::Null);
+            row.push(Value::from_json(value)?);
+        }
+        if let Some(name) = object.keys().next() {
+            return Err(format!("unknown key '{}'. line={}", name, self.line_number).into());
+        }
+        Ok(Some(row))
+    }
+}
+
+pub(crate) struct ParquetReader {
+    reader: ParquetRecordBatchReader,
+    names: Vec<String>,
+    batch: Option<RecordBatch>,
+    index: usize,
+}
+
+impl ParquetReader {
+    pub(crate) fn new(file: File) -> Result<ParquetReader, DataError> {
+        let builder = ParquetRecordBatchReaderBuilder::try_new(file)?;
+        let names = builder
+            .schema()
+            .fields()
+            .iter()
+            .map(|field| field.name().clone())
+            .collect();
+        Ok(ParquetReader {
+            reader: builder.build()?,
+            names,
+            batch: None,
+            index: 0,
+        })
+    }
+}
+
+impl RowReader for ParquetReader {
+    fn column_names(&self) -> &[String] {
+        &self.names
+    }
+
+    fn next_row(&mut self) -> Result<Option<Vec<Value>>, DataError> {
+        loop {
+            if let Some(batch) = &self.batch
+                && self.index < batch.num_rows()
+            {
+                let row = batch
+                    .columns()
+                    .iter()
+                    .map(|array| columnar::value_at(array.as_ref(), self.index))
+                    .collect::<Result<Vec<_>, _>>()?;
+                self.index += 1;
+                return Ok(Some(row));
+            }
+            match self.reader.next() {
+                Some(batch) => {
+                    self.batch = Some(batch?);
+                    self.index = 0;
+                }
+                None => return Ok(None),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn csv() {
+        let data = "id,name\n1,abc\n2,\n";
+        let mut reader = CsvReader::new(data.as_bytes(), "").unwrap();
+        assert_eq!(&["id", "name"], reader.column_names());
+        assert_eq!(
+            Some(vec![
+                Value::Character("1".to_string()),
+                Value::Character("abc".to_string())
+            ]),
+            reader.next_row().unwrap()
+        );
+        assert_eq!(
+            Some(vec![Value::Character("2".to_string()), Value::Null]),
+            reader.next_row().unwrap()
+        );
+        assert_eq!(None, reader.next_row().unwrap());
+    }
+
+    #[test]
+    fn json_lines() {
+        let data = "{\"id\":1,\"name\":\"abc\"}\n\n{\"id\":2}\n{\"id\":3,\"x\":0}\n";
+        let mut reader = JsonLinesReader::new(data.as_bytes()).unwrap();
+        assert_eq!(&["id", "name"], reader.column_names());
+        assert_eq!(
+            Some(vec![
+                Value::Character("1".to_string()),
+                Value::Character("abc".to_string())
+            ]),
+            reader.next_row().unwrap()
+        );
+        assert_eq!(
+            Some(vec![Value::Character("2".to_string()), Value::Null]),
+            reader.next_row().unwrap()
+        );
+        assert!(reader.next_row().is_err());
+    }
+}
@@ -0,0 +1,370 @@
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime};
+use tsubakuro_rust_core::prelude::*;
+
+use crate::error::DataError;
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+const TIME_FORMAT: &str = "%H:%M:%S%.f";
+const DATE_TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S%.f";
+
+/// Value of a column, independent of the file format.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Value {
+    Null,
+    Boolean(bool),
+    Int4(i32),
+    Int8(i64),
+    Float4(f32),
+    Float8(f64),
+    Decimal(BigDecimal),
+    Character(String),
+    Octet(Vec<u8>),
+    Date(NaiveDate),
+    TimeOfDay(NaiveTime),
+    TimePoint(NaiveDateTime),
+    TimeOfDayWithTimeZone(NaiveTime, FixedOffset),
+    TimePointWithTimeZone(DateTime<FixedOffset>),
+}
+
+impl Value {
+    /// Retrieves the value on the column of the cursor position.
+    pub(crate) async fn fetch(
+        query_result: &mut SqlQueryResult,
+        column: &SqlColumn,
+    ) -> Result<Value, DataError> {
+        if query_result.is_null()? {
+            return Ok(Value::Null);
+        }
+
+        let value = match column.atom_type() {
+            Some(AtomType::Boolean) => Value::Boolean(query_result.fetch().await?),
+            Some(AtomType::Int4) => Value::Int4(query_result.fetch().await?),
+            Some(AtomType::Int8) => Value::Int8(query_result.fetch().await?),
+            Some(AtomType::Float4) => Value::Float4(query_result.fetch().await?),
+            Some(AtomType::Float8) => Value::Float8(query_result.fetch().await?),
+            Some(AtomType::Decimal) => Value::Decimal(query_result.fetch().await?),
+            Some(AtomType::Character) => Value::Character(query_result.fetch().await?),
+            Some(AtomType::Octet) => Value::Octet(query_result.fetch().await?),
+            Some(AtomType::Date) => Value::Date(query_result.fetch().await?),
+            Some(AtomType::TimeOfDay) => Value::TimeOfDay(query_result.fetch().await?),
+            Some(AtomType::TimePoint) => Value::TimePoint(query_result.fetch().await?),
+            Some(AtomType::TimeOfDayWithTimeZone) => {
+                let (time, offset) = query_result.fetch().await?;
+                Value::TimeOfDayWithTimeZone(time, offset)
+            }
+            Some(AtomType::TimePointWithTimeZone) => {
+                Value::TimePointWithTimeZone(query_result.fetch().await?)
+            }
+            atom_type => {
+                return Err(format!(
+                    "unsupported column type. column={}, type={:?}",
+                    column.name(),
+                    atom_type
+                )
+                .into());
+            }
+        };
+        Ok(value)
+    }
+
+    fn atom_type(&self) -> Option<AtomType> {
+        let atom_type = match self {
+            Value::Null => return None,
+            Value::Boolean(_) => AtomType::Boolean,
+            Value::Int4(_) => AtomType::Int4,
+            Value::Int8(_) => AtomType::Int8,
+            Value::Float4(_) => AtomType::Float4,
+            Value::Float8(_) => AtomType::Float8,
+            Value::Decimal(_) => AtomType::Decimal,
+            Value::Character(_) => AtomType::Character,
+            Value::Octet(_) => AtomType::Octet,
+            Value::Date(_) => AtomType::Date,
+            Value::TimeOfDay(_) => AtomType::TimeOfDay,
+            Value::TimePoint(_) => AtomType::TimePoint,
+            Value::TimeOfDayWithTimeZone(_, _) => AtomType::TimeOfDayWithTimeZone,
+            Value::TimePointWithTimeZone(_) => AtomType::TimePointWithTimeZone,
+        };
+        Some(atom_type)
+    }
+
+    /// Converts to the text representation (`None` if null).
+    pub(crate) fn to_text(&self) -> Option<String> {
+        let text = match self {
+            Value::Null => return None,
+            Value::Boolean(value) => value.to_string(),
+            Value::Int4(value) => value.to_string(),
+            Value::Int8(value) => value.to_string(),
+            Value::Float4(value) => value.to_string(),
+            Value::Float8(value) => value.to_string(),
+            Value::Decimal(value) => value.to_plain_string(),
+            Value::Character(value) => value.clone(),
+            Value::Octet(value) => value.iter().map(|b| format!("{b:02x}")).collect(),
+            Value::Date(value) => value.format(DATE_FORMAT).to_string(),
+            Value::TimeOfDay(value) => value.format(TIME_FORMAT).to_string(),
+            Value::TimePoint(value) => value.format(DATE_TIME_FORMAT).to_string(),
+            Value::TimeOfDayWithTimeZone(time, offset) => {
+                format!("{}{}", time.format(TIME_FORMAT), offset)
+            }
+            Value::TimePointWithTimeZone(value) => {
+                value.format("%Y-%m-%d %H:%M:%S%.f%:z").to_string()
+            }
+        };
+        Some(text)
+    }
+
+    /// Parses the text representation as the type.
+    pub(crate) fn parse(text: &str, atom_type: AtomType) -> Result<Value, DataError> {
+        let error = || DataError::Message(format!("invalid {atom_type:?} value: {text}"));
+
+        let s = text.trim();
+        let value = match atom_type {
+            AtomType::Boolean => match s.to_ascii_lowercase().as_str() {
+                "true" | "t" | "1" => Value::Boolean(true),
+                "false" | "f" | "0" => Value::Boolean(false),
+                _ => return Err(error()),
+            },
+            AtomType::Int4 => Value::Int4(s.parse().map_err(|_| error())?),
+            AtomType::Int8 => Value::Int8(s.parse().map_err(|_| error())?),
+            AtomType::Float4 => Value::Float4(s.parse().map_err(|_| error())?),
+            AtomType::Float8 => Value::Float8(s.parse().map_err(|_| error())?),
+            AtomType::Decimal => Value::Decimal(s.parse().map_err(|_| error())?),
+            AtomType::Character => Value::Character(text.to_string()),
+            AtomType::Octet => Value::Octet(parse_hex(s).ok_or_else(error)?),
+            AtomType::Date => {
+                Value::Date(NaiveDate::parse_from_str(s, DATE_FORMAT).map_err(|_| error())?)
+            }
+            AtomType::TimeOfDay => {
+                Value::TimeOfDay(NaiveTime::parse_from_str(s, TIME_FORMAT).map_err(|_| error())?)
+            }
+            AtomType::TimePoint => {
+                let s = s.replacen('T', " ", 1);
+                let value = NaiveDateTime::parse_from_str(&s, DATE_TIME_FORMAT);
+                Value::TimePoint(value.map_err(|_| error())?)
+            }
+            AtomType::TimeOfDayWithTimeZone => {
+                let (time, offset) = split_offset(s).ok_or_else(error)?;
+                let time = NaiveTime::parse_from_str(time, TIME_FORMAT).map_err(|_| error())?;
+                Value::TimeOfDayWithTimeZone(time, offset)
+            }
+            AtomType::TimePointWithTimeZone => {
+                let s = s.replacen('T', " ", 1);
+                let (date_time, offset) = split_offset(&s).ok_or_else(error)?;
+                let date_time = NaiveDateTime::parse_from_str(date_time, DATE_TIME_FORMAT)
+                    .map_err(|_| error())?;
+                let value = date_time
+                    .and_local_timezone(offset)
+                    .single()
+                    .ok_or_else(error)?;
+                Value::TimePointWithTimeZone(value)
+            }
+            _ => return Err(format!("unsupported type: {atom_type:?}").into()),
+        };
+        Ok(value)
+    }
+
+    /// Converts to the type.
+    pub(crate) fn coerce(self, atom_type: AtomType) -> Result<Value, DataError> {
+        if self == Value::Null || self.atom_type() == Some(atom_type) {
+            return Ok(self);
+        }
+        let value = match (&self, atom_type) {
+            (Value::Int4(value), AtomType::Int8) => Value::Int8(*value as i64),
+            (Value::Float4(value), AtomType::Float8) => Value::Float8(*value as f64),
+            (Value::TimePointWithTimeZone(value), AtomType::TimePoint) => {
+                Value::TimePoint(value.naive_utc())
+            }
+            (Value::TimePoint(value), AtomType::TimePointWithTimeZone) => {
+                Value::TimePointWithTimeZone(value.and_utc().fixed_offset())
+            }
+            _ => {
+                let text = self.to_text().unwrap_or_default();
+                Value::parse(&text, atom_type)?
+            }
+        };
+        Ok(value)
+    }
+
+    /// Converts to a parameter of a prepared statement.
+    pub(crate) fn to_parameter(&self, name: &str) -> SqlParameter {
+        match self {
+            Value::Null => SqlParameter::null(name),
+            Value::Boolean(value) => SqlParameter::of(name, *value),
+            Value::Int4(value) => SqlParameter::of(name, *value),
+            Value::Int8(value) => SqlParameter::of(name, *value),
+            Value::Float4(value) => SqlParameter::of(name, *value),
+            Value::Float8(value) => SqlParameter::of(name, *value),
+            Value::Decimal(value) => SqlParameter::of(name, value),
+            Value::Character(value) => SqlParameter::of(name, value),
+            Value::Octet(value) => SqlParameter::of(name, value),
+            Value::Date(value) => SqlParameter::of(name, value),
+            Value::TimeOfDay(value) => SqlParameter::of(name, value),
+            Value::TimePoint(value) => SqlParameter::of(name, value),
+            Value::TimeOfDayWithTimeZone(time, offset) => SqlParameter::of(name, (*time, *offset)),
+            Value::TimePointWithTimeZone(value) => SqlParameter::of(name, value),
+        }
+    }
+
+    /// Converts to a JSON value.
+    ///
+    /// Decimal values are written as strings to keep the precision.
+    pub(crate) fn to_json(&self) -> serde_json::Value {
+        use serde_json::Value as Json;
+        match self {
+            Value::Null => Json::Null,
+            Value::Boolean(value) => Json::Bool(*value),
+            Value::Int4(value) => Json::from(*value),
+            Value::Int8(value) => Json::from(*value),
+            Value::Float4(value) => float_to_json(value.to_string()),
+            Value::Float8(value) => float_to_json(value.to_string()),
+            value => Json::String(value.to_text().unwrap_or_default()),
+        }
+    }
+
+    /// Converts from a JSON value.
+    ///
+    /// Numbers and strings are kept as text, and converted by [`Value::coerce`].
+    pub(crate) fn from_json(json: serde_json::Value) -> Result<Value, DataError> {
+        use serde_json::Value as Json;
+        let value = match json {
+            Json::Null => Value::Null,
+            Json::Bool(value) => Value::Boolean(value),
+            Json::Number(value) => Value::Character(value.to_string()),
+            Json::String(value) => Value::Character(value),
+            json => return Err(format!("unsupported json value: {json}").into()),
+        };
+        Ok(value)
+    }
+}
+
+fn float_to_json(text: String) -> serde_json::Value {
+    // NaN and infinity are not JSON numbers
+    match serde_json::from_str::<serde_json::Number>(&text) {
+        Ok(number) => serde_json::Value::Number(number),
+        Err(_) => serde_json::Value::String(text),
+    }
+}
+
+fn parse_hex(s: &str) -> Option<Vec<u8>> {
+    let s = s
+        .strip_prefix("X'")
+        .and_then(|s| s.strip_suffix('\''))
+        .unwrap_or(s);
+    if !s.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(s.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+fn split_offset(s: &str) -> Option<(&str, FixedOffset)> {
+    if let Some(s) = s.strip_suffix('Z') {
+        return Some((s, FixedOffset::east_opt(0)?));
+    }
+    // the offset starts with the last sign after the time part
+    let time_start = s.find(':').unwrap_or(0);
+    let index = s[time_start..].rfind(['+', '-'])? + time_start;
+    let offset = s[index..].parse().ok()?;
+    Some((&s[..index], offset))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn text() {
+        let values = [
+            (Value::Boolean(true), AtomType::Boolean),
+            (Value::Int4(-123), AtomType::Int4),
+            (Value::Int8(1234567890123), AtomType::Int8),
+            (Value::Float8(1.5), AtomType::Float8),
+            (
+                Value::Decimal("123.450".parse().unwrap()),
+                AtomType::Decimal,
+            ),
+            (Value::Character("a,b".to_string()), AtomType::Character),
+            (Value::Octet(vec![0x00, 0xab, 0xff]), AtomType::Octet),
+            (
+                Value::Date(NaiveDate::from_ymd_opt(2025, 1, 16).unwrap()),
+                AtomType::Date,
+            ),
+            (
+                Value::TimeOfDay(NaiveTime::from_hms_nano_opt(12, 34, 56, 789).unwrap()),
+                AtomType::TimeOfDay,
+            ),
+            (
+                Value::TimeOfDayWithTimeZone(
+                    NaiveTime::from_hms_opt(12, 34, 56).unwrap(),
+                    FixedOffset::west_opt(5 * 3600).unwrap(),
+                ),
+                AtomType::TimeOfDayWithTimeZone,
+            ),
+            (
+                Value::TimePointWithTimeZone(
+                    DateTime::parse_from_rfc3339("2025-01-16T12:34:56.5+09:00").unwrap(),
+                ),
+                AtomType::TimePointWithTimeZone,
+            ),
+        ];
+        for (value, atom_type) in values {
+            let text = value.to_text().unwrap();
+            assert_eq!(value, Value::parse(&text, atom_type).unwrap(), "{text}");
+        }
+    }
+
+    #[test]
+    fn parse() {
+        let expected = NaiveDate::from_ymd_opt(2025, 1, 16)
+            .unwrap()
+            .and_hms_opt(12, 34, 56)
+            .unwrap();
+        assert_eq!(
+            Value::TimePoint(expected),
+            Value::parse("2025-01-16T12:34:56", AtomType::TimePoint).unwrap()
+        );
+        assert_eq!(
+            Value::TimePointWithTimeZone(expected.and_utc().fixed_offset()),
+            Value::parse("2025-01-16 12:34:56Z", AtomType::TimePointWithTimeZone).unwrap()
+        );
+        assert_eq!(
+            Value::Octet(vec![0x12, 0x34]),
+            Value::parse("X'1234'", AtomType::Octet).unwrap()
+        );
+        assert!(Value::parse("123", AtomType::Octet).is_err());
+        assert!(Value::parse("abc", AtomType::Int4).is_err());
+    }
+
+    #[test]
+    fn coerce() {
+        assert_eq!(
+            Value::Int8(1),
+            Value::Character("1".to_string())
+                .coerce(AtomType::Int8)
+                .unwrap()
+        );
+        assert_eq!(
+            Value::Int8(1),
+            Value::Int4(1).coerce(AtomType::Int8).unwrap()
+        );
+        assert_eq!(Value::Null, Value::Null.coerce(AtomType::Int8).unwrap());
+        assert!(Value::Int8(i64::MAX).coerce(AtomType::Int4).is_err());
+    }
+
+    #[test]
+    fn json() {
+        assert_eq!(serde_json::json!(1), Value::Int4(1).to_json());
+        assert_eq!(serde_json::json!(1.5), Value::Float8(1.5).to_json());
+        assert_eq!(serde_json::json!("NaN"), Value::Float8(f64::NAN).to_json());
+        assert_eq!(
+            serde_json::json!("1.10"),
+            Value::Decimal("1.10".parse().unwrap()).to_json()
+        );
+        assert_eq!(
+            Value::Character("1.5".to_string()),
+            Value::from_json(serde_json::json!(1.5)).unwrap()
+        );
+    }
+}
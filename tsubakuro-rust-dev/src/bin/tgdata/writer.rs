@@ -0,0 +1,158 @@
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+    sync::Arc,
+};
+
+use arrow_schema::Schema;
+use parquet::arrow::ArrowWriter;
+use tsubakuro_rust_core::prelude::*;
+
+use crate::{columnar, error::DataError, format::FileFormat, value::Value};
+
+const PARQUET_BATCH_SIZE: usize = 8192;
+
+/// Writer of exported rows.
+pub(crate) trait RowWriter {
+    fn write_row(&mut self, row: &[Value]) -> Result<(), DataError>;
+
+    /// Flushes the buffered rows and finishes the file.
+    fn finish(self: Box<Self>) -> Result<(), DataError>;
+}
+
+/// Creates a writer for the columns.
+pub(crate) fn create_writer(
+    format: FileFormat,
+    path: &Path,
+    columns: &[SqlColumn],
+    null_string: &str,
+) -> Result<Box<dyn RowWriter>, DataError> {
+    let file = File::create(path)?;
+    let names: Vec<String> = columns.iter().map(|c| c.name().clone()).collect();
+
+    let writer: Box<dyn RowWriter> = match format {
+        FileFormat::Csv => Box::new(CsvWriter::new(file, &names, null_string)?),
+        FileFormat::JsonLines => Box::new(JsonLinesWriter::new(file, names)),
+        FileFormat::Parquet => {
+            let schema = Arc::new(columnar::schema(columns)?);
+            Box::new(ParquetWriter::new(file, schema)?)
+        }
+    };
+    Ok(writer)
+}
+
+pub(crate) struct CsvWriter<W: Write> {
+    writer: csv::Writer<W>,
+    null_string: String,
+}
+
+impl<W: Write> CsvWriter<W> {
+    pub(crate) fn new(
+        writer: W,
+        names: &[String],
+        null_string: &str,
+    ) -> Result<CsvWriter<W>, DataError> {
+        let mut writer = csv::Writer::from_writer(writer);
+        writer.write_record(names)?;
+        Ok(CsvWriter {
+            writer,
+            null_string: null_string.to_string(),
+        })
+    }
+}
+
+impl<W: Write> RowWriter for CsvWriter<W> {
+    fn write_row(&mut self, row: &[Value]) -> Result<(), DataError> {
+        let record = row
+            .iter()
+            .map(|value| value.to_text().unwrap_or_else(|| self.null_string.clone()));
+        self.writer.write_record(record)?;
+        Ok(())
+    }
+
+    fn finish(mut self: Box<Self>) -> Result<(), DataError> {
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+pub(crate) struct JsonLinesWriter<W: Write> {
+    writer: BufWriter<W>,
+    names: Vec<String>,
+}
+
+impl<W: Write> JsonLinesWriter<W> {
+    pub(crate) fn new(writer: W, names: Vec<String>) -> JsonLinesWriter<W> {
+        JsonLinesWriter {
+            writer: BufWriter::new(writer),
+            names,
+        }
+    }
+}
+
+impl<W: Write> RowWriter for JsonLinesWriter<W> {
+    fn write_row(&mut self, row: &[Value]) -> Result<(), DataError> {
+        // written by hand to keep the column order
+        let mut line = String::from("{");
+        for (i, (name, value)) in self.names.iter().zip(row).enumerate() {
+            if i != 0 {
+                line.push(',');
+            }
+            line.push_str(&serde_json::to_string(name)?);
+            line.push(':');
+            line.push_str(&value.to_json().to_string());
+        }
+        line.push_str("}\n");
+        self.writer.write_all(line.as_bytes())?;
+        Ok(())
+    }
+
+    fn finish(mut self: Box<Self>) -> Result<(), DataError> {
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+pub(crate) struct ParquetWriter<W: Write + Send> {
+    writer: ArrowWriter<W>,
+    schema: Arc<Schema>,
+    rows: Vec<Vec<Value>>,
+}
+
+impl<W: Write + Send> ParquetWriter<W> {
+    pub(crate) fn new(writer: W, schema: Arc<Schema>) -> Result<ParquetWriter<W>, DataError> {
+        let writer = ArrowWriter::try_new(writer, schema.clone(), None)?;
+        Ok(ParquetWriter {
+            writer,
+            schema,
+            rows: Vec::with_capacity(PARQUET_BATCH_SIZE),
+        })
+    }
+
+    fn flush_rows(&mut self) -> Result<(), DataError> {
+        if self.rows.is_empty() {
+            return Ok(());
+        }
+        let batch = columnar::to_record_batch(&self.schema, &self.rows)?;
+        self.writer.write(&batch)?;
+        self.rows.clear();
+        Ok(())
+    }
+}
+
+impl<W: Write + Send> RowWriter for ParquetWriter<W> {
+    fn write_row(&mut self, row: &[Value]) -> Result<(), DataError> {
+        self.rows.push(row.to_vec());
+        if self.rows.len() >= PARQUET_BATCH_SIZE {
+            self.flush_rows()?;
+        }
+        Ok(())
+    }
+
+    fn finish(mut self: Box<Self>) -> Result<(), DataError> {
+        self.flush_rows()?;
+        self.writer.close()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,245 @@
+//! Schema migration tool for Tsurugi.
+
+use std::{path::PathBuf, process::ExitCode, time::Duration};
+
+use tsubakuro_rust_core::prelude::*;
+
+const USAGE: &str = r#"usage: tgmigrate <command> [options] [connection-url]
+
+command:
+  migrate                apply the pending migrations
+  status                 show the state of the migrations
+  dry-run                show the migrations to be applied
+
+connection-url:
+  tcp://[user[:password]@]host:port[?key=value&...]
+  (if omitted, TSURUGI_* environment variables are used)
+
+options:
+  --dir <directory>      migration script directory (default: migrations)
+  --table <table>        history table (default: tsubakuro_schema_history)
+  --config <file>        read connection option from file (.toml or .json)
+  --credentials <file>   read credential from file
+  --timeout <duration>   default timeout (e.g. 30s, 500ms)
+  -h, --help             show this help
+
+migration script:
+  V<version>__<description>.sql (e.g. V1__create_tables.sql)"#;
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    Migrate,
+    Status,
+    DryRun,
+}
+
+#[derive(Debug, Default)]
+struct Arguments {
+    command: Option<Command>,
+    connection_url: Option<String>,
+    dir: Option<PathBuf>,
+    table: Option<String>,
+    config: Option<PathBuf>,
+    credentials: Option<PathBuf>,
+    timeout: Option<Duration>,
+    help: bool,
+}
+
+impl Arguments {
+    fn parse(args: impl IntoIterator<Item = String>) -> Result<Arguments, String> {
+        let mut arguments = Arguments::default();
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| {
+                args.next()
+                    .ok_or_else(|| format!("value is not specified for {name}"))
+            };
+            match arg.as_str() {
+                "--dir" => arguments.dir = Some(PathBuf::from(value(&arg)?)),
+                "--table" => arguments.table = Some(value(&arg)?),
+                "--config" => arguments.config = Some(PathBuf::from(value(&arg)?)),
+                "--credentials" => arguments.credentials = Some(PathBuf::from(value(&arg)?)),
+                "--timeout" => arguments.timeout = Some(parse_duration(&value(&arg)?)?),
+                "-h" | "--help" => arguments.help = true,
+                _ if arg.starts_with('-') => return Err(format!("unknown option: {arg}")),
+                "migrate" if arguments.command.is_none() => {
+                    arguments.command = Some(Command::Migrate);
+                }
+                "status" if arguments.command.is_none() => {
+                    arguments.command = Some(Command::Status);
+                }
+                "dry-run" if arguments.command.is_none() => {
+                    arguments.command = Some(Command::DryRun);
+                }
+                _ => {
+                    if arguments.command.is_none() {
+                        return Err(format!("unknown command: {arg}"));
+                    }
+                    if arguments.connection_url.is_some() {
+                        return Err(format!("unexpected argument: {arg}"));
+                    }
+                    arguments.connection_url = Some(arg);
+                }
+            }
+        }
+
+        Ok(arguments)
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn connection_option(&self) -> Result<ConnectionOption, TgError> {
+        let mut option = if let Some(config) = &self.config {
+            ConnectionOption::from_file(config)?
+        } else if let Some(url) = &self.connection_url {
+            ConnectionOption::from_url(url)?
+        } else {
+            ConnectionOption::from_env()?
+        };
+
+        if let Some(credentials) = &self.credentials {
+            option.set_credential(Credential::load(credentials)?);
+        }
+        if let Some(timeout) = self.timeout {
+            option.set_default_timeout(timeout);
+        }
+        option.set_application_name("tgmigrate");
+        Ok(option)
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn migrator(&self) -> Result<Migrator, TgError> {
+        let dir = self
+            .dir
+            .clone()
+            .unwrap_or_else(|| PathBuf::from("migrations"));
+        let mut migrator = Migrator::from_dir(dir)?;
+        if let Some(table) = &self.table {
+            migrator.set_history_table(table);
+        }
+        Ok(migrator)
+    }
+}
+
+/// Parses a duration such as `30s`, `500ms`, `1m` (seconds if no unit).
+fn parse_duration(s: &str) -> Result<Duration, String> {
+    let s = s.trim();
+    let index = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (number, unit) = s.split_at(index);
+    let number: u64 = number
+        .parse()
+        .map_err(|_| format!("invalid duration: {s}"))?;
+    let duration = match unit {
+        "" | "s" => Duration::from_secs(number),
+        "ms" => Duration::from_millis(number),
+        "m" | "min" => Duration::from_secs(number * 60),
+        _ => return Err(format!("invalid duration unit: {s}")),
+    };
+    Ok(duration)
+}
+
+fn state_name(state: MigrationState) -> &'static str {
+    match state {
+        MigrationState::Pending => "pending",
+        MigrationState::Applied => "applied",
+        MigrationState::ChecksumMismatch => "checksum mismatch",
+        MigrationState::OutOfOrder => "out of order",
+        MigrationState::Missing => "missing",
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let arguments = match Arguments::parse(std::env::args().skip(1)) {
+        Ok(arguments) => arguments,
+        Err(message) => {
+            eprintln!("{message}\n\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+    if arguments.help || arguments.command.is_none() {
+        println!("{USAGE}");
+        return ExitCode::SUCCESS;
+    }
+
+    match run(&arguments).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[allow(clippy::result_large_err)]
+async fn run(arguments: &Arguments) -> Result<(), TgError> {
+    let migrator = arguments.migrator()?;
+    let option = arguments.connection_option()?;
+    let session = Session::connect(&option).await?;
+    let mut client: SqlClient = session.make_client();
+    client.set_default_timeout(option.default_timeout());
+
+    let result = match arguments.command {
+        Some(Command::Migrate) => migrator.migrate(&client).await.map(|versions| {
+            for version in &versions {
+                println!("applied V{version}");
+            }
+            println!("{} migrations applied", versions.len());
+        }),
+        Some(Command::Status) => migrator.status(&client).await.map(|status| {
+            for s in status {
+                println!(
+                    "V{:<10} {:<20} {}",
+                    s.version(),
+                    state_name(s.state()),
+                    s.description()
+                );
+            }
+        }),
+        Some(Command::DryRun) => migrator.dry_run(&client).await.map(|pending| {
+            for migration in &pending {
+                println!("V{} {}", migration.version(), migration.description());
+                for sql in migration.statements() {
+                    println!("  {sql};");
+                }
+            }
+            println!("{} migrations to be applied", pending.len());
+        }),
+        None => Ok(()),
+    };
+    session.close().await?;
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Arguments, String> {
+        Arguments::parse(args.iter().map(|s| s.to_string()))
+    }
+
+    #[test]
+    fn arguments() {
+        let arguments = parse(&[
+            "dry-run",
+            "tcp://localhost:12345",
+            "--dir",
+            "db/migrations",
+            "--table",
+            "history",
+        ])
+        .unwrap();
+        assert_eq!(Some(Command::DryRun), arguments.command);
+        assert_eq!(
+            Some("tcp://localhost:12345".to_string()),
+            arguments.connection_url
+        );
+        assert_eq!(Some(PathBuf::from("db/migrations")), arguments.dir);
+        assert_eq!(Some("history".to_string()), arguments.table);
+
+        assert!(parse(&["status", "--dir"]).is_err());
+        assert!(parse(&["unknown"]).is_err());
+        assert!(parse(&["migrate", "a", "b"]).is_err());
+    }
+}
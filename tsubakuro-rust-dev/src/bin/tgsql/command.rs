@@ -0,0 +1,290 @@
+use std::time::Duration;
+
+use tsubakuro_rust_core::prelude::*;
+
+/// Console meta-command (a line starting with `\`).
+#[derive(Debug, PartialEq)]
+pub(crate) enum Command {
+    Help,
+    Exit,
+    Tables,
+    Describe(String),
+    Explain(String),
+    Begin(BeginOption),
+    Commit(CommitType),
+    Rollback,
+    Status,
+    Timeout(Option<Duration>),
+}
+
+/// Options of `\begin`.
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct BeginOption {
+    pub(crate) transaction_type: Option<TransactionType>,
+    pub(crate) write_preserve: Vec<String>,
+    pub(crate) inclusive_read_area: Vec<String>,
+    pub(crate) exclusive_read_area: Vec<String>,
+    pub(crate) label: Option<String>,
+}
+
+impl BeginOption {
+    pub(crate) fn to_transaction_option(&self) -> TransactionOption {
+        let transaction_type = match self.transaction_type {
+            Some(transaction_type) => transaction_type,
+            None if !self.write_preserve.is_empty() => TransactionType::Long,
+            None => TransactionType::Short,
+        };
+        let mut option = TransactionOption::from(transaction_type);
+        option.set_write_preserve(&self.write_preserve);
+        option.set_inclusive_read_area(&self.inclusive_read_area);
+        option.set_exclusive_read_area(&self.exclusive_read_area);
+        if let Some(label) = &self.label {
+            option.set_transaction_label(label.as_str());
+        }
+        option
+    }
+}
+
+pub(crate) const HELP: &str = r#"SQL statements are terminated by ';'.
+
+\tables                     list tables
+\describe <table>           show table metadata
+\explain <sql>              show execution plan
+\begin [occ | ltx | rtx] [write preserve <table>, ...]
+       [read area include <table>, ...] [read area exclude <table>, ...]
+       [label <label>]      start a transaction
+\commit [accepted | available | stored | propagated]
+                            commit the transaction
+\rollback                   rollback the transaction
+\status                     show the transaction status
+\timeout [<duration>]       show or set timeout (e.g. 30s, 500ms, 1m)
+\help                       show this help
+\exit                       exit"#;
+
+impl Command {
+    /// Parses a meta-command line.
+    pub(crate) fn parse(line: &str) -> Result<Command, String> {
+        let line = line.trim().trim_end_matches(';').trim_end();
+        let line = line
+            .strip_prefix('\\')
+            .ok_or_else(|| format!("not a command: {line}"))?;
+        let (name, rest) = match line.split_once(char::is_whitespace) {
+            Some((name, rest)) => (name, rest.trim()),
+            None => (line, ""),
+        };
+
+        let command = match name.to_ascii_lowercase().as_str() {
+            "help" | "h" | "?" => Command::Help,
+            "exit" | "quit" | "q" => Command::Exit,
+            "tables" | "dt" => Command::Tables,
+            "describe" | "desc" | "d" => {
+                if rest.is_empty() {
+                    return Err("table name is not specified".to_string());
+                }
+                Command::Describe(rest.to_string())
+            }
+            "explain" => {
+                if rest.is_empty() {
+                    return Err("sql is not specified".to_string());
+                }
+                Command::Explain(rest.to_string())
+            }
+            "begin" => Command::Begin(parse_begin(rest)?),
+            "commit" => Command::Commit(parse_commit_type(rest)?),
+            "rollback" => Command::Rollback,
+            "status" => Command::Status,
+            "timeout" => {
+                if rest.is_empty() {
+                    Command::Timeout(None)
+                } else {
+                    Command::Timeout(Some(parse_duration(rest)?))
+                }
+            }
+            _ => return Err(format!("unknown command: \\{name}. see \\help")),
+        };
+        Ok(command)
+    }
+}
+
+fn tokenize(s: &str) -> Vec<String> {
+    s.replace(',', " ")
+        .split_whitespace()
+        .map(String::from)
+        .collect()
+}
+
+fn parse_begin(s: &str) -> Result<BeginOption, String> {
+    let tokens = tokenize(s);
+    let mut option = BeginOption::default();
+
+    let mut i = 0;
+    let keyword = |i: usize| tokens.get(i).map(|t| t.to_ascii_lowercase());
+    while let Some(token) = keyword(i) {
+        i += 1;
+        match token.as_str() {
+            "occ" | "short" => option.transaction_type = Some(TransactionType::Short),
+            "ltx" | "long" => option.transaction_type = Some(TransactionType::Long),
+            "rtx" | "readonly" => option.transaction_type = Some(TransactionType::ReadOnly),
+            "read" if keyword(i).as_deref() == Some("only") => {
+                i += 1;
+                option.transaction_type = Some(TransactionType::ReadOnly);
+            }
+            "write" if keyword(i).as_deref() == Some("preserve") => {
+                i += 1;
+                option.write_preserve.extend(table_names(&tokens, &mut i));
+            }
+            "read" if keyword(i).as_deref() == Some("area") => {
+                i += 1;
+                match keyword(i).as_deref() {
+                    Some("include") => {
+                        i += 1;
+                        option
+                            .inclusive_read_area
+                            .extend(table_names(&tokens, &mut i));
+                    }
+                    Some("exclude") => {
+                        i += 1;
+                        option
+                            .exclusive_read_area
+                            .extend(table_names(&tokens, &mut i));
+                    }
+                    _ => return Err("expected 'include' or 'exclude' after 'read area'".into()),
+                }
+            }
+            "include" => option
+                .inclusive_read_area
+                .extend(table_names(&tokens, &mut i)),
+            "exclude" => option
+                .exclusive_read_area
+                .extend(table_names(&tokens, &mut i)),
+            "label" | "as" => {
+                let label = tokens.get(i).ok_or("label is not specified")?;
+                i += 1;
+                option.label = Some(label.clone());
+            }
+            _ => return Err(format!("unknown begin option: {token}")),
+        }
+    }
+
+    Ok(option)
+}
+
+fn table_names(tokens: &[String], i: &mut usize) -> Vec<String> {
+    const KEYWORDS: [&str; 11] = [
+        "occ", "short", "ltx", "long", "rtx", "readonly", "read", "write", "include", "exclude",
+        "label",
+    ];
+
+    let mut names = Vec::new();
+    while let Some(token) = tokens.get(*i) {
+        if KEYWORDS.contains(&token.to_ascii_lowercase().as_str()) {
+            break;
+        }
+        names.push(token.clone());
+        *i += 1;
+    }
+    names
+}
+
+fn parse_commit_type(s: &str) -> Result<CommitType, String> {
+    let commit_type = match s.to_ascii_lowercase().as_str() {
+        "" | "default" => CommitType::Unspecified,
+        "accepted" => CommitType::Accepted,
+        "available" => CommitType::Available,
+        "stored" => CommitType::Stored,
+        "propagated" => CommitType::Propagated,
+        _ => return Err(format!("unknown commit type: {s}")),
+    };
+    Ok(commit_type)
+}
+
+/// Parses a duration such as `30s`, `500ms`, `1m`, `1h` (seconds if no unit).
+pub(crate) fn parse_duration(s: &str) -> Result<Duration, String> {
+    let s = s.trim();
+    let index = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (number, unit) = s.split_at(index);
+    let number: u64 = number
+        .parse()
+        .map_err(|_| format!("invalid duration: {s}"))?;
+    let duration = match unit.trim() {
+        "" | "s" => Duration::from_secs(number),
+        "ms" => Duration::from_millis(number),
+        "m" | "min" => Duration::from_secs(number * 60),
+        "h" => Duration::from_secs(number * 60 * 60),
+        _ => return Err(format!("invalid duration unit: {s}")),
+    };
+    Ok(duration)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        assert_eq!(Ok(Command::Tables), Command::parse("\\tables"));
+        assert_eq!(
+            Ok(Command::Describe("t1".to_string())),
+            Command::parse("\\describe t1;")
+        );
+        assert_eq!(
+            Ok(Command::Timeout(Some(Duration::from_millis(500)))),
+            Command::parse("\\timeout 500ms")
+        );
+        assert_eq!(Ok(Command::Timeout(None)), Command::parse("\\timeout"));
+        assert_eq!(
+            Ok(Command::Commit(CommitType::Stored)),
+            Command::parse("\\commit stored")
+        );
+        assert!(Command::parse("\\unknown").is_err());
+        assert!(Command::parse("\\describe").is_err());
+    }
+
+    #[test]
+    fn parse_begin_ltx() {
+        let Ok(Command::Begin(option)) = Command::parse(
+            "\\begin ltx write preserve t1, t2 read area include t3 exclude t4 label test",
+        ) else {
+            panic!();
+        };
+        assert_eq!(Some(TransactionType::Long), option.transaction_type);
+        assert_eq!(vec!["t1", "t2"], option.write_preserve);
+        assert_eq!(vec!["t3"], option.inclusive_read_area);
+        assert_eq!(vec!["t4"], option.exclusive_read_area);
+        assert_eq!(Some("test".to_string()), option.label);
+    }
+
+    #[test]
+    fn parse_begin_default() {
+        let Ok(Command::Begin(option)) = Command::parse("\\begin") else {
+            panic!();
+        };
+        assert_eq!(BeginOption::default(), option);
+        assert_eq!(
+            TransactionType::Short,
+            option.to_transaction_option().transaction_type()
+        );
+
+        let Ok(Command::Begin(option)) = Command::parse("\\begin read only") else {
+            panic!();
+        };
+        assert_eq!(Some(TransactionType::ReadOnly), option.transaction_type);
+
+        let Ok(Command::Begin(option)) = Command::parse("\\begin write preserve t1") else {
+            panic!();
+        };
+        assert_eq!(
+            TransactionType::Long,
+            option.to_transaction_option().transaction_type()
+        );
+    }
+
+    #[test]
+    fn duration() {
+        assert_eq!(Ok(Duration::from_secs(30)), parse_duration("30"));
+        assert_eq!(Ok(Duration::from_secs(30)), parse_duration("30s"));
+        assert_eq!(Ok(Duration::from_secs(120)), parse_duration("2m"));
+        assert!(parse_duration("x").is_err());
+        assert!(parse_duration("1y").is_err());
+    }
+}
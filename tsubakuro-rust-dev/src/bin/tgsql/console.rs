@@ -0,0 +1,320 @@
+use std::{sync::Arc, time::Duration};
+
+use tsubakuro_rust_core::prelude::*;
+
+use crate::{
+    command::{BeginOption, Command, HELP},
+    format::{fetch_string, is_numeric, render_error, render_execute_result, render_table},
+};
+
+/// Result of a console input.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum Flow {
+    Continue,
+    /// Continue, but the input failed.
+    Error,
+    Exit,
+}
+
+/// SQL console bound to a session.
+///
+/// Statements are executed in the transaction started by `\begin`,
+/// or in an implicit OCC transaction which is committed immediately.
+pub(crate) struct Console {
+    session: Arc<Session>,
+    client: SqlClient,
+    transaction: Option<Transaction>,
+}
+
+impl Console {
+    pub(crate) fn new(session: Arc<Session>, timeout: Duration) -> Console {
+        let mut client: SqlClient = session.make_client();
+        client.set_default_timeout(timeout);
+        Console {
+            session,
+            client,
+            transaction: None,
+        }
+    }
+
+    /// Returns true if a transaction started by `\begin` is active.
+    pub(crate) fn in_transaction(&self) -> bool {
+        self.transaction.is_some()
+    }
+
+    /// Executes a meta-command line and prints the result.
+    pub(crate) async fn execute_command(&mut self, line: &str) -> Flow {
+        let command = match Command::parse(line) {
+            Ok(command) => command,
+            Err(message) => {
+                eprintln!("{message}");
+                return Flow::Error;
+            }
+        };
+        if command == Command::Exit {
+            return Flow::Exit;
+        }
+
+        if let Err(e) = self.run_command(command).await {
+            eprintln!("{}", render_error(&e));
+            return Flow::Error;
+        }
+        Flow::Continue
+    }
+
+    async fn run_command(&mut self, command: Command) -> Result<(), TgError> {
+        match command {
+            Command::Help => println!("{HELP}"),
+            Command::Exit => {}
+            Command::Tables => self.tables().await?,
+            Command::Describe(table_name) => self.describe(&table_name).await?,
+            Command::Explain(sql) => {
+                let explain = self.client.explain(&sql).await?;
+                println!("{}", explain.contents());
+            }
+            Command::Begin(option) => self.begin(&option).await?,
+            Command::Commit(commit_type) => self.commit(commit_type).await?,
+            Command::Rollback => self.rollback().await?,
+            Command::Status => self.status().await?,
+            Command::Timeout(timeout) => {
+                if let Some(timeout) = timeout {
+                    self.client.set_default_timeout(timeout);
+                }
+                println!("timeout: {:?}", self.client.default_timeout());
+            }
+        }
+        Ok(())
+    }
+
+    async fn tables(&self) -> Result<(), TgError> {
+        let table_list = self.client.list_tables().await?;
+        for name in table_list.table_names() {
+            println!("{name}");
+        }
+        Ok(())
+    }
+
+    async fn describe(&self, table_name: &str) -> Result<(), TgError> {
+        let metadata = match self.client.resolve_table_metadata(table_name).await? {
+            Some(metadata) => metadata,
+            None => {
+                eprintln!("table not found: {table_name}");
+                return Ok(());
+            }
+        };
+
+        println!(
+            "{}.{}.{}",
+            metadata.database_name(),
+            metadata.schema_name(),
+            metadata.table_name()
+        );
+        if let Some(description) = metadata.description() {
+            println!("{description}");
+        }
+
+        let rows: Vec<Vec<String>> = metadata
+            .columns()
+            .iter()
+            .map(|column| {
+                let primary_key = metadata.primary_keys().contains(column.name());
+                vec![
+                    column.name().clone(),
+                    column.sql_type().unwrap_or_default(),
+                    match column.nullable() {
+                        Some(false) => "NOT NULL".to_string(),
+                        _ => String::new(),
+                    },
+                    if primary_key { "PK" } else { "" }.to_string(),
+                    column.description().cloned().unwrap_or_default(),
+                ]
+            })
+            .collect();
+        let headers = ["column", "type", "null", "key", "description"];
+        println!("{}", render_table(&headers, &[false; 5], &rows));
+        Ok(())
+    }
+
+    async fn begin(&mut self, option: &BeginOption) -> Result<(), TgError> {
+        if self.transaction.is_some() {
+            eprintln!("transaction is already started. \\commit or \\rollback first");
+            return Ok(());
+        }
+
+        let transaction_option = option.to_transaction_option();
+        let transaction = self.client.start_transaction(&transaction_option).await?;
+        println!(
+            "transaction started. type={:?}, id={}",
+            transaction_option.transaction_type(),
+            transaction.transaction_id()
+        );
+        self.transaction = Some(transaction);
+        Ok(())
+    }
+
+    async fn commit(&mut self, commit_type: CommitType) -> Result<(), TgError> {
+        let Some(transaction) = self.transaction.take() else {
+            eprintln!("transaction is not started");
+            return Ok(());
+        };
+
+        let mut commit_option = CommitOption::new();
+        commit_option.set_commit_type(commit_type);
+        let result = self.client.commit(&transaction, &commit_option).await;
+        transaction.close().await?;
+        result?;
+        println!("committed");
+        Ok(())
+    }
+
+    async fn rollback(&mut self) -> Result<(), TgError> {
+        let Some(transaction) = self.transaction.take() else {
+            eprintln!("transaction is not started");
+            return Ok(());
+        };
+
+        let result = self.client.rollback(&transaction).await;
+        transaction.close().await?;
+        result?;
+        println!("rolled back");
+        Ok(())
+    }
+
+    async fn status(&self) -> Result<(), TgError> {
+        let Some(transaction) = &self.transaction else {
+            println!("transaction is not started");
+            return Ok(());
+        };
+
+        let status = self.client.get_transaction_status(transaction).await?;
+        println!(
+            "transaction id={}, status={:?}",
+            transaction.transaction_id(),
+            status.status()
+        );
+        Ok(())
+    }
+
+    /// Executes a SQL statement and prints the result.
+    pub(crate) async fn execute_statement(&mut self, sql: &str) -> Flow {
+        let result = match &self.transaction {
+            Some(transaction) => Self::run(&self.client, transaction, sql).await,
+            None => self.run_implicit(sql).await,
+        };
+        match result {
+            Ok(output) => {
+                println!("{output}");
+                Flow::Continue
+            }
+            Err(e) => {
+                eprintln!("{}", render_error(&e));
+                if self.transaction.is_some() && e.diagnostic_code().is_some() {
+                    eprintln!("the transaction may be aborted. \\rollback to end it");
+                }
+                Flow::Error
+            }
+        }
+    }
+
+    async fn run_implicit(&self, sql: &str) -> Result<String, TgError> {
+        let transaction_option = TransactionOption::new();
+        let transaction = self.client.start_transaction(&transaction_option).await?;
+
+        let result = Self::run(&self.client, &transaction, sql).await;
+        let result = match result {
+            Ok(output) => {
+                let commit_option = CommitOption::new();
+                self.client
+                    .commit(&transaction, &commit_option)
+                    .await
+                    .map(|_| output)
+            }
+            Err(e) => {
+                let _ = self.client.rollback(&transaction).await;
+                Err(e)
+            }
+        };
+        transaction.close().await?;
+        result
+    }
+
+    async fn run(
+        client: &SqlClient,
+        transaction: &Transaction,
+        sql: &str,
+    ) -> Result<String, TgError> {
+        if is_query(sql) {
+            let mut query_result = client.query(transaction, sql).await?;
+            let output = Self::render_query_result(&mut query_result).await;
+            query_result.close().await?;
+            output
+        } else {
+            let execute_result = client.execute(transaction, sql).await?;
+            Ok(render_execute_result(&execute_result))
+        }
+    }
+
+    async fn render_query_result(query_result: &mut SqlQueryResult) -> Result<String, TgError> {
+        let columns: Vec<SqlColumn> = match query_result.get_metadata() {
+            Some(metadata) => metadata.columns().clone(),
+            None => vec![],
+        };
+
+        let mut rows = Vec::new();
+        while query_result.next_row().await? {
+            let mut row = Vec::with_capacity(columns.len());
+            for column in &columns {
+                if !query_result.next_column().await? {
+                    break;
+                }
+                row.push(fetch_string(query_result, column).await?);
+            }
+            rows.push(row);
+        }
+
+        let headers: Vec<&str> = columns.iter().map(|c| c.name().as_str()).collect();
+        let align_right: Vec<bool> = columns.iter().map(is_numeric).collect();
+        Ok(render_table(&headers, &align_right, &rows))
+    }
+
+    /// Rollbacks the active transaction and closes the session.
+    pub(crate) async fn close(mut self) {
+        if self.transaction.is_some()
+            && let Err(e) = self.rollback().await
+        {
+            eprintln!("{}", render_error(&e));
+        }
+        if let Err(e) = self.session.close().await {
+            eprintln!("{}", render_error(&e));
+        }
+    }
+}
+
+/// Returns true if the statement returns a result set.
+fn is_query(sql: &str) -> bool {
+    // comments are skipped
+    let keyword: String = SqlCodeChars::new(sql)
+        .map(|(_, c)| c)
+        .skip_while(|c| c.is_whitespace() || *c == '(')
+        .take_while(|c| c.is_ascii_alphabetic())
+        .collect();
+    ["select", "values", "with", "table"]
+        .iter()
+        .any(|k| keyword.eq_ignore_ascii_case(k))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn query() {
+        assert!(is_query("select * from t"));
+        assert!(is_query("  SELECT 1"));
+        assert!(is_query("(select 1) union (select 2)"));
+        assert!(is_query("with a as (select 1) select * from a"));
+        assert!(!is_query("insert into t values(1)"));
+        assert!(!is_query("selection"));
+        assert!(is_query("-- comment\n/* a */ select 1"));
+    }
+}
@@ -0,0 +1,206 @@
+use tsubakuro_rust_core::prelude::*;
+
+const NULL: &str = "null";
+
+/// Retrieves the value on the column of the cursor position as a string.
+pub(crate) async fn fetch_string(
+    query_result: &mut SqlQueryResult,
+    column: &SqlColumn,
+) -> Result<String, TgError> {
+    if query_result.is_null()? {
+        return Ok(NULL.to_string());
+    }
+
+    let value = match column.atom_type() {
+        Some(AtomType::Boolean) => fetch::<bool>(query_result).await?.to_string(),
+        Some(AtomType::Int4) => fetch::<i32>(query_result).await?.to_string(),
+        Some(AtomType::Int8) => fetch::<i64>(query_result).await?.to_string(),
+        Some(AtomType::Float4) => fetch::<f32>(query_result).await?.to_string(),
+        Some(AtomType::Float8) => fetch::<f64>(query_result).await?.to_string(),
+        Some(AtomType::Decimal) => fetch::<bigdecimal::BigDecimal>(query_result)
+            .await?
+            .to_plain_string(),
+        Some(AtomType::Character) => fetch::<String>(query_result).await?,
+        Some(AtomType::Octet) => {
+            let value = fetch::<Vec<u8>>(query_result).await?;
+            let hex: String = value.iter().map(|b| format!("{b:02x}")).collect();
+            format!("X'{hex}'")
+        }
+        Some(AtomType::Date) => fetch::<chrono::NaiveDate>(query_result)
+            .await?
+            .format("%Y-%m-%d")
+            .to_string(),
+        Some(AtomType::TimeOfDay) => fetch::<chrono::NaiveTime>(query_result)
+            .await?
+            .format("%H:%M:%S%.f")
+            .to_string(),
+        Some(AtomType::TimePoint) => fetch::<chrono::NaiveDateTime>(query_result)
+            .await?
+            .format("%Y-%m-%d %H:%M:%S%.f")
+            .to_string(),
+        Some(AtomType::TimeOfDayWithTimeZone) => {
+            let (time, offset) =
+                fetch::<(chrono::NaiveTime, chrono::FixedOffset)>(query_result).await?;
+            format!("{}{}", time.format("%H:%M:%S%.f"), offset)
+        }
+        Some(AtomType::TimePointWithTimeZone) => {
+            fetch::<chrono::DateTime<chrono::FixedOffset>>(query_result)
+                .await?
+                .format("%Y-%m-%d %H:%M:%S%.f%:z")
+                .to_string()
+        }
+        Some(AtomType::Blob) => {
+            let _ = fetch::<TgBlobReference>(query_result).await?;
+            "<BLOB>".to_string()
+        }
+        Some(AtomType::Clob) => {
+            let _ = fetch::<TgClobReference>(query_result).await?;
+            "<CLOB>".to_string()
+        }
+        atom_type => format!("<unsupported {atom_type:?}>"),
+    };
+    Ok(value)
+}
+
+async fn fetch<T>(query_result: &mut SqlQueryResult) -> Result<T, TgError>
+where
+    SqlQueryResult: SqlQueryResultFetch<T>,
+{
+    SqlQueryResultFetch::<T>::fetch(query_result).await
+}
+
+/// Returns true if the values of the column should be aligned right.
+pub(crate) fn is_numeric(column: &SqlColumn) -> bool {
+    matches!(
+        column.atom_type(),
+        Some(
+            AtomType::Int4
+                | AtomType::Int8
+                | AtomType::Float4
+                | AtomType::Float8
+                | AtomType::Decimal
+        )
+    )
+}
+
+fn width(s: &str) -> usize {
+    s.chars().count()
+}
+
+/// Renders rows as a table.
+///
+/// The values of the columns whose `align_right` is true are aligned right.
+pub(crate) fn render_table(headers: &[&str], align_right: &[bool], rows: &[Vec<String>]) -> String {
+    let mut widths: Vec<usize> = headers.iter().map(|h| width(h)).collect();
+    for row in rows {
+        for (i, value) in row.iter().enumerate() {
+            widths[i] = widths[i].max(width(value));
+        }
+    }
+
+    let mut s = String::new();
+    let separator: Vec<String> = widths.iter().map(|w| "-".repeat(*w)).collect();
+    let separator = format!("+-{}-+\n", separator.join("-+-"));
+
+    s.push_str(&separator);
+    let header: Vec<String> = headers
+        .iter()
+        .zip(&widths)
+        .map(|(h, w)| pad_right(h, *w))
+        .collect();
+    s.push_str(&format!("| {} |\n", header.join(" | ")));
+    s.push_str(&separator);
+    for row in rows {
+        let line: Vec<String> = row
+            .iter()
+            .zip(align_right.iter().zip(&widths))
+            .map(|(value, (right, w))| {
+                if *right {
+                    pad_left(value, *w)
+                } else {
+                    pad_right(value, *w)
+                }
+            })
+            .collect();
+        s.push_str(&format!("| {} |\n", line.join(" | ")));
+    }
+    s.push_str(&separator);
+
+    let n = rows.len();
+    s.push_str(&format!("({} row{})", n, if n == 1 { "" } else { "s" }));
+    s
+}
+
+fn pad_right(s: &str, w: usize) -> String {
+    format!("{}{}", s, " ".repeat(w.saturating_sub(width(s))))
+}
+
+fn pad_left(s: &str, w: usize) -> String {
+    format!("{}{}", " ".repeat(w.saturating_sub(width(s))), s)
+}
+
+/// Renders an execute result.
+pub(crate) fn render_execute_result(result: &SqlExecuteResult) -> String {
+    let mut counters: Vec<(&SqlCounterType, &i64)> = result.counters().iter().collect();
+    counters.sort_by_key(|(counter_type, _)| **counter_type as i32);
+
+    let counters: Vec<String> = counters
+        .iter()
+        .map(|(counter_type, count)| {
+            let name = counter_type.as_str_name().to_ascii_lowercase();
+            format!("{} {}", name.replace('_', " "), count)
+        })
+        .collect();
+    if counters.is_empty() {
+        "done".to_string()
+    } else {
+        counters.join(", ")
+    }
+}
+
+/// Renders an error, with the diagnostic code if it is a server error.
+pub(crate) fn render_error(error: &TgError) -> String {
+    match error {
+        TgError::ServerError(_, _, code, server_message) => {
+            format!("{}: {}", code, server_message)
+        }
+        TgError::TimeoutError(message) => format!("timeout: {message}"),
+        _ => format!("{error}"),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn table() {
+        let rows = vec![
+            vec!["1".to_string(), "abc".to_string()],
+            vec!["100".to_string(), "xy".to_string()],
+        ];
+        let expected = "\
++-----+------+
+| id  | name |
++-----+------+
+|   1 | abc  |
+| 100 | xy   |
++-----+------+
+(2 rows)";
+        assert_eq!(
+            expected,
+            render_table(&["id", "name"], &[true, false], &rows)
+        );
+    }
+
+    #[test]
+    fn empty_table() {
+        let expected = "\
++----+
+| id |
++----+
++----+
+(0 rows)";
+        assert_eq!(expected, render_table(&["id"], &[true], &[]));
+    }
+}
@@ -0,0 +1,260 @@
+//! Interactive SQL console for Tsurugi.
+
+mod command;
+mod console;
+mod format;
+mod statement;
+
+use std::{path::PathBuf, process::ExitCode, time::Duration};
+
+use rustyline::{DefaultEditor, error::ReadlineError};
+use tsubakuro_rust_core::prelude::*;
+
+use crate::{
+    console::{Console, Flow},
+    format::render_error,
+    statement::StatementBuffer,
+};
+
+const USAGE: &str = r#"usage: tgsql [options] [connection-url]
+
+connection-url:
+  tcp://[user[:password]@]host:port[?key=value&...]
+  (if omitted, TSURUGI_* environment variables are used)
+
+options:
+  --config <file>        read connection option from file (.toml or .json)
+  --credentials <file>   read credential from file
+  --timeout <duration>   default timeout (e.g. 30s, 500ms)
+  -c, --command <sql>    execute the statement or command and exit (repeatable)
+  --history <file>       history file (default: ~/.tgsql_history)
+  -h, --help             show this help"#;
+
+#[derive(Debug, Default)]
+struct Arguments {
+    connection_url: Option<String>,
+    config: Option<PathBuf>,
+    credentials: Option<PathBuf>,
+    timeout: Option<Duration>,
+    commands: Vec<String>,
+    history: Option<PathBuf>,
+    help: bool,
+}
+
+impl Arguments {
+    fn parse(args: impl IntoIterator<Item = String>) -> Result<Arguments, String> {
+        let mut arguments = Arguments::default();
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| {
+                args.next()
+                    .ok_or_else(|| format!("value is not specified for {name}"))
+            };
+            match arg.as_str() {
+                "--config" => arguments.config = Some(PathBuf::from(value(&arg)?)),
+                "--credentials" => arguments.credentials = Some(PathBuf::from(value(&arg)?)),
+                "--timeout" => {
+                    arguments.timeout = Some(command::parse_duration(&value(&arg)?)?);
+                }
+                "-c" | "--command" => arguments.commands.push(value(&arg)?),
+                "--history" => arguments.history = Some(PathBuf::from(value(&arg)?)),
+                "-h" | "--help" => arguments.help = true,
+                _ if arg.starts_with('-') => return Err(format!("unknown option: {arg}")),
+                _ => {
+                    if arguments.connection_url.is_some() {
+                        return Err(format!("unexpected argument: {arg}"));
+                    }
+                    arguments.connection_url = Some(arg);
+                }
+            }
+        }
+
+        Ok(arguments)
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn connection_option(&self) -> Result<ConnectionOption, TgError> {
+        let mut option = if let Some(config) = &self.config {
+            ConnectionOption::from_file(config)?
+        } else if let Some(url) = &self.connection_url {
+            ConnectionOption::from_url(url)?
+        } else {
+            ConnectionOption::from_env()?
+        };
+
+        if let Some(credentials) = &self.credentials {
+            option.set_credential(Credential::load(credentials)?);
+        }
+        if let Some(timeout) = self.timeout {
+            option.set_default_timeout(timeout);
+        }
+        option.set_application_name("tgsql");
+        Ok(option)
+    }
+
+    fn history_file(&self) -> Option<PathBuf> {
+        if let Some(history) = &self.history {
+            return Some(history.clone());
+        }
+        let home = std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE"))?;
+        Some(PathBuf::from(home).join(".tgsql_history"))
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let arguments = match Arguments::parse(std::env::args().skip(1)) {
+        Ok(arguments) => arguments,
+        Err(message) => {
+            eprintln!("{message}\n\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+    if arguments.help {
+        println!("{USAGE}");
+        return ExitCode::SUCCESS;
+    }
+
+    let option = match arguments.connection_option() {
+        Ok(option) => option,
+        Err(e) => {
+            eprintln!("{}", render_error(&e));
+            return ExitCode::FAILURE;
+        }
+    };
+    let session = match Session::connect(&option).await {
+        Ok(session) => session,
+        Err(e) => {
+            eprintln!("connect error. {}", render_error(&e));
+            return ExitCode::FAILURE;
+        }
+    };
+    let mut console = Console::new(session, option.default_timeout());
+
+    let mut failed = false;
+    if arguments.commands.is_empty() {
+        if let Err(e) = repl(&mut console, arguments.history_file()).await {
+            eprintln!("{e}");
+            failed = true;
+        }
+    } else {
+        for input in &arguments.commands {
+            match execute_input(&mut console, &mut StatementBuffer::new(), input, true).await {
+                Flow::Continue => {}
+                Flow::Error => failed = true,
+                Flow::Exit => break,
+            }
+        }
+    }
+
+    console.close().await;
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+async fn repl(console: &mut Console, history_file: Option<PathBuf>) -> Result<(), ReadlineError> {
+    let mut editor = DefaultEditor::new()?;
+    if let Some(history_file) = &history_file {
+        let _ = editor.load_history(history_file);
+    }
+    println!("tgsql - type \\help for help, \\exit to exit");
+
+    let mut buffer = StatementBuffer::new();
+    loop {
+        let prompt = match (buffer.is_empty(), console.in_transaction()) {
+            (true, false) => "tgsql> ",
+            (true, true) => "tgsql*> ",
+            (false, _) => "     -> ",
+        };
+        let line = match editor.readline(prompt) {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) => {
+                buffer.clear();
+                continue;
+            }
+            Err(ReadlineError::Eof) => break,
+            Err(e) => return Err(e),
+        };
+        if !line.trim().is_empty() {
+            let _ = editor.add_history_entry(line.as_str());
+        }
+
+        if execute_input(console, &mut buffer, &line, false).await == Flow::Exit {
+            break;
+        }
+    }
+
+    if let Some(history_file) = &history_file {
+        let _ = editor.save_history(history_file);
+    }
+    Ok(())
+}
+
+/// Executes a line of input.
+///
+/// If `complete` is true, an unterminated statement is executed as well.
+///
+/// Returns [Flow::Error] if any statement fails.
+async fn execute_input(
+    console: &mut Console,
+    buffer: &mut StatementBuffer,
+    line: &str,
+    complete: bool,
+) -> Flow {
+    if buffer.is_empty() && line.trim_start().starts_with('\\') {
+        return console.execute_command(line).await;
+    }
+
+    let mut flow = Flow::Continue;
+    let mut statements = buffer.push_line(line);
+    if complete {
+        statements.extend(buffer.take());
+    }
+    for statement in statements {
+        if console.execute_statement(&statement).await == Flow::Error {
+            flow = Flow::Error;
+        }
+    }
+    flow
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Arguments, String> {
+        Arguments::parse(args.iter().map(|s| s.to_string()))
+    }
+
+    #[test]
+    fn arguments() {
+        let arguments = parse(&[
+            "tcp://localhost:12345",
+            "--credentials",
+            "/path/to/credentials.key",
+            "-c",
+            "select 1",
+            "--timeout",
+            "10s",
+        ])
+        .unwrap();
+        assert_eq!(
+            Some("tcp://localhost:12345".to_string()),
+            arguments.connection_url
+        );
+        assert_eq!(
+            Some(PathBuf::from("/path/to/credentials.key")),
+            arguments.credentials
+        );
+        assert_eq!(vec!["select 1"], arguments.commands);
+        assert_eq!(Some(Duration::from_secs(10)), arguments.timeout);
+
+        assert!(parse(&["--unknown"]).is_err());
+        assert!(parse(&["--config"]).is_err());
+        assert!(parse(&["tcp://a:1", "tcp://b:2"]).is_err());
+    }
+}
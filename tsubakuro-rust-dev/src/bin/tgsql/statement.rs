@@ -0,0 +1,115 @@
+use tsubakuro_rust_core::prelude::{SqlCodeChars, split_statements};
+
+/// Accumulates input lines and splits them into SQL statements.
+///
+/// A statement is terminated by `;` outside of string literals, delimited identifiers and comments.
+/// (See [split_statements])
+#[derive(Debug, Default)]
+pub(crate) struct StatementBuffer {
+    buffer: String,
+}
+
+impl StatementBuffer {
+    pub(crate) fn new() -> StatementBuffer {
+        StatementBuffer::default()
+    }
+
+    /// Returns true if no statement is in progress.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.buffer.trim().is_empty()
+    }
+
+    /// Adds a line, and returns the statements completed by the line.
+    pub(crate) fn push_line(&mut self, line: &str) -> Vec<String> {
+        self.buffer.push_str(line);
+        self.buffer.push('\n');
+
+        let mut chars = SqlCodeChars::new(&self.buffer);
+        let end = chars
+            .by_ref()
+            .filter(|(_, c)| *c == ';')
+            .last()
+            .map(|(i, _)| i + 1);
+        let unterminated = chars.is_unterminated();
+
+        let mut statements = Vec::new();
+        if let Some(end) = end {
+            statements = split_statements(&self.buffer[..end])
+                .into_iter()
+                .map(String::from)
+                .collect();
+            self.buffer.drain(..end);
+        }
+        // discard the rest without code (e.g. line comment)
+        if !unterminated && split_statements(&self.buffer).is_empty() {
+            self.buffer.clear();
+        }
+
+        statements
+    }
+
+    /// Discards the statement in progress.
+    pub(crate) fn clear(&mut self) {
+        self.buffer.clear();
+    }
+
+    /// Takes the statement in progress even if it is not terminated.
+    pub(crate) fn take(&mut self) -> Option<String> {
+        let buffer = std::mem::take(&mut self.buffer);
+        split_statements(&buffer)
+            .into_iter()
+            .next()
+            .map(String::from)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn split() {
+        let mut buffer = StatementBuffer::new();
+        assert_eq!(
+            vec!["select 1", "select 2"],
+            buffer.push_line("select 1; select 2;")
+        );
+        assert!(buffer.is_empty());
+
+        assert!(buffer.push_line("select *").is_empty());
+        assert!(!buffer.is_empty());
+        assert_eq!(vec!["select *\nfrom t"], buffer.push_line("from t;"));
+    }
+
+    #[test]
+    fn quote_and_comment() {
+        let mut buffer = StatementBuffer::new();
+        assert_eq!(
+            vec!["select 'a;b', \"c;d\" from t"],
+            buffer.push_line("select 'a;b', \"c;d\" from t; -- comment;")
+        );
+        assert!(buffer.is_empty());
+
+        assert!(buffer.push_line("select 'a").is_empty());
+        assert_eq!(vec!["select 'a\n;b'"], buffer.push_line(";b';"));
+
+        assert!(buffer.push_line("select /* ; ").is_empty());
+        assert_eq!(vec!["select /* ; \n*/ 1"], buffer.push_line("*/ 1;"));
+
+        assert!(buffer.push_line("/* ;").is_empty());
+        assert!(!buffer.is_empty());
+        assert!(buffer.push_line("*/").is_empty());
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn take() {
+        let mut buffer = StatementBuffer::new();
+        assert!(buffer.push_line("select 1").is_empty());
+        assert_eq!(Some("select 1".to_string()), buffer.take());
+        assert_eq!(None, buffer.take());
+
+        assert!(buffer.push_line("-- comment").is_empty());
+        assert_eq!(None, buffer.take());
+    }
+}
@@ -891,6 +891,22 @@ TsurugiFfiRc tsurugi_ffi_context_get_server_error_code_number(TsurugiFfiContextH
 TsurugiFfiRc tsurugi_ffi_context_get_server_error_structured_code(TsurugiFfiContextHandle context,
                                                                   TsurugiFfiStringHandle *structured_code_out);
 
+/**
+ * Context: Get supplemental text of server error.
+ *
+ * Available only if a server error has occurred.
+ *
+ * # Receiver
+ * - `context` - Context object.
+ *
+ * # Returns
+ * - `supplemental_text_out` - supplemental text. `null` if the server does not provide it.
+ *
+ * since 0.11.0
+ */
+TsurugiFfiRc tsurugi_ffi_context_get_server_error_supplemental_text(TsurugiFfiContextHandle context,
+                                                                    TsurugiFfiStringHandle *supplemental_text_out);
+
 /**
  * Context: Dispose.
  *
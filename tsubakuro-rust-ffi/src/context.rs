@@ -22,6 +22,7 @@
 //! | [`tsurugi_ffi_context_get_server_error_category_str`]    | "SQL"        |               |
 //! | [`tsurugi_ffi_context_get_server_error_code_number`]     | 3001         |               |
 //! | [`tsurugi_ffi_context_get_server_error_structured_code`] | "SQL-03001"  |               |
+//! | [`tsurugi_ffi_context_get_server_error_supplemental_text`] |            | `null` if none |
 //!
 //! See [Error Code of Tsurugi Services](https://github.com/project-tsurugi/tsurugidb/blob/master/docs/error-code-tsurugi-services.md) for structured_code and error_name.
 
@@ -48,6 +49,7 @@ pub(crate) struct TsurugiFfiContext {
     error_message: Option<CString>,
     diagnostic_category_str: Option<CString>,
     diagnostic_structured_code: Option<CString>,
+    diagnostic_supplemental_text: Option<CString>,
 }
 
 impl TsurugiFfiContext {
@@ -77,6 +79,7 @@ impl TsurugiFfiContext {
         cchar_field_clear!(context.error_message);
         cchar_field_clear!(context.diagnostic_category_str);
         cchar_field_clear!(context.diagnostic_structured_code);
+        cchar_field_clear!(context.diagnostic_supplemental_text);
     }
 }
 
@@ -111,6 +114,7 @@ pub extern "C" fn tsurugi_ffi_context_create(
         error_message: None,
         diagnostic_category_str: None,
         diagnostic_structured_code: None,
+        diagnostic_supplemental_text: None,
     });
 
     let handle = Box::into_raw(context);
@@ -487,6 +491,73 @@ pub extern "C" fn tsurugi_ffi_context_get_server_error_structured_code(
     rc
 }
 
+/// Context: Get supplemental text of server error.
+///
+/// Available only if a server error has occurred.
+///
+/// # Receiver
+/// - `context` - Context object.
+///
+/// # Returns
+/// - `supplemental_text_out` - supplemental text. `null` if the server does not provide it.
+///
+/// since 0.11.0
+#[no_mangle]
+pub extern "C" fn tsurugi_ffi_context_get_server_error_supplemental_text(
+    context: TsurugiFfiContextHandle,
+    supplemental_text_out: *mut TsurugiFfiStringHandle,
+) -> TsurugiFfiRc {
+    const FUNCTION_NAME: &str = "tsurugi_ffi_context_get_server_error_supplemental_text()";
+    trace!(
+        "{FUNCTION_NAME} start. context={:?}, supplemental_text_out={:?}",
+        context,
+        supplemental_text_out
+    );
+
+    ffi_arg_out_initialize!(supplemental_text_out, std::ptr::null_mut());
+    ffi_arg_require_non_null!(std::ptr::null_mut(), FUNCTION_NAME, 0, context);
+    ffi_arg_require_non_null!(
+        std::ptr::null_mut(),
+        FUNCTION_NAME,
+        1,
+        supplemental_text_out
+    );
+
+    let context = unsafe { &mut *context };
+
+    if context.diagnostic_supplemental_text.is_none() {
+        match &context.error {
+            Some(e) => match e.diagnostic_code() {
+                Some(_) => {
+                    if let Some(value) = e.supplemental_text() {
+                        let value = value.clone();
+                        cchar_field_set!(
+                            std::ptr::null_mut(),
+                            context.diagnostic_supplemental_text,
+                            value
+                        );
+                    }
+                }
+                None => return TSURUGI_FFI_RC_FFI_DIAGNOSTIC_CODE_NOT_FOUND,
+            },
+            None => return TSURUGI_FFI_RC_FFI_DIAGNOSTIC_CODE_NOT_FOUND,
+        }
+    }
+
+    let ptr = cstring_to_cchar!(context.diagnostic_supplemental_text);
+    unsafe {
+        *supplemental_text_out = ptr;
+    }
+
+    let rc = TSURUGI_FFI_RC_OK;
+    trace!(
+        "{FUNCTION_NAME} end rc={:x}. (supplemental_text={:?})",
+        rc,
+        ptr
+    );
+    rc
+}
+
 /// Context: Dispose.
 ///
 /// # Receiver
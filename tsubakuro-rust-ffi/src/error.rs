@@ -34,4 +34,11 @@ impl TsurugiFfiError {
             _ => None,
         }
     }
+
+    pub(crate) fn supplemental_text(&self) -> Option<&String> {
+        match self {
+            TsurugiFfiError::CoreError(_, e) => e.supplemental_text(),
+            _ => None,
+        }
+    }
 }
@@ -41,6 +41,7 @@ pub(crate) struct TsurugiOdbcConnectArguments {
     auth_token: Option<String>,
     credentials: Option<String>,
     credential_type: TsurugiOdbcCredentialType,
+    options: Vec<(String, String)>,
 }
 
 impl std::fmt::Debug for TsurugiOdbcConnectArguments {
@@ -53,6 +54,10 @@ impl std::fmt::Debug for TsurugiOdbcConnectArguments {
             .field("auth_token", &"****")
             .field("credentials", &self.credentials)
             .field("credential_type", &self.credential_type)
+            .field(
+                "options",
+                &self.options.iter().map(|(key, _)| key).collect::<Vec<_>>(),
+            )
             .finish()
     }
 }
@@ -67,6 +72,7 @@ impl TsurugiOdbcConnectArguments {
             auth_token: None,
             credentials: None,
             credential_type: TsurugiOdbcCredentialType::Null,
+            options: Vec::new(),
         }
     }
 
@@ -126,6 +132,15 @@ impl TsurugiOdbcConnectArguments {
         self.credential_type
     }
 
+    /// Set the options of [ConnectionOption::set_by_string()].
+    pub fn set_options(&mut self, options: Vec<(String, String)>) {
+        self.options = options;
+    }
+
+    pub fn options(&self) -> &[(String, String)] {
+        &self.options
+    }
+
     #[allow(clippy::result_large_err)]
     fn credential(&self) -> Result<Credential, TgError> {
         use TsurugiOdbcCredentialType::*;
@@ -196,17 +211,28 @@ pub(crate) fn connect_tsurugi(
         }
     };
     debug!("{dbc}.{FUNCTION_NAME}: endpoint={}", endpoint);
-    let endpoint = match Endpoint::parse(endpoint) {
-        Ok(value) => value,
-        Err(e) => {
-            debug!("{dbc}.{FUNCTION_NAME}: endpoint parse error. {:?}", e);
+
+    let mut connection_option = ConnectionOption::new();
+    for (key, value) in arg.options() {
+        if let Err(e) = connection_option.set_by_string(key, value) {
+            debug!("{dbc}.{FUNCTION_NAME}: option error. key={}, {:?}", key, e);
             dbc.add_diag(
-                TsurugiOdbcError::ConnectEndpointError,
-                format!("{odbc_function_name}: endpoint parse error. {}", e),
+                TsurugiOdbcError::ConnectOptionError,
+                format!("{odbc_function_name}: option error. {}", e),
             );
             return SqlReturn::SQL_ERROR;
         }
-    };
+    }
+
+    // comma-separated urls for multiple endpoints
+    if let Err(e) = connection_option.set_endpoint_url(endpoint) {
+        debug!("{dbc}.{FUNCTION_NAME}: endpoint parse error. {:?}", e);
+        dbc.add_diag(
+            TsurugiOdbcError::ConnectEndpointError,
+            format!("{odbc_function_name}: endpoint parse error. {}", e),
+        );
+        return SqlReturn::SQL_ERROR;
+    }
 
     let credential = match arg.credential() {
         Ok(value) => value,
@@ -220,9 +246,6 @@ pub(crate) fn connect_tsurugi(
         }
     };
     debug!("{dbc}.{FUNCTION_NAME}: credential={:?}", credential);
-
-    let mut connection_option = ConnectionOption::new();
-    connection_option.set_endpoint(endpoint.clone());
     connection_option.set_credential(credential);
 
     let runtime = dbc.runtime();
@@ -286,6 +309,7 @@ pub(crate) fn connect_tsurugi(
             return SqlReturn::SQL_ERROR;
         }
     };
+    let endpoint = session.endpoint().clone();
     dbc.set_session(session);
 
     let info = TsurugiOdbcConnectedInfo {
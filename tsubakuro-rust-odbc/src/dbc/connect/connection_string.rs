@@ -1,5 +1,7 @@
 use std::collections::HashMap;
 
+use tsubakuro_rust_core::prelude::ConnectionOption;
+
 use crate::dbc::connect::connect_tsurugi::TsurugiOdbcCredentialType;
 
 pub(crate) const KEY_DRIVER: &str = "Driver";
@@ -11,6 +13,17 @@ pub(crate) const KEY_AUTH_TOKEN: &str = "AuthToken";
 pub(crate) const KEY_CREDENTIALS: &str = "Credentials";
 pub(crate) const KEY_CREDENTIAL_TYPE: &str = "CredentialType";
 
+const ODBC_KEYS: [&str; 8] = [
+    KEY_DRIVER,
+    KEY_DSN,
+    KEY_ENDPOINT,
+    KEY_USER,
+    KEY_PASSWORD,
+    KEY_AUTH_TOKEN,
+    KEY_CREDENTIALS,
+    KEY_CREDENTIAL_TYPE,
+];
+
 pub(crate) struct ConnectionAttributes {
     attributes: HashMap<String, String>,
 }
@@ -98,6 +111,21 @@ impl ConnectionAttributes {
             None => None,
         }
     }
+
+    /// Returns the options of [ConnectionOption::set_by_string()], sorted by key.
+    ///
+    /// The ODBC keys (e.g. `UID`) are not included.
+    pub(crate) fn options(&self) -> Vec<(String, String)> {
+        let mut options: Vec<(String, String)> = self
+            .attributes
+            .iter()
+            .filter(|(key, _)| !ODBC_KEYS.iter().any(|k| k.eq_ignore_ascii_case(key)))
+            .filter(|(key, _)| ConnectionOption::is_option_key(key))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+        options.sort();
+        options
+    }
 }
 
 #[cfg(test)]
@@ -110,4 +138,18 @@ mod test {
         assert_eq!(Some(&"Value1".to_string()), attributes.get("key1"));
         assert_eq!(Some(&"value2".to_string()), attributes.get("key2"));
     }
+
+    #[test]
+    fn test_options() {
+        let attributes = ConnectionAttributes::parse(
+            "Driver=Tsurugi;Endpoint=tcp://localhost:12345;UID=user;Credentials=path;Application_Name=app;default_timeout=30s;Description=x;",
+        );
+        assert_eq!(
+            vec![
+                ("application_name".to_string(), "app".to_string()),
+                ("default_timeout".to_string(), "30s".to_string()),
+            ],
+            attributes.options()
+        );
+    }
 }
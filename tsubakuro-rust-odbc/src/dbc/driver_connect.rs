@@ -225,6 +225,7 @@ impl DriverConnect {
                 arg.set_credential_type(TsurugiOdbcCredentialType::File);
             }
         }
+        arg.set_options(attributes.options());
 
         let (arg, modified) = match self.arguments_completion(arg) {
             Ok(value) => value,
@@ -264,6 +265,9 @@ impl DriverConnect {
     ) -> Result<(TsurugiOdbcConnectArguments, bool), SqlReturn> {
         const FUNCTION_NAME: &str = "arguments_completion()";
 
+        // the dialog does not edit the options
+        let options = arg.options().to_vec();
+
         let driver_completion = match DriverCompletion::try_from(self.driver_completion) {
             Ok(value) => value,
             Err(value) => {
@@ -317,6 +321,8 @@ impl DriverConnect {
                 }
             }
         };
+        let mut arg = arg;
+        arg.set_options(options);
 
         Ok((arg, true))
     }
@@ -424,6 +430,10 @@ fn to_connection_string(
         }
     }
 
+    for (key, value) in arg.options() {
+        vec.push(format!("{}={};", key, value));
+    }
+
     vec.join("")
 }
 
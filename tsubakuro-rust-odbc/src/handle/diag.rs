@@ -1,6 +1,7 @@
 use std::sync::{Arc, Mutex};
 
 use log::debug;
+use tsubakuro_rust_core::prelude::{SqlServiceCode, TgError};
 
 use crate::{
     ctype::SqlReturn,
@@ -29,6 +30,7 @@ pub enum TsurugiOdbcError {
     ConnectEndpointNotFound = 30001,
     ConnectEndpointError = 30002,
     ConnectCredentialError = 30003,
+    ConnectOptionError = 30004,
     ConnectError = 30011,
     ConnectTimeout = 30012,
     ConnectAuthenticationError = 30013,
@@ -147,6 +149,7 @@ impl From<&TsurugiOdbcError> for &str {
             ConnectEndpointNotFound => "08001",
             ConnectEndpointError => "08001",
             ConnectCredentialError => "08001",
+            ConnectOptionError => "08001",
             ConnectError => "08S01",
             ConnectTimeout => "HYT01",
             ConnectAuthenticationError => "28000",
@@ -241,9 +244,33 @@ impl From<&TsurugiOdbcError> for &str {
     }
 }
 
+// state from the kind of TgError
+fn tg_error_state(error: &TgError) -> Option<&'static str> {
+    if error.is_constraint_violation() {
+        Some("23000")
+    } else if error.is_transaction_inactive() {
+        Some("25000")
+    } else if error.is_timeout() {
+        Some("HYT00")
+    } else {
+        error.sql_service_code().and_then(not_found_state)
+    }
+}
+
+// state from the not found error code.
+// (42S02 is "base table or view not found", so only TARGET_NOT_FOUND_EXCEPTION is mapped to it)
+fn not_found_state(code: SqlServiceCode) -> Option<&'static str> {
+    match code {
+        SqlServiceCode::TargetNotFoundException => Some("42S02"),
+        SqlServiceCode::TransactionNotFoundException => Some("25000"),
+        _ => None,
+    }
+}
+
 #[derive(Debug)]
 pub(crate) struct TsurugiOdbcDiagRec {
     error_code: TsurugiOdbcError,
+    state: &'static str,
     message: String,
 }
 
@@ -251,14 +278,31 @@ impl TsurugiOdbcDiagRec {
     fn new(error_code: TsurugiOdbcError, message: String) -> TsurugiOdbcDiagRec {
         TsurugiOdbcDiagRec {
             error_code,
+            state: (&error_code).into(),
             message,
         }
     }
 
+    fn with_error(
+        error_code: TsurugiOdbcError,
+        message: String,
+        error: &TgError,
+    ) -> TsurugiOdbcDiagRec {
+        let mut diag_rec = TsurugiOdbcDiagRec::new(error_code, message);
+        if let Some(state) = tg_error_state(error) {
+            diag_rec.state = state;
+        }
+        diag_rec
+    }
+
     pub(crate) fn error_code(&self) -> TsurugiOdbcError {
         self.error_code
     }
 
+    pub(crate) fn state(&self) -> &'static str {
+        self.state
+    }
+
     pub(crate) fn message(&self) -> &String {
         &self.message
     }
@@ -288,6 +332,21 @@ impl TsurugiOdbcDiagCollection {
         list.push(Arc::new(diag_rec));
     }
 
+    /// Adds a diag record of TgError.
+    ///
+    /// The state is taken from the kind of the error (e.g. unique violation is `23000`) if possible.
+    pub(crate) fn add_diag_with_error(
+        &self,
+        error_code: TsurugiOdbcError,
+        message: impl Into<String>,
+        error: &TgError,
+    ) {
+        let diag_rec = TsurugiOdbcDiagRec::with_error(error_code, message.into(), error);
+
+        let mut list = self.diag_list.lock().unwrap();
+        list.push(Arc::new(diag_rec));
+    }
+
     pub(crate) fn len(&self) -> usize {
         let list = self.diag_list.lock().unwrap();
         list.len()
@@ -328,3 +387,48 @@ pub(crate) fn get_diag_collection(
     };
     Ok(diags)
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn diag_rec_state() {
+        let diag_rec = TsurugiOdbcDiagRec::new(TsurugiOdbcError::PrepareError, "test".to_string());
+        assert_eq!("HY000", diag_rec.state());
+
+        let error = TgError::TimeoutError("test timeout".to_string());
+        let diag_rec = TsurugiOdbcDiagRec::with_error(
+            TsurugiOdbcError::PreparedExecuteError,
+            "test".to_string(),
+            &error,
+        );
+        assert_eq!("HYT00", diag_rec.state());
+        assert_eq!(
+            TsurugiOdbcError::PreparedExecuteError,
+            diag_rec.error_code()
+        );
+
+        let error = TgError::ClientError("test".to_string(), None);
+        let diag_rec = TsurugiOdbcDiagRec::with_error(
+            TsurugiOdbcError::PrepareError,
+            "test".to_string(),
+            &error,
+        );
+        assert_eq!("HY000", diag_rec.state());
+
+        assert_eq!(
+            Some("42S02"),
+            not_found_state(SqlServiceCode::TargetNotFoundException)
+        );
+        assert_eq!(
+            Some("25000"),
+            not_found_state(SqlServiceCode::TransactionNotFoundException)
+        );
+        assert_eq!(
+            None,
+            not_found_state(SqlServiceCode::StatementNotFoundException)
+        );
+        assert_eq!(None, not_found_state(SqlServiceCode::SqlServiceException));
+    }
+}
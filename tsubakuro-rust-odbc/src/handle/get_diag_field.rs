@@ -208,7 +208,7 @@ fn get_diag_field(
         SQL_DIAG_MESSAGE_TEXT => write_string(diag.message(), arg),
         SQL_DIAG_NATIVE => write_integer(diag.error_code() as SqlInteger, arg),
         SQL_DIAG_SERVER_NAME => write_string(&server_name(handle_type, handle), arg),
-        SQL_DIAG_SQLSTATE => write_string((&diag.error_code()).into(), arg),
+        SQL_DIAG_SQLSTATE => write_string(diag.state(), arg),
         SQL_DIAG_SUBCLASS_ORIGIN => write_string(ODBC_DRIVER_NAME, arg),
         _ => {
             warn!(
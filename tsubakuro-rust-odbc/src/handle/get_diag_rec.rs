@@ -63,7 +63,7 @@ pub extern "system" fn SQLGetDiagRec(
         }
     };
 
-    let state_code: &str = (&diag.error_code()).into();
+    let state_code = diag.state();
     let rc1 = write_char(
         "SQLDiagRec.state",
         state_code,
@@ -146,7 +146,7 @@ pub extern "system" fn SQLGetDiagRecW(
         }
     };
 
-    let state_code: &str = (&diag.error_code()).into();
+    let state_code = diag.state();
     let rc1 = write_wchar(
         "SQLDiagRecW.state",
         state_code,
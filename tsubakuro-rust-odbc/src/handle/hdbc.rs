@@ -248,9 +248,10 @@ impl TsurugiOdbcDbc {
                     }
                     Err(e) => {
                         warn!("{self}.{FUNCTION_NAME}: start_transaction() error. {:?}", e);
-                        self.add_diag(
+                        self.add_diag_with_error(
                             TsurugiOdbcError::TransactionStartError,
                             format!("start transaction error. {}", e),
+                            &e,
                         );
                         return Err(SqlReturn::SQL_ERROR);
                     }
@@ -305,9 +306,10 @@ impl TsurugiOdbcDbc {
                     "{self}.{function_name}: transaction.commit() error. {:?}",
                     e
                 );
-                diags.add_diag(
+                diags.add_diag_with_error(
                     TsurugiOdbcError::TransactionCommitError,
                     format!("commit error. {}", e),
+                    &e,
                 );
                 SqlReturn::SQL_ERROR
             }
@@ -484,6 +486,15 @@ impl TsurugiOdbcDbc {
         self.diags.add_diag(error, message);
     }
 
+    pub(crate) fn add_diag_with_error(
+        &self,
+        error: TsurugiOdbcError,
+        message: impl Into<String>,
+        tg_error: &TgError,
+    ) {
+        self.diags.add_diag_with_error(error, message, tg_error);
+    }
+
     pub(crate) fn diag_collection(&self) -> Arc<TsurugiOdbcDiagCollection> {
         self.diags.clone()
     }
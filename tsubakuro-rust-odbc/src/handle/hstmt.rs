@@ -6,7 +6,7 @@ use std::{
 
 use log::debug;
 use tokio::runtime::Runtime;
-use tsubakuro_rust_core::prelude::SqlClient;
+use tsubakuro_rust_core::prelude::{SqlClient, TgError};
 
 use crate::{
     ctype::{SqlReturn, SqlULen},
@@ -313,6 +313,15 @@ impl TsurugiOdbcStmt {
         self.diags.add_diag(error, message);
     }
 
+    pub(crate) fn add_diag_with_error(
+        &self,
+        error: TsurugiOdbcError,
+        message: impl Into<String>,
+        tg_error: &TgError,
+    ) {
+        self.diags.add_diag_with_error(error, message, tg_error);
+    }
+
     pub(crate) fn diag_collection(&self) -> Arc<TsurugiOdbcDiagCollection> {
         self.diags.clone()
     }
@@ -1,5 +1,5 @@
 use log::{debug, trace, warn};
-use tsubakuro_rust_core::prelude::{AtomType, SqlColumn, TableMetadata};
+use tsubakuro_rust_core::prelude::{AtomType, SqlColumn, SqlServiceCode, TableMetadata};
 
 use crate::{
     check_sql_client_or_err, check_stmt,
@@ -167,23 +167,19 @@ pub(crate) fn get_table_metadata(
             Ok(Some(metadata))
         }
         Err(e) => {
-            if let Some(code) = e.diagnostic_code() {
-                if code.category_number() == 3 && code.code_number() == 2014 {
-                    // SQL-02014 TARGET_NOT_FOUND_EXCEPTION
-                    debug!(
-                        "{stmt}.{FUNCTION_NAME}: sql_client.get_table_metadata() table not found"
-                    );
-                    return Ok(None);
-                }
+            if e.sql_service_code() == Some(SqlServiceCode::TargetNotFoundException) {
+                debug!("{stmt}.{FUNCTION_NAME}: sql_client.get_table_metadata() table not found");
+                return Ok(None);
             }
 
             warn!(
                 "{stmt}.{FUNCTION_NAME}: sql_client.get_table_metadata() error. {:?}",
                 e
             );
-            stmt.add_diag(
+            stmt.add_diag_with_error(
                 TsurugiOdbcError::GetTableMetadataError,
                 format!("get table metadta error. {}", e),
+                &e,
             );
             Err(SqlReturn::SQL_ERROR)
         }
@@ -93,9 +93,10 @@ pub(crate) fn prepared_query(
         }
         Err(e) => {
             warn!("{stmt}.{FUNCTION_NAME}: prepared_query() error. {:?}", e);
-            stmt.add_diag(
+            stmt.add_diag_with_error(
                 TsurugiOdbcError::PreparedQueryError,
                 format!("query error. {}", e),
+                &e,
             );
             stmt.rollback_if_auto_commit();
             return Err(SqlReturn::SQL_ERROR);
@@ -138,9 +139,10 @@ pub(crate) fn prepared_execute(
         }
         Err(e) => {
             warn!("{stmt}.{FUNCTION_NAME}: prepared_execute() error. {:?}", e);
-            stmt.add_diag(
+            stmt.add_diag_with_error(
                 TsurugiOdbcError::PreparedExecuteError,
                 format!("SQL execute error. {}", e),
+                &e,
             );
             stmt.rollback_if_auto_commit();
             return SqlReturn::SQL_ERROR;
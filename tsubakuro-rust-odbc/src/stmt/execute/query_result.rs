@@ -55,9 +55,10 @@ impl TsurugiOdbcStatementProcessor for TsurugiOdbcQueryResult {
             Ok(next) => next,
             Err(e) => {
                 warn!("{stmt}.{FUNCTION_NAME}: next_row() error. {:?}", e);
-                stmt.add_diag(
+                stmt.add_diag_with_error(
                     TsurugiOdbcError::SqlQueryResultNextRowError,
                     format!("next_row error. {}", e),
+                    &e,
                 );
                 return SqlReturn::SQL_ERROR;
             }
@@ -104,9 +105,10 @@ impl TsurugiOdbcStatementProcessor for TsurugiOdbcQueryResult {
                 Ok(next) => next,
                 Err(e) => {
                     warn!("{stmt}.{FUNCTION_NAME}: next_column() error. {:?}", e);
-                    stmt.add_diag(
+                    stmt.add_diag_with_error(
                         TsurugiOdbcError::SqlQueryResultNextColumnError,
                         format!("next_column error. {}", e),
+                        &e,
                     );
                     return SqlReturn::SQL_ERROR;
                 }
@@ -149,9 +151,10 @@ impl TsurugiOdbcStatementProcessor for TsurugiOdbcQueryResult {
                     "{stmt}.{FUNCTION_NAME}: query_result.is_null() error. {:?}",
                     e
                 );
-                stmt.add_diag(
+                stmt.add_diag_with_error(
                     TsurugiOdbcError::SqlQueryResultIsNullError,
                     format!("is_null error. {}", e),
+                    &e,
                 );
                 return SqlReturn::SQL_ERROR;
             }
@@ -161,9 +164,10 @@ impl TsurugiOdbcStatementProcessor for TsurugiOdbcQueryResult {
             Ok(rc) => rc,
             Err(e) => {
                 warn!("{stmt}.{FUNCTION_NAME}: fetch_value() error. {:?}", e);
-                stmt.add_diag(
+                stmt.add_diag_with_error(
                     TsurugiOdbcError::SqlQueryResultFetchError,
                     format!("fetch_value error. {}", e),
+                    &e,
                 );
                 SqlReturn::SQL_ERROR
             }
@@ -284,9 +288,10 @@ impl TsurugiOdbcQueryResult {
                     "{stmt}.{FUNCTION_NAME}: query_result.close() error. {:?}",
                     e
                 );
-                stmt.add_diag(
+                stmt.add_diag_with_error(
                     TsurugiOdbcError::SqlQueryResultCloseError,
                     format!("query_result close error. {}", e),
+                    &e,
                 );
                 SqlReturn::SQL_SUCCESS_WITH_INFO
             }
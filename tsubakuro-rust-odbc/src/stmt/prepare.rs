@@ -127,9 +127,10 @@ impl TsurugiOdbcPrepare {
                 }
                 Err(e) => {
                     warn!("{stmt}.{FUNCTION_NAME}: prepare() error. {:?}", e);
-                    stmt.add_diag(
+                    stmt.add_diag_with_error(
                         TsurugiOdbcError::PrepareError,
                         format!("prepare error. {}", e),
+                        &e,
                     );
                     return Err(SqlReturn::SQL_ERROR);
                 }
@@ -109,9 +109,10 @@ fn tables(stmt: &mut TsurugiOdbcStmt) -> SqlReturn {
         }
         Err(e) => {
             warn!("{stmt}.{FUNCTION_NAME}: list_tables() error. {:?}", e);
-            stmt.add_diag(
+            stmt.add_diag_with_error(
                 TsurugiOdbcError::ListTablesError,
                 format!("list tables error. {}", e),
+                &e,
             );
             return SqlReturn::SQL_ERROR;
         }
@@ -38,6 +38,9 @@ use crate::{
 ///     shutdown_option (ShutdownOption): Shutdown option.
 ///     default_timeout (int): Default timeout in seconds.
 ///
+/// The other keys of the Rust `ConnectionOption::set_by_string()` can also be specified by the arguments
+/// (e.g. `connect_timeout="10s"`, `lob_transfer_type="relay"`). since 0.11.0
+///
 /// Examples:
 ///     ```python
 ///     import tsurugi_dbapi as tsurugi
@@ -120,6 +123,11 @@ pub struct Config {
     /// Default timeout in seconds.
     #[pyo3(get, set)]
     default_timeout: Option<u64>,
+    /// Other options of `ConnectionOption::set_by_string()`. (e.g. `connect_timeout`, `lob_transfer_type`)
+    ///
+    /// since 0.11.0
+    #[serde(default)]
+    options: Vec<(String, String)>,
 }
 
 impl Default for Config {
@@ -140,6 +148,7 @@ impl Default for Config {
             commit_option: None,
             shutdown_option: None,
             default_timeout: None,
+            options: Vec::new(),
         }
     }
 }
@@ -229,6 +238,9 @@ impl Config {
         if let Some(default_timeout) = &other.default_timeout {
             self.default_timeout = Some(*default_timeout);
         }
+        for (key, value) in &other.options {
+            self.set_option(key, value.clone());
+        }
     }
 
     pub fn __eq__(&self, other: &Config) -> bool {
@@ -376,6 +388,7 @@ impl Config {
                 })?;
                 self.default_timeout = Some(timeout);
             }
+            _ if ConnectionOption::is_option_key(key) => self.set_option(key, value.to_string()),
             _ => debug!("Unknown key: {}", key),
         }
         Ok(())
@@ -399,10 +412,22 @@ impl Config {
             "lob_upload_timeout" => self.lob_upload_timeout = Some(value.extract()?),
             "lob_download_timeout" => self.lob_download_timeout = Some(value.extract()?),
             "default_timeout" | "timeout" => self.default_timeout = Some(value.extract()?),
+            _ if ConnectionOption::is_option_key(key) => {
+                self.set_option(key, value.str()?.to_string())
+            }
             _ => debug!("Unknown key: {}", key),
         }
         Ok(())
     }
+
+    /// Set an option of `ConnectionOption::set_by_string()`.
+    fn set_option(&mut self, key: &str, value: String) {
+        let key = key.to_ascii_lowercase();
+        match self.options.iter_mut().find(|(k, _)| *k == key) {
+            Some(entry) => entry.1 = value,
+            None => self.options.push((key, value)),
+        }
+    }
 }
 
 impl Config {
@@ -446,6 +471,12 @@ impl Config {
 
         connection_option.set_default_timeout(self.default_timeout());
 
+        for (key, value) in &self.options {
+            connection_option
+                .set_by_string(key, value)
+                .map_err(to_pyerr)?;
+        }
+
         Ok(connection_option)
     }
 
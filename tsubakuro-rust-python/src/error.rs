@@ -1,4 +1,4 @@
-use pyo3::{exceptions::PyException, *};
+use pyo3::{exceptions::PyException, types::PyAnyMethods, *};
 use pyo3_stub_gen::create_exception;
 use tsubakuro_rust_core::prelude::{DiagnosticCode, TgError};
 
@@ -426,7 +426,16 @@ create_exception!(
 );
 
 pub(crate) fn to_pyerr(err: TgError) -> PyErr {
-    match err {
+    let attributes = [
+        ("is_unique_violation", err.is_unique_violation()),
+        ("is_constraint_violation", err.is_constraint_violation()),
+        ("is_not_found", err.is_not_found()),
+        ("is_transaction_inactive", err.is_transaction_inactive()),
+        ("is_timeout", err.is_timeout()),
+    ];
+    let supplemental_text = err.supplemental_text().cloned();
+
+    let pyerr = match err {
         TgError::ClientError(message, cause) => {
             if let Some(cause) = cause {
                 InterfaceError::new_err(format!("{}: {}", message, cause))
@@ -447,7 +456,16 @@ pub(crate) fn to_pyerr(err: TgError) -> PyErr {
         TgError::ServerError(_, _, diagnostic_code, server_message) => {
             server_error_to_pyerr(server_message, diagnostic_code)
         }
-    }
+    };
+
+    Python::attach(|py| {
+        let value = pyerr.value(py);
+        for (name, flag) in attributes {
+            let _ = value.setattr(name, flag);
+        }
+        let _ = value.setattr("supplemental_text", supplemental_text);
+    });
+    pyerr
 }
 
 macro_rules! server_error {
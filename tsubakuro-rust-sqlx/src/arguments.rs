@@ -0,0 +1,191 @@
+use sqlx_core::{
+    arguments::Arguments,
+    encode::{Encode, IsNull},
+    error::BoxDynError,
+    impl_into_arguments_for_arguments,
+    types::Type,
+};
+use tsubakuro_rust_core::prelude::{
+    positional_placeholder_name, replace_positional_placeholders, SqlCodeChars, SqlParameter,
+    SqlPlaceholder,
+};
+
+use crate::{value::TsurugiData, Tsurugi, TsurugiTypeInfo};
+
+/// Arguments (parameters) of a statement.
+///
+/// The arguments are bound to the positional markers (`?` or `$1`, `$2`, ...) in order.
+///
+/// since 0.11.0
+#[derive(Debug, Default, Clone)]
+pub struct TsurugiArguments {
+    values: Vec<(TsurugiTypeInfo, TsurugiData)>,
+}
+
+impl TsurugiArguments {
+    pub(crate) fn placeholders(&self) -> Vec<SqlPlaceholder> {
+        self.values
+            .iter()
+            .enumerate()
+            .map(|(i, (type_info, _))| {
+                SqlPlaceholder::of_atom_type(
+                    &positional_placeholder_name(i + 1),
+                    type_info.atom_type(),
+                )
+            })
+            .collect()
+    }
+
+    pub(crate) fn parameters(&self) -> Vec<SqlParameter> {
+        self.values
+            .iter()
+            .enumerate()
+            .map(|(i, (_, data))| data.to_parameter(&positional_placeholder_name(i + 1)))
+            .collect()
+    }
+}
+
+impl<'q> Arguments<'q> for TsurugiArguments {
+    type Database = Tsurugi;
+
+    fn reserve(&mut self, additional: usize, _size: usize) {
+        self.values.reserve(additional);
+    }
+
+    fn add<T>(&mut self, value: T) -> Result<(), BoxDynError>
+    where
+        T: 'q + Encode<'q, Self::Database> + Type<Self::Database>,
+    {
+        let type_info = value.produces().unwrap_or_else(T::type_info);
+
+        let mut buffer = Vec::with_capacity(1);
+        let data = match value.encode(&mut buffer)? {
+            IsNull::Yes => TsurugiData::Null,
+            IsNull::No => buffer.pop().ok_or("no value is encoded")?,
+        };
+        self.values.push((type_info, data));
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        self.values.len()
+    }
+}
+
+impl_into_arguments_for_arguments!(TsurugiArguments);
+
+/// Replaces positional markers (`?` or `$1`, `$2`, ...) with named placeholders (`:p1`, `:p2`, ...).
+///
+/// Markers in string literals, quoted identifiers and comments are not replaced.
+/// Returns the SQL and the number of parameters.
+pub(crate) fn rewrite_placeholders(sql: &str) -> Result<(String, usize), String> {
+    let (sql, question_count) = replace_positional_placeholders(sql);
+    let (sql, dollar_max) = replace_numbered_placeholders(&sql)?;
+    if question_count > 0 && dollar_max > 0 {
+        return Err("'?' and '$n' parameter markers cannot be mixed".to_string());
+    }
+    Ok((sql, question_count.max(dollar_max)))
+}
+
+/// Replaces numbered markers (`$1`, `$2`, ...) with named placeholders (`:p1`, `:p2`, ...).
+///
+/// Returns the SQL and the maximum number of the markers.
+fn replace_numbered_placeholders(sql: &str) -> Result<(String, usize), String> {
+    let mut result = String::with_capacity(sql.len() + 8);
+    let mut max = 0;
+    let mut last = 0;
+
+    let mut chars = SqlCodeChars::new(sql).peekable();
+    while let Some((i, c)) = chars.next() {
+        if c != '$' {
+            continue;
+        }
+        let mut end = i + 1;
+        while chars
+            .next_if(|(j, d)| *j == end && d.is_ascii_digit())
+            .is_some()
+        {
+            end += 1;
+        }
+        if end == i + 1 {
+            continue;
+        }
+
+        let number = &sql[i + 1..end];
+        let position: usize = number
+            .parse()
+            .map_err(|_| format!("invalid parameter marker ${number}"))?;
+        if position == 0 {
+            return Err("parameter marker $0 is invalid".to_string());
+        }
+        max = max.max(position);
+        result.push_str(&sql[last..i]);
+        result.push(':');
+        result.push_str(&positional_placeholder_name(position));
+        last = end;
+    }
+    result.push_str(&sql[last..]);
+
+    Ok((result, max))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use tsubakuro_rust_core::prelude::AtomType;
+
+    #[test]
+    fn rewrite_question() {
+        let (sql, count) =
+            rewrite_placeholders("select * from tb where a = ? and b = ? and c = '?'").unwrap();
+        assert_eq!(
+            "select * from tb where a = :p1 and b = :p2 and c = '?'",
+            sql
+        );
+        assert_eq!(2, count);
+    }
+
+    #[test]
+    fn rewrite_dollar() {
+        let (sql, count) =
+            rewrite_placeholders("update tb set a = $2 where b = $1 or c = $2 -- $3\n").unwrap();
+        assert_eq!(
+            "update tb set a = :p2 where b = :p1 or c = :p2 -- $3\n",
+            sql
+        );
+        assert_eq!(2, count);
+
+        let (sql, count) = rewrite_placeholders("select \"?\" /* $1 ? */ from tb").unwrap();
+        assert_eq!("select \"?\" /* $1 ? */ from tb", sql);
+        assert_eq!(0, count);
+
+        let (sql, count) = rewrite_placeholders("select $1/* 2 */2, '$3' from tb").unwrap();
+        assert_eq!("select :p1/* 2 */2, '$3' from tb", sql);
+        assert_eq!(1, count);
+    }
+
+    #[test]
+    fn rewrite_error() {
+        assert!(rewrite_placeholders("select * from tb where a = ? and b = $1").is_err());
+        assert!(rewrite_placeholders("select * from tb where a = $0").is_err());
+    }
+
+    #[test]
+    fn add() {
+        let mut arguments = TsurugiArguments::default();
+        arguments.add(1_i32).unwrap();
+        arguments.add("abc").unwrap();
+        arguments.add(None::<i64>).unwrap();
+        assert_eq!(3, arguments.len());
+
+        let placeholders = arguments.placeholders();
+        assert_eq!(Some(&"p1".to_string()), placeholders[0].name());
+        assert_eq!(Some(AtomType::Int4), placeholders[0].atom_type());
+        assert_eq!(Some(AtomType::Character), placeholders[1].atom_type());
+        assert_eq!(Some(AtomType::Int8), placeholders[2].atom_type());
+
+        let parameters = arguments.parameters();
+        assert_eq!(Some(&"p3".to_string()), parameters[2].name());
+        assert_eq!(None, parameters[2].value());
+    }
+}
@@ -0,0 +1,64 @@
+use sqlx_core::column::Column;
+use tsubakuro_rust_core::prelude::{AtomType, SqlColumn};
+
+use crate::{Tsurugi, TsurugiTypeInfo};
+
+/// Column of a query result.
+///
+/// since 0.11.0
+#[derive(Debug, Clone)]
+pub struct TsurugiColumn {
+    ordinal: usize,
+    name: String,
+    type_info: TsurugiTypeInfo,
+    nullable: Option<bool>,
+}
+
+impl TsurugiColumn {
+    pub(crate) fn new(ordinal: usize, column: &SqlColumn) -> TsurugiColumn {
+        let atom_type = column.atom_type().unwrap_or(AtomType::Unknown);
+        TsurugiColumn {
+            ordinal,
+            name: column.name().clone(),
+            type_info: TsurugiTypeInfo::new(atom_type),
+            nullable: column.nullable(),
+        }
+    }
+
+    /// Whether the column is nullable (`None` if unknown).
+    ///
+    /// since 0.11.0
+    pub fn nullable(&self) -> Option<bool> {
+        self.nullable
+    }
+}
+
+impl Column for TsurugiColumn {
+    type Database = Tsurugi;
+
+    fn ordinal(&self) -> usize {
+        self.ordinal
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn type_info(&self) -> &TsurugiTypeInfo {
+        &self.type_info
+    }
+}
+
+/// Returns the index of the column name.
+///
+/// The name is compared case-sensitively first, and then case-insensitively.
+pub(crate) fn find_column(columns: &[TsurugiColumn], name: &str) -> Option<usize> {
+    columns
+        .iter()
+        .position(|column| column.name == name)
+        .or_else(|| {
+            columns
+                .iter()
+                .position(|column| column.name.eq_ignore_ascii_case(name))
+        })
+}
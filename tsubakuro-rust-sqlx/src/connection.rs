@@ -0,0 +1,177 @@
+use std::{sync::Arc, time::Duration};
+
+use futures_core::future::BoxFuture;
+use sqlx_core::{
+    connection::{Connection, LogSettings},
+    error::Error,
+    transaction::Transaction as SqlxTransaction,
+};
+use tsubakuro_rust_core::prelude::*;
+
+use crate::{error::to_sqlx_error, Tsurugi, TsurugiConnectOptions};
+
+/// Connection to Tsurugi.
+///
+/// since 0.11.0
+pub struct TsurugiConnection {
+    session: Arc<Session>,
+    pub(crate) client: SqlClient,
+    transaction_option: TransactionOption,
+    pub(crate) next_transaction_option: Option<TransactionOption>,
+    pub(crate) transaction: Option<Transaction>,
+    pub(crate) log_settings: LogSettings,
+}
+
+impl std::fmt::Debug for TsurugiConnection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TsurugiConnection")
+            .field("endpoint", &self.session.endpoint().to_string())
+            .field("transaction_option", &self.transaction_option)
+            .field("in_transaction", &self.transaction.is_some())
+            .finish()
+    }
+}
+
+impl TsurugiConnection {
+    pub(crate) fn new(session: Arc<Session>, options: &TsurugiConnectOptions) -> TsurugiConnection {
+        let mut client: SqlClient = session.make_client();
+        client.set_default_timeout(options.get_connection_option().default_timeout());
+        TsurugiConnection {
+            session,
+            client,
+            transaction_option: options.get_transaction_option().clone(),
+            next_transaction_option: None,
+            transaction: None,
+            log_settings: options.log_settings.clone(),
+        }
+    }
+
+    /// Get session.
+    ///
+    /// since 0.11.0
+    pub fn session(&self) -> &Arc<Session> {
+        &self.session
+    }
+
+    /// Get SqlClient.
+    ///
+    /// since 0.11.0
+    pub fn sql_client(&self) -> &SqlClient {
+        &self.client
+    }
+
+    /// Get the current Tsurugi transaction, if a sqlx transaction is active.
+    ///
+    /// since 0.11.0
+    pub fn current_transaction(&self) -> Option<&Transaction> {
+        self.transaction.as_ref()
+    }
+
+    /// Set the transaction option.
+    ///
+    /// The option is used by [`Connection::begin`], and by statements executed outside of a transaction.
+    ///
+    /// since 0.11.0
+    pub fn set_transaction_option(&mut self, transaction_option: TransactionOption) {
+        self.transaction_option = transaction_option;
+    }
+
+    /// Get the transaction option.
+    ///
+    /// since 0.11.0
+    pub fn transaction_option(&self) -> &TransactionOption {
+        &self.transaction_option
+    }
+
+    /// Set default timeout.
+    ///
+    /// since 0.11.0
+    pub fn set_default_timeout(&mut self, timeout: Duration) {
+        self.client.set_default_timeout(timeout);
+    }
+
+    /// Starts a transaction with the transaction option.
+    ///
+    /// # Examples
+    /// ```
+    /// use tsubakuro_rust_core::prelude::*;
+    /// use tsubakuro_rust_sqlx::{Tsurugi, TsurugiConnection};
+    ///
+    /// async fn example(connection: &mut TsurugiConnection) -> Result<(), sqlx_core::Error> {
+    ///     let mut transaction_option = TransactionOption::from(TransactionType::Long);
+    ///     transaction_option.set_write_preserve(&["customer"]);
+    ///
+    ///     let mut transaction = connection.begin_with_option(transaction_option).await?;
+    ///     sqlx_core::query::query::<Tsurugi>("delete from customer")
+    ///         .execute(&mut *transaction)
+    ///         .await?;
+    ///     transaction.commit().await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// since 0.11.0
+    pub fn begin_with_option(
+        &mut self,
+        transaction_option: TransactionOption,
+    ) -> BoxFuture<'_, Result<SqlxTransaction<'_, Tsurugi>, Error>> {
+        self.next_transaction_option = Some(transaction_option);
+        SqlxTransaction::begin(self, None)
+    }
+
+    /// Returns the transaction option for the next transaction.
+    pub(crate) fn take_transaction_option(&mut self) -> TransactionOption {
+        self.next_transaction_option
+            .take()
+            .unwrap_or_else(|| self.transaction_option.clone())
+    }
+}
+
+impl Connection for TsurugiConnection {
+    type Database = Tsurugi;
+
+    type Options = TsurugiConnectOptions;
+
+    fn close(mut self) -> BoxFuture<'static, Result<(), Error>> {
+        Box::pin(async move {
+            if let Some(transaction) = self.transaction.take() {
+                let _ = self.client.rollback(&transaction).await;
+                let _ = transaction.close().await;
+            }
+            self.session.close().await.map_err(to_sqlx_error)
+        })
+    }
+
+    fn close_hard(mut self) -> BoxFuture<'static, Result<(), Error>> {
+        Box::pin(async move {
+            drop(self.transaction.take());
+            self.session.close().await.map_err(to_sqlx_error)
+        })
+    }
+
+    fn ping(&mut self) -> BoxFuture<'_, Result<(), Error>> {
+        Box::pin(async move {
+            self.session
+                .update_expiration_time(None)
+                .await
+                .map_err(to_sqlx_error)
+        })
+    }
+
+    fn begin(&mut self) -> BoxFuture<'_, Result<SqlxTransaction<'_, Tsurugi>, Error>>
+    where
+        Self: Sized,
+    {
+        SqlxTransaction::begin(self, None)
+    }
+
+    fn shrink_buffers(&mut self) {}
+
+    fn flush(&mut self) -> BoxFuture<'_, Result<(), Error>> {
+        Box::pin(async { Ok(()) })
+    }
+
+    fn should_flush(&self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,39 @@
+use sqlx_core::database::Database;
+
+use crate::{
+    TsurugiArguments, TsurugiColumn, TsurugiConnection, TsurugiData, TsurugiQueryResult,
+    TsurugiRow, TsurugiStatement, TsurugiTransactionManager, TsurugiTypeInfo, TsurugiValue,
+    TsurugiValueRef,
+};
+
+/// Tsurugi database driver.
+///
+/// since 0.11.0
+#[derive(Debug)]
+pub struct Tsurugi;
+
+impl Database for Tsurugi {
+    type Connection = TsurugiConnection;
+
+    type TransactionManager = TsurugiTransactionManager;
+
+    type Row = TsurugiRow;
+
+    type QueryResult = TsurugiQueryResult;
+
+    type Column = TsurugiColumn;
+
+    type TypeInfo = TsurugiTypeInfo;
+
+    type Value = TsurugiValue;
+    type ValueRef<'r> = TsurugiValueRef<'r>;
+
+    type Arguments<'q> = TsurugiArguments;
+    type ArgumentBuffer<'q> = Vec<TsurugiData>;
+
+    type Statement<'q> = TsurugiStatement<'q>;
+
+    const NAME: &'static str = "Tsurugi";
+
+    const URL_SCHEMES: &'static [&'static str] = &["tsurugi"];
+}
@@ -0,0 +1,94 @@
+use std::{borrow::Cow, error::Error as StdError};
+
+use sqlx_core::error::{DatabaseError, Error, ErrorKind};
+use tsubakuro_rust_core::prelude::{SqlServiceCode, TgError};
+
+/// Error returned from Tsurugi.
+///
+/// The original [`TgError`] (always `TgError::ServerError`) is available by [`Self::tg_error`].
+///
+/// since 0.11.0
+#[derive(Debug)]
+pub struct TsurugiDatabaseError(TgError);
+
+impl TsurugiDatabaseError {
+    /// Get the original error.
+    ///
+    /// since 0.11.0
+    pub fn tg_error(&self) -> &TgError {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for TsurugiDatabaseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl StdError for TsurugiDatabaseError {}
+
+impl DatabaseError for TsurugiDatabaseError {
+    fn message(&self) -> &str {
+        self.0.message()
+    }
+
+    /// Returns the structured diagnostic code (e.g. `SQL-02001`).
+    fn code(&self) -> Option<Cow<'_, str>> {
+        self.0
+            .diagnostic_code()
+            .map(|code| Cow::Owned(code.structured_code()))
+    }
+
+    fn as_error(&self) -> &(dyn StdError + Send + Sync + 'static) {
+        self
+    }
+
+    fn as_error_mut(&mut self) -> &mut (dyn StdError + Send + Sync + 'static) {
+        self
+    }
+
+    fn into_error(self: Box<Self>) -> Box<dyn StdError + Send + Sync + 'static> {
+        self
+    }
+
+    fn kind(&self) -> ErrorKind {
+        match self.0.sql_service_code() {
+            Some(SqlServiceCode::UniqueConstraintViolationException) => ErrorKind::UniqueViolation,
+            Some(SqlServiceCode::NotNullConstraintViolationException) => {
+                ErrorKind::NotNullViolation
+            }
+            Some(SqlServiceCode::ReferentialIntegrityConstraintViolationException) => {
+                ErrorKind::ForeignKeyViolation
+            }
+            Some(SqlServiceCode::CheckConstraintViolationException) => ErrorKind::CheckViolation,
+            _ => ErrorKind::Other,
+        }
+    }
+}
+
+/// Converts TgError to sqlx Error.
+pub(crate) fn to_sqlx_error(error: TgError) -> Error {
+    match error {
+        TgError::ServerError(..) => Error::Database(Box::new(TsurugiDatabaseError(error))),
+        TgError::TimeoutError(_) => {
+            Error::Io(std::io::Error::new(std::io::ErrorKind::TimedOut, error))
+        }
+        TgError::IoError(..) => Error::Io(std::io::Error::other(error)),
+        TgError::ClientError(..) => Error::Protocol(error.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn client_error() {
+        let error = to_sqlx_error(TgError::ClientError("example".to_string(), None));
+        assert!(matches!(error, Error::Protocol(message) if message == "example"));
+
+        let error = to_sqlx_error(TgError::TimeoutError("timeout".to_string()));
+        assert!(matches!(error, Error::Io(e) if e.kind() == std::io::ErrorKind::TimedOut));
+    }
+}
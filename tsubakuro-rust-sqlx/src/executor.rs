@@ -0,0 +1,321 @@
+use std::{borrow::Cow, sync::Arc};
+
+use futures_core::{future::BoxFuture, stream::BoxStream};
+use futures_util::TryStreamExt;
+use sqlx_core::{
+    describe::Describe,
+    error::Error,
+    executor::{Execute, Executor},
+    ext::async_stream::{TryAsyncStream, Yielder},
+    logger::QueryLogger,
+    Either,
+};
+use tsubakuro_rust_core::prelude::*;
+
+use crate::{
+    arguments::rewrite_placeholders, error::to_sqlx_error, value::TsurugiData, Tsurugi,
+    TsurugiArguments, TsurugiColumn, TsurugiConnection, TsurugiQueryResult, TsurugiRow,
+    TsurugiStatement, TsurugiTypeInfo, TsurugiValue,
+};
+
+type QueryStep = Either<TsurugiQueryResult, TsurugiRow>;
+
+/// The number of rows fetched at once.
+const ROW_BATCH_SIZE: usize = 64;
+
+impl TsurugiConnection {
+    /// Executes the statement, and yields the rows (and the result).
+    ///
+    /// Outside of a sqlx transaction, the statement is executed in its own transaction
+    /// and committed after all rows are yielded.
+    async fn run(
+        &mut self,
+        sql: &str,
+        arguments: Option<TsurugiArguments>,
+        yielder: &Yielder<QueryStep>,
+    ) -> Result<(), Error> {
+        let mut logger = QueryLogger::new(sql, self.log_settings.clone());
+
+        let (sql, count) = rewrite_placeholders(sql).map_err(Error::InvalidArgument)?;
+        let arguments = arguments.unwrap_or_default();
+        if sqlx_core::arguments::Arguments::len(&arguments) != count {
+            return Err(Error::InvalidArgument(format!(
+                "parameter count mismatch. markers={}, arguments={}",
+                count,
+                sqlx_core::arguments::Arguments::len(&arguments)
+            )));
+        }
+
+        let prepared_statement = self
+            .client
+            .prepare(&sql, arguments.placeholders())
+            .await
+            .map_err(to_sqlx_error)?;
+        let result = self
+            .run_prepared(
+                &prepared_statement,
+                arguments.parameters(),
+                &mut logger,
+                yielder,
+            )
+            .await;
+        let close_result = prepared_statement.close().await;
+        result?;
+        close_result.map_err(to_sqlx_error)?;
+
+        logger.finish();
+        Ok(())
+    }
+
+    async fn run_prepared(
+        &mut self,
+        prepared_statement: &SqlPreparedStatement,
+        parameters: Vec<SqlParameter>,
+        logger: &mut QueryLogger<'_>,
+        yielder: &Yielder<QueryStep>,
+    ) -> Result<(), Error> {
+        if let Some(transaction) = &self.transaction {
+            return execute(
+                &self.client,
+                transaction,
+                prepared_statement,
+                parameters,
+                logger,
+                yielder,
+            )
+            .await;
+        }
+
+        // auto commit
+        let transaction_option = self.transaction_option().clone();
+        let transaction = self
+            .client
+            .start_transaction(&transaction_option)
+            .await
+            .map_err(to_sqlx_error)?;
+        let result = match execute(
+            &self.client,
+            &transaction,
+            prepared_statement,
+            parameters,
+            logger,
+            yielder,
+        )
+        .await
+        {
+            Ok(_) => {
+                let commit_option = CommitOption::new();
+                self.client
+                    .commit(&transaction, &commit_option)
+                    .await
+                    .map_err(to_sqlx_error)
+            }
+            Err(e) => {
+                let _ = self.client.rollback(&transaction).await;
+                Err(e)
+            }
+        };
+        let close_result = transaction.close().await;
+        result?;
+        close_result.map_err(to_sqlx_error)?;
+        Ok(())
+    }
+
+    async fn prepare_statement<'q>(
+        &mut self,
+        sql: &'q str,
+        parameters: &[TsurugiTypeInfo],
+    ) -> Result<TsurugiStatement<'q>, Error> {
+        let (rewritten, count) = rewrite_placeholders(sql).map_err(Error::InvalidArgument)?;
+        let placeholders: Vec<SqlPlaceholder> = (0..count)
+            .map(|i| {
+                let atom_type = parameters
+                    .get(i)
+                    .map(|type_info| type_info.atom_type())
+                    .unwrap_or(AtomType::Unknown);
+                SqlPlaceholder::of_atom_type(&positional_placeholder_name(i + 1), atom_type)
+            })
+            .collect();
+
+        let prepared_statement = self
+            .client
+            .prepare(&rewritten, placeholders)
+            .await
+            .map_err(to_sqlx_error)?;
+        let result = if prepared_statement.has_result_records() {
+            let parameters = (0..count)
+                .map(|i| SqlParameter::null(&positional_placeholder_name(i + 1)))
+                .collect();
+            self.client
+                .prepared_explain(&prepared_statement, parameters)
+                .await
+                .map(|explain_result| to_columns(explain_result.columns()))
+        } else {
+            Ok(Arc::from([]))
+        };
+        let close_result = prepared_statement.close().await;
+        let columns = result.map_err(to_sqlx_error)?;
+        close_result.map_err(to_sqlx_error)?;
+
+        Ok(TsurugiStatement {
+            sql: Cow::Borrowed(sql),
+            parameters: parameters.iter().take(count).copied().collect(),
+            parameter_count: count,
+            columns,
+        })
+    }
+}
+
+async fn execute(
+    client: &SqlClient,
+    transaction: &Transaction,
+    prepared_statement: &SqlPreparedStatement,
+    parameters: Vec<SqlParameter>,
+    logger: &mut QueryLogger<'_>,
+    yielder: &Yielder<QueryStep>,
+) -> Result<(), Error> {
+    if !prepared_statement.has_result_records() {
+        let execute_result = client
+            .prepared_execute(transaction, prepared_statement, parameters)
+            .await
+            .map_err(to_sqlx_error)?;
+        let result = TsurugiQueryResult::from(&execute_result);
+        logger.increase_rows_affected(result.rows_affected());
+        yielder.r#yield(Either::Left(result)).await;
+        return Ok(());
+    }
+
+    let mut query_result = client
+        .prepared_query(transaction, prepared_statement, parameters)
+        .await
+        .map_err(to_sqlx_error)?;
+    let result = yield_rows(&mut query_result, logger, yielder).await;
+    let close_result = query_result.close().await;
+    result?;
+    close_result.map_err(to_sqlx_error)?;
+
+    yielder
+        .r#yield(Either::Left(TsurugiQueryResult::default()))
+        .await;
+    Ok(())
+}
+
+/// Yields all rows of the query result.
+///
+/// The rows are fetched in batches with [`SqlQueryResult::fetch_batch`],
+/// whose future is `Send` (unlike [`SqlQueryResultFetch`]),
+/// so that they are fetched on the runtime which polls the stream.
+async fn yield_rows(
+    query_result: &mut SqlQueryResult,
+    logger: &mut QueryLogger<'_>,
+    yielder: &Yielder<QueryStep>,
+) -> Result<(), Error> {
+    let columns = query_result
+        .get_metadata()
+        .map(|metadata| to_columns(metadata.columns()))
+        .unwrap_or_else(|| Arc::from([]));
+
+    loop {
+        let batch = query_result
+            .fetch_batch(ROW_BATCH_SIZE)
+            .await
+            .map_err(to_sqlx_error)?;
+        if batch.is_empty() {
+            return Ok(());
+        }
+
+        let row_count = batch.row_count();
+        let mut column_values = Vec::with_capacity(columns.len());
+        for column in batch.into_columns() {
+            let values = TsurugiData::from_column_values(column.into_values())?;
+            column_values.push(values.into_iter());
+        }
+        for _ in 0..row_count {
+            let values = column_values
+                .iter_mut()
+                .zip(columns.iter())
+                .map(|(values, column)| {
+                    let type_info = *sqlx_core::column::Column::type_info(column);
+                    let data = values.next().unwrap_or(TsurugiData::Null);
+                    TsurugiValue::new(data, type_info)
+                })
+                .collect();
+            let row = TsurugiRow::new(values, columns.clone());
+            logger.increment_rows_returned();
+            yielder.r#yield(Either::Right(row)).await;
+        }
+    }
+}
+
+fn to_columns(columns: &[SqlColumn]) -> Arc<[TsurugiColumn]> {
+    columns
+        .iter()
+        .enumerate()
+        .map(|(i, column)| TsurugiColumn::new(i, column))
+        .collect()
+}
+
+/// Executes statements.
+///
+/// The rows of a query are streamed as they are fetched.
+impl<'c> Executor<'c> for &'c mut TsurugiConnection {
+    type Database = Tsurugi;
+
+    fn fetch_many<'e, 'q: 'e, E>(self, mut query: E) -> BoxStream<'e, Result<QueryStep, Error>>
+    where
+        'c: 'e,
+        E: 'q + Execute<'q, Self::Database>,
+    {
+        let sql = query.sql();
+        let arguments = query.take_arguments().map_err(Error::Encode);
+        Box::pin(TryAsyncStream::new(move |yielder| async move {
+            self.run(sql, arguments?, &yielder).await
+        }))
+    }
+
+    fn fetch_optional<'e, 'q: 'e, E>(
+        self,
+        query: E,
+    ) -> BoxFuture<'e, Result<Option<TsurugiRow>, Error>>
+    where
+        'c: 'e,
+        E: 'q + Execute<'q, Self::Database>,
+    {
+        Box::pin(async move {
+            // consume all steps, so that the statement is completed (and committed)
+            let mut steps = self.fetch_many(query);
+            let mut first_row = None;
+            while let Some(step) = steps.try_next().await? {
+                if let Either::Right(row) = step {
+                    first_row.get_or_insert(row);
+                }
+            }
+            Ok(first_row)
+        })
+    }
+
+    fn prepare_with<'e, 'q: 'e>(
+        self,
+        sql: &'q str,
+        parameters: &'e [TsurugiTypeInfo],
+    ) -> BoxFuture<'e, Result<TsurugiStatement<'q>, Error>>
+    where
+        'c: 'e,
+    {
+        Box::pin(async move { self.prepare_statement(sql, parameters).await })
+    }
+
+    fn describe<'e, 'q: 'e>(self, sql: &'q str) -> BoxFuture<'e, Result<Describe<Tsurugi>, Error>>
+    where
+        'c: 'e,
+    {
+        Box::pin(async move {
+            let statement = self.prepare_statement(sql, &[]).await?;
+            Ok(Describe {
+                nullable: statement.columns.iter().map(|c| c.nullable()).collect(),
+                columns: statement.columns.to_vec(),
+                parameters: Some(Either::Right(statement.parameter_count)),
+            })
+        })
+    }
+}
@@ -0,0 +1,86 @@
+//! [tsubakuro-rust-sqlx](https://github.com/project-tsurugi/tsubakuro-rust/tree/master/tsubakuro-rust-sqlx)
+//! is the [sqlx](https://crates.io/crates/sqlx) driver for [Tsurugi](https://github.com/project-tsurugi/tsurugidb),
+//! built on [tsubakuro-rust-core](https://crates.io/crates/tsubakuro-rust-core).
+//!
+//! # Overview
+//! 1. Connect with a url `tsurugi://host:port` (or `tcp://host:port`).
+//!    - The url is the same as [`ConnectionOption::from_url()`](tsubakuro_rust_core::prelude::ConnectionOption::from_url)
+//!      except for the scheme.
+//! 2. Execute SQL with sqlx functions such as `sqlx::query()`.
+//!    - Positional parameters are written as `?` or `$1`, `$2`, ...
+//!    - Outside of a sqlx transaction, each statement is executed in its own transaction and committed.
+//! 3. `Connection::begin()` starts a Tsurugi transaction with the transaction option of the connection.
+//!    - Use [`TsurugiConnection::begin_with_option()`] to specify the transaction option per transaction.
+//!    - Nested transactions (savepoints) are not supported.
+//!
+//! # Examples
+//! ```
+//! use sqlx_core::{connection::Connection, executor::Executor, query::query, row::Row};
+//! use tsubakuro_rust_sqlx::{TsurugiConnection, Tsurugi};
+//!
+//! async fn example() -> Result<(), sqlx_core::Error> {
+//!     let mut connection = TsurugiConnection::connect("tsurugi://localhost:12345").await?;
+//!
+//!     let mut transaction = connection.begin().await?;
+//!     query::<Tsurugi>("insert into customer values(?, ?, ?)")
+//!         .bind(4_i64)
+//!         .bind("example")
+//!         .bind(Some(20))
+//!         .execute(&mut *transaction)
+//!         .await?;
+//!     transaction.commit().await?;
+//!
+//!     let rows = query::<Tsurugi>("select c_id, c_name from customer where c_age >= $1")
+//!         .bind(20)
+//!         .fetch_all(&mut connection)
+//!         .await?;
+//!     for row in rows {
+//!         let id: i64 = row.try_get("c_id")?;
+//!         let name: Option<String> = row.try_get(1)?;
+//!         println!("{id}: {name:?}");
+//!     }
+//!
+//!     connection.close().await?;
+//!     Ok(())
+//! }
+//! ```
+//!
+//! since 0.11.0
+
+mod arguments;
+mod column;
+mod connection;
+mod database;
+mod error;
+mod executor;
+mod options;
+mod query_result;
+mod row;
+mod statement;
+mod transaction;
+mod type_info;
+mod types;
+mod value;
+
+pub use arguments::TsurugiArguments;
+pub use column::TsurugiColumn;
+pub use connection::TsurugiConnection;
+pub use database::Tsurugi;
+pub use error::TsurugiDatabaseError;
+pub use options::TsurugiConnectOptions;
+pub use query_result::TsurugiQueryResult;
+pub use row::TsurugiRow;
+pub use statement::TsurugiStatement;
+pub use transaction::TsurugiTransactionManager;
+pub use type_info::TsurugiTypeInfo;
+pub use value::{TsurugiData, TsurugiValue, TsurugiValueRef};
+
+/// An alias for [`Pool`](sqlx_core::pool::Pool), specialized for Tsurugi.
+///
+/// since 0.11.0
+pub type TsurugiPool = sqlx_core::pool::Pool<Tsurugi>;
+
+/// An alias for [`Transaction`](sqlx_core::transaction::Transaction), specialized for Tsurugi.
+///
+/// since 0.11.0
+pub type TsurugiTransaction<'c> = sqlx_core::transaction::Transaction<'c, Tsurugi>;
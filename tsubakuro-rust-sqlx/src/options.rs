@@ -0,0 +1,151 @@
+use std::{str::FromStr, time::Duration};
+
+use futures_core::future::BoxFuture;
+use log::LevelFilter;
+use sqlx_core::{
+    connection::{ConnectOptions, LogSettings},
+    error::Error,
+    Url,
+};
+use tsubakuro_rust_core::prelude::*;
+
+use crate::{error::to_sqlx_error, TsurugiConnection};
+
+/// Options to connect to Tsurugi.
+///
+/// The url is `tsurugi://[user[:password]@]host:port[?key=value&...]`.
+/// Except for the scheme, it is the same as [`ConnectionOption::from_url`].
+///
+/// # Examples
+/// ```
+/// use sqlx_core::connection::ConnectOptions;
+/// use tsubakuro_rust_core::prelude::*;
+/// use tsubakuro_rust_sqlx::TsurugiConnectOptions;
+///
+/// async fn example() -> Result<(), sqlx_core::Error> {
+///     let options: TsurugiConnectOptions = "tsurugi://localhost:12345?default_timeout=30s".parse()?;
+///     let options = options.transaction_option(TransactionOption::from(TransactionType::Long));
+///
+///     let connection = options.connect().await?;
+///     Ok(())
+/// }
+/// ```
+///
+/// since 0.11.0
+#[derive(Debug, Clone)]
+pub struct TsurugiConnectOptions {
+    connection_option: ConnectionOption,
+    transaction_option: TransactionOption,
+    pub(crate) log_settings: LogSettings,
+}
+
+impl TsurugiConnectOptions {
+    /// Creates a new instance.
+    ///
+    /// since 0.11.0
+    pub fn new(connection_option: ConnectionOption) -> TsurugiConnectOptions {
+        TsurugiConnectOptions {
+            connection_option,
+            transaction_option: TransactionOption::default(),
+            log_settings: LogSettings::default(),
+        }
+    }
+
+    /// Get connection option.
+    ///
+    /// since 0.11.0
+    pub fn get_connection_option(&self) -> &ConnectionOption {
+        &self.connection_option
+    }
+
+    /// Set the default transaction option of the connections.
+    ///
+    /// The default is OCC (short transaction).
+    ///
+    /// since 0.11.0
+    pub fn transaction_option(mut self, transaction_option: TransactionOption) -> Self {
+        self.transaction_option = transaction_option;
+        self
+    }
+
+    /// Get the default transaction option of the connections.
+    ///
+    /// since 0.11.0
+    pub fn get_transaction_option(&self) -> &TransactionOption {
+        &self.transaction_option
+    }
+}
+
+impl FromStr for TsurugiConnectOptions {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let url = Url::parse(s).map_err(|e| Error::Configuration(Box::new(e)))?;
+        Self::from_url(&url)
+    }
+}
+
+impl ConnectOptions for TsurugiConnectOptions {
+    type Connection = TsurugiConnection;
+
+    fn from_url(url: &Url) -> Result<Self, Error> {
+        let url = match url.scheme() {
+            "tsurugi" => format!("tcp{}", &url.as_str()["tsurugi".len()..]),
+            _ => url.to_string(),
+        };
+        let connection_option =
+            ConnectionOption::from_url(&url).map_err(|e| Error::Configuration(Box::new(e)))?;
+        Ok(TsurugiConnectOptions::new(connection_option))
+    }
+
+    fn connect(&self) -> BoxFuture<'_, Result<TsurugiConnection, Error>> {
+        Box::pin(async move {
+            let session = Session::connect(&self.connection_option)
+                .await
+                .map_err(to_sqlx_error)?;
+            Ok(TsurugiConnection::new(session, self))
+        })
+    }
+
+    fn log_statements(mut self, level: LevelFilter) -> Self {
+        self.log_settings.log_statements(level);
+        self
+    }
+
+    fn log_slow_statements(mut self, level: LevelFilter, duration: Duration) -> Self {
+        self.log_settings.log_slow_statements(level, duration);
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn from_str() {
+        let options: TsurugiConnectOptions = "tsurugi://localhost:12345?application_name=example"
+            .parse()
+            .unwrap();
+        let connection_option = options.get_connection_option();
+        assert_eq!(
+            "tcp://localhost:12345",
+            connection_option.endpoint().unwrap().to_string()
+        );
+        assert_eq!(
+            Some(&"example".to_string()),
+            connection_option.application_name()
+        );
+        assert_eq!(
+            TransactionType::Short,
+            options.get_transaction_option().transaction_type()
+        );
+
+        let options: TsurugiConnectOptions = "tcp://localhost:12345".parse().unwrap();
+        assert!(options.get_connection_option().endpoint().is_some());
+
+        assert!("postgres://localhost:5432"
+            .parse::<TsurugiConnectOptions>()
+            .is_err());
+    }
+}
@@ -0,0 +1,34 @@
+use tsubakuro_rust_core::prelude::SqlExecuteResult;
+
+/// Result of a statement execution.
+///
+/// since 0.11.0
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TsurugiQueryResult {
+    rows_affected: u64,
+}
+
+impl TsurugiQueryResult {
+    /// Get the number of rows inserted, updated, merged or deleted.
+    ///
+    /// since 0.11.0
+    pub fn rows_affected(&self) -> u64 {
+        self.rows_affected
+    }
+}
+
+impl From<&SqlExecuteResult> for TsurugiQueryResult {
+    fn from(value: &SqlExecuteResult) -> Self {
+        TsurugiQueryResult {
+            rows_affected: value.rows().max(0) as u64,
+        }
+    }
+}
+
+impl Extend<TsurugiQueryResult> for TsurugiQueryResult {
+    fn extend<T: IntoIterator<Item = TsurugiQueryResult>>(&mut self, iter: T) {
+        for result in iter {
+            self.rows_affected += result.rows_affected;
+        }
+    }
+}
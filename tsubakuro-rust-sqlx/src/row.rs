@@ -0,0 +1,46 @@
+use std::sync::Arc;
+
+use sqlx_core::{
+    column::ColumnIndex, error::Error, impl_column_index_for_row, row::Row, value::Value,
+};
+
+use crate::{column::find_column, Tsurugi, TsurugiColumn, TsurugiValue, TsurugiValueRef};
+
+/// Row of a query result.
+///
+/// since 0.11.0
+#[derive(Debug, Clone)]
+pub struct TsurugiRow {
+    values: Vec<TsurugiValue>,
+    columns: Arc<[TsurugiColumn]>,
+}
+
+impl TsurugiRow {
+    pub(crate) fn new(values: Vec<TsurugiValue>, columns: Arc<[TsurugiColumn]>) -> TsurugiRow {
+        TsurugiRow { values, columns }
+    }
+}
+
+impl Row for TsurugiRow {
+    type Database = Tsurugi;
+
+    fn columns(&self) -> &[TsurugiColumn] {
+        &self.columns
+    }
+
+    fn try_get_raw<I>(&self, index: I) -> Result<TsurugiValueRef<'_>, Error>
+    where
+        I: ColumnIndex<Self>,
+    {
+        let index = index.index(self)?;
+        Ok(self.values[index].as_ref())
+    }
+}
+
+impl_column_index_for_row!(TsurugiRow);
+
+impl ColumnIndex<TsurugiRow> for &'_ str {
+    fn index(&self, row: &TsurugiRow) -> Result<usize, Error> {
+        find_column(&row.columns, self).ok_or_else(|| Error::ColumnNotFound(self.to_string()))
+    }
+}
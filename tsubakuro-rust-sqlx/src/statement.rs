@@ -0,0 +1,60 @@
+use std::{borrow::Cow, sync::Arc};
+
+use sqlx_core::{
+    column::ColumnIndex, error::Error, impl_column_index_for_statement, impl_statement_query,
+    statement::Statement, Either,
+};
+
+use crate::{column::find_column, Tsurugi, TsurugiArguments, TsurugiColumn, TsurugiTypeInfo};
+
+/// Statement prepared by [`Executor::prepare`](sqlx_core::executor::Executor::prepare).
+///
+/// The statement is not kept on the server; it is prepared again when executed.
+///
+/// since 0.11.0
+#[derive(Debug, Clone)]
+pub struct TsurugiStatement<'q> {
+    pub(crate) sql: Cow<'q, str>,
+    pub(crate) parameters: Vec<TsurugiTypeInfo>,
+    pub(crate) parameter_count: usize,
+    pub(crate) columns: Arc<[TsurugiColumn]>,
+}
+
+impl<'q> Statement<'q> for TsurugiStatement<'q> {
+    type Database = Tsurugi;
+
+    fn to_owned(&self) -> TsurugiStatement<'static> {
+        TsurugiStatement {
+            sql: Cow::Owned(self.sql.to_string()),
+            parameters: self.parameters.clone(),
+            parameter_count: self.parameter_count,
+            columns: self.columns.clone(),
+        }
+    }
+
+    fn sql(&self) -> &str {
+        &self.sql
+    }
+
+    fn parameters(&self) -> Option<Either<&[TsurugiTypeInfo], usize>> {
+        if self.parameters.len() == self.parameter_count {
+            Some(Either::Left(&self.parameters))
+        } else {
+            Some(Either::Right(self.parameter_count))
+        }
+    }
+
+    fn columns(&self) -> &[TsurugiColumn] {
+        &self.columns
+    }
+
+    impl_statement_query!(TsurugiArguments);
+}
+
+impl_column_index_for_statement!(TsurugiStatement);
+
+impl ColumnIndex<TsurugiStatement<'_>> for &'_ str {
+    fn index(&self, statement: &TsurugiStatement<'_>) -> Result<usize, Error> {
+        find_column(&statement.columns, self).ok_or_else(|| Error::ColumnNotFound(self.to_string()))
+    }
+}
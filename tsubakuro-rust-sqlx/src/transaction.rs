@@ -0,0 +1,83 @@
+use std::borrow::Cow;
+
+use futures_core::future::BoxFuture;
+use sqlx_core::{error::Error, transaction::TransactionManager};
+use tsubakuro_rust_core::prelude::CommitOption;
+
+use crate::{error::to_sqlx_error, Tsurugi, TsurugiConnection};
+
+/// Transaction manager of Tsurugi.
+///
+/// A sqlx transaction is mapped to a Tsurugi transaction started with
+/// [`TsurugiConnection::transaction_option`] (or the option given to [`TsurugiConnection::begin_with_option`]).
+/// Nested transactions and custom `BEGIN` statements are not supported.
+///
+/// since 0.11.0
+pub struct TsurugiTransactionManager;
+
+impl TransactionManager for TsurugiTransactionManager {
+    type Database = Tsurugi;
+
+    fn begin<'conn>(
+        conn: &'conn mut TsurugiConnection,
+        statement: Option<Cow<'static, str>>,
+    ) -> BoxFuture<'conn, Result<(), Error>> {
+        Box::pin(async move {
+            let transaction_option = conn.take_transaction_option();
+            if let Some(statement) = statement {
+                return Err(Error::InvalidArgument(format!(
+                    "BEGIN statement is not supported. use TsurugiConnection::begin_with_option(). statement={statement}"
+                )));
+            }
+            if conn.transaction.is_some() {
+                return Err(Error::InvalidArgument(
+                    "nested transaction is not supported".to_string(),
+                ));
+            }
+
+            let transaction = conn
+                .client
+                .start_transaction(&transaction_option)
+                .await
+                .map_err(to_sqlx_error)?;
+            conn.transaction = Some(transaction);
+            Ok(())
+        })
+    }
+
+    fn commit(conn: &mut TsurugiConnection) -> BoxFuture<'_, Result<(), Error>> {
+        Box::pin(async move {
+            let Some(transaction) = conn.transaction.take() else {
+                return Ok(());
+            };
+
+            let commit_option = CommitOption::new();
+            let result = conn.client.commit(&transaction, &commit_option).await;
+            let close_result = transaction.close().await;
+            result.map_err(to_sqlx_error)?;
+            close_result.map_err(to_sqlx_error)
+        })
+    }
+
+    fn rollback(conn: &mut TsurugiConnection) -> BoxFuture<'_, Result<(), Error>> {
+        Box::pin(async move {
+            let Some(transaction) = conn.transaction.take() else {
+                return Ok(());
+            };
+
+            let result = conn.client.rollback(&transaction).await;
+            let close_result = transaction.close().await;
+            result.map_err(to_sqlx_error)?;
+            close_result.map_err(to_sqlx_error)
+        })
+    }
+
+    fn start_rollback(conn: &mut TsurugiConnection) {
+        // Transaction::drop() disposes the transaction (and the server rolls it back)
+        drop(conn.transaction.take());
+    }
+
+    fn get_transaction_depth(conn: &TsurugiConnection) -> usize {
+        usize::from(conn.transaction.is_some())
+    }
+}
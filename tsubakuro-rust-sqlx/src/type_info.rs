@@ -0,0 +1,67 @@
+use std::fmt::Display;
+
+use sqlx_core::type_info::TypeInfo;
+use tsubakuro_rust_core::prelude::AtomType;
+
+/// Type information of Tsurugi.
+///
+/// since 0.11.0
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TsurugiTypeInfo(AtomType);
+
+impl TsurugiTypeInfo {
+    /// Creates a new instance.
+    ///
+    /// since 0.11.0
+    pub fn new(atom_type: AtomType) -> TsurugiTypeInfo {
+        TsurugiTypeInfo(atom_type)
+    }
+
+    /// Get AtomType.
+    ///
+    /// since 0.11.0
+    pub fn atom_type(&self) -> AtomType {
+        self.0
+    }
+}
+
+impl From<AtomType> for TsurugiTypeInfo {
+    fn from(value: AtomType) -> Self {
+        TsurugiTypeInfo::new(value)
+    }
+}
+
+impl TypeInfo for TsurugiTypeInfo {
+    fn is_null(&self) -> bool {
+        matches!(self.0, AtomType::Unknown | AtomType::TypeUnspecified)
+    }
+
+    fn name(&self) -> &str {
+        match self.0 {
+            AtomType::Boolean => "BOOLEAN",
+            AtomType::Int4 => "INT",
+            AtomType::Int8 => "BIGINT",
+            AtomType::Float4 => "REAL",
+            AtomType::Float8 => "DOUBLE",
+            AtomType::Decimal => "DECIMAL",
+            AtomType::Character => "VARCHAR",
+            AtomType::Octet => "VARBINARY",
+            AtomType::Bit => "BIT",
+            AtomType::Date => "DATE",
+            AtomType::TimeOfDay => "TIME",
+            AtomType::TimePoint => "TIMESTAMP",
+            AtomType::DatetimeInterval => "INTERVAL",
+            AtomType::TimeOfDayWithTimeZone => "TIME WITH TIME ZONE",
+            AtomType::TimePointWithTimeZone => "TIMESTAMP WITH TIME ZONE",
+            AtomType::Clob => "CLOB",
+            AtomType::Blob => "BLOB",
+            AtomType::Unknown | AtomType::TypeUnspecified => "NULL",
+        }
+    }
+}
+
+impl Display for TsurugiTypeInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(self.name())
+    }
+}
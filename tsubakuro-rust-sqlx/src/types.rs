@@ -0,0 +1,342 @@
+//! Mapping between Rust types and Tsurugi types.
+//!
+//! | Rust type                            | Tsurugi type               |
+//! |--------------------------------------|----------------------------|
+//! | `bool`                               | `BOOLEAN`                  |
+//! | `i32`                                | `INT`                      |
+//! | `i64`                                | `BIGINT` (or `INT`)        |
+//! | `f32`                                | `REAL`                     |
+//! | `f64`                                | `DOUBLE` (or `REAL`)       |
+//! | `BigDecimal`                         | `DECIMAL`                  |
+//! | `String`, `&str`                     | `CHAR`, `VARCHAR`          |
+//! | `Vec<u8>`, `&[u8]`                   | `BINARY`, `VARBINARY`      |
+//! | `NaiveDate`                          | `DATE`                     |
+//! | `NaiveTime`                          | `TIME`                     |
+//! | `NaiveDateTime`                      | `TIMESTAMP`                |
+//! | `(NaiveTime, FixedOffset)`           | `TIME WITH TIME ZONE`      |
+//! | `DateTime<FixedOffset>`, `DateTime<Utc>` | `TIMESTAMP WITH TIME ZONE` |
+
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use sqlx_core::{
+    decode::Decode,
+    encode::{Encode, IsNull},
+    error::BoxDynError,
+    impl_encode_for_option,
+    types::Type,
+};
+use tsubakuro_rust_core::prelude::AtomType;
+
+use crate::{value::TsurugiData, Tsurugi, TsurugiTypeInfo, TsurugiValueRef};
+
+impl_encode_for_option!(Tsurugi);
+
+fn decode_error(value: &TsurugiValueRef<'_>, type_name: &str) -> BoxDynError {
+    format!(
+        "cannot decode {} value as {}",
+        value.type_info.atom_type().as_str_name(),
+        type_name
+    )
+    .into()
+}
+
+/// Implements `Type`, `Encode` and `Decode` for a type which is mapped to a variant of `TsurugiData`.
+///
+/// `$compatible` lists the types that the value can be decoded from.
+macro_rules! impl_type {
+    ($t:ty, $atom_type:expr, [$($compatible:path),*], $variant:ident) => {
+        impl Type<Tsurugi> for $t {
+            fn type_info() -> TsurugiTypeInfo {
+                TsurugiTypeInfo::new($atom_type)
+            }
+
+            fn compatible(ty: &TsurugiTypeInfo) -> bool {
+                matches!(ty.atom_type(), $($compatible)|*)
+            }
+        }
+
+        impl Encode<'_, Tsurugi> for $t {
+            fn encode_by_ref(&self, buf: &mut Vec<TsurugiData>) -> Result<IsNull, BoxDynError> {
+                buf.push(TsurugiData::$variant(self.clone()));
+                Ok(IsNull::No)
+            }
+        }
+    };
+}
+
+impl_type!(bool, AtomType::Boolean, [AtomType::Boolean], Boolean);
+impl_type!(i32, AtomType::Int4, [AtomType::Int4], Int4);
+impl_type!(i64, AtomType::Int8, [AtomType::Int4, AtomType::Int8], Int8);
+impl_type!(f32, AtomType::Float4, [AtomType::Float4], Float4);
+impl_type!(
+    f64,
+    AtomType::Float8,
+    [AtomType::Float4, AtomType::Float8],
+    Float8
+);
+impl_type!(BigDecimal, AtomType::Decimal, [AtomType::Decimal], Decimal);
+impl_type!(
+    String,
+    AtomType::Character,
+    [AtomType::Character],
+    Character
+);
+impl_type!(Vec<u8>, AtomType::Octet, [AtomType::Octet], Octet);
+impl_type!(NaiveDate, AtomType::Date, [AtomType::Date], Date);
+impl_type!(
+    NaiveTime,
+    AtomType::TimeOfDay,
+    [AtomType::TimeOfDay],
+    TimeOfDay
+);
+impl_type!(
+    NaiveDateTime,
+    AtomType::TimePoint,
+    [AtomType::TimePoint],
+    TimePoint
+);
+impl_type!(
+    DateTime<FixedOffset>,
+    AtomType::TimePointWithTimeZone,
+    [AtomType::TimePointWithTimeZone],
+    TimePointWithTimeZone
+);
+
+impl Decode<'_, Tsurugi> for bool {
+    fn decode(value: TsurugiValueRef<'_>) -> Result<Self, BoxDynError> {
+        match value.data {
+            TsurugiData::Boolean(v) => Ok(*v),
+            _ => Err(decode_error(&value, "bool")),
+        }
+    }
+}
+
+impl Decode<'_, Tsurugi> for i32 {
+    fn decode(value: TsurugiValueRef<'_>) -> Result<Self, BoxDynError> {
+        match value.data {
+            TsurugiData::Int4(v) => Ok(*v),
+            _ => Err(decode_error(&value, "i32")),
+        }
+    }
+}
+
+impl Decode<'_, Tsurugi> for i64 {
+    fn decode(value: TsurugiValueRef<'_>) -> Result<Self, BoxDynError> {
+        match value.data {
+            TsurugiData::Int4(v) => Ok(*v as i64),
+            TsurugiData::Int8(v) => Ok(*v),
+            _ => Err(decode_error(&value, "i64")),
+        }
+    }
+}
+
+impl Decode<'_, Tsurugi> for f32 {
+    fn decode(value: TsurugiValueRef<'_>) -> Result<Self, BoxDynError> {
+        match value.data {
+            TsurugiData::Float4(v) => Ok(*v),
+            _ => Err(decode_error(&value, "f32")),
+        }
+    }
+}
+
+impl Decode<'_, Tsurugi> for f64 {
+    fn decode(value: TsurugiValueRef<'_>) -> Result<Self, BoxDynError> {
+        match value.data {
+            TsurugiData::Float4(v) => Ok(*v as f64),
+            TsurugiData::Float8(v) => Ok(*v),
+            _ => Err(decode_error(&value, "f64")),
+        }
+    }
+}
+
+impl Decode<'_, Tsurugi> for BigDecimal {
+    fn decode(value: TsurugiValueRef<'_>) -> Result<Self, BoxDynError> {
+        match value.data {
+            TsurugiData::Decimal(v) => Ok(v.clone()),
+            _ => Err(decode_error(&value, "BigDecimal")),
+        }
+    }
+}
+
+impl Decode<'_, Tsurugi> for String {
+    fn decode(value: TsurugiValueRef<'_>) -> Result<Self, BoxDynError> {
+        <&str as Decode<Tsurugi>>::decode(value).map(String::from)
+    }
+}
+
+impl Type<Tsurugi> for str {
+    fn type_info() -> TsurugiTypeInfo {
+        TsurugiTypeInfo::new(AtomType::Character)
+    }
+}
+
+impl Encode<'_, Tsurugi> for &'_ str {
+    fn encode_by_ref(&self, buf: &mut Vec<TsurugiData>) -> Result<IsNull, BoxDynError> {
+        buf.push(TsurugiData::Character(self.to_string()));
+        Ok(IsNull::No)
+    }
+}
+
+impl<'r> Decode<'r, Tsurugi> for &'r str {
+    fn decode(value: TsurugiValueRef<'r>) -> Result<Self, BoxDynError> {
+        match value.data {
+            TsurugiData::Character(v) => Ok(v),
+            _ => Err(decode_error(&value, "str")),
+        }
+    }
+}
+
+impl Decode<'_, Tsurugi> for Vec<u8> {
+    fn decode(value: TsurugiValueRef<'_>) -> Result<Self, BoxDynError> {
+        <&[u8] as Decode<Tsurugi>>::decode(value).map(<[u8]>::to_vec)
+    }
+}
+
+impl Type<Tsurugi> for [u8] {
+    fn type_info() -> TsurugiTypeInfo {
+        TsurugiTypeInfo::new(AtomType::Octet)
+    }
+}
+
+impl Encode<'_, Tsurugi> for &'_ [u8] {
+    fn encode_by_ref(&self, buf: &mut Vec<TsurugiData>) -> Result<IsNull, BoxDynError> {
+        buf.push(TsurugiData::Octet(self.to_vec()));
+        Ok(IsNull::No)
+    }
+}
+
+impl<'r> Decode<'r, Tsurugi> for &'r [u8] {
+    fn decode(value: TsurugiValueRef<'r>) -> Result<Self, BoxDynError> {
+        match value.data {
+            TsurugiData::Octet(v) => Ok(v),
+            _ => Err(decode_error(&value, "[u8]")),
+        }
+    }
+}
+
+impl Decode<'_, Tsurugi> for NaiveDate {
+    fn decode(value: TsurugiValueRef<'_>) -> Result<Self, BoxDynError> {
+        match value.data {
+            TsurugiData::Date(v) => Ok(*v),
+            _ => Err(decode_error(&value, "NaiveDate")),
+        }
+    }
+}
+
+impl Decode<'_, Tsurugi> for NaiveTime {
+    fn decode(value: TsurugiValueRef<'_>) -> Result<Self, BoxDynError> {
+        match value.data {
+            TsurugiData::TimeOfDay(v) => Ok(*v),
+            _ => Err(decode_error(&value, "NaiveTime")),
+        }
+    }
+}
+
+impl Decode<'_, Tsurugi> for NaiveDateTime {
+    fn decode(value: TsurugiValueRef<'_>) -> Result<Self, BoxDynError> {
+        match value.data {
+            TsurugiData::TimePoint(v) => Ok(*v),
+            _ => Err(decode_error(&value, "NaiveDateTime")),
+        }
+    }
+}
+
+impl Type<Tsurugi> for (NaiveTime, FixedOffset) {
+    fn type_info() -> TsurugiTypeInfo {
+        TsurugiTypeInfo::new(AtomType::TimeOfDayWithTimeZone)
+    }
+}
+
+impl Encode<'_, Tsurugi> for (NaiveTime, FixedOffset) {
+    fn encode_by_ref(&self, buf: &mut Vec<TsurugiData>) -> Result<IsNull, BoxDynError> {
+        buf.push(TsurugiData::TimeOfDayWithTimeZone(self.0, self.1));
+        Ok(IsNull::No)
+    }
+}
+
+impl Decode<'_, Tsurugi> for (NaiveTime, FixedOffset) {
+    fn decode(value: TsurugiValueRef<'_>) -> Result<Self, BoxDynError> {
+        match value.data {
+            TsurugiData::TimeOfDayWithTimeZone(time, offset) => Ok((*time, *offset)),
+            _ => Err(decode_error(&value, "(NaiveTime, FixedOffset)")),
+        }
+    }
+}
+
+impl Decode<'_, Tsurugi> for DateTime<FixedOffset> {
+    fn decode(value: TsurugiValueRef<'_>) -> Result<Self, BoxDynError> {
+        match value.data {
+            TsurugiData::TimePointWithTimeZone(v) => Ok(*v),
+            _ => Err(decode_error(&value, "DateTime<FixedOffset>")),
+        }
+    }
+}
+
+impl Type<Tsurugi> for DateTime<Utc> {
+    fn type_info() -> TsurugiTypeInfo {
+        TsurugiTypeInfo::new(AtomType::TimePointWithTimeZone)
+    }
+}
+
+impl Encode<'_, Tsurugi> for DateTime<Utc> {
+    fn encode_by_ref(&self, buf: &mut Vec<TsurugiData>) -> Result<IsNull, BoxDynError> {
+        buf.push(TsurugiData::TimePointWithTimeZone(self.fixed_offset()));
+        Ok(IsNull::No)
+    }
+}
+
+impl Decode<'_, Tsurugi> for DateTime<Utc> {
+    fn decode(value: TsurugiValueRef<'_>) -> Result<Self, BoxDynError> {
+        <DateTime<FixedOffset> as Decode<Tsurugi>>::decode(value).map(|v| v.to_utc())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use sqlx_core::{type_info::TypeInfo, value::Value};
+
+    use crate::TsurugiValue;
+
+    fn value(data: TsurugiData, atom_type: AtomType) -> TsurugiValue {
+        TsurugiValue::new(data, TsurugiTypeInfo::new(atom_type))
+    }
+
+    #[test]
+    fn decode() {
+        let v = value(TsurugiData::Int4(123), AtomType::Int4);
+        assert_eq!(123, v.try_decode::<i32>().unwrap());
+        assert_eq!(123_i64, v.try_decode::<i64>().unwrap());
+        assert!(v.try_decode::<String>().is_err());
+
+        let v = value(
+            TsurugiData::Character("abc".to_string()),
+            AtomType::Character,
+        );
+        assert_eq!("abc", v.try_decode::<&str>().unwrap());
+        assert_eq!("abc", v.try_decode::<String>().unwrap());
+
+        let v = value(TsurugiData::Null, AtomType::Character);
+        assert_eq!(None, v.try_decode::<Option<String>>().unwrap());
+        assert!(v.try_decode::<String>().is_err());
+
+        let time = DateTime::parse_from_rfc3339("2025-01-16T12:34:56+09:00").unwrap();
+        let v = value(
+            TsurugiData::TimePointWithTimeZone(time),
+            AtomType::TimePointWithTimeZone,
+        );
+        assert_eq!(time, v.try_decode::<DateTime<FixedOffset>>().unwrap());
+        assert_eq!(time.to_utc(), v.try_decode::<DateTime<Utc>>().unwrap());
+    }
+
+    #[test]
+    fn type_info() {
+        assert_eq!("BIGINT", <i64 as Type<Tsurugi>>::type_info().name());
+        assert_eq!("VARCHAR", <&str as Type<Tsurugi>>::type_info().to_string());
+        assert_eq!(
+            "TIMESTAMP WITH TIME ZONE",
+            <DateTime<Utc> as Type<Tsurugi>>::type_info().name()
+        );
+        assert!(TsurugiTypeInfo::new(AtomType::Unknown).is_null());
+    }
+}
@@ -0,0 +1,162 @@
+use std::borrow::Cow;
+
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime};
+use sqlx_core::{
+    error::Error,
+    value::{Value, ValueRef},
+};
+use tsubakuro_rust_core::prelude::*;
+
+use crate::{Tsurugi, TsurugiTypeInfo};
+
+/// Value of a column or a parameter.
+///
+/// Custom [`Encode`](sqlx_core::encode::Encode) implementations push a value to the argument buffer (`Vec<TsurugiData>`).
+///
+/// since 0.11.0
+#[derive(Debug, Clone, PartialEq)]
+pub enum TsurugiData {
+    Null,
+    Boolean(bool),
+    Int4(i32),
+    Int8(i64),
+    Float4(f32),
+    Float8(f64),
+    Decimal(BigDecimal),
+    Character(String),
+    Octet(Vec<u8>),
+    Date(NaiveDate),
+    TimeOfDay(NaiveTime),
+    TimePoint(NaiveDateTime),
+    TimeOfDayWithTimeZone(NaiveTime, FixedOffset),
+    TimePointWithTimeZone(DateTime<FixedOffset>),
+}
+
+impl TsurugiData {
+    /// Converts the values of a column of [`SqlQueryResultBatch`].
+    pub(crate) fn from_column_values(values: SqlColumnValues) -> Result<Vec<TsurugiData>, Error> {
+        fn convert<T>(values: Vec<Option<T>>, f: impl Fn(T) -> TsurugiData) -> Vec<TsurugiData> {
+            values
+                .into_iter()
+                .map(|value| value.map_or(TsurugiData::Null, &f))
+                .collect()
+        }
+
+        let values = match values {
+            SqlColumnValues::Boolean(values) => convert(values, TsurugiData::Boolean),
+            SqlColumnValues::Int4(values) => convert(values, TsurugiData::Int4),
+            SqlColumnValues::Int8(values) => convert(values, TsurugiData::Int8),
+            SqlColumnValues::Float4(values) => convert(values, TsurugiData::Float4),
+            SqlColumnValues::Float8(values) => convert(values, TsurugiData::Float8),
+            SqlColumnValues::Decimal(values) => convert(values, |value| {
+                TsurugiData::Decimal(TgDecimal::from(value).into())
+            }),
+            SqlColumnValues::Character(values) => convert(values, TsurugiData::Character),
+            SqlColumnValues::Octet(values) => convert(values, TsurugiData::Octet),
+            SqlColumnValues::Date(values) => {
+                convert(values, |value| TsurugiData::Date(value.into()))
+            }
+            SqlColumnValues::TimeOfDay(values) => {
+                convert(values, |value| TsurugiData::TimeOfDay(value.into()))
+            }
+            SqlColumnValues::TimePoint(values) => {
+                convert(values, |value| TsurugiData::TimePoint(value.into()))
+            }
+            SqlColumnValues::TimeOfDayWithTimeZone(values) => convert(values, |value| {
+                let (time, offset) = value.into();
+                TsurugiData::TimeOfDayWithTimeZone(time, offset)
+            }),
+            SqlColumnValues::TimePointWithTimeZone(values) => convert(values, |value| {
+                TsurugiData::TimePointWithTimeZone(value.into())
+            }),
+            SqlColumnValues::Blob(_) | SqlColumnValues::Clob(_) => {
+                return Err(Error::Protocol(
+                    "unsupported column type (BLOB, CLOB)".to_string(),
+                ))
+            }
+        };
+        Ok(values)
+    }
+
+    /// Converts to a parameter of a prepared statement.
+    pub(crate) fn to_parameter(&self, name: &str) -> SqlParameter {
+        match self {
+            TsurugiData::Null => SqlParameter::null(name),
+            TsurugiData::Boolean(value) => SqlParameter::of(name, *value),
+            TsurugiData::Int4(value) => SqlParameter::of(name, *value),
+            TsurugiData::Int8(value) => SqlParameter::of(name, *value),
+            TsurugiData::Float4(value) => SqlParameter::of(name, *value),
+            TsurugiData::Float8(value) => SqlParameter::of(name, *value),
+            TsurugiData::Decimal(value) => SqlParameter::of(name, value),
+            TsurugiData::Character(value) => SqlParameter::of(name, value),
+            TsurugiData::Octet(value) => SqlParameter::of(name, value),
+            TsurugiData::Date(value) => SqlParameter::of(name, value),
+            TsurugiData::TimeOfDay(value) => SqlParameter::of(name, value),
+            TsurugiData::TimePoint(value) => SqlParameter::of(name, value),
+            TsurugiData::TimeOfDayWithTimeZone(time, offset) => {
+                SqlParameter::of(name, (*time, *offset))
+            }
+            TsurugiData::TimePointWithTimeZone(value) => SqlParameter::of(name, value),
+        }
+    }
+}
+
+/// Owned value of a column.
+///
+/// since 0.11.0
+#[derive(Debug, Clone)]
+pub struct TsurugiValue {
+    pub(crate) data: TsurugiData,
+    pub(crate) type_info: TsurugiTypeInfo,
+}
+
+impl TsurugiValue {
+    pub(crate) fn new(data: TsurugiData, type_info: TsurugiTypeInfo) -> TsurugiValue {
+        TsurugiValue { data, type_info }
+    }
+}
+
+impl Value for TsurugiValue {
+    type Database = Tsurugi;
+
+    fn as_ref(&self) -> TsurugiValueRef<'_> {
+        TsurugiValueRef {
+            data: &self.data,
+            type_info: self.type_info,
+        }
+    }
+
+    fn type_info(&self) -> Cow<'_, TsurugiTypeInfo> {
+        Cow::Borrowed(&self.type_info)
+    }
+
+    fn is_null(&self) -> bool {
+        self.data == TsurugiData::Null
+    }
+}
+
+/// Borrowed value of a column.
+///
+/// since 0.11.0
+#[derive(Debug, Clone, Copy)]
+pub struct TsurugiValueRef<'r> {
+    pub(crate) data: &'r TsurugiData,
+    pub(crate) type_info: TsurugiTypeInfo,
+}
+
+impl<'r> ValueRef<'r> for TsurugiValueRef<'r> {
+    type Database = Tsurugi;
+
+    fn to_owned(&self) -> TsurugiValue {
+        TsurugiValue::new(self.data.clone(), self.type_info)
+    }
+
+    fn type_info(&self) -> Cow<'_, TsurugiTypeInfo> {
+        Cow::Borrowed(&self.type_info)
+    }
+
+    fn is_null(&self) -> bool {
+        *self.data == TsurugiData::Null
+    }
+}